@@ -214,6 +214,8 @@ where
 		.collator_protocol(CollatorProtocolSubsystem::new(ProtocolSide::Validator {
 			keystore: keystore.clone(),
 			eviction_policy: Default::default(),
+			scoreboard_path: None,
+			preferred_collators: Default::default(),
 			metrics: Metrics::register(registry)?,
 		}))
 		.network_bridge(NetworkBridgeSubsystem::new(
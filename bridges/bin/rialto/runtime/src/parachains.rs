@@ -75,7 +75,9 @@ impl parachains_paras_inherent::Config for Runtime {
 	type WeightInfo = parachains_paras_inherent::TestWeightInfo;
 }
 
-impl parachains_scheduler::Config for Runtime {}
+impl parachains_scheduler::Config for Runtime {
+	type OnClaimEnded = ();
+}
 
 impl parachains_session_info::Config for Runtime {}
 
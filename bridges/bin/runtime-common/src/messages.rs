@@ -26,6 +26,7 @@ use bp_messages::{
 	target_chain::{DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages},
 	InboundLaneData, LaneId, Message, MessageData, MessageKey, MessageNonce, OutboundLaneData,
 };
+use bp_polkadot_core::parachains::ParaId;
 use bp_runtime::{
 	messages::{DispatchFeePayment, MessageDispatchResult},
 	ChainId, Size, StorageProofChecker,
@@ -424,23 +425,58 @@ pub mod source {
 		pallet_bridge_grandpa::Pallet::<ThisRuntime, GrandpaInstance>::parse_finalized_storage_proof(
 			bridged_header_hash.into(),
 			StorageProof::new(storage_proof),
-			|storage| {
-				// Messages delivery proof is just proof of single storage key read => any error
-				// is fatal.
-				let storage_inbound_lane_data_key =
-					pallet_bridge_messages::storage_keys::inbound_lane_data_key(B::BRIDGED_MESSAGES_PALLET_NAME, &lane);
-				let raw_inbound_lane_data = storage
-					.read_value(storage_inbound_lane_data_key.0.as_ref())
-					.map_err(|_| "Failed to read inbound lane state from storage proof")?
-					.ok_or("Inbound lane state is missing from the messages proof")?;
-				let inbound_lane_data = InboundLaneData::decode(&mut &raw_inbound_lane_data[..])
-					.map_err(|_| "Failed to decode inbound lane state from the proof")?;
-
-				Ok((lane, inbound_lane_data))
-			},
+			|storage| read_inbound_lane_data::<B, _>(storage, lane),
 		)
 		.map_err(<&'static str>::from)?
 	}
+
+	/// Verify proof of This -> Bridged chain messages delivery, given the Bridged chain is a
+	/// parachain whose heads are tracked by the parachains pallet.
+	pub fn verify_messages_delivery_proof_from_parachain<
+		B: MessageBridge,
+		ThisRuntime,
+		ParachainsInstance: 'static,
+	>(
+		proof: FromBridgedChainMessagesDeliveryProof<HashOf<BridgedChain<B>>>,
+		parachain: ParaId,
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, &'static str>
+	where
+		ThisRuntime: pallet_bridge_parachains::Config<ParachainsInstance>,
+		HashOf<BridgedChain<B>>:
+			Into<pallet_bridge_parachains::ParaHash<ThisRuntime, ParachainsInstance>>,
+	{
+		let FromBridgedChainMessagesDeliveryProof { bridged_header_hash, storage_proof, lane } =
+			proof;
+		pallet_bridge_parachains::Pallet::<ThisRuntime, ParachainsInstance>::parse_finalized_storage_proof(
+			parachain,
+			bridged_header_hash.into(),
+			StorageProof::new(storage_proof),
+			|storage| read_inbound_lane_data::<B, _>(storage, lane),
+		)
+		.map_err(<&'static str>::from)?
+	}
+
+	/// Read the state of the inbound lane from the storage proof of messages delivery.
+	fn read_inbound_lane_data<B: MessageBridge, H: Hasher>(
+		storage: StorageProofChecker<H>,
+		lane: LaneId,
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, &'static str> {
+		// Messages delivery proof is just proof of single storage key read => any error
+		// is fatal.
+		let storage_inbound_lane_data_key =
+			pallet_bridge_messages::storage_keys::inbound_lane_data_key(
+				B::BRIDGED_MESSAGES_PALLET_NAME,
+				&lane,
+			);
+		let raw_inbound_lane_data = storage
+			.read_value(storage_inbound_lane_data_key.0.as_ref())
+			.map_err(|_| "Failed to read inbound lane state from storage proof")?
+			.ok_or("Inbound lane state is missing from the messages proof")?;
+		let inbound_lane_data = InboundLaneData::decode(&mut &raw_inbound_lane_data[..])
+			.map_err(|_| "Failed to decode inbound lane state from the proof")?;
+
+		Ok((lane, inbound_lane_data))
+	}
 }
 
 /// Sub-module that is declaring types required for processing Bridged -> This chain messages.
@@ -632,6 +668,46 @@ pub mod target {
 		.map_err(Into::into)
 	}
 
+	/// Verify proof of Bridged -> This chain messages, given the Bridged chain is a parachain
+	/// whose heads are tracked by the parachains pallet.
+	///
+	/// The `messages_count` argument verification (sane limits) is supposed to be made
+	/// outside of this function. This function only verifies that the proof declares exactly
+	/// `messages_count` messages.
+	pub fn verify_messages_proof_from_parachain<
+		B: MessageBridge,
+		ThisRuntime,
+		ParachainsInstance: 'static,
+	>(
+		proof: FromBridgedChainMessagesProof<HashOf<BridgedChain<B>>>,
+		messages_count: u32,
+		parachain: ParaId,
+	) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, &'static str>
+	where
+		ThisRuntime: pallet_bridge_parachains::Config<ParachainsInstance>,
+		HashOf<BridgedChain<B>>:
+			Into<pallet_bridge_parachains::ParaHash<ThisRuntime, ParachainsInstance>>,
+	{
+		verify_messages_proof_with_parser::<B, _, _>(
+			proof,
+			messages_count,
+			|bridged_header_hash, bridged_storage_proof| {
+				pallet_bridge_parachains::Pallet::<ThisRuntime, ParachainsInstance>::parse_finalized_storage_proof(
+					parachain,
+					bridged_header_hash.into(),
+					StorageProof::new(bridged_storage_proof),
+					|storage_adapter| storage_adapter,
+				)
+				.map(|storage| StorageProofCheckerAdapter::<_, B> {
+					storage,
+					_dummy: Default::default(),
+				})
+				.map_err(|err| MessageProofError::Custom(err.into()))
+			},
+		)
+		.map_err(Into::into)
+	}
+
 	#[derive(Debug, PartialEq)]
 	pub(crate) enum MessageProofError {
 		Empty,
@@ -771,8 +847,18 @@ pub mod target {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::mock::{run_test, Origin, TestRuntime, TEST_PARACHAIN};
+	use bp_header_chain::InitializationData;
+	use bp_polkadot_core::parachains::{
+		parachain_head_storage_key_at_source, ParaHead, ParaHeadsProof,
+	};
 	use codec::{Decode, Encode};
-	use frame_support::weights::Weight;
+	use frame_support::{assert_ok, weights::Weight};
+	use sp_core::H256;
+	use sp_runtime::{
+		testing::Header,
+		traits::{BlakeTwo256, Header as HeaderT},
+	};
 	use std::ops::RangeInclusive;
 
 	const DELIVERY_TRANSACTION_WEIGHT: Weight = 100;
@@ -973,7 +1059,7 @@ mod tests {
 	struct BridgedChain;
 
 	impl ChainWithMessages for BridgedChain {
-		type Hash = ();
+		type Hash = H256;
 		type AccountId = BridgedChainAccountId;
 		type Signer = BridgedChainSigner;
 		type Signature = BridgedChainSignature;
@@ -1341,9 +1427,9 @@ mod tests {
 		1..=0
 	}
 
-	fn messages_proof(nonces_end: MessageNonce) -> target::FromBridgedChainMessagesProof<()> {
+	fn messages_proof(nonces_end: MessageNonce) -> target::FromBridgedChainMessagesProof<H256> {
 		target::FromBridgedChainMessagesProof {
-			bridged_header_hash: (),
+			bridged_header_hash: Default::default(),
 			storage_proof: vec![],
 			lane: Default::default(),
 			nonces_start: 1,
@@ -1573,4 +1659,182 @@ mod tests {
 			100 + 50 * 10 + 777,
 		);
 	}
+
+	fn prepare_storage_proof(entries: Vec<(Vec<u8>, Vec<u8>)>) -> (H256, Vec<Vec<u8>>) {
+		use sp_state_machine::{backend::Backend, prove_read, InMemoryBackend};
+
+		let state_version = sp_runtime::StateVersion::default();
+		let storage_keys = entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+		let storage =
+			entries.into_iter().map(|(key, value)| (key, Some(value))).collect::<Vec<_>>();
+		let backend = <InMemoryBackend<BlakeTwo256>>::from((vec![(None, storage)], state_version));
+		let root = backend.storage_root(std::iter::empty(), state_version).0;
+		let proof = prove_read(backend, &storage_keys).unwrap().iter_nodes().collect();
+
+		(root, proof)
+	}
+
+	/// Import head of the test parachain with given state root and return its hash.
+	fn import_parachain_head(state_root: H256) -> H256 {
+		let parachain_head =
+			Header::new(1, Default::default(), state_root, Default::default(), Default::default());
+		let (relay_state_root, parachain_heads_proof) = prepare_storage_proof(vec![(
+			parachain_head_storage_key_at_source("Paras", TEST_PARACHAIN).0,
+			ParaHead(parachain_head.encode()).encode(),
+		)]);
+		let relay_header = Header::new(
+			0,
+			Default::default(),
+			relay_state_root,
+			Default::default(),
+			Default::default(),
+		);
+		let relay_block_hash = relay_header.hash();
+
+		assert_ok!(pallet_bridge_grandpa::Pallet::<TestRuntime>::initialize(
+			Origin::root(),
+			InitializationData {
+				header: Box::new(relay_header),
+				authority_list: vec![],
+				set_id: 0,
+				is_halted: false,
+			},
+		));
+		assert_ok!(pallet_bridge_parachains::Pallet::<TestRuntime>::submit_parachain_heads(
+			Origin::signed(1),
+			relay_block_hash,
+			vec![TEST_PARACHAIN],
+			ParaHeadsProof(parachain_heads_proof),
+		));
+
+		parachain_head.hash()
+	}
+
+	fn test_message_data() -> MessageData<BridgedChainBalance> {
+		MessageData { payload: vec![42], fee: BridgedChainBalance(0) }
+	}
+
+	fn parachain_messages_proof() -> target::FromBridgedChainMessagesProof<H256> {
+		let (state_root, storage_proof) = prepare_storage_proof(vec![(
+			pallet_bridge_messages::storage_keys::message_key(
+				OnThisChainBridge::BRIDGED_MESSAGES_PALLET_NAME,
+				TEST_LANE_ID,
+				1,
+			)
+			.0,
+			test_message_data().encode(),
+		)]);
+		target::FromBridgedChainMessagesProof {
+			bridged_header_hash: import_parachain_head(state_root),
+			storage_proof,
+			lane: *TEST_LANE_ID,
+			nonces_start: 1,
+			nonces_end: 1,
+		}
+	}
+
+	fn test_inbound_lane_data() -> InboundLaneData<ThisChainAccountId> {
+		InboundLaneData { relayers: Default::default(), last_confirmed_nonce: 1 }
+	}
+
+	fn parachain_messages_delivery_proof() -> source::FromBridgedChainMessagesDeliveryProof<H256> {
+		let (state_root, storage_proof) = prepare_storage_proof(vec![(
+			pallet_bridge_messages::storage_keys::inbound_lane_data_key(
+				OnThisChainBridge::BRIDGED_MESSAGES_PALLET_NAME,
+				TEST_LANE_ID,
+			)
+			.0,
+			test_inbound_lane_data().encode(),
+		)]);
+		source::FromBridgedChainMessagesDeliveryProof {
+			bridged_header_hash: import_parachain_head(state_root),
+			storage_proof,
+			lane: *TEST_LANE_ID,
+		}
+	}
+
+	#[test]
+	fn messages_proof_from_parachain_is_accepted() {
+		run_test(|| {
+			assert_eq!(
+				target::verify_messages_proof_from_parachain::<OnThisChainBridge, TestRuntime, ()>(
+					parachain_messages_proof(),
+					1,
+					TEST_PARACHAIN,
+				),
+				Ok(vec![(
+					*TEST_LANE_ID,
+					ProvedLaneMessages {
+						lane_state: None,
+						messages: vec![Message {
+							key: MessageKey { lane_id: *TEST_LANE_ID, nonce: 1 },
+							data: test_message_data(),
+						}],
+					},
+				)]
+				.into_iter()
+				.collect()),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_proof_from_unknown_parachain_is_rejected() {
+		run_test(|| {
+			assert_eq!(
+				target::verify_messages_proof_from_parachain::<OnThisChainBridge, TestRuntime, ()>(
+					parachain_messages_proof(),
+					1,
+					ParaId(TEST_PARACHAIN.0 + 1),
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_proof_at_unknown_parachain_head_is_rejected() {
+		run_test(|| {
+			let mut proof = parachain_messages_proof();
+			proof.bridged_header_hash = H256::repeat_byte(42);
+			assert_eq!(
+				target::verify_messages_proof_from_parachain::<OnThisChainBridge, TestRuntime, ()>(
+					proof,
+					1,
+					TEST_PARACHAIN,
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_delivery_proof_from_parachain_is_accepted() {
+		run_test(|| {
+			assert_eq!(
+				source::verify_messages_delivery_proof_from_parachain::<
+					OnThisChainBridge,
+					TestRuntime,
+					(),
+				>(parachain_messages_delivery_proof(), TEST_PARACHAIN),
+				Ok((*TEST_LANE_ID, test_inbound_lane_data())),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_delivery_proof_at_unknown_parachain_head_is_rejected() {
+		run_test(|| {
+			let mut proof = parachain_messages_delivery_proof();
+			proof.bridged_header_hash = H256::repeat_byte(42);
+			assert_eq!(
+				source::verify_messages_delivery_proof_from_parachain::<
+					OnThisChainBridge,
+					TestRuntime,
+					(),
+				>(proof, TEST_PARACHAIN),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
 }
@@ -0,0 +1,97 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Everything required to run benchmarks of parachains finality module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::messages_benchmarking::grow_trie;
+
+use bp_polkadot_core::parachains::{
+	parachain_head_storage_key_at_source, ParaHead, ParaHeadsProof, ParaId,
+};
+use codec::Encode;
+use frame_support::traits::Get;
+use pallet_bridge_grandpa::BridgedHeader;
+use pallet_bridge_messages::benchmarking::ProofSize;
+use pallet_bridge_parachains::{ParaHeader, RelayBlockHash};
+use sp_core::Hasher;
+use sp_runtime::{
+	traits::{Header as HeaderT, One},
+	DigestItem,
+};
+use sp_std::prelude::*;
+use sp_trie::{record_all_keys, trie_types::TrieDBMut, Layout, MemoryDB, Recorder, TrieMut};
+
+/// Prepare proof of parachain heads for the `submit_parachain_heads` call.
+///
+/// Every head is a parachain header, padded by a digest item of `parachain_head_size` bytes.
+/// If the proof is smaller than `proof_size` bytes, extra trie nodes are added to it.
+pub fn prepare_parachain_heads_proof<R, PI, H, MH>(
+	parachains: &[ParaId],
+	parachain_head_size: u32,
+	proof_size: u32,
+	make_relay_header: MH,
+) -> (RelayBlockHash<R, PI>, ParaHeadsProof)
+where
+	R: pallet_bridge_parachains::Config<PI>,
+	PI: 'static,
+	H: Hasher,
+	MH: Fn(H::Out) -> BridgedHeader<R, R::BridgesGrandpaPalletInstance>,
+{
+	let mut parachain_head: ParaHeader<R, PI> = HeaderT::new(
+		One::one(),
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Default::default(),
+	);
+	parachain_head
+		.digest_mut()
+		.push(DigestItem::Other(vec![42u8; parachain_head_size as usize]));
+	let parachain_head = ParaHead(parachain_head.encode());
+
+	// insert all heads to the trie
+	let mut root = Default::default();
+	let mut mdb = MemoryDB::default();
+	{
+		let mut trie = TrieDBMut::<H>::new(&mut mdb, &mut root);
+		for parachain in parachains {
+			let storage_key =
+				parachain_head_storage_key_at_source(R::ParasPalletName::get(), *parachain);
+			trie.insert(&storage_key.0, &parachain_head.encode())
+				.map_err(|_| "TrieMut::insert has failed")
+				.expect("TrieMut::insert should not fail in benchmarks");
+		}
+	}
+	root = grow_trie(root, &mut mdb, ProofSize::HasExtraNodes(proof_size));
+
+	// generate heads storage proof
+	let mut proof_recorder = Recorder::<H::Out>::new();
+	record_all_keys::<Layout<H>, _>(&mdb, &root, &mut proof_recorder)
+		.map_err(|_| "record_all_keys has failed")
+		.expect("record_all_keys should not fail in benchmarks");
+	let proof = proof_recorder.drain().into_iter().map(|n| n.data.to_vec()).collect();
+
+	// prepare relay chain header and insert it into the GRANDPA pallet
+	let relay_header = make_relay_header(root);
+	let relay_block_hash = relay_header.hash();
+	pallet_bridge_grandpa::initialize_for_benchmarks::<R, R::BridgesGrandpaPalletInstance>(
+		relay_header,
+	);
+
+	(relay_block_hash, ParaHeadsProof(proof))
+}
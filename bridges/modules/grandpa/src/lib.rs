@@ -26,6 +26,10 @@
 //! with justifications signed by the current validator set we know of. The header is inspected for
 //! a `ScheduledChanges` digest item, which is then used to update to next validator set.
 //!
+//! Forced authority set changes, which GRANDPA resorts to when it stalls, are not finalized by the
+//! authority set we know of. They have to be enacted through the privileged
+//! `submit_forced_authority_change` call, given a justification by the new authority set.
+//!
 //! Since this pallet only tracks finalized headers it does not deal with forks. Forks can only
 //! occur if the GRANDPA validator set on the bridged chain is either colluding or there is a severe
 //! bug causing resulting in an equivocation. Such events are outside the scope of this pallet.
@@ -38,13 +42,16 @@
 
 use crate::weights::WeightInfo;
 
-use bp_header_chain::{justification::GrandpaJustification, InitializationData};
+use bp_header_chain::{
+	find_grandpa_authorities_forced_change, justification::GrandpaJustification, ForcedChangeData,
+	InitializationData,
+};
 use bp_runtime::{BlockNumberOf, Chain, HashOf, HasherOf, HeaderOf};
 use finality_grandpa::voter_set::VoterSet;
 use frame_support::{ensure, fail};
 use frame_system::{ensure_signed, RawOrigin};
 use sp_finality_grandpa::{ConsensusLog, GRANDPA_ENGINE_ID};
-use sp_runtime::traits::{BadOrigin, Header as HeaderT, Zero};
+use sp_runtime::traits::{BadOrigin, CheckedAdd, Header as HeaderT, Zero};
 use sp_std::{boxed::Box, convert::TryInto};
 
 #[cfg(test)]
@@ -250,6 +257,70 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Enact a forced change of the authority set of the bridged chain.
+		///
+		/// The new authorities are taken from the forced change signaled by the `change_header`.
+		/// They must have finalized the `finality_target`, which must be at least the delay of the
+		/// change after the `change_header`. If successful, the new authority set becomes the
+		/// current one and the `finality_target` becomes the best finalized header.
+		///
+		/// May only be called either by root, or by `PalletOwner`, even if the pallet is halted.
+		/// The call does not verify that the `finality_target` descends from the `change_header`,
+		/// so it is important that you ensure that valid data is being passed in.
+		#[pallet::weight((
+			T::WeightInfo::submit_finality_proof(
+				change_data.justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+				change_data.justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+			),
+			DispatchClass::Operational,
+		))]
+		pub fn submit_forced_authority_change(
+			origin: OriginFor<T>,
+			change_data: super::ForcedChangeData<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_owner_or_root::<T, I>(origin)?;
+
+			let ForcedChangeData { change_header, finality_target, justification, set_id } =
+				change_data;
+			let best_finalized = <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get())
+				.ok_or(<Error<T, I>>::NotInitialized)?;
+
+			let (hash, number) = (finality_target.hash(), *finality_target.number());
+			ensure!(*best_finalized.number() < number, <Error<T, I>>::OldHeader);
+
+			let (_, change) = find_grandpa_authorities_forced_change(&*change_header)
+				.ok_or(<Error<T, I>>::NoForcedChange)?;
+			// A change whose enactment overflows the block number is never enacted.
+			let enacted_at = change_header
+				.number()
+				.checked_add(&change.delay)
+				.ok_or(<Error<T, I>>::PrematureForcedChange)?;
+			ensure!(number >= enacted_at, <Error<T, I>>::PrematureForcedChange);
+
+			let current_set_id = <CurrentAuthoritySet<T, I>>::get().set_id;
+			ensure!(set_id > current_set_id, <Error<T, I>>::InvalidAuthoritySet);
+			let authority_set = bp_header_chain::AuthoritySet::new(change.next_authorities, set_id);
+			verify_justification::<T, I>(&justification, hash, number, authority_set.clone())?;
+
+			// The finality target may schedule yet another change, which is then enacted right
+			// away and supersedes the forced one.
+			if !try_enact_authority_change::<T, I>(&finality_target, set_id)? {
+				<CurrentAuthoritySet<T, I>>::put(&authority_set);
+			}
+			insert_header::<T, I>(*finality_target, hash);
+
+			log::info!(
+				target: "runtime::bridge-grandpa",
+				"Forced transition from authority set {} to {} at header {:?}! New authorities are: {:?}",
+				current_set_id,
+				set_id,
+				hash,
+				authority_set,
+			);
+
+			Ok(().into())
+		}
 	}
 
 	/// The current number of requests which have written to storage.
@@ -363,6 +434,11 @@ pub mod pallet {
 		Halted,
 		/// The storage proof doesn't contains storage root. So it is invalid for given header.
 		StorageRootMismatch,
+		/// The header does not signal a forced authority set change.
+		NoForcedChange,
+		/// The header is not finalized by the new authorities, as it precedes the enactment of the
+		/// forced authority set change.
+		PrematureForcedChange,
 	}
 
 	/// Check the given header for a GRANDPA scheduled authority set change. If a change
@@ -380,7 +456,7 @@ pub mod pallet {
 
 		// We don't support forced changes - at that point governance intervention is required.
 		ensure!(
-			super::find_forced_change(header).is_none(),
+			find_grandpa_authorities_forced_change(header).is_none(),
 			<Error<T, I>>::UnsupportedScheduledChange
 		);
 
@@ -553,6 +629,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		<ImportedHeaders<T, I>>::contains_key(hash)
 	}
 
+	/// Get a finalized header known to the bridge pallet, if it has not been pruned yet.
+	pub fn finalized_header(hash: BridgedBlockHash<T, I>) -> Option<BridgedHeader<T, I>> {
+		<ImportedHeaders<T, I>>::get(hash)
+	}
+
 	/// Verify that the passed storage proof is valid, given it is crafted using
 	/// known finalized header. If the proof is valid, then the `parse` callback
 	/// is called and the function returns its result.
@@ -587,25 +668,6 @@ pub(crate) fn find_scheduled_change<H: HeaderT>(
 	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
 }
 
-/// Checks the given header for a consensus digest signaling a **forced** scheduled change and
-/// extracts it.
-pub(crate) fn find_forced_change<H: HeaderT>(
-	header: &H,
-) -> Option<(H::Number, sp_finality_grandpa::ScheduledChange<H::Number>)> {
-	use sp_runtime::generic::OpaqueDigestItemId;
-
-	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
-
-	let filter_log = |log: ConsensusLog<H::Number>| match log {
-		ConsensusLog::ForcedChange(delay, change) => Some((delay, change)),
-		_ => None,
-	};
-
-	// find the first consensus digest with the right ID which converts to
-	// the right kind of consensus log.
-	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
-}
-
 /// (Re)initialize bridge with given header for using it in `pallet-bridge-messages` benchmarks.
 #[cfg(feature = "runtime-benchmarks")]
 pub fn initialize_for_benchmarks<T: Config<I>, I: 'static>(header: BridgedHeader<T, I>) {
@@ -624,7 +686,7 @@ mod tests {
 	use crate::mock::{run_test, test_header, Origin, TestHeader, TestNumber, TestRuntime};
 	use bp_test_utils::{
 		authority_list, make_default_justification, make_justification_for_header,
-		JustificationGeneratorParams, ALICE, BOB,
+		JustificationGeneratorParams, ALICE, BOB, DAVE, EVE, FERDIE,
 	};
 	use codec::Encode;
 	use frame_support::{assert_err, assert_noop, assert_ok, weights::PostDispatchInfo};
@@ -693,6 +755,42 @@ mod tests {
 		Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, consensus_log.encode())] }
 	}
 
+	fn forced_change_data(
+		delay: u64,
+		target: u8,
+		set_id: sp_finality_grandpa::SetId,
+	) -> ForcedChangeData<TestHeader> {
+		let next_authorities = vec![(DAVE, 1), (EVE, 1), (FERDIE, 1)];
+		let consensus_log = ConsensusLog::<TestNumber>::ForcedChange(
+			0,
+			sp_finality_grandpa::ScheduledChange {
+				next_authorities: next_authorities
+					.iter()
+					.map(|(id, weight)| ((*id).into(), *weight))
+					.collect(),
+				delay,
+			},
+		);
+
+		let mut change_header = test_header(2);
+		change_header.digest =
+			Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, consensus_log.encode())] };
+		let finality_target = test_header(target.into());
+		let justification = make_justification_for_header(JustificationGeneratorParams {
+			header: finality_target.clone(),
+			set_id: 2,
+			authorities: next_authorities,
+			..Default::default()
+		});
+
+		ForcedChangeData {
+			change_header: Box::new(change_header),
+			finality_target: Box::new(finality_target),
+			justification,
+			set_id,
+		}
+	}
+
 	#[test]
 	fn init_root_or_owner_origin_can_initialize_pallet() {
 		run_test(|| {
@@ -1007,6 +1105,112 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn forced_authority_change_enacts_new_authority_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let change_data = forced_change_data(2, 4, 2);
+			let target_hash = change_data.finality_target.hash();
+			assert_ok!(Pallet::<TestRuntime>::submit_forced_authority_change(
+				Origin::root(),
+				change_data
+			));
+
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), target_hash);
+			assert!(<ImportedHeaders<TestRuntime>>::contains_key(target_hash));
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(
+					vec![(DAVE.into(), 1), (EVE.into(), 1), (FERDIE.into(), 1)],
+					2
+				),
+			);
+		})
+	}
+
+	#[test]
+	fn forced_authority_change_may_be_submitted_by_owner_even_if_halted() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			PalletOwner::<TestRuntime>::put(2);
+			IsHalted::<TestRuntime>::put(true);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_forced_authority_change(
+					Origin::signed(1),
+					forced_change_data(2, 4, 2)
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::submit_forced_authority_change(
+				Origin::signed(2),
+				forced_change_data(2, 4, 2)
+			));
+		})
+	}
+
+	#[test]
+	fn forced_authority_change_rejects_invalid_evidence() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// The change header does not signal a forced change.
+			let mut change_data = forced_change_data(2, 4, 2);
+			change_data.change_header.digest = change_log(0);
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_forced_authority_change(Origin::root(), change_data),
+				<Error<TestRuntime>>::NoForcedChange
+			);
+
+			// The finality target precedes the enactment of the change.
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_forced_authority_change(
+					Origin::root(),
+					forced_change_data(3, 4, 2)
+				),
+				<Error<TestRuntime>>::PrematureForcedChange
+			);
+
+			// The change is never enacted, as its delay overflows the block number.
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_forced_authority_change(
+					Origin::root(),
+					forced_change_data(u64::MAX, 4, 2)
+				),
+				<Error<TestRuntime>>::PrematureForcedChange
+			);
+
+			// The new authority set must have a greater ID than the current one.
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_forced_authority_change(
+					Origin::root(),
+					forced_change_data(2, 4, 1)
+				),
+				<Error<TestRuntime>>::InvalidAuthoritySet
+			);
+
+			// The justification is made for another set ID.
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_forced_authority_change(
+					Origin::root(),
+					forced_change_data(2, 4, 3)
+				),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+
+			// The finality target must extend the chain.
+			assert_ok!(submit_finality_proof(5));
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_forced_authority_change(
+					Origin::root(),
+					forced_change_data(2, 4, 2)
+				),
+				<Error<TestRuntime>>::OldHeader
+			);
+		})
+	}
+
 	#[test]
 	fn parse_finalized_storage_proof_rejects_proof_on_unknown_header() {
 		run_test(|| {
@@ -0,0 +1,95 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parachains finality pallet benchmarking.
+
+use crate::{weights_ext::DEFAULT_PARACHAIN_HEAD_SIZE, Call, RelayBlockHash};
+
+use bp_polkadot_core::parachains::{ParaHeadsProof, ParaId};
+use frame_benchmarking::{account, benchmarks_instance_pallet};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+/// Pallet we're benchmarking here.
+pub struct Pallet<T: Config<I>, I: 'static>(crate::Pallet<T, I>);
+
+/// Trait that must be implemented by runtime.
+pub trait Config<I: 'static>: crate::Config<I> {
+	/// Returns tracked parachains that may be used in benchmarks.
+	fn parachains() -> Vec<ParaId>;
+	/// Generate parachain heads proof and prepare environment for verifying this proof.
+	///
+	/// Heads of all given parachains must be included in the proof. If the proof is smaller
+	/// than `proof_size` bytes, extra trie nodes are added to it.
+	fn prepare_parachain_heads_proof(
+		parachains: &[ParaId],
+		parachain_head_size: u32,
+		proof_size: u32,
+	) -> (RelayBlockHash<Self, I>, ParaHeadsProof);
+}
+
+const SEED: u32 = 0;
+
+benchmarks_instance_pallet! {
+	// Benchmark `submit_parachain_heads` extrinsic with different number of parachains.
+	submit_parachain_heads_with_n_parachains {
+		let p in 1..T::parachains().len() as u32;
+		let sender = account("sender", 0, SEED);
+		let parachains = T::parachains().into_iter().take(p as usize).collect::<Vec<_>>();
+		let (relay_block_hash, parachain_heads_proof) = T::prepare_parachain_heads_proof(
+			&parachains,
+			DEFAULT_PARACHAIN_HEAD_SIZE,
+			0,
+		);
+	}: submit_parachain_heads(RawOrigin::Signed(sender), relay_block_hash, parachains.clone(), parachain_heads_proof)
+	verify {
+		for parachain in parachains {
+			assert!(crate::Pallet::<T, I>::best_parachain_head(parachain).is_some());
+		}
+	}
+
+	// Benchmark `submit_parachain_heads` extrinsic with 1kb proof size.
+	submit_parachain_heads_with_1kb_proof {
+		let sender = account("sender", 0, SEED);
+		let parachains = T::parachains().into_iter().take(1).collect::<Vec<_>>();
+		let (relay_block_hash, parachain_heads_proof) = T::prepare_parachain_heads_proof(
+			&parachains,
+			DEFAULT_PARACHAIN_HEAD_SIZE,
+			1024,
+		);
+	}: submit_parachain_heads(RawOrigin::Signed(sender), relay_block_hash, parachains.clone(), parachain_heads_proof)
+	verify {
+		for parachain in parachains {
+			assert!(crate::Pallet::<T, I>::best_parachain_head(parachain).is_some());
+		}
+	}
+
+	// Benchmark `submit_parachain_heads` extrinsic with 16kb proof size.
+	submit_parachain_heads_with_16kb_proof {
+		let sender = account("sender", 0, SEED);
+		let parachains = T::parachains().into_iter().take(1).collect::<Vec<_>>();
+		let (relay_block_hash, parachain_heads_proof) = T::prepare_parachain_heads_proof(
+			&parachains,
+			DEFAULT_PARACHAIN_HEAD_SIZE,
+			16 * 1024,
+		);
+	}: submit_parachain_heads(RawOrigin::Signed(sender), relay_block_hash, parachains.clone(), parachain_heads_proof)
+	verify {
+		for parachain in parachains {
+			assert!(crate::Pallet::<T, I>::best_parachain_head(parachain).is_some());
+		}
+	}
+}
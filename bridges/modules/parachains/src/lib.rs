@@ -0,0 +1,560 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parachains Pallet
+//!
+//! This pallet tracks heads of the parachains of a bridged relay chain, whose finality is tracked
+//! by an instance of the `pallet-bridge-grandpa` pallet.
+//!
+//! Parachain heads are submitted along with a storage proof of the `paras::Heads` map, built at
+//! a relay chain header that is known to the GRANDPA pallet. The best known head of every tracked
+//! parachain is stored, so storage proofs built at the parachain may be verified against it,
+//! e.g. proofs of messages sent from the parachain.
+//!
+//! All tracked parachains are expected to use the same header type, which is defined by the
+//! `BridgedParachain` of the pallet configuration. Heads of other parachains are ignored.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// Runtime-generated enums
+#![allow(clippy::large_enum_variant)]
+
+pub use weights::WeightInfo;
+pub use weights_ext::WeightInfoExt;
+
+use bp_polkadot_core::parachains::{
+	parachain_head_storage_key_at_source, ParaHead, ParaHeadsProof, ParaId,
+};
+use bp_runtime::{Chain, HashOf, HasherOf, HeaderOf, StorageProofChecker};
+use codec::{Decode, Encode};
+use frame_support::{traits::Contains, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_runtime::traits::Header as HeaderT;
+use sp_std::{convert::TryInto, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+
+/// Pallet containing weights for this pallet.
+pub mod weights;
+pub mod weights_ext;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+// Re-export in crate namespace for `construct_runtime!`
+pub use pallet::*;
+
+/// Block hash of the bridged relay chain.
+pub type RelayBlockHash<T, I> =
+	pallet_bridge_grandpa::BridgedBlockHash<T, <T as Config<I>>::BridgesGrandpaPalletInstance>;
+/// Block number of the bridged relay chain.
+pub type RelayBlockNumber<T, I> =
+	pallet_bridge_grandpa::BridgedBlockNumber<T, <T as Config<I>>::BridgesGrandpaPalletInstance>;
+/// Hasher of the bridged relay chain.
+pub type RelayBlockHasher<T, I> =
+	pallet_bridge_grandpa::BridgedBlockHasher<T, <T as Config<I>>::BridgesGrandpaPalletInstance>;
+/// The GRANDPA pallet that tracks finality of the bridged relay chain.
+pub type GrandpaPalletOf<T, I> =
+	pallet_bridge_grandpa::Pallet<T, <T as Config<I>>::BridgesGrandpaPalletInstance>;
+/// Block hash of the bridged parachain.
+pub type ParaHash<T, I> = HashOf<<T as Config<I>>::BridgedParachain>;
+/// Hasher of the bridged parachain.
+pub type ParaHasher<T, I> = HasherOf<<T as Config<I>>::BridgedParachain>;
+/// Header of the bridged parachain.
+pub type ParaHeader<T, I> = HeaderOf<<T as Config<I>>::BridgedParachain>;
+
+/// The best known head of a parachain.
+#[derive(Clone, Decode, Encode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BestParaHead<RelayBlockNumber, ParaHash> {
+	/// Number of the relay chain block the head has been read at.
+	pub at_relay_block_number: RelayBlockNumber,
+	/// Hash of the head.
+	pub head_hash: ParaHash,
+	/// Position of the next imported head in the `ImportedParaHashes` ring buffer.
+	pub next_imported_hash_position: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>:
+		pallet_bridge_grandpa::Config<Self::BridgesGrandpaPalletInstance>
+	{
+		/// Instance of the GRANDPA pallet that tracks finality of the bridged relay chain.
+		type BridgesGrandpaPalletInstance: 'static;
+
+		/// The parachain we are bridging to here.
+		type BridgedParachain: Chain;
+
+		/// Name of the `paras` pallet in the runtime of the bridged relay chain.
+		#[pallet::constant]
+		type ParasPalletName: Get<&'static str>;
+
+		/// Parachains whose heads are tracked by the pallet.
+		type TrackedParachains: Contains<ParaId>;
+
+		/// Maximal number of heads to keep in the storage, per parachain.
+		///
+		/// The setting is there to prevent growing the on-chain state indefinitely. Note
+		/// the setting does not relate to block numbers - we will simply keep as much items
+		/// in the storage, so it doesn't guarantee any fixed timeframe for parachain heads.
+		#[pallet::constant]
+		type HeadsToKeep: Get<u32>;
+
+		/// Benchmark results for this chain.
+		type WeightInfo: WeightInfoExt;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Import heads of the given parachains, given a storage proof of the `paras::Heads` map,
+		/// built at the given relay chain header.
+		///
+		/// The relay chain header must be known to the GRANDPA pallet. Heads of untracked
+		/// parachains, missing or undecodable heads and heads that are not newer than the best
+		/// known ones are ignored, so that concurrent submissions can't make the call fail.
+		#[pallet::weight(T::WeightInfo::submit_parachain_heads_weight(
+			parachain_heads_proof,
+			parachains.len().try_into().unwrap_or(u32::MAX),
+		))]
+		pub fn submit_parachain_heads(
+			origin: OriginFor<T>,
+			relay_block_hash: RelayBlockHash<T, I>,
+			parachains: Vec<ParaId>,
+			parachain_heads_proof: ParaHeadsProof,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+
+			let relay_block_number = *GrandpaPalletOf::<T, I>::finalized_header(relay_block_hash)
+				.ok_or(<Error<T, I>>::UnknownRelayChainBlock)?
+				.number();
+
+			GrandpaPalletOf::<T, I>::parse_finalized_storage_proof(
+				relay_block_hash,
+				sp_trie::StorageProof::new(parachain_heads_proof.0),
+				move |storage| {
+					for parachain in parachains {
+						if !T::TrackedParachains::contains(&parachain) {
+							log::trace!(
+								target: "runtime::bridge-parachains",
+								"Ignoring head of untracked parachain {:?}",
+								parachain,
+							);
+							continue
+						}
+
+						match read_parachain_head::<T, I>(&storage, parachain) {
+							Ok(Some(head)) =>
+								import_parachain_head::<T, I>(parachain, relay_block_number, head),
+							Ok(None) => log::trace!(
+								target: "runtime::bridge-parachains",
+								"Parachain {:?} has no head at relay chain block {:?}",
+								parachain,
+								relay_block_hash,
+							),
+							Err(e) => log::trace!(
+								target: "runtime::bridge-parachains",
+								"Failed to read head of parachain {:?} from the proof: {}",
+								parachain,
+								e,
+							),
+						}
+					}
+				},
+			)?;
+
+			Ok(().into())
+		}
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The relay chain block is not known to the GRANDPA pallet.
+		UnknownRelayChainBlock,
+		/// The parachain head is not known to the pallet.
+		UnknownParaHead,
+		/// The storage proof doesn't contains storage root. So it is invalid for given header.
+		StorageRootMismatch,
+	}
+
+	/// The best known heads of the tracked parachains.
+	#[pallet::storage]
+	pub(super) type BestParaHeads<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ParaId, BestParaHead<RelayBlockNumber<T, I>, ParaHash<T, I>>>;
+
+	/// Parachain heads which have been imported into the pallet.
+	#[pallet::storage]
+	pub(super) type ImportedParaHeads<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, ParaId, Identity, ParaHash<T, I>, ParaHeader<T, I>>;
+
+	/// A ring buffer of imported parachain head hashes, per parachain. Ordered by the insertion
+	/// time.
+	#[pallet::storage]
+	pub(super) type ImportedParaHashes<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, ParaId, Twox64Concat, u32, ParaHash<T, I>>;
+
+	/// Read the head of the given parachain from the storage proof of the `paras::Heads` map.
+	pub(crate) fn read_parachain_head<T: Config<I>, I: 'static>(
+		storage: &StorageProofChecker<RelayBlockHasher<T, I>>,
+		parachain: ParaId,
+	) -> Result<Option<ParaHeader<T, I>>, &'static str> {
+		let storage_key =
+			parachain_head_storage_key_at_source(T::ParasPalletName::get(), parachain);
+		let raw_head = match storage.read_value(storage_key.0.as_ref()) {
+			Ok(Some(raw_head)) => raw_head,
+			Ok(None) => return Ok(None),
+			Err(_) => return Err("head is missing from the storage proof"),
+		};
+		let head = ParaHead::decode(&mut &raw_head[..]).map_err(|_| "failed to decode head")?;
+		ParaHeader::<T, I>::decode(&mut &head.0[..])
+			.map(Some)
+			.map_err(|_| "failed to decode header from the head")
+	}
+
+	/// Import a previously verified parachain head to the storage, if it is newer than the best
+	/// known head of the parachain.
+	///
+	/// Note this function solely takes care of updating the storage and pruning old entries,
+	/// but does not verify the validity of such import.
+	pub(crate) fn import_parachain_head<T: Config<I>, I: 'static>(
+		parachain: ParaId,
+		relay_block_number: RelayBlockNumber<T, I>,
+		head: ParaHeader<T, I>,
+	) {
+		let head_hash = head.hash();
+		let next_imported_hash_position = match <BestParaHeads<T, I>>::get(parachain) {
+			Some(best_head) if best_head.at_relay_block_number >= relay_block_number => {
+				log::trace!(
+					target: "runtime::bridge-parachains",
+					"Ignoring head of parachain {:?} at relay chain block {:?}: head at block {:?} is already known",
+					parachain,
+					relay_block_number,
+					best_head.at_relay_block_number,
+				);
+				return
+			},
+			Some(best_head) if best_head.head_hash == head_hash => {
+				// The parachain has not progressed, only remember that its head is still the best.
+				<BestParaHeads<T, I>>::insert(
+					parachain,
+					BestParaHead { at_relay_block_number: relay_block_number, ..best_head },
+				);
+				return
+			},
+			Some(best_head) => best_head.next_imported_hash_position,
+			None => 0,
+		};
+
+		let pruning = <ImportedParaHashes<T, I>>::try_get(parachain, next_imported_hash_position);
+		<ImportedParaHeads<T, I>>::insert(parachain, head_hash, head);
+		<ImportedParaHashes<T, I>>::insert(parachain, next_imported_hash_position, head_hash);
+		<BestParaHeads<T, I>>::insert(
+			parachain,
+			BestParaHead {
+				at_relay_block_number: relay_block_number,
+				head_hash,
+				next_imported_hash_position: (next_imported_hash_position + 1) %
+					T::HeadsToKeep::get(),
+			},
+		);
+		log::trace!(
+			target: "runtime::bridge-parachains",
+			"Imported head {:?} of parachain {:?} at relay chain block {:?}",
+			head_hash,
+			parachain,
+			relay_block_number,
+		);
+
+		if let Ok(head_hash) = pruning {
+			log::debug!(
+				target: "runtime::bridge-parachains",
+				"Pruning old head {:?} of parachain {:?}.",
+				head_hash,
+				parachain,
+			);
+			<ImportedParaHeads<T, I>>::remove(parachain, head_hash);
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Get the best known head of the given parachain.
+	pub fn best_parachain_head(parachain: ParaId) -> Option<ParaHeader<T, I>> {
+		let best_head = <BestParaHeads<T, I>>::get(parachain)?;
+		<ImportedParaHeads<T, I>>::get(parachain, best_head.head_hash)
+	}
+
+	/// Check if a particular head of the given parachain is known to the pallet.
+	pub fn is_known_parachain_head(parachain: ParaId, hash: ParaHash<T, I>) -> bool {
+		<ImportedParaHeads<T, I>>::contains_key(parachain, hash)
+	}
+
+	/// Verify that the passed storage proof is valid, given it is crafted using
+	/// known head of the given parachain. If the proof is valid, then the `parse` callback
+	/// is called and the function returns its result.
+	pub fn parse_finalized_storage_proof<R>(
+		parachain: ParaId,
+		hash: ParaHash<T, I>,
+		storage_proof: sp_trie::StorageProof,
+		parse: impl FnOnce(StorageProofChecker<ParaHasher<T, I>>) -> R,
+	) -> Result<R, sp_runtime::DispatchError> {
+		let head = <ImportedParaHeads<T, I>>::get(parachain, hash)
+			.ok_or(Error::<T, I>::UnknownParaHead)?;
+		let storage_proof_checker = StorageProofChecker::new(*head.state_root(), storage_proof)
+			.map_err(|_| Error::<T, I>::StorageRootMismatch)?;
+
+		Ok(parse(storage_proof_checker))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{run_test, Origin, TestRuntime, UNTRACKED_PARACHAIN_ID};
+	use bp_header_chain::InitializationData;
+	use bp_test_utils::{authority_list, make_default_justification};
+	use frame_support::{assert_noop, assert_ok};
+	use sp_runtime::{
+		testing::{Header, H256},
+		traits::BlakeTwo256,
+	};
+
+	fn initialize_relay_chain() {
+		assert_ok!(pallet_bridge_grandpa::Pallet::<TestRuntime>::initialize(
+			Origin::root(),
+			InitializationData {
+				header: Box::new(bp_test_utils::test_header(0)),
+				authority_list: authority_list(),
+				set_id: 1,
+				is_halted: false,
+			},
+		));
+	}
+
+	fn import_relay_chain_header(number: u64, state_root: H256) -> H256 {
+		let mut header: Header = bp_test_utils::test_header(number);
+		header.state_root = state_root;
+		let hash = header.hash();
+		let justification = make_default_justification(&header);
+		assert_ok!(pallet_bridge_grandpa::Pallet::<TestRuntime>::submit_finality_proof(
+			Origin::signed(1),
+			Box::new(header),
+			justification,
+		));
+		hash
+	}
+
+	fn parachain_header(number: u64, state_root: H256) -> Header {
+		let mut header: Header = bp_test_utils::test_header(number);
+		header.state_root = state_root;
+		header
+	}
+
+	fn prepare_parachain_heads_proof(heads: Vec<(u32, Header)>) -> (H256, ParaHeadsProof) {
+		use sp_state_machine::{backend::Backend, prove_read, InMemoryBackend};
+
+		let state_version = sp_runtime::StateVersion::default();
+		let storage_keys = heads
+			.iter()
+			.map(|(parachain, _)| {
+				parachain_head_storage_key_at_source("Paras", ParaId(*parachain)).0
+			})
+			.collect::<Vec<_>>();
+		let storage = storage_keys
+			.iter()
+			.zip(heads)
+			.map(|(key, (_, head))| (key.clone(), Some(ParaHead(head.encode()).encode())))
+			.collect::<Vec<_>>();
+		let backend = <InMemoryBackend<BlakeTwo256>>::from((vec![(None, storage)], state_version));
+		let root = backend.storage_root(std::iter::empty(), state_version).0;
+		let proof = prove_read(backend, &storage_keys).unwrap().iter_nodes().collect();
+
+		(root, ParaHeadsProof(proof))
+	}
+
+	fn submit_parachain_heads(
+		relay_block_number: u64,
+		parachains: Vec<u32>,
+		heads: Vec<(u32, Header)>,
+	) -> (H256, ParaHeadsProof) {
+		let (state_root, proof) = prepare_parachain_heads_proof(heads);
+		let relay_block_hash = import_relay_chain_header(relay_block_number, state_root);
+		assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+			Origin::signed(1),
+			relay_block_hash,
+			parachains.into_iter().map(ParaId).collect(),
+			proof.clone(),
+		));
+		(relay_block_hash, proof)
+	}
+
+	fn best_head(parachain: u32) -> Option<BestParaHead<u64, H256>> {
+		BestParaHeads::<TestRuntime>::get(ParaId(parachain))
+	}
+
+	#[test]
+	fn imports_heads_of_tracked_parachains() {
+		run_test(|| {
+			initialize_relay_chain();
+
+			let head_1 = parachain_header(5, Default::default());
+			let head_2 = parachain_header(7, Default::default());
+			submit_parachain_heads(
+				1,
+				vec![1, 2, UNTRACKED_PARACHAIN_ID],
+				vec![(1, head_1.clone()), (2, head_2.clone()), (UNTRACKED_PARACHAIN_ID, head_2)],
+			);
+
+			assert_eq!(
+				best_head(1),
+				Some(BestParaHead {
+					at_relay_block_number: 1,
+					head_hash: head_1.hash(),
+					next_imported_hash_position: 1,
+				}),
+			);
+			assert_eq!(Pallet::<TestRuntime>::best_parachain_head(ParaId(1)), Some(head_1));
+			assert!(best_head(2).is_some());
+			assert_eq!(best_head(UNTRACKED_PARACHAIN_ID), None);
+		})
+	}
+
+	#[test]
+	fn ignores_heads_missing_from_proof() {
+		run_test(|| {
+			initialize_relay_chain();
+
+			submit_parachain_heads(
+				1,
+				vec![1, 2],
+				vec![(1, parachain_header(5, Default::default()))],
+			);
+
+			assert!(best_head(1).is_some());
+			assert_eq!(best_head(2), None);
+		})
+	}
+
+	#[test]
+	fn imports_only_newer_heads() {
+		run_test(|| {
+			initialize_relay_chain();
+
+			let head_5 = parachain_header(5, Default::default());
+			let head_10 = parachain_header(10, Default::default());
+			let (relay_block_1, proof_1) = submit_parachain_heads(1, vec![1], vec![(1, head_5)]);
+			submit_parachain_heads(2, vec![1], vec![(1, head_10.clone())]);
+			assert_eq!(best_head(1).unwrap().head_hash, head_10.hash());
+
+			// The head read at an older relay chain block is ignored.
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				Origin::signed(1),
+				relay_block_1,
+				vec![ParaId(1)],
+				proof_1,
+			));
+			assert_eq!(best_head(1).unwrap().head_hash, head_10.hash());
+
+			// The same head read at a newer relay chain block is not imported again.
+			submit_parachain_heads(3, vec![1], vec![(1, head_10.clone())]);
+			assert_eq!(
+				best_head(1),
+				Some(BestParaHead {
+					at_relay_block_number: 3,
+					head_hash: head_10.hash(),
+					next_imported_hash_position: 0,
+				}),
+			);
+		})
+	}
+
+	#[test]
+	fn prunes_old_heads() {
+		run_test(|| {
+			initialize_relay_chain();
+
+			let heads =
+				(1..=3).map(|n| parachain_header(n, Default::default())).collect::<Vec<_>>();
+			for (relay_block_number, head) in (1..).zip(&heads) {
+				submit_parachain_heads(relay_block_number, vec![1], vec![(1, head.clone())]);
+			}
+
+			assert!(!Pallet::<TestRuntime>::is_known_parachain_head(ParaId(1), heads[0].hash()));
+			assert!(Pallet::<TestRuntime>::is_known_parachain_head(ParaId(1), heads[1].hash()));
+			assert!(Pallet::<TestRuntime>::is_known_parachain_head(ParaId(1), heads[2].hash()));
+		})
+	}
+
+	#[test]
+	fn rejects_heads_at_unknown_relay_chain_block() {
+		run_test(|| {
+			initialize_relay_chain();
+
+			let (_, proof) =
+				prepare_parachain_heads_proof(vec![(1, parachain_header(5, Default::default()))]);
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_parachain_heads(
+					Origin::signed(1),
+					H256::repeat_byte(42),
+					vec![ParaId(1)],
+					proof,
+				),
+				Error::<TestRuntime>::UnknownRelayChainBlock,
+			);
+		})
+	}
+
+	#[test]
+	fn parse_finalized_storage_proof_works() {
+		run_test(|| {
+			initialize_relay_chain();
+
+			let (state_root, storage_proof) = bp_runtime::craft_valid_storage_proof();
+			let head = parachain_header(5, state_root);
+			submit_parachain_heads(1, vec![1], vec![(1, head.clone())]);
+
+			assert_eq!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(
+					ParaId(1),
+					head.hash(),
+					storage_proof.clone(),
+					|checker| checker.read_value(b"key1").unwrap(),
+				),
+				Ok(Some(b"value1".to_vec())),
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(
+					ParaId(2),
+					head.hash(),
+					storage_proof,
+					|_| (),
+				),
+				Error::<TestRuntime>::UnknownParaHead,
+			);
+		})
+	}
+}
@@ -0,0 +1,83 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weight-related utilities.
+
+use crate::weights::{RialtoWeight, WeightInfo};
+
+use bp_runtime::Size;
+use frame_support::weights::Weight;
+
+/// Size of the regular parachain head.
+///
+/// It's not that we are expecting all parachain heads to share the same size or that we would
+/// reject all heads that have larger/lesser size. It is about head size that we use in benchmarks.
+/// Relayer would need to pay additional fee for extra bytes.
+///
+/// 384 is a bit larger (1.3 times) than the size of the randomly chosen Polkadot block.
+pub const DEFAULT_PARACHAIN_HEAD_SIZE: u32 = 384;
+
+/// Number of extra bytes (excluding size of storage value itself) of storage proof, built at
+/// the relay chain. This mostly depends on number of entries (and their density) in the storage
+/// trie. Some reserve is reserved to account future chain growth.
+pub const EXTRA_STORAGE_PROOF_SIZE: u32 = 1024;
+
+/// Extended weight info.
+pub trait WeightInfoExt: WeightInfo {
+	/// Size of proof that is already included in the heads submission weight.
+	///
+	/// The proof may be larger than that, because the heads may be larger than
+	/// [`DEFAULT_PARACHAIN_HEAD_SIZE`] or the relayer may add extra trie nodes to the proof. The
+	/// relayer is charged for the extra bytes then.
+	fn expected_extra_storage_proof_size() -> u32;
+
+	/// Weight of the parachain heads submission extrinsic.
+	fn submit_parachain_heads_weight(proof: &impl Size, parachains_count: u32) -> Weight {
+		let base_weight = Self::submit_parachain_heads_with_n_parachains(parachains_count);
+
+		// the proof may be larger than the expected one, so we charge for the extra bytes
+		let expected_proof_size = parachains_count
+			.saturating_mul(DEFAULT_PARACHAIN_HEAD_SIZE)
+			.saturating_add(Self::expected_extra_storage_proof_size());
+		let extra_proof_size = proof.size_hint().saturating_sub(expected_proof_size);
+
+		base_weight.saturating_add(Self::storage_proof_size_overhead(extra_proof_size))
+	}
+
+	/// Returns weight that needs to be accounted when storage proof of given size is received.
+	///
+	/// The cost of a byte is derived from the difference between the 1KB and 16KB proof weights,
+	/// so it is no more accurate than those until they are benchmarked.
+	fn storage_proof_size_overhead(proof_size: u32) -> Weight {
+		let proof_size_in_bytes = proof_size as Weight;
+		let byte_weight = (Self::submit_parachain_heads_with_16kb_proof() -
+			Self::submit_parachain_heads_with_1kb_proof()) /
+			(15 * 1024);
+		proof_size_in_bytes.saturating_mul(byte_weight)
+	}
+}
+
+impl WeightInfoExt for () {
+	fn expected_extra_storage_proof_size() -> u32 {
+		EXTRA_STORAGE_PROOF_SIZE
+	}
+}
+
+impl<T: frame_system::Config> WeightInfoExt for RialtoWeight<T> {
+	fn expected_extra_storage_proof_size() -> u32 {
+		EXTRA_STORAGE_PROOF_SIZE
+	}
+}
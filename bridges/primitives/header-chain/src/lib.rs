@@ -21,6 +21,7 @@
 
 use codec::{Codec, Decode, Encode, EncodeLike};
 use core::{clone::Clone, cmp::Eq, default::Default, fmt::Debug};
+use justification::GrandpaJustification;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -69,6 +70,23 @@ pub struct InitializationData<H: HeaderT> {
 	pub is_halted: bool,
 }
 
+/// Data required for enacting a forced change of the GRANDPA authority set in the bridge pallet.
+///
+/// GRANDPA resorts to forced changes when it stalls. They are not finalized by the previous
+/// authority set, so the bridge pallet can't follow them on its own.
+#[derive(Encode, Decode, RuntimeDebug, PartialEq, Eq, Clone, TypeInfo)]
+pub struct ForcedChangeData<H: HeaderT> {
+	/// The header signaling the forced change.
+	pub change_header: Box<H>,
+	/// A header finalized by the new authorities, at least the delay of the change after the
+	/// `change_header`.
+	pub finality_target: Box<H>,
+	/// The justification of the `finality_target` by the new authorities.
+	pub justification: GrandpaJustification<H>,
+	/// The ID of the new authority set.
+	pub set_id: SetId,
+}
+
 /// base trait for verifying transaction inclusion proofs.
 pub trait InclusionProofVerifier {
 	/// Transaction type.
@@ -131,3 +149,22 @@ pub fn find_grandpa_authorities_scheduled_change<H: HeaderT>(
 	// the right kind of consensus log.
 	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
 }
+
+/// Find header digest that signals a forced change of the GRANDPA authorities set.
+///
+/// Returns the median last finalized block number the change was signaled with, along with the
+/// change itself.
+pub fn find_grandpa_authorities_forced_change<H: HeaderT>(
+	header: &H,
+) -> Option<(H::Number, sp_finality_grandpa::ScheduledChange<H::Number>)> {
+	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+
+	let filter_log = |log: ConsensusLog<H::Number>| match log {
+		ConsensusLog::ForcedChange(median, change) => Some((median, change)),
+		_ => None,
+	};
+
+	// find the first consensus digest with the right ID which converts to
+	// the right kind of consensus log.
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
+}
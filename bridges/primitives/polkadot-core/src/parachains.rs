@@ -0,0 +1,81 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Primitives of the parachains of Polkadot-like chains.
+//!
+//! The types here are encoded the same way as their counterparts in the Polkadot repository, so
+//! they can be read from storage proofs of the bridged relay chain without depending on it.
+
+use bp_runtime::{storage_map_final_key_twox64_concat, Size};
+use frame_support::RuntimeDebug;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::storage::StorageKey;
+use sp_std::{convert::TryFrom, vec::Vec};
+
+/// Name of the storage map of the `paras` pallet that holds the heads of all parachains.
+pub const PARAS_HEADS_MAP_NAME: &str = "Heads";
+
+/// Parachain id.
+///
+/// This is an equivalent of the `polkadot_parachain::Id`, which is encoded as a plain `u32`.
+#[derive(
+	Clone,
+	Copy,
+	Decode,
+	Default,
+	Encode,
+	Eq,
+	Hash,
+	Ord,
+	PartialEq,
+	PartialOrd,
+	RuntimeDebug,
+	TypeInfo,
+)]
+pub struct ParaId(pub u32);
+
+impl From<u32> for ParaId {
+	fn from(id: u32) -> Self {
+		ParaId(id)
+	}
+}
+
+/// Parachain head.
+///
+/// This is an equivalent of the `polkadot_parachain::HeadData`. For Substrate-based parachains
+/// it holds the encoded parachain header.
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParaHead(pub Vec<u8>);
+
+/// Storage proof of parachain heads, built at the relay chain.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParaHeadsProof(pub Vec<Vec<u8>>);
+
+impl Size for ParaHeadsProof {
+	fn size_hint(&self) -> u32 {
+		u32::try_from(self.0.iter().fold(0usize, |sum, node| sum.saturating_add(node.len())))
+			.unwrap_or(u32::MAX)
+	}
+}
+
+/// Return the storage key of the given parachain head in the `paras` pallet of the relay chain.
+pub fn parachain_head_storage_key_at_source(
+	paras_pallet_name: &str,
+	para_id: ParaId,
+) -> StorageKey {
+	storage_map_final_key_twox64_concat(paras_pallet_name, PARAS_HEADS_MAP_NAME, &para_id.encode())
+}
@@ -37,6 +37,7 @@ mod relay_headers;
 mod relay_headers_and_messages;
 mod relay_messages;
 mod resubmit_transactions;
+mod submit_forced_change;
 mod swap_tokens;
 
 /// Parse relay CLI args.
@@ -69,6 +70,12 @@ pub enum Command {
 	///
 	/// Sends initialization transaction to bootstrap the bridge with current finalized block data.
 	InitBridge(init_bridge::InitBridge),
+	/// Submit forced GRANDPA authorities set change to the on-chain bridge pallet.
+	///
+	/// Sends transaction that enacts the forced change signaled by the given source chain header,
+	/// once a header finalized by the new authorities set is available. Must be signed by the
+	/// owner of the pallet or, if the target chain has one, by the sudo key.
+	SubmitForcedChange(submit_forced_change::SubmitForcedChange),
 	/// Send custom message over the bridge.
 	///
 	/// Allows interacting with the bridge by sending messages over `Messages` component.
@@ -107,7 +114,8 @@ impl Command {
 			Self::RelayHeaders(_) |
 			Self::RelayMessages(_) |
 			Self::RelayHeadersAndMessages(_) |
-			Self::InitBridge(_) => {
+			Self::InitBridge(_) |
+			Self::SubmitForcedChange(_) => {
 				initialize_relay();
 			},
 			_ => {
@@ -124,6 +132,7 @@ impl Command {
 			Self::RelayMessages(arg) => arg.run().await?,
 			Self::RelayHeadersAndMessages(arg) => arg.run().await?,
 			Self::InitBridge(arg) => arg.run().await?,
+			Self::SubmitForcedChange(arg) => arg.run().await?,
 			Self::SendMessage(arg) => arg.run().await?,
 			Self::EncodeCall(arg) => arg.run().await?,
 			Self::EncodeMessage(arg) => arg.run().await?,
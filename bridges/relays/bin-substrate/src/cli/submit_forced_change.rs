@@ -0,0 +1,223 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::cli::{SourceConnectionParams, TargetConnectionParams, TargetSigningParams};
+use bp_header_chain::ForcedChangeData;
+use bp_runtime::Chain as ChainBase;
+use codec::Encode;
+use relay_substrate_client::{Chain, TransactionSignScheme, UnsignedTransaction};
+use sp_core::{Bytes, Pair, H256};
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+/// Submit forced GRANDPA authorities set change to the bridge pallet.
+#[derive(StructOpt)]
+pub struct SubmitForcedChange {
+	/// A bridge instance to submit the change to.
+	#[structopt(possible_values = SubmitForcedChangeBridge::VARIANTS, case_insensitive = true)]
+	bridge: SubmitForcedChangeBridge,
+	/// Hash of the source chain header that signals the forced change.
+	#[structopt(long)]
+	change_header: H256,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+/// Bridge to submit the forced change to.
+pub enum SubmitForcedChangeBridge {
+	MillauToRialto,
+	RialtoToMillau,
+	WestendToMillau,
+	RococoToWococo,
+	WococoToRococo,
+	KusamaToPolkadot,
+	PolkadotToKusama,
+}
+
+macro_rules! select_bridge {
+	($bridge: expr, $generic: tt) => {
+		match $bridge {
+			SubmitForcedChangeBridge::MillauToRialto => {
+				type Source = relay_millau_client::Millau;
+				type Target = relay_rialto_client::Rialto;
+
+				fn encode_forced_change(
+					change_data: ForcedChangeData<<Source as ChainBase>::Header>,
+				) -> <Target as Chain>::Call {
+					let forced_change_call =
+						rialto_runtime::BridgeGrandpaMillauCall::submit_forced_authority_change {
+							change_data,
+						};
+					rialto_runtime::SudoCall::sudo { call: Box::new(forced_change_call.into()) }
+						.into()
+				}
+
+				$generic
+			},
+			SubmitForcedChangeBridge::RialtoToMillau => {
+				type Source = relay_rialto_client::Rialto;
+				type Target = relay_millau_client::Millau;
+
+				fn encode_forced_change(
+					change_data: ForcedChangeData<<Source as ChainBase>::Header>,
+				) -> <Target as Chain>::Call {
+					let forced_change_call = millau_runtime::BridgeGrandpaCall::<
+						millau_runtime::Runtime,
+						millau_runtime::RialtoGrandpaInstance,
+					>::submit_forced_authority_change {
+						change_data,
+					};
+					millau_runtime::SudoCall::sudo { call: Box::new(forced_change_call.into()) }
+						.into()
+				}
+
+				$generic
+			},
+			SubmitForcedChangeBridge::WestendToMillau => {
+				type Source = relay_westend_client::Westend;
+				type Target = relay_millau_client::Millau;
+
+				fn encode_forced_change(
+					change_data: ForcedChangeData<<Source as ChainBase>::Header>,
+				) -> <Target as Chain>::Call {
+					let forced_change_call = millau_runtime::BridgeGrandpaCall::<
+						millau_runtime::Runtime,
+						millau_runtime::WestendGrandpaInstance,
+					>::submit_forced_authority_change {
+						change_data,
+					};
+					millau_runtime::SudoCall::sudo { call: Box::new(forced_change_call.into()) }
+						.into()
+				}
+
+				$generic
+			},
+			SubmitForcedChangeBridge::RococoToWococo => {
+				type Source = relay_rococo_client::Rococo;
+				type Target = relay_wococo_client::Wococo;
+
+				fn encode_forced_change(
+					change_data: ForcedChangeData<<Source as ChainBase>::Header>,
+				) -> <Target as Chain>::Call {
+					use relay_wococo_client::runtime::{
+						BridgeGrandpaRococoCall as GrandpaCall, Call,
+					};
+
+					Call::BridgeGrandpaRococo(GrandpaCall::submit_forced_authority_change(
+						change_data,
+					))
+				}
+
+				$generic
+			},
+			SubmitForcedChangeBridge::WococoToRococo => {
+				type Source = relay_wococo_client::Wococo;
+				type Target = relay_rococo_client::Rococo;
+
+				fn encode_forced_change(
+					change_data: ForcedChangeData<<Source as ChainBase>::Header>,
+				) -> <Target as Chain>::Call {
+					use relay_rococo_client::runtime::{
+						BridgeGrandpaWococoCall as GrandpaCall, Call,
+					};
+
+					Call::BridgeGrandpaWococo(GrandpaCall::submit_forced_authority_change(
+						change_data,
+					))
+				}
+
+				$generic
+			},
+			SubmitForcedChangeBridge::KusamaToPolkadot => {
+				type Source = relay_kusama_client::Kusama;
+				type Target = relay_polkadot_client::Polkadot;
+
+				fn encode_forced_change(
+					change_data: ForcedChangeData<<Source as ChainBase>::Header>,
+				) -> <Target as Chain>::Call {
+					use relay_polkadot_client::runtime::{
+						BridgeKusamaGrandpaCall as GrandpaCall, Call,
+					};
+
+					Call::BridgeKusamaGrandpa(GrandpaCall::submit_forced_authority_change(
+						change_data,
+					))
+				}
+
+				$generic
+			},
+			SubmitForcedChangeBridge::PolkadotToKusama => {
+				type Source = relay_polkadot_client::Polkadot;
+				type Target = relay_kusama_client::Kusama;
+
+				fn encode_forced_change(
+					change_data: ForcedChangeData<<Source as ChainBase>::Header>,
+				) -> <Target as Chain>::Call {
+					use relay_kusama_client::runtime::{
+						BridgePolkadotGrandpaCall as GrandpaCall, Call,
+					};
+
+					Call::BridgePolkadotGrandpa(GrandpaCall::submit_forced_authority_change(
+						change_data,
+					))
+				}
+
+				$generic
+			},
+		}
+	};
+}
+
+impl SubmitForcedChange {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_bridge!(self.bridge, {
+			let source_client = self.source.to_client::<Source>().await?;
+			let target_client = self.target.to_client::<Target>().await?;
+			let target_sign = self.target_sign.to_keypair::<Target>()?;
+
+			substrate_relay_helper::forced_authority_change::submit_forced_change(
+				source_client,
+				target_client.clone(),
+				target_sign.public().into(),
+				self.change_header,
+				move |transaction_nonce, change_data| {
+					Bytes(
+						Target::sign_transaction(
+							*target_client.genesis_hash(),
+							&target_sign,
+							relay_substrate_client::TransactionEra::immortal(),
+							UnsignedTransaction::new(
+								encode_forced_change(change_data),
+								transaction_nonce,
+							),
+						)
+						.encode(),
+					)
+				},
+			)
+			.await;
+
+			Ok(())
+		})
+	}
+}
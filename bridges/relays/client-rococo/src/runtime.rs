@@ -91,6 +91,10 @@ pub enum BridgeGrandpaWococoCall {
 	),
 	#[codec(index = 1)]
 	initialize(bp_header_chain::InitializationData<<PolkadotLike as Chain>::Header>),
+	#[codec(index = 4)]
+	submit_forced_authority_change(
+		bp_header_chain::ForcedChangeData<<PolkadotLike as Chain>::Header>,
+	),
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
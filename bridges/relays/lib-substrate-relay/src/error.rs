@@ -55,4 +55,14 @@ pub enum Error<Hash: Debug + MaybeDisplay, HeaderNumber: Debug + MaybeDisplay> {
 	/// Failed to retrieve header by the hash from the source chain.
 	#[error("Failed to retrieve {0} header with hash {1}: {:?}")]
 	RetrieveHeader(&'static str, Hash, client::Error),
+	/// The header of the source chain does not signal a forced GRANDPA authorities set change.
+	#[error("{0} header {1} does not signal a forced GRANDPA authorities set change")]
+	NoForcedChange(&'static str, Hash),
+	/// The forced GRANDPA authorities set change signaled by the header of the source chain is
+	/// never enacted, because the number of its enactment block overflows.
+	#[error("Forced {0} GRANDPA authorities set change signaled by header {1} is never enacted")]
+	ForcedChangeNeverEnacted(&'static str, Hash),
+	/// Failed to guess the id of the GRANDPA authorities set enacted by a forced change.
+	#[error("Failed to guess forced {0} GRANDPA authorities set id: checked all possible ids in range [0; {1}]")]
+	GuessForcedChangeAuthorities(&'static str, HeaderNumber),
 }
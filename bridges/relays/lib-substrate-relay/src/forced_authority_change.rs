@@ -0,0 +1,200 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Submit a forced change of the GRANDPA authorities set of the source chain to the target chain.
+//!
+//! GRANDPA resorts to forced authorities set changes when it stalls. They are not finalized by
+//! the previous authorities set, so the `pallet-bridge-grandpa` pallet can't follow them on its
+//! own and the bridge stops. The transaction built here calls the privileged
+//! `submit_forced_authority_change()` function of the pallet, which enacts the change given a
+//! header finalized by the new authorities set.
+
+use crate::{error::Error, headers_initialize::source_header};
+
+use bp_header_chain::{
+	find_grandpa_authorities_forced_change,
+	justification::{verify_justification, GrandpaJustification},
+	ForcedChangeData,
+};
+use codec::Decode;
+use finality_grandpa::voter_set::VoterSet;
+use num_traits::{CheckedAdd, One, Zero};
+use relay_substrate_client::{Chain, Client};
+use sp_core::Bytes;
+use sp_runtime::traits::Header as HeaderT;
+
+/// Submit transaction enacting the forced GRANDPA authorities set change, signaled by the source
+/// chain header with given hash.
+pub async fn submit_forced_change<SourceChain: Chain, TargetChain: Chain>(
+	source_client: Client<SourceChain>,
+	target_client: Client<TargetChain>,
+	target_transactions_signer: TargetChain::AccountId,
+	change_header_hash: SourceChain::Hash,
+	prepare_forced_change_transaction: impl FnOnce(TargetChain::Index, ForcedChangeData<SourceChain::Header>) -> Bytes
+		+ Send
+		+ 'static,
+) {
+	let result = do_submit_forced_change(
+		source_client,
+		target_client,
+		target_transactions_signer,
+		change_header_hash,
+		prepare_forced_change_transaction,
+	)
+	.await;
+
+	match result {
+		Ok(tx_hash) => log::info!(
+			target: "bridge",
+			"Successfully submitted {}-headers bridge forced authorities change transaction to {}: {:?}",
+			SourceChain::NAME,
+			TargetChain::NAME,
+			tx_hash,
+		),
+		Err(err) => log::error!(
+			target: "bridge",
+			"Failed to submit {}-headers bridge forced authorities change transaction to {}: {:?}",
+			SourceChain::NAME,
+			TargetChain::NAME,
+			err,
+		),
+	}
+}
+
+/// Craft and submit forced change transaction, returning any error that may occur.
+async fn do_submit_forced_change<SourceChain: Chain, TargetChain: Chain>(
+	source_client: Client<SourceChain>,
+	target_client: Client<TargetChain>,
+	target_transactions_signer: TargetChain::AccountId,
+	change_header_hash: SourceChain::Hash,
+	prepare_forced_change_transaction: impl FnOnce(TargetChain::Index, ForcedChangeData<SourceChain::Header>) -> Bytes
+		+ Send
+		+ 'static,
+) -> Result<TargetChain::Hash, Error<SourceChain::Hash, <SourceChain::Header as HeaderT>::Number>> {
+	let change_data = prepare_forced_change_data(source_client, change_header_hash).await?;
+	log::info!(
+		target: "bridge",
+		"Prepared forced authorities change data for {}-headers bridge at {}: {:?}",
+		SourceChain::NAME,
+		TargetChain::NAME,
+		change_data,
+	);
+
+	let forced_change_tx_hash = target_client
+		.submit_signed_extrinsic(target_transactions_signer, move |_, transaction_nonce| {
+			prepare_forced_change_transaction(transaction_nonce, change_data)
+		})
+		.await
+		.map_err(|err| Error::SubmitTransaction(TargetChain::NAME, err))?;
+	Ok(forced_change_tx_hash)
+}
+
+/// Prepare forced change data for the GRANDPA verifier pallet.
+async fn prepare_forced_change_data<SourceChain: Chain>(
+	source_client: Client<SourceChain>,
+	change_header_hash: SourceChain::Hash,
+) -> Result<
+	ForcedChangeData<SourceChain::Header>,
+	Error<SourceChain::Hash, <SourceChain::Header as HeaderT>::Number>,
+> {
+	let change_header = source_header(&source_client, change_header_hash).await?;
+	let (_, change) = find_grandpa_authorities_forced_change(&change_header)
+		.ok_or(Error::NoForcedChange(SourceChain::NAME, change_header_hash))?;
+	let enacted_at = change_header
+		.number()
+		.checked_add(&change.delay)
+		.ok_or(Error::ForcedChangeNeverEnacted(SourceChain::NAME, change_header_hash))?;
+	log::trace!(target: "bridge", "Selected {} forced authorities change, enacted at {}: {:?}",
+		SourceChain::NAME,
+		enacted_at,
+		change.next_authorities,
+	);
+
+	// Wait for the first justification of a header that is finalized by the new authorities set.
+	// We assume that there are no further authorities set changes in between.
+	let justifications = source_client
+		.subscribe_justifications()
+		.await
+		.map_err(|err| Error::Subscribe(SourceChain::NAME, err))?;
+	let justification = loop {
+		let justification = justifications
+			.next()
+			.await
+			.map_err(|e| Error::ReadJustification(SourceChain::NAME, e))
+			.and_then(|justification| {
+				justification.ok_or(Error::ReadJustificationStreamEnded(SourceChain::NAME))
+			})?;
+		let justification: GrandpaJustification<SourceChain::Header> =
+			Decode::decode(&mut &justification.0[..])
+				.map_err(|err| Error::DecodeJustification(SourceChain::NAME, err))?;
+
+		if justification.commit.target_number >= enacted_at {
+			break justification
+		}
+	};
+
+	let (target_header_hash, target_header_number) =
+		(justification.commit.target_hash, justification.commit.target_number);
+	let finality_target = source_header(&source_client, target_header_hash).await?;
+	log::trace!(target: "bridge", "Selected {} finality target: {}/{}",
+		SourceChain::NAME,
+		target_header_number,
+		target_header_hash,
+	);
+
+	// The id of the new authorities set is not part of the change, so let's guess it by verifying
+	// the justification.
+	let mut set_id = 0;
+	let mut min_possible_block_number = SourceChain::BlockNumber::zero();
+	let next_authorities = VoterSet::new(change.next_authorities.clone())
+		.ok_or(Error::ReadInvalidAuthorities(SourceChain::NAME, change.next_authorities))?;
+	loop {
+		log::trace!(
+			target: "bridge", "Trying {} GRANDPA authorities set id: {}",
+			SourceChain::NAME,
+			set_id,
+		);
+
+		let is_valid_set_id = verify_justification::<SourceChain::Header>(
+			(target_header_hash, target_header_number),
+			set_id,
+			&next_authorities,
+			&justification,
+		)
+		.is_ok();
+
+		if is_valid_set_id {
+			break
+		}
+
+		set_id += 1;
+		min_possible_block_number += One::one();
+		if min_possible_block_number > target_header_number {
+			// there can't be more authorities set changes than headers => fail
+			return Err(Error::GuessForcedChangeAuthorities(
+				SourceChain::NAME,
+				target_header_number,
+			))
+		}
+	}
+
+	Ok(ForcedChangeData {
+		change_header: Box::new(change_header),
+		finality_target: Box::new(finality_target),
+		justification,
+		set_id,
+	})
+}
@@ -217,7 +217,7 @@ async fn prepare_initialization_data<SourceChain: Chain>(
 }
 
 /// Read header by hash from the source client.
-async fn source_header<SourceChain: Chain>(
+pub(crate) async fn source_header<SourceChain: Chain>(
 	source_client: &Client<SourceChain>,
 	header_hash: SourceChain::Hash,
 ) -> Result<SourceChain::Header, Error<SourceChain::Hash, <SourceChain::Header as HeaderT>::Number>>
@@ -24,12 +24,14 @@ pub mod conversion_rate_update;
 pub mod error;
 pub mod finality_pipeline;
 pub mod finality_target;
+pub mod forced_authority_change;
 pub mod headers_initialize;
 pub mod helpers;
 pub mod messages_lane;
 pub mod messages_source;
 pub mod messages_target;
 pub mod on_demand_headers;
+pub mod parachain_heads;
 
 /// Default relay loop stall timeout. If transactions generated by relay are immortal, then
 /// this timeout is used.
@@ -0,0 +1,204 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relay heads of the parachains of a Substrate relay chain to the `pallet-bridge-parachains`
+//! pallet at the target chain.
+//!
+//! The loop follows the best finalized relay chain header known to the GRANDPA pallet at the
+//! target chain. Whenever a tracked parachain has a head at that header that differs from the
+//! best head known to the parachains pallet, the storage proof of the heads is submitted to the
+//! `submit_parachain_heads()` function of the pallet.
+
+use bp_polkadot_core::parachains::{
+	parachain_head_storage_key_at_source, ParaHead, ParaHeadsProof, ParaId,
+};
+use bp_runtime::storage_map_final_key_twox64_concat;
+use codec::{Decode, Encode};
+use pallet_bridge_parachains::BestParaHead;
+use relay_substrate_client::{Chain, ChainBase, Client, Error as SubstrateError};
+use sp_core::Bytes;
+use sp_runtime::traits::Hash as HashT;
+
+/// Name of the map of best parachain heads in the parachains pallet.
+const BEST_PARA_HEADS_MAP_NAME: &str = "BestParaHeads";
+
+/// Parameters of the parachain heads relay.
+#[derive(Debug, Clone)]
+pub struct ParachainHeadsParams {
+	/// Parachains whose heads are relayed.
+	pub parachains: Vec<ParaId>,
+	/// Name of the `paras` pallet in the runtime of the source relay chain.
+	pub paras_pallet_name: &'static str,
+	/// Name of the parachains pallet in the runtime of the target chain.
+	pub parachains_pallet_name: &'static str,
+	/// Name of the target chain runtime method that returns the best finalized source relay
+	/// chain header.
+	pub best_finalized_source_header_method: &'static str,
+}
+
+/// Run infinite parachain heads relay loop.
+///
+/// `SourceParachain` is the parachain whose header type is used by the parachains pallet. It is
+/// only used to compute hashes of the parachain heads.
+pub fn run_parachain_heads_loop<SourceRelayChain, SourceParachain, TargetChain>(
+	source_client: Client<SourceRelayChain>,
+	target_client: Client<TargetChain>,
+	target_transactions_signer: TargetChain::AccountId,
+	params: ParachainHeadsParams,
+	prepare_submit_parachain_heads: impl Fn(TargetChain::Index, SourceRelayChain::Hash, Vec<ParaId>, ParaHeadsProof) -> Bytes
+		+ Clone
+		+ Send
+		+ Sync
+		+ 'static,
+) where
+	SourceRelayChain: Chain,
+	SourceParachain: ChainBase,
+	TargetChain: Chain,
+{
+	async_std::task::spawn(async move {
+		let mut submitted_at = None;
+		loop {
+			async_std::task::sleep(SourceRelayChain::AVERAGE_BLOCK_INTERVAL).await;
+			let result = update_parachain_heads::<SourceRelayChain, SourceParachain, TargetChain>(
+				&source_client,
+				&target_client,
+				target_transactions_signer.clone(),
+				&params,
+				&mut submitted_at,
+				prepare_submit_parachain_heads.clone(),
+			)
+			.await;
+			if let Err(error) = result {
+				log::error!(
+					target: "bridge",
+					"Failed to relay {} parachain heads to {}: {:?}",
+					SourceRelayChain::NAME,
+					TargetChain::NAME,
+					error,
+				);
+			}
+		}
+	});
+}
+
+/// Submit the heads of parachains that have changed at the best finalized source relay chain
+/// header known to the target chain.
+///
+/// Nothing is submitted at the relay chain header we have already submitted heads at, until the
+/// target chain learns about a newer relay chain header.
+async fn update_parachain_heads<SourceRelayChain, SourceParachain, TargetChain>(
+	source_client: &Client<SourceRelayChain>,
+	target_client: &Client<TargetChain>,
+	target_transactions_signer: TargetChain::AccountId,
+	params: &ParachainHeadsParams,
+	submitted_at: &mut Option<SourceRelayChain::Hash>,
+	prepare_submit_parachain_heads: impl FnOnce(TargetChain::Index, SourceRelayChain::Hash, Vec<ParaId>, ParaHeadsProof) -> Bytes
+		+ Send
+		+ 'static,
+) -> Result<(), SubstrateError>
+where
+	SourceRelayChain: Chain,
+	SourceParachain: ChainBase,
+	TargetChain: Chain,
+{
+	let target_best_finalized_hash = target_client.best_finalized_header_hash().await?;
+	let encoded_best_finalized_source = target_client
+		.state_call(
+			params.best_finalized_source_header_method.into(),
+			Bytes(Vec::new()),
+			Some(target_best_finalized_hash),
+		)
+		.await?;
+	let (_, relay_block_hash): (SourceRelayChain::BlockNumber, SourceRelayChain::Hash) =
+		Decode::decode(&mut &encoded_best_finalized_source.0[..])
+			.map_err(SubstrateError::ResponseParseFailed)?;
+	if *submitted_at == Some(relay_block_hash) {
+		return Ok(())
+	}
+
+	let mut updated_parachains = Vec::new();
+	for parachain in &params.parachains {
+		let head_at_source: Option<ParaHead> = source_client
+			.storage_value(
+				parachain_head_storage_key_at_source(params.paras_pallet_name, *parachain),
+				Some(relay_block_hash),
+			)
+			.await?;
+		let head_hash_at_source = match head_at_source {
+			Some(head) => <SourceParachain::Hasher as HashT>::hash(&head.0),
+			None => continue,
+		};
+
+		let best_head_at_target: Option<
+			BestParaHead<SourceRelayChain::BlockNumber, SourceParachain::Hash>,
+		> = target_client
+			.storage_value(
+				storage_map_final_key_twox64_concat(
+					params.parachains_pallet_name,
+					BEST_PARA_HEADS_MAP_NAME,
+					&parachain.encode(),
+				),
+				Some(target_best_finalized_hash),
+			)
+			.await?;
+		if best_head_at_target.map(|best_head| best_head.head_hash) != Some(head_hash_at_source) {
+			log::trace!(
+				target: "bridge",
+				"Selected head {:?} of {} parachain {:?} for relay to {}",
+				head_hash_at_source,
+				SourceRelayChain::NAME,
+				parachain,
+				TargetChain::NAME,
+			);
+			updated_parachains.push(*parachain);
+		}
+	}
+
+	if updated_parachains.is_empty() {
+		return Ok(())
+	}
+
+	let storage_keys = updated_parachains
+		.iter()
+		.map(|parachain| parachain_head_storage_key_at_source(params.paras_pallet_name, *parachain))
+		.collect();
+	let proof = source_client.prove_storage(storage_keys, relay_block_hash).await?;
+	let proof = ParaHeadsProof(proof.iter_nodes().collect());
+
+	let parachains_count = updated_parachains.len();
+	let tx_hash = target_client
+		.submit_signed_extrinsic(target_transactions_signer, move |_, transaction_nonce| {
+			prepare_submit_parachain_heads(
+				transaction_nonce,
+				relay_block_hash,
+				updated_parachains,
+				proof,
+			)
+		})
+		.await?;
+	*submitted_at = Some(relay_block_hash);
+	log::info!(
+		target: "bridge",
+		"Submitted heads of {} {} parachains at {:?} to {}: {:?}",
+		parachains_count,
+		SourceRelayChain::NAME,
+		relay_block_hash,
+		TargetChain::NAME,
+		tx_hash,
+	);
+
+	Ok(())
+}
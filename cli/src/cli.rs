@@ -16,7 +16,7 @@
 
 //! Polkadot CLI library.
 
-use structopt::StructOpt;
+use structopt::{clap::arg_enum, StructOpt};
 
 #[allow(missing_docs)]
 #[derive(Debug, StructOpt)]
@@ -68,6 +68,13 @@ pub enum Subcommand {
 
 	/// Key management CLI utilities
 	Key(sc_cli::KeySubcommand),
+
+	/// Decode the records of the parachains DB and print them as JSON.
+	#[structopt(name = "parachains-db")]
+	ParachainsDb(ParachainsDbCmd),
+
+	/// Re-validate the candidate of a dispute from the evidence written by a node.
+	CheckDisputeEvidence(CheckDisputeEvidenceCmd),
 }
 
 #[allow(missing_docs)]
@@ -77,6 +84,67 @@ pub struct ValidationWorkerCommand {
 	pub socket_path: String,
 }
 
+#[allow(missing_docs)]
+#[derive(Debug, StructOpt)]
+pub struct ParachainsDbCmd {
+	/// The sections of the parachains DB to inspect. All sections are inspected by default.
+	#[structopt(
+		long = "section",
+		case_insensitive = true,
+		possible_values = &ParachainsDbSection::variants(),
+	)]
+	pub sections: Vec<ParachainsDbSection>,
+
+	/// Only show the records concerning the given session.
+	#[structopt(long)]
+	pub session: Option<u32>,
+
+	/// Only show the records concerning the given relay chain block hash.
+	#[structopt(long)]
+	pub block: Option<sp_core::H256>,
+
+	/// Only show the records concerning the given candidate hash.
+	#[structopt(long)]
+	pub candidate: Option<sp_core::H256>,
+
+	/// The database backend of the parachains DB.
+	#[structopt(
+		long,
+		case_insensitive = true,
+		possible_values = &ParachainsDbBackend::variants(),
+		default_value = "RocksDb",
+	)]
+	pub parachains_db_backend: ParachainsDbBackend,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, StructOpt)]
+pub struct CheckDisputeEvidenceCmd {
+	/// The evidence file, as written to the directory given with `--dispute-evidence-dir`.
+	#[structopt(parse(from_os_str))]
+	pub path: std::path::PathBuf,
+}
+
+arg_enum! {
+	/// A section of the parachains DB, owned by a single subsystem.
+	#[allow(missing_docs)]
+	#[derive(Debug, Clone, Copy)]
+	pub enum ParachainsDbSection {
+		AvailabilityStore,
+		ApprovalVoting,
+		ChainSelection,
+		DisputeCoordinator,
+	}
+}
+
 #[allow(missing_docs)]
 #[derive(Debug, StructOpt)]
 pub struct RunCmd {
@@ -115,6 +183,89 @@ pub struct RunCmd {
 	/// commonly `127.0.0.1:6831`.
 	#[structopt(long)]
 	pub jaeger_agent: Option<std::net::SocketAddr>,
+
+	/// Run the PVF preparation and execution workers in a sandbox.
+	///
+	/// The workers are put into separate user, mount and network namespaces, can only access the
+	/// artifact cache and are restricted to an allowlist of system calls. Only supported on Linux.
+	#[structopt(long)]
+	pub pvf_sandbox: bool,
+
+	/// Limit the address space of the PVF execution workers to the given number of MiB.
+	///
+	/// Executions that fail to reserve the memory for the PVF instance within the limit are not
+	/// used to judge the candidate. Note that wasmtime reserves large regions of virtual memory,
+	/// so the limit must be set generously. Unlimited by default.
+	#[structopt(long, value_name = "MiB")]
+	pub pvf_execute_address_space_limit: Option<u64>,
+
+	/// Re-execute candidates that fail on the compiled engine with the interpreter.
+	///
+	/// If the two execution engines disagree, the candidate is not deemed invalid and the
+	/// disagreement is reported. This considerably slows down the validation of invalid
+	/// candidates and is meant for diagnosing executor bugs.
+	#[structopt(long)]
+	pub pvf_dual_execute: bool,
+
+	/// The database backend of the parachains DB.
+	///
+	/// Switching an existing node to ParityDB migrates its RocksDB parachains DB on startup.
+	#[structopt(
+		long,
+		case_insensitive = true,
+		possible_values = &ParachainsDbBackend::variants(),
+		default_value = "RocksDb",
+	)]
+	pub parachains_db_backend: ParachainsDbBackend,
+
+	/// Write the evidence of disputes concluding against a candidate to the given directory.
+	///
+	/// The evidence can be examined offline with the `check-dispute-evidence` command.
+	#[structopt(long, parse(from_os_str))]
+	pub dispute_evidence_dir: Option<std::path::PathBuf>,
+
+	/// Fetch the collations advertised by a collator before all others while validating its para.
+	///
+	/// Takes the form `PARA_ID=COLLATOR_ID`, with the collator ID in SS58 format, and can be given
+	/// multiple times. Meant for parachains with a permissioned set of collators.
+	#[structopt(long = "preferred-collator", value_name = "PARA_ID=COLLATOR_ID")]
+	pub preferred_collators: Vec<String>,
+
+	/// Record the signals and messages delivered to the given subsystem to `--trace-path`.
+	///
+	/// The trace can be replayed against a standalone instance of the subsystem to reproduce
+	/// its behavior.
+	#[structopt(
+		long,
+		case_insensitive = true,
+		possible_values = &TracedSubsystem::variants(),
+		requires = "trace-path",
+	)]
+	pub trace_subsystem: Option<TracedSubsystem>,
+
+	/// The file the trace requested with `--trace-subsystem` is written to.
+	#[structopt(long, parse(from_os_str), requires = "trace-subsystem")]
+	pub trace_path: Option<std::path::PathBuf>,
+}
+
+arg_enum! {
+	/// The database backend of the parachains DB.
+	#[allow(missing_docs)]
+	#[derive(Debug, Clone, Copy)]
+	pub enum ParachainsDbBackend {
+		RocksDb,
+		ParityDb,
+	}
+}
+
+arg_enum! {
+	/// The subsystems whose signals and messages can be recorded.
+	#[allow(missing_docs)]
+	#[derive(Debug, Clone, Copy)]
+	pub enum TracedSubsystem {
+		ApprovalVoting,
+		DisputeCoordinator,
+	}
 }
 
 #[allow(missing_docs)]
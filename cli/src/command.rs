@@ -14,12 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::cli::{Cli, Subcommand};
+use crate::cli::{
+	Cli, ParachainsDbBackend, ParachainsDbCmd, ParachainsDbSection, Subcommand, TracedSubsystem,
+};
 use futures::future::TryFutureExt;
 use log::info;
 use sc_cli::{Role, RuntimeVersion, SubstrateCli};
 use service::{self, IdentifyVariant};
-use sp_core::crypto::Ss58AddressFormatRegistry;
+use sp_core::crypto::{Ss58AddressFormatRegistry, Ss58Codec};
+use std::collections::HashMap;
 
 pub use crate::error::Error;
 pub use polkadot_performance_test::PerfCheckError;
@@ -216,6 +219,62 @@ fn ensure_dev(spec: &Box<dyn service::ChainSpec>) -> std::result::Result<(), Str
 	}
 }
 
+fn parachains_db_backend(backend: ParachainsDbBackend) -> service::ParachainsDbBackend {
+	match backend {
+		ParachainsDbBackend::RocksDb => service::ParachainsDbBackend::RocksDb,
+		ParachainsDbBackend::ParityDb => service::ParachainsDbBackend::ParityDb,
+	}
+}
+
+fn subsystem_trace(
+	subsystem: Option<TracedSubsystem>,
+	path: Option<std::path::PathBuf>,
+) -> Option<service::SubsystemTrace> {
+	let subsystem = match subsystem? {
+		TracedSubsystem::ApprovalVoting => service::TracedSubsystem::ApprovalVoting,
+		TracedSubsystem::DisputeCoordinator => service::TracedSubsystem::DisputeCoordinator,
+	};
+	Some(service::SubsystemTrace { subsystem, path: path? })
+}
+
+fn preferred_collators(
+	args: &[String],
+) -> Result<HashMap<service::ParaId, Vec<service::CollatorId>>> {
+	let mut preferred_collators = HashMap::<_, Vec<_>>::new();
+	for arg in args {
+		let invalid =
+			|reason| Error::Other(format!("Invalid preferred collator `{}`: {}", arg, reason));
+		let (para_id, collator_id) =
+			arg.split_once('=').ok_or_else(|| invalid("expected `PARA_ID=COLLATOR_ID`"))?;
+		let para_id = para_id.parse::<u32>().map_err(|_| invalid("invalid para ID"))?;
+		let collator_id = sp_core::sr25519::Public::from_ss58check(collator_id)
+			.map_err(|_| invalid("invalid SS58 collator ID"))?;
+		preferred_collators.entry(para_id.into()).or_default().push(collator_id.into());
+	}
+	Ok(preferred_collators)
+}
+
+fn parachains_db_section(section: ParachainsDbSection) -> service::parachains_db_inspect::Section {
+	use service::parachains_db_inspect::Section;
+
+	match section {
+		ParachainsDbSection::AvailabilityStore => Section::AvailabilityStore,
+		ParachainsDbSection::ApprovalVoting => Section::ApprovalVoting,
+		ParachainsDbSection::ChainSelection => Section::ChainSelection,
+		ParachainsDbSection::DisputeCoordinator => Section::DisputeCoordinator,
+	}
+}
+
+impl sc_cli::CliConfiguration for ParachainsDbCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
 /// Runs performance checks.
 /// Should only be used in release build since the check would take too much time otherwise.
 fn host_perf_check() -> Result<()> {
@@ -269,6 +328,31 @@ where
 
 	let jaeger_agent = cli.run.jaeger_agent;
 
+	let enable_pvf_sandbox = cli.run.pvf_sandbox;
+	if enable_pvf_sandbox {
+		polkadot_node_core_pvf::check_sandbox_support().map_err(|e| {
+			Error::Other(format!("The PVF worker sandbox cannot be enabled: {}", e))
+		})?;
+	}
+
+	let pvf_execute_address_space_limit = cli
+		.run
+		.pvf_execute_address_space_limit
+		.map(|mib| mib.saturating_mul(1024 * 1024));
+
+	let parachains_db_backend = parachains_db_backend(cli.run.parachains_db_backend);
+
+	let preferred_collators = preferred_collators(&cli.run.preferred_collators)?;
+
+	let subsystem_trace = subsystem_trace(cli.run.trace_subsystem, cli.run.trace_path.clone());
+
+	let dispute_evidence_dir = cli.run.dispute_evidence_dir.clone();
+	if let Some(dir) = &dispute_evidence_dir {
+		std::fs::create_dir_all(dir).map_err(|e| {
+			Error::Other(format!("Cannot create the dispute evidence directory {:?}: {}", dir, e))
+		})?;
+	}
+
 	runner.run_node_until_exit(move |config| async move {
 		let role = config.role.clone();
 
@@ -281,6 +365,13 @@ where
 				cli.run.beefy,
 				jaeger_agent,
 				None,
+				enable_pvf_sandbox,
+				pvf_execute_address_space_limit,
+				cli.run.pvf_dual_execute,
+				parachains_db_backend,
+				dispute_evidence_dir,
+				preferred_collators,
+				subsystem_trace,
 				overseer_gen,
 			)
 			.map(|full| full.task_manager)
@@ -447,6 +538,45 @@ pub fn run() -> Result<()> {
 			host_perf_check()
 		},
 		Some(Subcommand::Key(cmd)) => Ok(cmd.run(&cli)?),
+		Some(Subcommand::ParachainsDb(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+
+			Ok(runner.sync_run(|config| {
+				let root = config
+					.database
+					.path()
+					.ok_or_else(|| Error::Other("The parachains DB is kept on disk only".into()))?
+					.to_owned();
+				let sections = if cmd.sections.is_empty() {
+					service::parachains_db_inspect::Section::all().to_vec()
+				} else {
+					cmd.sections.iter().copied().map(parachains_db_section).collect()
+				};
+				let filter = service::parachains_db_inspect::Filter {
+					session: cmd.session,
+					block: cmd.block,
+					candidate: cmd.candidate.map(service::parachains_db_inspect::CandidateHash),
+				};
+
+				let records = service::parachains_db_inspect::inspect(
+					root,
+					parachains_db_backend(cmd.parachains_db_backend),
+					&sections,
+					&filter,
+				)
+				.map_err(service::Error::from)?;
+				println!("{:#}", records);
+
+				Ok::<_, Error>(())
+			})?)
+		},
+		Some(Subcommand::CheckDisputeEvidence(cmd)) => {
+			let mut builder = sc_cli::LoggerBuilder::new("");
+			builder.with_colors(true);
+			builder.init()?;
+
+			crate::dispute_evidence::check_dispute_evidence(&cmd.path)
+		},
 		#[cfg(feature = "try-runtime")]
 		Some(Subcommand::TryRuntime(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
@@ -0,0 +1,186 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Re-validating the candidate of a dispute offline, from the evidence written by a node.
+
+use crate::error::Error;
+use parity_scale_codec::{Decode, Encode};
+use polkadot_node_core_pvf::{sp_maybe_compressed_blob, testing::validate_candidate};
+use polkadot_node_primitives::{
+	BlockData, DisputeEvidence, POV_BOMB_LIMIT, VALIDATION_CODE_BOMB_LIMIT,
+};
+use polkadot_parachain::primitives::{ValidationParams, ValidationResult};
+use polkadot_primitives::v1::CandidateCommitments;
+use std::path::Path;
+
+/// Print the dispute described by the evidence file at `path` and re-validate its candidate.
+pub fn check_dispute_evidence(path: &Path) -> Result<(), Error> {
+	let bytes = std::fs::read(path)
+		.map_err(|e| Error::Other(format!("Failed to read {:?}: {}", path, e)))?;
+	let evidence = DisputeEvidence::decode(&mut &bytes[..])
+		.map_err(|e| Error::Other(format!("Failed to decode {:?}: {}", path, e)))?;
+	let candidate_receipt = evidence.candidate_receipt();
+
+	println!("candidate: {:?}", candidate_receipt.hash());
+	println!("para:      {}", candidate_receipt.descriptor.para_id);
+	println!("session:   {}", evidence.session);
+	println!(
+		"votes:     {} valid, {} invalid",
+		evidence.votes.valid.len(),
+		evidence.votes.invalid.len(),
+	);
+	match revalidate(&evidence) {
+		Ok(()) => println!("verdict:   valid"),
+		Err(reason) => println!("verdict:   invalid, {}", reason),
+	}
+
+	Ok(())
+}
+
+/// Validate the candidate in the same way candidate validation does it, returning why it is
+/// invalid if it is.
+fn revalidate(evidence: &DisputeEvidence) -> Result<(), String> {
+	let candidate_receipt = evidence.candidate_receipt();
+	let descriptor = &candidate_receipt.descriptor;
+	let validation_data = &evidence.available_data.validation_data;
+	let pov = &evidence.available_data.pov;
+
+	if evidence.validation_code.hash() != descriptor.validation_code_hash {
+		return Err("the validation code does not match the descriptor".into())
+	}
+	if pov.hash() != descriptor.pov_hash {
+		return Err("the PoV does not match the descriptor".into())
+	}
+	if validation_data.hash() != descriptor.persisted_validation_data_hash {
+		return Err("the validation data does not match the descriptor".into())
+	}
+
+	let code = sp_maybe_compressed_blob::decompress(
+		&evidence.validation_code.0,
+		VALIDATION_CODE_BOMB_LIMIT,
+	)
+	.map_err(|e| format!("the validation code cannot be decompressed: {}", e))?;
+	let block_data = sp_maybe_compressed_blob::decompress(&pov.block_data.0, POV_BOMB_LIMIT)
+		.map_err(|e| format!("the PoV cannot be decompressed: {}", e))?;
+	let params = ValidationParams {
+		parent_head: validation_data.parent_head.clone(),
+		block_data: BlockData(block_data.to_vec()),
+		relay_parent_number: validation_data.relay_parent_number,
+		relay_parent_storage_root: validation_data.relay_parent_storage_root,
+	};
+
+	let result = validate_candidate(&code, &params.encode())
+		.map_err(|e| format!("the execution failed: {}", e))?;
+	let result = ValidationResult::decode(&mut &result[..])
+		.map_err(|e| format!("the result cannot be decoded: {}", e))?;
+
+	if result.head_data.hash() != descriptor.para_head {
+		return Err("the head data does not match the descriptor".into())
+	}
+	let commitments = CandidateCommitments {
+		head_data: result.head_data,
+		upward_messages: result.upward_messages,
+		horizontal_messages: result.horizontal_messages,
+		new_validation_code: result.new_validation_code,
+		processed_downward_messages: result.processed_downward_messages,
+		hrmp_watermark: result.hrmp_watermark,
+	};
+	if commitments.hash() != candidate_receipt.commitments_hash {
+		return Err("the commitments do not match the receipt".into())
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_primitives::{AvailableData, CandidateVotes, PoV};
+	use polkadot_parachain::primitives::HeadData;
+	use polkadot_primitives::v1::{
+		CandidateReceipt, Hash, PersistedValidationData, ValidationCode,
+	};
+	use std::sync::Arc;
+	use test_helpers::dummy_candidate_descriptor;
+
+	/// The evidence of a valid adder block on top of the genesis head.
+	fn adder_evidence() -> DisputeEvidence {
+		let parent_head =
+			adder::HeadData { number: 0, parent_hash: [0; 32], post_state: adder::hash_state(0) };
+		let block_data = adder::BlockData { state: 0, add: 512 };
+		let head_data = adder::execute(parent_head.hash(), parent_head.clone(), &block_data)
+			.expect("the block is valid");
+
+		let validation_data = PersistedValidationData {
+			parent_head: HeadData(parent_head.encode()),
+			relay_parent_number: 1,
+			..Default::default()
+		};
+		let pov = PoV { block_data: BlockData(block_data.encode()) };
+		let validation_code = ValidationCode(adder::wasm_binary_unwrap().to_vec());
+		let commitments = CandidateCommitments {
+			head_data: HeadData(head_data.encode()),
+			hrmp_watermark: validation_data.relay_parent_number,
+			..Default::default()
+		};
+
+		let mut descriptor = dummy_candidate_descriptor(Hash::zero());
+		descriptor.persisted_validation_data_hash = validation_data.hash();
+		descriptor.pov_hash = pov.hash();
+		descriptor.para_head = commitments.head_data.hash();
+		descriptor.validation_code_hash = validation_code.hash();
+		let candidate_receipt =
+			CandidateReceipt { descriptor, commitments_hash: commitments.hash() };
+
+		DisputeEvidence {
+			session: 1,
+			votes: CandidateVotes { candidate_receipt, valid: Vec::new(), invalid: Vec::new() },
+			available_data: AvailableData { pov: Arc::new(pov), validation_data },
+			validation_code,
+		}
+	}
+
+	#[test]
+	fn valid_candidate_passes_revalidation() {
+		assert_eq!(revalidate(&adder_evidence()), Ok(()));
+	}
+
+	#[test]
+	fn invalid_candidate_fails_revalidation() {
+		let mut evidence = adder_evidence();
+		evidence.available_data.pov = Arc::new(PoV { block_data: BlockData(vec![1, 2, 3]) });
+		assert_eq!(revalidate(&evidence), Err("the PoV does not match the descriptor".into()));
+
+		let mut evidence = adder_evidence();
+		evidence.votes.candidate_receipt.commitments_hash = Hash::repeat_byte(1);
+		assert_eq!(revalidate(&evidence), Err("the commitments do not match the receipt".into()));
+	}
+
+	#[test]
+	fn evidence_files_are_checked() {
+		let path = std::env::temp_dir()
+			.join(format!("check-dispute-evidence-{}.evidence", std::process::id()));
+
+		std::fs::write(&path, adder_evidence().encode()).unwrap();
+		assert!(check_dispute_evidence(&path).is_ok());
+
+		std::fs::write(&path, [1, 2, 3]).unwrap();
+		assert!(check_dispute_evidence(&path).is_err());
+
+		std::fs::remove_file(&path).unwrap();
+		assert!(check_dispute_evidence(&path).is_err());
+	}
+}
@@ -23,6 +23,8 @@ mod cli;
 #[cfg(feature = "cli")]
 mod command;
 #[cfg(feature = "cli")]
+mod dispute_evidence;
+#[cfg(feature = "cli")]
 mod error;
 #[cfg(all(feature = "cli", build_type = "release"))]
 mod host_perf_check;
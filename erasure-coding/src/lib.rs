@@ -28,8 +28,11 @@ use std::convert::TryFrom;
 
 use parity_scale_codec::{Decode, Encode};
 use polkadot_node_primitives::{AvailableData, Proof};
-use polkadot_primitives::v0::{self, BlakeTwo256, Hash as H256, HashT};
-use sp_core::Blake2Hasher;
+use polkadot_primitives::{
+	v0::{self, BlakeTwo256, Hash as H256, HashT},
+	v1::{CoreIndex, ValidatorIndex},
+};
+use sp_core::{hashing::blake2_256, Blake2Hasher};
 use thiserror::Error;
 use trie::{
 	trie_types::{TrieDB, TrieDBMutV0 as TrieDBMut},
@@ -98,6 +101,15 @@ pub const fn recovery_threshold(n_validators: usize) -> Result<usize, Error> {
 	Ok(needed + 1)
 }
 
+/// Obtain the number of systematic chunks that should be enough to recover the data.
+///
+/// The systematic chunks are the ones with the lowest indices, which contain the
+/// original data. Recovering from them only requires a concatenation of their
+/// contents instead of a full decode.
+pub fn systematic_recovery_threshold(n_validators: usize) -> Result<usize, Error> {
+	code_params(n_validators).map(|params| params.k())
+}
+
 fn code_params(n_validators: usize) -> Result<CodeParams, Error> {
 	// we need to be able to reconstruct from 1/3 - eps
 
@@ -230,6 +242,112 @@ where
 	Decode::decode(&mut &payload_bytes[..]).or_else(|_e| Err(Error::BadPayload))
 }
 
+/// Reconstruct the v1 available data from the systematic chunks.
+///
+/// The chunks with indices `0..systematic_recovery_threshold(n_validators)` have to be
+/// provided, in order of their indices.
+///
+/// Works only up to 65536 validators, and `n_validators` must be non-zero.
+pub fn reconstruct_from_systematic_v1<I>(
+	n_validators: usize,
+	chunks: &[I],
+) -> Result<AvailableData, Error>
+where
+	I: AsRef<[u8]>,
+{
+	reconstruct_from_systematic(n_validators, chunks)
+}
+
+/// Reconstruct decodable data from the systematic chunks.
+///
+/// The chunks with indices `0..systematic_recovery_threshold(n_validators)` have to be
+/// provided, in order of their indices. Any further chunks are ignored.
+///
+/// Works only up to 65536 validators, and `n_validators` must be non-zero.
+pub fn reconstruct_from_systematic<I, T: Decode>(
+	n_validators: usize,
+	chunks: &[I],
+) -> Result<T, Error>
+where
+	I: AsRef<[u8]>,
+{
+	let threshold = systematic_recovery_threshold(n_validators)?;
+	let chunks = chunks.get(..threshold).ok_or(Error::NotEnoughChunks)?;
+
+	let shard_len = chunks[0].as_ref().len();
+	if shard_len % 2 != 0 {
+		return Err(Error::UnevenLength)
+	}
+	if shard_len == 0 || chunks.iter().any(|chunk| chunk.as_ref().len() != shard_len) {
+		return Err(Error::NonUniformChunks)
+	}
+
+	// The encoder splits the payload into runs of `threshold` symbols of two bytes each,
+	// the n-th symbol of every run ending up in the systematic chunk with index n.
+	let mut payload_bytes = Vec::with_capacity(threshold * shard_len);
+	for symbol in (0..shard_len).step_by(2) {
+		for chunk in chunks {
+			payload_bytes.extend_from_slice(&chunk.as_ref()[symbol..symbol + 2]);
+		}
+	}
+
+	Decode::decode(&mut &payload_bytes[..]).or_else(|_e| Err(Error::BadPayload))
+}
+
+/// The assignment of the erasure chunks of a candidate to validators.
+///
+/// Without chunk shuffling, validator `i` holds chunk `i` of every candidate. With chunk
+/// shuffling, the assignment is rotated by an offset derived from the relay parent and the core
+/// of the candidate, so that the validators holding the systematic chunks differ between cores
+/// and blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkMapping {
+	n_validators: u32,
+	offset: u32,
+}
+
+impl ChunkMapping {
+	/// The mapping where validator `i` holds chunk `i`.
+	pub fn identity(n_validators: usize) -> Self {
+		ChunkMapping { n_validators: n_validators as u32, offset: 0 }
+	}
+
+	/// The mapping for a candidate with the given relay parent, occupying the given core.
+	///
+	/// If `shuffling` is false, this is the identity mapping.
+	pub fn new(
+		shuffling: bool,
+		n_validators: usize,
+		relay_parent: &H256,
+		core_index: CoreIndex,
+	) -> Self {
+		if !shuffling || n_validators == 0 {
+			return Self::identity(n_validators)
+		}
+
+		let seed = blake2_256(&(relay_parent, core_index).encode());
+		let offset = u32::from_le_bytes([seed[0], seed[1], seed[2], seed[3]]) % n_validators as u32;
+		ChunkMapping { n_validators: n_validators as u32, offset }
+	}
+
+	/// The index of the chunk held by the given validator.
+	pub fn chunk_index(&self, validator_index: ValidatorIndex) -> ValidatorIndex {
+		if self.offset == 0 {
+			return validator_index
+		}
+		ValidatorIndex((validator_index.0 + self.offset) % self.n_validators.max(1))
+	}
+
+	/// The index of the validator holding the given chunk.
+	pub fn validator_index(&self, chunk_index: ValidatorIndex) -> ValidatorIndex {
+		if self.offset == 0 {
+			return chunk_index
+		}
+		let n_validators = self.n_validators.max(1);
+		ValidatorIndex((chunk_index.0 + n_validators - self.offset) % n_validators)
+	}
+}
+
 /// An iterator that yields merkle branches and chunk data for all chunks to
 /// be sent to other validators.
 pub struct Branches<'a, I> {
@@ -403,6 +521,61 @@ mod tests {
 		assert_eq!(reconstructed, available_data);
 	}
 
+	#[test]
+	fn systematic_round_trip_works() {
+		for n_validators in [2, 10, 100, 1000] {
+			let pov_block =
+				PoVBlock { block_data: BlockData((0..255).cycle().take(5000).collect()) };
+
+			let available_data =
+				AvailableData { pov_block, omitted_validation: OmittedValidationData::default() };
+			let chunks = obtain_chunks(n_validators, &available_data).unwrap();
+			let threshold = systematic_recovery_threshold(n_validators).unwrap();
+
+			let reconstructed: AvailableData =
+				reconstruct_from_systematic(n_validators, &chunks[..threshold]).unwrap();
+			assert_eq!(reconstructed, available_data);
+
+			assert_eq!(
+				reconstruct_from_systematic::<_, AvailableData>(
+					n_validators,
+					&chunks[..threshold - 1]
+				),
+				Err(Error::NotEnoughChunks),
+			);
+		}
+	}
+
+	#[test]
+	fn chunk_mapping_is_a_bijection() {
+		let n_validators = 10;
+		let relay_parent = H256::repeat_byte(0x42);
+
+		let identity = ChunkMapping::new(false, n_validators, &relay_parent, CoreIndex(1));
+		assert_eq!(identity, ChunkMapping::identity(n_validators));
+		assert_eq!(identity.chunk_index(ValidatorIndex(3)), ValidatorIndex(3));
+
+		let mappings: Vec<_> = (0..4)
+			.map(|core| ChunkMapping::new(true, n_validators, &relay_parent, CoreIndex(core)))
+			.collect();
+		for mapping in &mappings {
+			let mut chunk_indices: Vec<_> = (0..n_validators as u32)
+				.map(|v| mapping.chunk_index(ValidatorIndex(v)))
+				.collect();
+			for (v, chunk_index) in chunk_indices.iter().enumerate() {
+				assert_eq!(mapping.validator_index(*chunk_index), ValidatorIndex(v as u32));
+			}
+			chunk_indices.sort();
+			chunk_indices.dedup();
+			assert_eq!(chunk_indices.len(), n_validators);
+		}
+
+		// The holder of the first chunk depends on the core.
+		let holders: std::collections::HashSet<_> =
+			mappings.iter().map(|m| m.validator_index(ValidatorIndex(0))).collect();
+		assert!(holders.len() > 1);
+	}
+
 	#[test]
 	fn reconstruct_does_not_panic_on_low_validator_count() {
 		let reconstructed = reconstruct_v1(1, [].iter().cloned());
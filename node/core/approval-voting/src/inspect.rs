@@ -0,0 +1,261 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Read-only access to the block and candidate entries of approval voting, for debugging
+//! purposes.
+
+use std::collections::BTreeSet;
+
+use kvdb::KeyValueDB;
+use polkadot_node_subsystem::SubsystemResult;
+use polkadot_node_subsystem_util::inspection::Filter;
+use polkadot_primitives::v1::{CandidateHash, Hash};
+use serde_json::{json, Value};
+
+use crate::approval_db::v1::{
+	load_all_blocks, load_block_entry, load_candidate_entry, ApprovalEntry, Bitfield, BlockEntry,
+	CandidateEntry, Config,
+};
+
+/// Decodes the block and candidate entries selected by the filter.
+///
+/// Block entries are matched against their own hash and the candidates they include, candidate
+/// entries against the blocks they have approval entries for.
+pub fn entries(db: &dyn KeyValueDB, col_data: u32, filter: &Filter) -> SubsystemResult<Value> {
+	let config = Config { col_data };
+
+	let mut blocks = Vec::new();
+	let mut candidate_hashes = BTreeSet::new();
+	for block_hash in load_all_blocks(db, &config)? {
+		let entry = match load_block_entry(db, &config, &block_hash)? {
+			Some(entry) => entry,
+			None => continue,
+		};
+
+		let included = entry.candidates.iter().map(|(_, hash)| *hash).collect::<Vec<_>>();
+		candidate_hashes.extend(included.iter().copied());
+		if filter.matches(Some(entry.session), &[entry.block_hash], &included) {
+			blocks.push(block_entry_json(&entry));
+		}
+	}
+
+	let mut candidates = Vec::new();
+	for candidate_hash in candidate_hashes {
+		let entry = match load_candidate_entry(db, &config, &candidate_hash)? {
+			Some(entry) => entry,
+			None => continue,
+		};
+
+		let assigned_at = entry.block_assignments.keys().copied().collect::<Vec<_>>();
+		if filter.matches(Some(entry.session), &assigned_at, &[candidate_hash]) {
+			candidates.push(candidate_entry_json(&candidate_hash, &entry));
+		}
+	}
+
+	Ok(json!({ "blocks": blocks, "candidates": candidates }))
+}
+
+fn set_bits(bitfield: &Bitfield) -> Vec<usize> {
+	bitfield.iter().enumerate().filter(|(_, set)| **set).map(|(i, _)| i).collect()
+}
+
+fn block_entry_json(entry: &BlockEntry) -> Value {
+	let approved = set_bits(&entry.approved_bitfield);
+	let candidates = entry
+		.candidates
+		.iter()
+		.enumerate()
+		.map(|(i, (core, hash))| {
+			json!({
+				"core_index": core.0,
+				"candidate_hash": format!("{:?}", hash.0),
+				"approved": approved.contains(&i),
+			})
+		})
+		.collect::<Vec<_>>();
+
+	json!({
+		"block_hash": format!("{:?}", entry.block_hash),
+		"block_number": entry.block_number,
+		"parent_hash": format!("{:?}", entry.parent_hash),
+		"session": entry.session,
+		"slot": *entry.slot,
+		"relay_vrf_story": format!("{:?}", Hash::from(entry.relay_vrf_story)),
+		"candidates": candidates,
+		"children": entry.children.iter().map(|h| format!("{:?}", h)).collect::<Vec<_>>(),
+	})
+}
+
+fn approval_entry_json(block_hash: &Hash, entry: &ApprovalEntry) -> Value {
+	let tranches = entry
+		.tranches
+		.iter()
+		.map(|tranche| {
+			let assignments = tranche
+				.assignments
+				.iter()
+				.map(|(validator, tick)| {
+					json!({ "validator_index": validator.0, "tick": crate::Tick::from(*tick) })
+				})
+				.collect::<Vec<_>>();
+			json!({ "tranche": tranche.tranche, "assignments": assignments })
+		})
+		.collect::<Vec<_>>();
+	let our_assignment = entry.our_assignment.as_ref().map(|assignment| {
+		json!({
+			"tranche": assignment.tranche,
+			"validator_index": assignment.validator_index.0,
+			"triggered": assignment.triggered,
+		})
+	});
+
+	json!({
+		"block_hash": format!("{:?}", block_hash),
+		"backing_group": entry.backing_group.0,
+		"tranches": tranches,
+		"our_assignment": our_assignment,
+		"our_approval_sig": entry.our_approval_sig.is_some(),
+		"assignments": set_bits(&entry.assignments),
+		"approved": entry.approved,
+	})
+}
+
+fn candidate_entry_json(candidate_hash: &CandidateHash, entry: &CandidateEntry) -> Value {
+	let descriptor = &entry.candidate.descriptor;
+	let block_assignments = entry
+		.block_assignments
+		.iter()
+		.map(|(block_hash, approval_entry)| approval_entry_json(block_hash, approval_entry))
+		.collect::<Vec<_>>();
+
+	json!({
+		"candidate_hash": format!("{:?}", candidate_hash.0),
+		"para_id": u32::from(descriptor.para_id),
+		"relay_parent": format!("{:?}", descriptor.relay_parent),
+		"session": entry.session,
+		"block_assignments": block_assignments,
+		"approvals": set_bits(&entry.approvals),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		approval_db::v1::DbBackend,
+		backend::{Backend, OverlayedBackend},
+		ops::{add_block_entry, NewCandidateInfo},
+	};
+	use ::test_helpers::dummy_candidate_receipt;
+	use bitvec::order::Lsb0 as BitOrderLsb0;
+	use polkadot_primitives::v1::{CoreIndex, GroupIndex};
+	use sp_consensus_slots::Slot;
+	use std::sync::Arc;
+
+	fn make_block_entry(
+		block_hash: Hash,
+		block_number: u32,
+		candidates: Vec<(CoreIndex, CandidateHash)>,
+	) -> BlockEntry {
+		BlockEntry {
+			block_hash,
+			block_number,
+			parent_hash: Hash::repeat_byte(9),
+			session: 1,
+			slot: Slot::from(1),
+			relay_vrf_story: [0u8; 32],
+			approved_bitfield: bitvec::bitvec![BitOrderLsb0, u8; 0; candidates.len()],
+			candidates,
+			children: Vec::new(),
+		}
+	}
+
+	fn hashes(entries: &Value, field: &str) -> Vec<Value> {
+		entries.as_array().unwrap().iter().map(|entry| entry[field].clone()).collect()
+	}
+
+	#[test]
+	fn entries_are_decoded_and_filtered() {
+		let store: Arc<dyn KeyValueDB> = Arc::new(kvdb_memorydb::create(1));
+		let mut db = DbBackend::new(store.clone(), Config { col_data: 0 });
+
+		let block_a = Hash::repeat_byte(1);
+		let block_b = Hash::repeat_byte(2);
+		let candidate_a = dummy_candidate_receipt(Hash::repeat_byte(10));
+		let mut candidate_b = dummy_candidate_receipt(Hash::repeat_byte(10));
+		candidate_b.descriptor.para_id = 2.into();
+		let (hash_a, hash_b) = (candidate_a.hash(), candidate_b.hash());
+
+		let candidate_info = |hash: &CandidateHash| {
+			[(hash_a, &candidate_a, 0), (hash_b, &candidate_b, 1)]
+				.iter()
+				.find(|(h, _, _)| h == hash)
+				.map(|(_, candidate, group)| {
+					NewCandidateInfo::new((*candidate).clone(), GroupIndex(*group), None)
+				})
+		};
+
+		let mut overlay_db = OverlayedBackend::new(&db);
+		add_block_entry(
+			&mut overlay_db,
+			make_block_entry(block_a, 1, vec![(CoreIndex(0), hash_a)]).into(),
+			10,
+			&candidate_info,
+		)
+		.unwrap();
+		add_block_entry(
+			&mut overlay_db,
+			make_block_entry(block_b, 2, vec![(CoreIndex(0), hash_a), (CoreIndex(1), hash_b)])
+				.into(),
+			10,
+			&candidate_info,
+		)
+		.unwrap();
+		let write_ops = overlay_db.into_write_ops();
+		db.write(write_ops).unwrap();
+
+		let block_json = |hash: Hash| json!(format!("{:?}", hash));
+		let candidate_json = |hash: CandidateHash| json!(format!("{:?}", hash.0));
+
+		let all = entries(&*store, 0, &Filter::default()).unwrap();
+		assert_eq!(
+			hashes(&all["blocks"], "block_hash"),
+			vec![block_json(block_a), block_json(block_b)],
+		);
+		assert_eq!(all["blocks"][1]["candidates"][1]["core_index"], json!(1));
+		assert_eq!(all["blocks"][1]["candidates"][1]["approved"], json!(false));
+		assert_eq!(all["candidates"].as_array().unwrap().len(), 2);
+
+		let filter = Filter { block: Some(block_a), ..Default::default() };
+		let selected = entries(&*store, 0, &filter).unwrap();
+		assert_eq!(hashes(&selected["blocks"], "block_hash"), vec![block_json(block_a)]);
+		assert_eq!(hashes(&selected["candidates"], "candidate_hash"), vec![candidate_json(hash_a)]);
+
+		let filter = Filter { candidate: Some(hash_b), ..Default::default() };
+		let selected = entries(&*store, 0, &filter).unwrap();
+		assert_eq!(hashes(&selected["blocks"], "block_hash"), vec![block_json(block_b)]);
+		assert_eq!(hashes(&selected["candidates"], "candidate_hash"), vec![candidate_json(hash_b)]);
+		assert_eq!(
+			hashes(&selected["candidates"][0]["block_assignments"], "backing_group"),
+			vec![json!(1)],
+		);
+
+		let filter = Filter { session: Some(2), ..Default::default() };
+		let selected = entries(&*store, 0, &filter).unwrap();
+		assert!(selected["blocks"].as_array().unwrap().is_empty());
+		assert!(selected["candidates"].as_array().unwrap().is_empty());
+	}
+}
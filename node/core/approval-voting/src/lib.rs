@@ -52,7 +52,7 @@ use polkadot_node_subsystem_util::{
 };
 use polkadot_primitives::{
 	v1::{
-		ApprovalVote, BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt,
+		ApprovalVote, BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt, CoreIndex,
 		DisputeStatement, GroupIndex, Hash, SessionIndex, ValidDisputeStatementKind, ValidatorId,
 		ValidatorIndex, ValidatorPair, ValidatorSignature,
 	},
@@ -88,6 +88,7 @@ mod approval_db;
 mod backend;
 mod criteria;
 mod import;
+pub mod inspect;
 mod ops;
 mod persisted_entries;
 mod time;
@@ -676,6 +677,7 @@ enum Action {
 		candidate_index: CandidateIndex,
 		session: SessionIndex,
 		candidate: CandidateReceipt,
+		core_index: CoreIndex,
 		backing_group: GroupIndex,
 	},
 	InformDisputeCoordinator {
@@ -894,6 +896,7 @@ async fn handle_actions(
 				candidate_index,
 				session,
 				candidate,
+				core_index,
 				backing_group,
 			} => {
 				// Don't launch approval work if the node is syncing.
@@ -936,6 +939,7 @@ async fn handle_actions(
 										candidate,
 										validator_index,
 										block_hash,
+										core_index,
 										backing_group,
 									)
 									.await
@@ -2103,6 +2107,8 @@ fn process_wakeup(
 			block_entry.candidates().iter().position(|(_, h)| &candidate_hash == h);
 
 		if let Some(i) = index_in_candidate {
+			let core_index = block_entry.candidates()[i].0;
+
 			tracing::trace!(
 				target: LOG_TARGET,
 				?candidate_hash,
@@ -2120,6 +2126,7 @@ fn process_wakeup(
 				candidate_index: i as _,
 				session: block_entry.session(),
 				candidate: candidate_receipt,
+				core_index,
 				backing_group,
 			});
 		}
@@ -2155,6 +2162,7 @@ async fn launch_approval(
 	candidate: CandidateReceipt,
 	validator_index: ValidatorIndex,
 	block_hash: Hash,
+	core_index: CoreIndex,
 	backing_group: GroupIndex,
 ) -> SubsystemResult<RemoteHandle<ApprovalState>> {
 	let (a_tx, a_rx) = oneshot::channel();
@@ -2195,6 +2203,7 @@ async fn launch_approval(
 		candidate.clone(),
 		session_index,
 		Some(backing_group),
+		Some(core_index),
 		a_tx,
 	))
 	.await;
@@ -0,0 +1,149 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Read-only access to the candidate meta information of the availability store, for debugging
+//! purposes.
+
+use kvdb::KeyValueDB;
+use parity_scale_codec::Decode;
+use polkadot_node_subsystem_util::inspection::Filter;
+use polkadot_primitives::v1::CandidateHash;
+use serde_json::{json, Value};
+
+use super::{CandidateMeta, Config, Error, State, META_PREFIX};
+
+/// Decodes the meta information of every candidate in the availability store that is selected by
+/// the filter.
+///
+/// Candidates are matched against the blocks they were included in while unfinalized. The meta
+/// information doesn't record the session, so a session filter selects no candidates.
+pub fn candidate_meta(
+	db: &dyn KeyValueDB,
+	config: &Config,
+	filter: &Filter,
+) -> Result<Vec<Value>, Error> {
+	let mut entries = Vec::new();
+
+	for (key, value) in db.iter_with_prefix(config.col_meta, &META_PREFIX[..]) {
+		let candidate_hash = CandidateHash::decode(&mut &key[META_PREFIX.len()..])?;
+		let meta = CandidateMeta::decode(&mut &value[..])?;
+
+		let blocks = match meta.state {
+			State::Unfinalized(_, ref blocks) => blocks.iter().map(|(_, hash)| *hash).collect(),
+			_ => Vec::new(),
+		};
+		if !filter.matches(None, &blocks, &[candidate_hash]) {
+			continue
+		}
+
+		let state = match meta.state {
+			State::Unavailable(observed_at) =>
+				json!({ "unavailable": { "observed_at": observed_at.0 } }),
+			State::Unfinalized(observed_at, blocks) => {
+				let blocks = blocks
+					.iter()
+					.map(
+						|(number, hash)| json!({ "number": number.0, "hash": format!("{:?}", hash) }),
+					)
+					.collect::<Vec<_>>();
+				json!({ "unfinalized": { "observed_at": observed_at.0, "blocks": blocks } })
+			},
+			State::Finalized(finalized_at) => json!({ "finalized": { "at": finalized_at.0 } }),
+		};
+
+		entries.push(json!({
+			"candidate_hash": format!("{:?}", candidate_hash.0),
+			"state": state,
+			"data_available": meta.data_available,
+			"chunks_stored": meta
+				.chunks_stored
+				.iter()
+				.enumerate()
+				.filter(|(_, stored)| **stored)
+				.map(|(index, _)| index)
+				.collect::<Vec<_>>(),
+		}));
+	}
+
+	Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		super::{write_meta, BEBlockNumber, BETimestamp, BitOrderLsb0},
+		*,
+	};
+	use kvdb::DBTransaction;
+	use polkadot_primitives::v1::Hash;
+
+	#[test]
+	fn candidate_meta_is_decoded_and_filtered() {
+		let db = kvdb_memorydb::create(2);
+		let config = Config { col_data: 0, col_meta: 1 };
+
+		let block = Hash::repeat_byte(1);
+		let included = CandidateHash(Hash::repeat_byte(2));
+		let unavailable = CandidateHash(Hash::repeat_byte(3));
+
+		let mut transaction = DBTransaction::new();
+		write_meta(
+			&mut transaction,
+			&config,
+			&included,
+			&CandidateMeta {
+				state: State::Unfinalized(BETimestamp(5), vec![(BEBlockNumber(7), block)]),
+				data_available: true,
+				chunks_stored: bitvec::bitvec![BitOrderLsb0, u8; 1, 0, 1],
+			},
+		);
+		write_meta(
+			&mut transaction,
+			&config,
+			&unavailable,
+			&CandidateMeta {
+				state: State::Unavailable(BETimestamp(6)),
+				data_available: false,
+				chunks_stored: bitvec::bitvec![BitOrderLsb0, u8; 0; 3],
+			},
+		);
+		db.write(transaction).unwrap();
+
+		assert_eq!(candidate_meta(&db, &config, &Filter::default()).unwrap().len(), 2);
+
+		let filter = Filter { block: Some(block), ..Default::default() };
+		assert_eq!(
+			candidate_meta(&db, &config, &filter).unwrap(),
+			vec![json!({
+				"candidate_hash": format!("{:?}", included.0),
+				"state": {
+					"unfinalized": {
+						"observed_at": 5,
+						"blocks": [{ "number": 7, "hash": format!("{:?}", block) }],
+					},
+				},
+				"data_available": true,
+				"chunks_stored": [0, 2],
+			})],
+		);
+
+		let filter = Filter { candidate: Some(unavailable), ..Default::default() };
+		assert!(candidate_meta(&db, &config, &filter).unwrap().is_empty());
+
+		let filter = Filter { session: Some(1), ..Default::default() };
+		assert!(candidate_meta(&db, &config, &filter).unwrap().is_empty());
+	}
+}
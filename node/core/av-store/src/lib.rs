@@ -44,6 +44,7 @@ use polkadot_subsystem::{
 	SubsystemError,
 };
 
+pub mod inspect;
 mod metrics;
 pub use self::metrics::*;
 
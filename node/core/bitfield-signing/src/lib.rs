@@ -27,6 +27,7 @@ use futures::{
 	prelude::*,
 	Future,
 };
+use polkadot_erasure_coding::ChunkMapping;
 use polkadot_node_subsystem::{
 	errors::RuntimeApiError,
 	jaeger,
@@ -41,7 +42,7 @@ use polkadot_node_subsystem_util::{
 	metrics::{self, prometheus},
 	JobSender, JobSubsystem, JobTrait, Validator,
 };
-use polkadot_primitives::v1::{AvailabilityBitfield, CoreState, Hash, ValidatorIndex};
+use polkadot_primitives::v1::{AvailabilityBitfield, CoreIndex, CoreState, Hash, ValidatorIndex};
 use sp_keystore::{Error as KeystoreError, SyncCryptoStorePtr};
 use std::{iter::FromIterator, pin::Pin, time::Duration};
 use wasm_timer::{Delay, Instant};
@@ -80,8 +81,9 @@ pub enum Error {
 }
 
 /// If there is a candidate pending availability, query the Availability Store
-/// for whether we have the availability chunk for our validator index.
+/// for whether we have the availability chunk assigned to our validator index.
 async fn get_core_availability(
+	core_index: CoreIndex,
 	core: &CoreState,
 	validator_idx: ValidatorIndex,
 	sender: &Mutex<&mut impl SubsystemSender>,
@@ -90,6 +92,12 @@ async fn get_core_availability(
 	if let &CoreState::Occupied(ref core) = core {
 		let _span = span.child("query-chunk-availability");
 
+		let relay_parent = core.candidate_descriptor.relay_parent;
+		let chunk_shuffling = get_availability_chunk_shuffling(relay_parent, sender).await?;
+		let chunk_index =
+			ChunkMapping::new(chunk_shuffling, core.availability.len(), &relay_parent, core_index)
+				.chunk_index(validator_idx);
+
 		let (tx, rx) = oneshot::channel();
 		sender
 			.lock()
@@ -97,7 +105,7 @@ async fn get_core_availability(
 			.send_message(
 				AvailabilityStoreMessage::QueryChunkAvailability(
 					core.candidate_hash,
-					validator_idx,
+					chunk_index,
 					tx,
 				)
 				.into(),
@@ -139,6 +147,33 @@ async fn get_availability_cores(
 	}
 }
 
+/// Whether availability chunks are shuffled for candidates with the given relay parent.
+///
+/// Runtimes not supporting the API do not shuffle chunks.
+async fn get_availability_chunk_shuffling(
+	relay_parent: Hash,
+	sender: &Mutex<&mut impl SubsystemSender>,
+) -> Result<bool, Error> {
+	let (tx, rx) = oneshot::channel();
+	sender
+		.lock()
+		.await
+		.send_message(
+			RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::AvailabilityChunkShuffling(tx),
+			)
+			.into(),
+		)
+		.await;
+	match rx.await {
+		Ok(Ok(out)) => Ok(out),
+		Ok(Err(RuntimeApiError::NotSupported { .. })) => Ok(false),
+		Ok(Err(runtime_err)) => Err(runtime_err.into()),
+		Err(err) => Err(err.into()),
+	}
+}
+
 /// - get the list of core states from the runtime
 /// - for each core, concurrently determine chunk availability (see `get_core_availability`)
 /// - return the bitfield if there were no errors at any point in this process
@@ -164,11 +199,9 @@ async fn construct_availability_bitfield(
 
 	// Handle all cores concurrently
 	// `try_join_all` returns all results in the same order as the input futures.
-	let results = future::try_join_all(
-		availability_cores
-			.iter()
-			.map(|core| get_core_availability(core, validator_idx, &sender, span)),
-	)
+	let results = future::try_join_all(availability_cores.iter().enumerate().map(|(i, core)| {
+		get_core_availability(CoreIndex(i as u32), core, validator_idx, &sender, span)
+	}))
 	.await?;
 
 	tracing::debug!(
@@ -21,13 +21,21 @@ use polkadot_primitives::v1::{CandidateHash, OccupiedCore};
 use test_helpers::dummy_candidate_descriptor;
 
 fn occupied_core(para_id: u32, candidate_hash: CandidateHash) -> CoreState {
+	occupied_core_with_validators(para_id, candidate_hash, 0)
+}
+
+fn occupied_core_with_validators(
+	para_id: u32,
+	candidate_hash: CandidateHash,
+	n_validators: usize,
+) -> CoreState {
 	CoreState::Occupied(OccupiedCore {
 		group_responsible: para_id.into(),
 		next_up_on_available: None,
 		occupied_since: 100_u32,
 		time_out_at: 200_u32,
 		next_up_on_time_out: None,
-		availability: Default::default(),
+		availability: FromIterator::from_iter(std::iter::repeat(false).take(n_validators)),
 		candidate_hash,
 		candidate_descriptor: dummy_candidate_descriptor(Hash::zero()),
 	})
@@ -61,6 +69,11 @@ fn construct_availability_bitfield_works() {
 						assert_eq!(relay_parent, rp);
 						tx.send(Ok(vec![CoreState::Free, occupied_core(1, hash_a), occupied_core(2, hash_b)])).unwrap();
 					}
+					AllMessages::RuntimeApi(
+						RuntimeApiMessage::Request(_, RuntimeApiRequest::AvailabilityChunkShuffling(tx)),
+					) => {
+						tx.send(Ok(false)).unwrap();
+					}
 					AllMessages::AvailabilityStore(
 						AvailabilityStoreMessage::QueryChunkAvailability(c_hash, vidx, tx),
 					) => {
@@ -83,3 +96,68 @@ fn construct_availability_bitfield_works() {
 		}
 	});
 }
+
+#[test]
+fn construct_availability_bitfield_queries_shuffled_chunk_index() {
+	block_on(async move {
+		let relay_parent = Hash::default();
+		let validator_index = ValidatorIndex(1u32);
+		let n_validators = 10;
+
+		let (mut sender, mut receiver) = polkadot_node_subsystem_test_helpers::sender_receiver();
+		let future = construct_availability_bitfield(
+			relay_parent,
+			&jaeger::Span::Disabled,
+			validator_index,
+			&mut sender,
+		)
+		.fuse();
+		pin_mut!(future);
+
+		let hash_a = CandidateHash(Hash::repeat_byte(1));
+		let hash_b = CandidateHash(Hash::repeat_byte(2));
+		let candidate_relay_parent = dummy_candidate_descriptor(Hash::zero()).relay_parent;
+		let expected_chunk_index = |core_index| {
+			ChunkMapping::new(true, n_validators, &candidate_relay_parent, CoreIndex(core_index))
+				.chunk_index(validator_index)
+		};
+
+		loop {
+			futures::select! {
+				m = receiver.next() => match m.unwrap() {
+					AllMessages::RuntimeApi(
+						RuntimeApiMessage::Request(_, RuntimeApiRequest::AvailabilityCores(tx)),
+					) => {
+						tx.send(Ok(vec![
+							occupied_core_with_validators(1, hash_a, n_validators),
+							occupied_core_with_validators(2, hash_b, n_validators),
+						])).unwrap();
+					}
+					AllMessages::RuntimeApi(
+						RuntimeApiMessage::Request(rp, RuntimeApiRequest::AvailabilityChunkShuffling(tx)),
+					) => {
+						assert_eq!(rp, candidate_relay_parent);
+						tx.send(Ok(true)).unwrap();
+					}
+					AllMessages::AvailabilityStore(
+						AvailabilityStoreMessage::QueryChunkAvailability(c_hash, chunk_index, tx),
+					) => {
+						let core_index = if c_hash == hash_a { 0 } else { 1 };
+						assert_eq!(chunk_index, expected_chunk_index(core_index));
+
+						tx.send(true).unwrap();
+					},
+					o => panic!("Unknown message: {:?}", o),
+				},
+				r = future => match r {
+					Ok(r) => {
+						assert!(r.0.get(0).unwrap());
+						assert!(r.0.get(1).unwrap());
+						break
+					},
+					Err(e) => panic!("Failed: {:?}", e),
+				},
+			}
+		}
+	});
+}
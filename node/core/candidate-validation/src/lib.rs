@@ -66,6 +66,13 @@ pub struct Config {
 	/// The path to the executable which can be used for spawning PVF compilation & validation
 	/// workers.
 	pub program_path: PathBuf,
+	/// Whether the PVF compilation & validation workers should be run in a sandbox.
+	pub enable_sandbox: bool,
+	/// The limit of the address space of the PVF execution workers in bytes, if any.
+	pub execute_worker_address_space_limit: Option<u64>,
+	/// Whether candidates deemed invalid by the compiled engine should be re-executed with the
+	/// interpreter.
+	pub dual_execute: bool,
 }
 
 /// The candidate validation subsystem.
@@ -103,6 +110,9 @@ where
 			self.pvf_metrics,
 			self.config.artifacts_cache_path,
 			self.config.program_path,
+			self.config.enable_sandbox,
+			self.config.execute_worker_address_space_limit,
+			self.config.dual_execute,
 		)
 		.map_err(|e| SubsystemError::with_origin("candidate-validation", e))
 		.boxed();
@@ -116,15 +126,19 @@ async fn run<Context>(
 	pvf_metrics: polkadot_node_core_pvf::Metrics,
 	cache_path: PathBuf,
 	program_path: PathBuf,
+	enable_sandbox: bool,
+	execute_worker_address_space_limit: Option<u64>,
+	dual_execute: bool,
 ) -> SubsystemResult<()>
 where
 	Context: SubsystemContext<Message = CandidateValidationMessage>,
 	Context: overseer::SubsystemContext<Message = CandidateValidationMessage>,
 {
-	let (validation_host, task) = polkadot_node_core_pvf::start(
-		polkadot_node_core_pvf::Config::new(cache_path, program_path),
-		pvf_metrics,
-	);
+	let mut pvf_config = polkadot_node_core_pvf::Config::new(cache_path, program_path);
+	pvf_config.enable_sandbox = enable_sandbox;
+	pvf_config.execute_worker_address_space_limit = execute_worker_address_space_limit;
+	pvf_config.dual_execute = dual_execute;
+	let (validation_host, task) = polkadot_node_core_pvf::start(pvf_config, pvf_metrics);
 	ctx.spawn_blocking("pvf-validation-host", task.boxed())?;
 
 	loop {
@@ -543,8 +557,11 @@ async fn validate_candidate_exhaustive(
 	match result {
 		Err(ValidationError::InternalError(e)) => Err(ValidationFailed(e)),
 
-		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::HardTimeout)) =>
+		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::CpuTimeExceeded(_))) =>
 			Ok(ValidationResult::Invalid(InvalidCandidate::Timeout)),
+		// Failing to reserve the memory says nothing about the candidate, so refrain from voting.
+		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::MemoryExhausted(e))) =>
+			Err(ValidationFailed(format!("memory exhausted: {}", e))),
 		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::WorkerReportedError(e))) =>
 			Ok(ValidationResult::Invalid(InvalidCandidate::ExecutionError(e))),
 		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::AmbiguousWorkerDeath)) =>
@@ -598,6 +615,7 @@ impl ValidationBackend for ValidationHost {
 				timeout,
 				params.encode(),
 				polkadot_node_core_pvf::Priority::Normal,
+				polkadot_node_core_pvf::Engine::Wasmtime,
 				tx,
 			)
 			.await
@@ -497,7 +497,7 @@ fn candidate_validation_timeout_is_internal_error() {
 
 	let v = executor::block_on(validate_candidate_exhaustive(
 		MockValidateCandidateBackend::with_hardcoded_result(Err(
-			ValidationError::InvalidCandidate(WasmInvalidCandidate::HardTimeout),
+			ValidationError::InvalidCandidate(WasmInvalidCandidate::CpuTimeExceeded(0)),
 		)),
 		validation_data,
 		validation_code,
@@ -510,6 +510,41 @@ fn candidate_validation_timeout_is_internal_error() {
 	assert_matches!(v, Ok(ValidationResult::Invalid(InvalidCandidate::Timeout)));
 }
 
+#[test]
+fn candidate_validation_memory_exhausted_is_internal_error() {
+	let validation_data = PersistedValidationData { max_pov_size: 1024, ..Default::default() };
+
+	let pov = PoV { block_data: BlockData(vec![1; 32]) };
+	let validation_code = ValidationCode(vec![2; 16]);
+
+	let descriptor = make_valid_candidate_descriptor(
+		1.into(),
+		dummy_hash(),
+		validation_data.hash(),
+		pov.hash(),
+		validation_code.hash(),
+		dummy_hash(),
+		dummy_hash(),
+		Sr25519Keyring::Alice,
+	);
+
+	let v = executor::block_on(validate_candidate_exhaustive(
+		MockValidateCandidateBackend::with_hardcoded_result(Err(
+			ValidationError::InvalidCandidate(WasmInvalidCandidate::MemoryExhausted(
+				"out of memory".to_string(),
+			)),
+		)),
+		validation_data,
+		validation_code,
+		descriptor,
+		Arc::new(pov),
+		Duration::from_secs(0),
+		&Default::default(),
+	));
+
+	assert_matches!(v, Err(ValidationFailed(_)));
+}
+
 #[test]
 fn candidate_validation_code_mismatch_is_invalid() {
 	let validation_data = PersistedValidationData { max_pov_size: 1024, ..Default::default() };
@@ -538,7 +573,7 @@ fn candidate_validation_code_mismatch_is_invalid() {
 
 	let v = executor::block_on(validate_candidate_exhaustive(
 		MockValidateCandidateBackend::with_hardcoded_result(Err(
-			ValidationError::InvalidCandidate(WasmInvalidCandidate::HardTimeout),
+			ValidationError::InvalidCandidate(WasmInvalidCandidate::CpuTimeExceeded(0)),
 		)),
 		validation_data,
 		validation_code,
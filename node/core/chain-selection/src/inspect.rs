@@ -0,0 +1,182 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Read-only access to the block tree of chain selection, for debugging purposes.
+
+use std::sync::Arc;
+
+use kvdb::KeyValueDB;
+use polkadot_node_subsystem_util::inspection::Filter;
+use serde_json::{json, Value};
+
+use crate::{
+	backend::Backend,
+	db_backend::v1::{Config, DbBackend},
+	Approval, BlockEntry, Error,
+};
+
+/// Decodes the viable leaves and the unfinalized block tree, in ascending order of block numbers.
+///
+/// Only the blocks selected by the filter are returned. Blocks are matched against their own hash
+/// and don't record sessions or candidates, so a session or candidate filter selects no blocks.
+pub fn tree(db: Arc<dyn KeyValueDB>, col_data: u32, filter: &Filter) -> Result<Value, Error> {
+	let backend = DbBackend::new(db, Config { col_data });
+
+	let leaves = backend
+		.load_leaves()?
+		.inner
+		.iter()
+		.map(|leaf| {
+			json!({
+				"block_hash": format!("{:?}", leaf.block_hash),
+				"block_number": leaf.block_number,
+				"weight": leaf.weight,
+			})
+		})
+		.collect::<Vec<_>>();
+
+	let mut blocks = Vec::new();
+	if let Some(first) = backend.load_first_block_number()? {
+		for number in first.. {
+			let hashes = backend.load_blocks_by_number(number)?;
+			if hashes.is_empty() {
+				break
+			}
+
+			for hash in hashes {
+				if !filter.matches(None, &[hash], &[]) {
+					continue
+				}
+				if let Some(entry) = backend.load_block_entry(&hash)? {
+					blocks.push(block_entry_json(&entry));
+				}
+			}
+		}
+	}
+
+	Ok(json!({ "leaves": leaves, "blocks": blocks }))
+}
+
+fn block_entry_json(entry: &BlockEntry) -> Value {
+	let approval = match entry.viability.approval {
+		Approval::Approved => "approved",
+		Approval::Unapproved => "unapproved",
+		Approval::Stagnant => "stagnant",
+	};
+
+	json!({
+		"block_hash": format!("{:?}", entry.block_hash),
+		"block_number": entry.block_number,
+		"parent_hash": format!("{:?}", entry.parent_hash),
+		"children": entry.children.iter().map(|h| format!("{:?}", h)).collect::<Vec<_>>(),
+		"weight": entry.weight,
+		"viable": entry.viability.is_viable(),
+		"explicitly_reverted": entry.viability.explicitly_reverted,
+		"approval": approval,
+		"earliest_unviable_ancestor":
+			entry.viability.earliest_unviable_ancestor.map(|h| format!("{:?}", h)),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{backend::BackendWriteOp, LeafEntry, LeafEntrySet, ViabilityCriteria};
+	use polkadot_primitives::v1::{BlockNumber, Hash};
+
+	fn block_entry(
+		block_hash: Hash,
+		block_number: BlockNumber,
+		parent_hash: Hash,
+		children: Vec<Hash>,
+		approval: Approval,
+	) -> BlockEntry {
+		BlockEntry {
+			block_hash,
+			block_number,
+			parent_hash,
+			children,
+			viability: ViabilityCriteria {
+				earliest_unviable_ancestor: None,
+				explicitly_reverted: false,
+				approval,
+			},
+			weight: block_number as _,
+		}
+	}
+
+	#[test]
+	fn tree_is_decoded_in_order_and_filtered() {
+		let db: Arc<dyn KeyValueDB> = Arc::new(kvdb_memorydb::create(1));
+		let mut backend = DbBackend::new(db.clone(), Config { col_data: 0 });
+
+		let (a, b, c) = (Hash::repeat_byte(1), Hash::repeat_byte(2), Hash::repeat_byte(3));
+		let entries = vec![
+			block_entry(a, 1, Hash::repeat_byte(0), vec![b, c], Approval::Approved),
+			block_entry(b, 2, a, Vec::new(), Approval::Unapproved),
+			block_entry(c, 2, a, Vec::new(), Approval::Stagnant),
+		];
+		let leaves = LeafEntrySet {
+			inner: vec![
+				LeafEntry { weight: 2, block_number: 2, block_hash: b },
+				LeafEntry { weight: 2, block_number: 2, block_hash: c },
+			],
+		};
+
+		let mut ops = vec![
+			BackendWriteOp::WriteBlocksByNumber(1, vec![a]),
+			BackendWriteOp::WriteBlocksByNumber(2, vec![b, c]),
+			BackendWriteOp::WriteViableLeaves(leaves),
+		];
+		ops.extend(entries.into_iter().map(BackendWriteOp::WriteBlockEntry));
+		backend.write(ops).unwrap();
+
+		let all = tree(db.clone(), 0, &Filter::default()).unwrap();
+		let leaf_hashes = all["leaves"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.map(|leaf| leaf["block_hash"].clone())
+			.collect::<Vec<_>>();
+		assert_eq!(leaf_hashes, vec![json!(format!("{:?}", b)), json!(format!("{:?}", c))]);
+		let block_numbers = all["blocks"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.map(|block| block["block_number"].clone())
+			.collect::<Vec<_>>();
+		assert_eq!(block_numbers, vec![json!(1), json!(2), json!(2)]);
+
+		let filter = Filter { block: Some(c), ..Default::default() };
+		assert_eq!(
+			tree(db.clone(), 0, &filter).unwrap()["blocks"],
+			json!([{
+				"block_hash": format!("{:?}", c),
+				"block_number": 2,
+				"parent_hash": format!("{:?}", a),
+				"children": [],
+				"weight": 2,
+				"viable": false,
+				"explicitly_reverted": false,
+				"approval": "stagnant",
+				"earliest_unviable_ancestor": null,
+			}]),
+		);
+
+		let filter = Filter { session: Some(1), ..Default::default() };
+		assert!(tree(db, 0, &filter).unwrap()["blocks"].as_array().unwrap().is_empty());
+	}
+}
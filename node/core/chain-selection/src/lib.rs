@@ -37,6 +37,7 @@ use crate::backend::{Backend, BackendWriteOp, OverlayedBackend};
 
 mod backend;
 mod db_backend;
+pub mod inspect;
 mod tree;
 
 #[cfg(test)]
@@ -17,8 +17,8 @@
 //! Implements the dispute coordinator subsystem (dummy implementation).
 
 use polkadot_node_subsystem::{
-	messages::DisputeCoordinatorMessage, overseer, FromOverseer, OverseerSignal, SpawnedSubsystem,
-	SubsystemContext, SubsystemError,
+	messages::{DisputeCoordinatorMessage, DisputeEvidenceError},
+	overseer, FromOverseer, OverseerSignal, SpawnedSubsystem, SubsystemContext, SubsystemError,
 };
 use polkadot_primitives::v1::BlockNumber;
 
@@ -121,6 +121,9 @@ async fn handle_incoming(
 
 			let _ = tx.send(undisputed_chain);
 		},
+		DisputeCoordinatorMessage::AssembleEvidence(_, _, tx) => {
+			let _ = tx.send(Err(DisputeEvidenceError::UnknownCandidate));
+		},
 	}
 
 	Ok(())
@@ -48,7 +48,7 @@ use polkadot_node_subsystem::{
 use sc_keystore::LocalKeystore;
 use std::sync::Arc;
 
-pub use self::real::Config;
+pub use self::real::{db::inspect, Config};
 
 pub(crate) const LOG_TARGET: &str = "parachain::dispute-coordinator";
 
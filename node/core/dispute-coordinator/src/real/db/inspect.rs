@@ -0,0 +1,175 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Read-only access to the recent disputes and their votes, for debugging purposes.
+
+use kvdb::KeyValueDB;
+use polkadot_node_subsystem::SubsystemResult;
+use polkadot_node_subsystem_util::inspection::Filter;
+use polkadot_primitives::v1::{CandidateHash, SessionIndex, ValidatorIndex};
+use serde_json::{json, Value};
+
+use super::v1::{
+	load_candidate_votes, load_earliest_session, load_recent_disputes, CandidateVotes,
+	ColumnConfiguration,
+};
+use crate::status::DisputeStatus;
+
+/// Decodes the recent disputes selected by the filter, along with the votes on their candidates.
+///
+/// Disputes are matched against their session, the disputed candidate and its relay parent.
+pub fn disputes(db: &dyn KeyValueDB, col_data: u32, filter: &Filter) -> SubsystemResult<Value> {
+	let config = ColumnConfiguration { col_data };
+
+	let recent_disputes = load_recent_disputes(db, &config)?.unwrap_or_default();
+
+	let mut disputes = Vec::new();
+	for ((session, candidate_hash), status) in recent_disputes {
+		let votes = load_candidate_votes(db, &config, session, &candidate_hash)?;
+		let relay_parents = votes
+			.iter()
+			.map(|v| v.candidate_receipt.descriptor.relay_parent)
+			.collect::<Vec<_>>();
+		if !filter.matches(Some(session), &relay_parents, &[candidate_hash]) {
+			continue
+		}
+
+		disputes.push(dispute_json(session, &candidate_hash, status, votes.as_ref()));
+	}
+
+	Ok(json!({
+		"earliest_session": load_earliest_session(db, &config)?,
+		"disputes": disputes,
+	}))
+}
+
+fn dispute_json(
+	session: SessionIndex,
+	candidate_hash: &CandidateHash,
+	status: DisputeStatus,
+	votes: Option<&CandidateVotes>,
+) -> Value {
+	let status = match status {
+		DisputeStatus::Active => json!("active"),
+		DisputeStatus::Confirmed => json!("confirmed"),
+		DisputeStatus::ConcludedFor(at) => json!({ "concluded_for": at }),
+		DisputeStatus::ConcludedAgainst(at) => json!({ "concluded_against": at }),
+	};
+	let votes = votes.map(|votes| {
+		let descriptor = &votes.candidate_receipt.descriptor;
+		let valid = votes.valid.iter().map(|(kind, index, _)| vote_json(kind, index));
+		let invalid = votes.invalid.iter().map(|(kind, index, _)| vote_json(kind, index));
+		json!({
+			"para_id": u32::from(descriptor.para_id),
+			"relay_parent": format!("{:?}", descriptor.relay_parent),
+			"valid": valid.collect::<Vec<_>>(),
+			"invalid": invalid.collect::<Vec<_>>(),
+		})
+	});
+
+	json!({
+		"session": session,
+		"candidate_hash": format!("{:?}", candidate_hash.0),
+		"status": status,
+		"candidate_votes": votes,
+	})
+}
+
+fn vote_json(kind: &impl std::fmt::Debug, validator_index: &ValidatorIndex) -> Value {
+	json!({ "kind": format!("{:?}", kind), "validator_index": validator_index.0 })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{super::v1::DbBackend, *};
+	use crate::real::backend::{Backend, OverlayedBackend};
+	use ::test_helpers::dummy_candidate_receipt;
+	use polkadot_primitives::v1::{Hash, ValidDisputeStatementKind};
+	use sp_keyring::Sr25519Keyring;
+	use std::sync::Arc;
+
+	#[test]
+	fn disputes_are_decoded_and_filtered() {
+		let store: Arc<dyn KeyValueDB> = Arc::new(kvdb_memorydb::create(1));
+		let mut backend = DbBackend::new(store.clone(), ColumnConfiguration { col_data: 0 });
+
+		let relay_parent = Hash::repeat_byte(1);
+		let candidate_receipt = dummy_candidate_receipt(relay_parent);
+		let candidate_hash = candidate_receipt.hash();
+		let other_candidate_hash = CandidateHash(Hash::repeat_byte(2));
+		let signature = Sr25519Keyring::Alice.sign(&[]).into();
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		overlay_db.write_earliest_session(1);
+		overlay_db.write_recent_disputes(
+			vec![
+				((1, candidate_hash), DisputeStatus::ConcludedFor(10)),
+				((2, other_candidate_hash), DisputeStatus::Active),
+			]
+			.into_iter()
+			.collect(),
+		);
+		overlay_db.write_candidate_votes(
+			1,
+			candidate_hash,
+			CandidateVotes {
+				candidate_receipt,
+				valid: vec![(ValidDisputeStatementKind::Explicit, ValidatorIndex(0), signature)],
+				invalid: Vec::new(),
+			},
+		);
+		let write_ops = overlay_db.into_write_ops();
+		backend.write(write_ops).unwrap();
+
+		let all = disputes(&*store, 0, &Filter::default()).unwrap();
+		assert_eq!(all["earliest_session"], json!(1));
+		assert_eq!(all["disputes"].as_array().unwrap().len(), 2);
+
+		let filter = Filter { block: Some(relay_parent), ..Default::default() };
+		assert_eq!(
+			disputes(&*store, 0, &filter).unwrap()["disputes"],
+			json!([{
+				"session": 1,
+				"candidate_hash": format!("{:?}", candidate_hash.0),
+				"status": { "concluded_for": 10 },
+				"candidate_votes": {
+					"para_id": 1,
+					"relay_parent": format!("{:?}", relay_parent),
+					"valid": [{ "kind": "Explicit", "validator_index": 0 }],
+					"invalid": [],
+				},
+			}]),
+		);
+
+		let filter = Filter { session: Some(2), ..Default::default() };
+		assert_eq!(
+			disputes(&*store, 0, &filter).unwrap()["disputes"],
+			json!([{
+				"session": 2,
+				"candidate_hash": format!("{:?}", other_candidate_hash.0),
+				"status": "active",
+				"candidate_votes": null,
+			}]),
+		);
+
+		let filter =
+			Filter { candidate: Some(candidate_hash), session: Some(2), ..Default::default() };
+		assert!(disputes(&*store, 0, &filter).unwrap()["disputes"]
+			.as_array()
+			.unwrap()
+			.is_empty());
+	}
+}
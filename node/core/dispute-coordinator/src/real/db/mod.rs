@@ -16,4 +16,5 @@
 
 //! Database component for the dispute coordinator.
 
+pub mod inspect;
 pub(super) mod v1;
@@ -0,0 +1,149 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Assembling the evidence of disputes, see [`DisputeEvidence`].
+//!
+//! The votes are known to the coordinator already, the available data and the validation code
+//! are fetched in the same way as for participation.
+
+use std::path::PathBuf;
+
+use futures::channel::oneshot;
+use parity_scale_codec::Encode;
+
+use polkadot_node_primitives::{CandidateVotes, DisputeEvidence};
+use polkadot_node_subsystem::{
+	messages::{AvailabilityRecoveryMessage, DisputeEvidenceError},
+	RecoveryError, SubsystemSender,
+};
+use polkadot_node_subsystem_util::runtime::get_validation_code_by_hash;
+use polkadot_primitives::v1::{CandidateHash, Hash, SessionIndex};
+
+use crate::LOG_TARGET;
+
+/// Where assembled evidence goes.
+pub enum EvidenceSink {
+	/// Respond to a `DisputeCoordinatorMessage::AssembleEvidence` request.
+	Respond(oneshot::Sender<Result<DisputeEvidence, DisputeEvidenceError>>),
+	/// Write the SCALE encoded evidence to the given file.
+	Write(PathBuf),
+}
+
+/// The name of the file the evidence of the given dispute is written to.
+pub fn file_name(session: SessionIndex, candidate_hash: CandidateHash) -> String {
+	format!("{}-{:?}.evidence", session, candidate_hash.0)
+}
+
+/// Assemble the evidence of a dispute and hand it to `sink`.
+///
+/// The validation code is fetched from the state of `block_hash`. Only the write to an
+/// [`EvidenceSink::Write`] blocks on the file system, and is done on a thread of its own.
+pub async fn assemble_evidence(
+	mut sender: impl SubsystemSender,
+	block_hash: Hash,
+	session: SessionIndex,
+	votes: CandidateVotes,
+	sink: EvidenceSink,
+) {
+	let candidate_hash = votes.candidate_receipt.hash();
+	let evidence = assemble(&mut sender, block_hash, session, votes).await;
+
+	match sink {
+		EvidenceSink::Respond(tx) => {
+			let _ = tx.send(evidence);
+		},
+		EvidenceSink::Write(path) => match evidence {
+			Ok(evidence) =>
+				if let Err(err) = write(path.clone(), evidence.encode()).await {
+					tracing::warn!(
+						target: LOG_TARGET,
+						?err,
+						?candidate_hash,
+						?path,
+						"Writing dispute evidence failed",
+					);
+				} else {
+					tracing::info!(
+						target: LOG_TARGET,
+						?candidate_hash,
+						?path,
+						"Wrote dispute evidence",
+					);
+				},
+			Err(err) => {
+				tracing::warn!(
+					target: LOG_TARGET,
+					?err,
+					?candidate_hash,
+					"Assembling dispute evidence failed",
+				);
+			},
+		},
+	}
+}
+
+/// Write `bytes` to the file at `path` on a thread of its own, so that the file system does not
+/// block the executor. The subsystem's blocking tasks cannot be spawned from within a task.
+async fn write(path: PathBuf, bytes: Vec<u8>) -> std::io::Result<()> {
+	let (tx, rx) = oneshot::channel();
+	std::thread::Builder::new().name("dispute-evidence".into()).spawn(move || {
+		let _ = tx.send(std::fs::write(path, bytes));
+	})?;
+	rx.await.unwrap_or_else(|_| {
+		Err(std::io::Error::new(std::io::ErrorKind::Other, "evidence writing thread panicked"))
+	})
+}
+
+async fn assemble(
+	sender: &mut impl SubsystemSender,
+	block_hash: Hash,
+	session: SessionIndex,
+	votes: CandidateVotes,
+) -> Result<DisputeEvidence, DisputeEvidenceError> {
+	let (tx, rx) = oneshot::channel();
+	sender
+		.send_message(
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				votes.candidate_receipt.clone(),
+				session,
+				None,
+				None,
+				tx,
+			)
+			.into(),
+		)
+		.await;
+
+	let available_data = match rx.await {
+		Ok(Ok(data)) => data,
+		Ok(Err(err)) => return Err(DisputeEvidenceError::Unavailable(err)),
+		Err(oneshot::Canceled) =>
+			return Err(DisputeEvidenceError::Unavailable(RecoveryError::Unavailable)),
+	};
+
+	let validation_code_hash = votes.candidate_receipt.descriptor.validation_code_hash;
+	let validation_code =
+		match get_validation_code_by_hash(sender, block_hash, validation_code_hash).await {
+			Ok(Some(code)) => code,
+			Ok(None) => return Err(DisputeEvidenceError::ValidationCodeUnavailable),
+			Err(err) => {
+				tracing::warn!(target: LOG_TARGET, ?err, "Error when fetching validation code.");
+				return Err(DisputeEvidenceError::ValidationCodeUnavailable)
+			},
+		};
+
+	Ok(DisputeEvidence { session, votes, available_data, validation_code })
+}
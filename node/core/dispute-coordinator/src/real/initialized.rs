@@ -16,7 +16,7 @@
 
 //! Dispute coordinator subsystem in initialized state (after first active leaf is received).
 
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, path::PathBuf, sync::Arc};
 
 use futures::{
 	channel::{mpsc, oneshot},
@@ -32,7 +32,7 @@ use polkadot_node_primitives::{
 use polkadot_node_subsystem::{
 	messages::{
 		BlockDescription, DisputeCoordinatorMessage, DisputeDistributionMessage,
-		ImportStatementsResult, RuntimeApiMessage, RuntimeApiRequest,
+		DisputeEvidenceError, ImportStatementsResult, RuntimeApiMessage, RuntimeApiRequest,
 	},
 	overseer, ActivatedLeaf, ActiveLeavesUpdate, FromOverseer, OverseerSignal, SubsystemContext,
 };
@@ -58,6 +58,7 @@ use crate::{
 use super::{
 	backend::Backend,
 	db,
+	evidence::{self, EvidenceSink},
 	ordering::{CandidateComparator, OrderingProvider},
 	participation::{
 		self, Participation, ParticipationRequest, ParticipationStatement, WorkerMessageReceiver,
@@ -79,6 +80,7 @@ pub struct Initialized {
 	participation: Participation,
 	ordering_provider: OrderingProvider,
 	participation_receiver: WorkerMessageReceiver,
+	evidence_path: Option<PathBuf>,
 	metrics: Metrics,
 }
 
@@ -90,7 +92,7 @@ impl Initialized {
 		spam_slots: SpamSlots,
 		ordering_provider: OrderingProvider,
 	) -> Self {
-		let DisputeCoordinatorSubsystem { config: _, store: _, keystore, metrics } = subsystem;
+		let DisputeCoordinatorSubsystem { config, store: _, keystore, metrics } = subsystem;
 
 		let (participation_sender, participation_receiver) = mpsc::channel(1);
 		let participation = Participation::new(participation_sender);
@@ -104,6 +106,7 @@ impl Initialized {
 			ordering_provider,
 			participation,
 			participation_receiver,
+			evidence_path: config.evidence_path,
 			metrics,
 		}
 	}
@@ -590,11 +593,39 @@ impl Initialized {
 
 				let _ = tx.send(undisputed_chain);
 			},
+			DisputeCoordinatorMessage::AssembleEvidence(session, candidate_hash, tx) => {
+				match overlay_db.load_candidate_votes(session, &candidate_hash)? {
+					Some(votes) =>
+						self.fork_evidence(ctx, session, votes.into(), EvidenceSink::Respond(tx))?,
+					None => {
+						let _ = tx.send(Err(DisputeEvidenceError::UnknownCandidate));
+					},
+				}
+			},
 		}
 
 		Ok(Box::new(|| Ok(())))
 	}
 
+	/// Assemble the evidence of a dispute in the background and hand it to `sink`.
+	fn fork_evidence(
+		&self,
+		ctx: &mut impl SubsystemContext,
+		session: SessionIndex,
+		votes: CandidateVotes,
+		sink: EvidenceSink,
+	) -> Result<()> {
+		// The code is still known at the relay parent, if that has not been pruned yet.
+		let block_hash = self
+			.participation
+			.recent_block()
+			.map_or(votes.candidate_receipt.descriptor.relay_parent, |(_, hash)| hash);
+		let sender = ctx.sender().clone();
+		let task = evidence::assemble_evidence(sender, block_hash, session, votes, sink).boxed();
+		ctx.spawn("dispute-evidence", task).map_err(Fatal::SpawnFailed)?;
+		Ok(())
+	}
+
 	async fn handle_import_statements(
 		&mut self,
 		ctx: &mut impl SubsystemContext,
@@ -773,12 +804,13 @@ impl Initialized {
 			}
 			// Participate whenever the imported vote was local & we did not had no cast
 			// previously:
+			let core_index = self.ordering_provider.candidate_core(&candidate_hash);
 			let r = self
 				.participation
 				.queue_participation(
 					ctx,
 					comparator,
-					ParticipationRequest::new(candidate_receipt, session, n_validators),
+					ParticipationRequest::new(candidate_receipt, session, n_validators, core_index),
 				)
 				.await;
 			log_error(r)?;
@@ -828,6 +860,11 @@ impl Initialized {
 
 			if !was_concluded_invalid && concluded_invalid {
 				self.metrics.on_concluded_invalid();
+
+				if let Some(dir) = &self.evidence_path {
+					let path = dir.join(evidence::file_name(session, candidate_hash));
+					self.fork_evidence(ctx, session, votes.clone(), EvidenceSink::Write(path))?;
+				}
 			}
 
 			// Only write when updated:
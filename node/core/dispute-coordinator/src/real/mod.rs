@@ -24,7 +24,7 @@
 //! validation results as well as a sink for votes received by other subsystems. When importing a dispute vote from
 //! another node, this will trigger dispute participation to recover and validate the block.
 
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, path::PathBuf, sync::Arc};
 
 use futures::FutureExt;
 use kvdb::KeyValueDB;
@@ -90,6 +90,10 @@ mod spam_slots;
 /// if there are lots of them.
 pub(crate) mod participation;
 
+/// Assembling the evidence of disputes, on request or when a dispute concludes against a
+/// candidate.
+mod evidence;
+
 use crate::status::Clock;
 
 use crate::LOG_TARGET;
@@ -106,10 +110,13 @@ pub struct DisputeCoordinatorSubsystem {
 }
 
 /// Configuration for the dispute coordinator subsystem.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Config {
 	/// The data column in the store to use for dispute data.
 	pub col_data: u32,
+	/// The directory to write the evidence of disputes concluding against a candidate to, if
+	/// any.
+	pub evidence_path: Option<PathBuf>,
 }
 
 impl Config {
@@ -341,6 +348,7 @@ impl DisputeCoordinatorSubsystem {
 						votes.candidate_receipt.clone(),
 						session,
 						n_validators,
+						ordering_provider.candidate_core(candidate_hash),
 					),
 				));
 			}
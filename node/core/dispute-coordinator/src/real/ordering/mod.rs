@@ -16,7 +16,7 @@
 
 use std::{
 	cmp::{Ord, Ordering, PartialOrd},
-	collections::{BTreeMap, HashSet},
+	collections::{BTreeMap, HashMap, HashSet},
 };
 
 use futures::channel::oneshot;
@@ -26,7 +26,9 @@ use polkadot_node_subsystem::{
 	messages::ChainApiMessage, ActivatedLeaf, ActiveLeavesUpdate, ChainApiError, SubsystemSender,
 };
 use polkadot_node_subsystem_util::runtime::get_candidate_events;
-use polkadot_primitives::v1::{BlockNumber, CandidateEvent, CandidateHash, CandidateReceipt, Hash};
+use polkadot_primitives::v1::{
+	BlockNumber, CandidateEvent, CandidateHash, CandidateReceipt, CoreIndex, Hash,
+};
 
 use crate::{
 	error::{Fatal, FatalResult, Result},
@@ -40,8 +42,9 @@ const LRU_OBSERVED_BLOCKS_CAPACITY: usize = 20;
 
 /// Provider of `CandidateComparator` for candidates.
 pub struct OrderingProvider {
-	/// All candidates we have seen included, which not yet have been finalized.
-	included_candidates: HashSet<CandidateHash>,
+	/// All candidates we have seen included, which not yet have been finalized, along with the
+	/// core they occupied.
+	included_candidates: HashMap<CandidateHash, CoreIndex>,
 	/// including block -> `CandidateHash`
 	///
 	/// We need this to clean up `included_candidates` on `ActiveLeavesUpdate`.
@@ -137,7 +140,7 @@ impl OrderingProvider {
 		initial_head: ActivatedLeaf,
 	) -> Result<Self> {
 		let mut s = Self {
-			included_candidates: HashSet::new(),
+			included_candidates: HashMap::new(),
 			candidates_by_block_number: BTreeMap::new(),
 			last_observed_blocks: LruCache::new(LRU_OBSERVED_BLOCKS_CAPACITY),
 		};
@@ -157,7 +160,7 @@ impl OrderingProvider {
 		candidate: &CandidateReceipt,
 	) -> FatalResult<Option<CandidateComparator>> {
 		let candidate_hash = candidate.hash();
-		if !self.included_candidates.contains(&candidate_hash) {
+		if !self.included_candidates.contains_key(&candidate_hash) {
 			return Ok(None)
 		}
 		let n = match get_block_number(sender, candidate.descriptor().relay_parent).await? {
@@ -175,6 +178,11 @@ impl OrderingProvider {
 		Ok(Some(CandidateComparator { relay_parent_block_number: n, candidate_hash }))
 	}
 
+	/// The core a not yet finalized candidate occupied, if we have seen it included.
+	pub fn candidate_core(&self, candidate_hash: &CandidateHash) -> Option<CoreIndex> {
+		self.included_candidates.get(candidate_hash).copied()
+	}
+
 	/// Query active leaves for any candidate `CandidateEvent::CandidateIncluded` events.
 	///
 	/// and updates current heads, so we can query candidates for all non finalized blocks.
@@ -208,12 +216,13 @@ impl OrderingProvider {
 					.await?
 					.into_iter()
 					.filter_map(|ev| match ev {
-						CandidateEvent::CandidateIncluded(receipt, _, _, _) => Some(receipt),
+						CandidateEvent::CandidateIncluded(receipt, _, core_index, _) =>
+							Some((receipt, core_index)),
 						_ => None,
 					});
-				for receipt in included {
+				for (receipt, core_index) in included {
 					let candidate_hash = receipt.hash();
-					self.included_candidates.insert(candidate_hash);
+					self.included_candidates.insert(candidate_hash, core_index);
 					self.candidates_by_block_number
 						.entry(block_num)
 						.or_default()
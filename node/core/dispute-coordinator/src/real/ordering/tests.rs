@@ -254,6 +254,7 @@ fn ordering_provider_provides_ordering_when_initialized() {
 		assert_matches!(r, Ok(Some(r2)) => {
 			assert_eq!(r2.relay_parent_block_number, 1);
 		});
+		assert_eq!(ordering.candidate_core(&candidate.hash()), Some(CoreIndex::from(0)));
 	});
 }
 
@@ -164,6 +164,11 @@ impl Participation {
 		Ok(self.queue.queue(comparator, req).map_err(NonFatal::QueueError)?)
 	}
 
+	/// The most recent block we know of, if any.
+	pub fn recent_block(&self) -> Option<(BlockNumber, Hash)> {
+		self.recent_block
+	}
+
 	/// Message from a worker task was received - get the outcome.
 	///
 	/// Call this function to keep participations going and to receive `ParticipationStatement`s.
@@ -260,6 +265,7 @@ async fn participate(
 				req.candidate_receipt().clone(),
 				req.session(),
 				None,
+				req.core_index(),
 				recover_available_data_tx,
 			)
 			.into(),
@@ -18,7 +18,7 @@ use std::collections::{BTreeMap, HashMap};
 
 use thiserror::Error;
 
-use polkadot_primitives::v1::{CandidateHash, CandidateReceipt, SessionIndex};
+use polkadot_primitives::v1::{CandidateHash, CandidateReceipt, CoreIndex, SessionIndex};
 
 use crate::real::ordering::CandidateComparator;
 
@@ -73,6 +73,8 @@ pub struct ParticipationRequest {
 	candidate_receipt: CandidateReceipt,
 	session: SessionIndex,
 	n_validators: usize,
+	/// The core the candidate occupied, if known.
+	core_index: Option<CoreIndex>,
 }
 
 /// Entry for the best effort queue.
@@ -97,8 +99,15 @@ impl ParticipationRequest {
 		candidate_receipt: CandidateReceipt,
 		session: SessionIndex,
 		n_validators: usize,
+		core_index: Option<CoreIndex>,
 	) -> Self {
-		Self { candidate_hash: candidate_receipt.hash(), candidate_receipt, session, n_validators }
+		Self {
+			candidate_hash: candidate_receipt.hash(),
+			candidate_receipt,
+			session,
+			n_validators,
+			core_index,
+		}
 	}
 
 	pub fn candidate_receipt(&'_ self) -> &'_ CandidateReceipt {
@@ -113,6 +122,9 @@ impl ParticipationRequest {
 	pub fn n_validators(&self) -> usize {
 		self.n_validators
 	}
+	pub fn core_index(&self) -> Option<CoreIndex> {
+		self.core_index
+	}
 	pub fn into_candidate_info(self) -> (CandidateHash, CandidateReceipt) {
 		let Self { candidate_hash, candidate_receipt, .. } = self;
 		(candidate_hash, candidate_receipt)
@@ -39,7 +39,8 @@ use polkadot_node_subsystem_test_helpers::{
 	make_subsystem_context, TestSubsystemContext, TestSubsystemContextHandle,
 };
 use polkadot_primitives::v1::{
-	BlakeTwo256, CandidateCommitments, HashT, Header, PersistedValidationData, ValidationCode,
+	BlakeTwo256, CandidateCommitments, CoreIndex, HashT, Header, PersistedValidationData,
+	ValidationCode,
 };
 
 type VirtualOverseer = TestSubsystemContextHandle<DisputeCoordinatorMessage>;
@@ -74,7 +75,7 @@ async fn participate_with_commitments_hash(
 	let session = 1;
 	let n_validators = 10;
 
-	let req = ParticipationRequest::new(candidate_receipt, session, n_validators);
+	let req = ParticipationRequest::new(candidate_receipt, session, n_validators, None);
 
 	participation.queue_participation(ctx, None, req).await
 }
@@ -129,7 +130,7 @@ pub async fn participation_missing_availability(ctx_handle: &mut VirtualOverseer
 	assert_matches!(
 		ctx_handle.recv().await,
 		AllMessages::AvailabilityRecovery(
-			AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, tx)
+			AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, tx)
 		) => {
 			tx.send(Err(RecoveryError::Unavailable)).unwrap();
 		},
@@ -148,7 +149,7 @@ async fn recover_available_data(virtual_overseer: &mut VirtualOverseer) {
 	assert_matches!(
 		virtual_overseer.recv().await,
 		AllMessages::AvailabilityRecovery(
-			AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, tx)
+			AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, tx)
 		) => {
 			tx.send(Ok(available_data)).unwrap();
 		},
@@ -206,7 +207,7 @@ fn same_req_wont_get_queued_if_participation_is_already_running() {
 		assert_matches!(
 			ctx_handle.recv().await,
 			AllMessages::AvailabilityRecovery(
-				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, tx)
+				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, tx)
 			) => {
 				tx.send(Err(RecoveryError::Unavailable)).unwrap();
 			},
@@ -252,7 +253,7 @@ fn reqs_get_queued_when_out_of_capacity() {
 			assert_matches!(
 				ctx_handle.recv().await,
 				AllMessages::AvailabilityRecovery(
-					AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, tx)
+					AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, tx)
 				) => {
 					tx.send(Err(RecoveryError::Unavailable)).unwrap();
 				},
@@ -314,7 +315,7 @@ fn cannot_participate_if_cannot_recover_available_data() {
 		assert_matches!(
 			ctx_handle.recv().await,
 			AllMessages::AvailabilityRecovery(
-				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, tx)
+				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, tx)
 			) => {
 				tx.send(Err(RecoveryError::Unavailable)).unwrap();
 			},
@@ -331,6 +332,31 @@ fn cannot_participate_if_cannot_recover_available_data() {
 	})
 }
 
+#[test]
+fn core_of_the_candidate_is_passed_to_recovery() {
+	futures::executor::block_on(async {
+		let (mut ctx, mut ctx_handle) = make_our_subsystem_context(TaskExecutor::new());
+
+		let (sender, _worker_receiver) = mpsc::channel(1);
+		let mut participation = Participation::new(sender);
+		activate_leaf(&mut ctx, &mut participation, 10).await.unwrap();
+
+		let candidate_receipt = dummy_candidate_receipt_bad_sig(dummy_hash(), dummy_hash());
+		let req = ParticipationRequest::new(candidate_receipt, 1, 10, Some(CoreIndex(3)));
+		participation.queue_participation(&mut ctx, None, req).await.unwrap();
+
+		assert_matches!(
+			ctx_handle.recv().await,
+			AllMessages::AvailabilityRecovery(
+				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, core_index, _)
+			) => {
+				assert_eq!(core_index, Some(CoreIndex(3)));
+			},
+			"overseer did not receive recover available data message",
+		);
+	})
+}
+
 #[test]
 fn cannot_participate_if_cannot_recover_validation_code() {
 	futures::executor::block_on(async {
@@ -381,7 +407,7 @@ fn cast_invalid_vote_if_available_data_is_invalid() {
 		assert_matches!(
 			ctx_handle.recv().await,
 			AllMessages::AvailabilityRecovery(
-				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, tx)
+				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, tx)
 			) => {
 				tx.send(Err(RecoveryError::Invalid)).unwrap();
 			},
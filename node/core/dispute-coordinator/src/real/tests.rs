@@ -30,13 +30,15 @@ use futures::{
 };
 
 use kvdb::KeyValueDB;
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 
-use polkadot_node_primitives::SignedDisputeStatement;
+use polkadot_node_primitives::{
+	AvailableData, BlockData, DisputeEvidence, PoV, SignedDisputeStatement,
+};
 use polkadot_node_subsystem::{
 	messages::{
-		ChainApiMessage, DisputeCoordinatorMessage, DisputeDistributionMessage,
-		ImportStatementsResult,
+		AvailabilityRecoveryMessage, ChainApiMessage, DisputeCoordinatorMessage,
+		DisputeDistributionMessage, DisputeEvidenceError, ImportStatementsResult,
 	},
 	overseer::FromOverseer,
 	ChainApiError, OverseerSignal,
@@ -53,12 +55,16 @@ use polkadot_node_subsystem::{
 	messages::{AllMessages, BlockDescription, RuntimeApiMessage, RuntimeApiRequest},
 	ActivatedLeaf, ActiveLeavesUpdate, LeafStatus,
 };
-use polkadot_node_subsystem_test_helpers::{make_subsystem_context, TestSubsystemContextHandle};
+use polkadot_node_subsystem_test_helpers::{
+	make_subsystem_context,
+	replay::{make_trace, replay_trace},
+	TestSubsystemContextHandle,
+};
 use polkadot_primitives::{
 	v1::{
 		BlakeTwo256, BlockNumber, CandidateCommitments, CandidateHash, CandidateReceipt, Hash,
-		HashT, Header, MultiDisputeStatementSet, ScrapedOnChainVotes, SessionIndex, ValidatorId,
-		ValidatorIndex,
+		HashT, Header, MultiDisputeStatementSet, PersistedValidationData, ScrapedOnChainVotes,
+		SessionIndex, ValidationCode, ValidatorId, ValidatorIndex,
 	},
 	v2::SessionInfo,
 };
@@ -155,7 +161,7 @@ impl Default for TestState {
 		let subsystem_keystore = make_keystore(&[Sr25519Keyring::Alice]).into();
 
 		let db = Arc::new(kvdb_memorydb::create(1));
-		let config = Config { col_data: 0 };
+		let config = Config { col_data: 0, evidence_path: None };
 
 		TestState {
 			validators,
@@ -1548,3 +1554,334 @@ fn negative_issue_local_statement_only_triggers_import() {
 		})
 	});
 }
+
+#[test]
+fn dispute_evidence_is_assembled() {
+	test_harness(|mut test_state, mut virtual_overseer| {
+		Box::pin(async move {
+			let session = 1;
+
+			test_state.handle_resume_sync(&mut virtual_overseer, session).await;
+
+			let candidate_receipt = make_valid_candidate_receipt();
+			let candidate_hash = candidate_receipt.hash();
+
+			test_state.activate_leaf_at_session(&mut virtual_overseer, session, 1).await;
+
+			let valid_vote =
+				test_state.issue_statement_with_index(3, candidate_hash, session, true).await;
+
+			let invalid_vote =
+				test_state.issue_statement_with_index(1, candidate_hash, session, false).await;
+
+			let (pending_confirmation, _confirmation_rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOverseer::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_hash,
+						candidate_receipt: candidate_receipt.clone(),
+						session,
+						statements: vec![
+							(valid_vote, ValidatorIndex(3)),
+							(invalid_vote, ValidatorIndex(1)),
+						],
+						pending_confirmation,
+					},
+				})
+				.await;
+
+			participation_with_distribution(&mut virtual_overseer, &candidate_hash).await;
+
+			let (tx, rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOverseer::Communication {
+					msg: DisputeCoordinatorMessage::AssembleEvidence(session, candidate_hash, tx),
+				})
+				.await;
+
+			let available_data = AvailableData {
+				pov: Arc::new(PoV { block_data: BlockData(vec![1, 2, 3]) }),
+				validation_data: PersistedValidationData::default(),
+			};
+			let validation_code = ValidationCode(vec![4, 5, 6]);
+
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::AvailabilityRecovery(AvailabilityRecoveryMessage::RecoverAvailableData(
+					receipt,
+					recovery_session,
+					_,
+					_,
+					tx,
+				)) => {
+					assert_eq!(receipt, candidate_receipt);
+					assert_eq!(recovery_session, session);
+					tx.send(Ok(available_data.clone())).unwrap();
+				}
+			);
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_,
+					RuntimeApiRequest::ValidationCodeByHash(code_hash, tx),
+				)) => {
+					assert_eq!(code_hash, candidate_receipt.descriptor.validation_code_hash);
+					tx.send(Ok(Some(validation_code.clone()))).unwrap();
+				}
+			);
+
+			let evidence = rx.await.unwrap().unwrap();
+			assert_eq!(evidence.session, session);
+			assert_eq!(evidence.candidate_receipt(), &candidate_receipt);
+			assert_eq!(evidence.votes.valid.len(), 2);
+			assert_eq!(evidence.votes.invalid.len(), 1);
+			assert_eq!(evidence.available_data, available_data);
+			assert_eq!(evidence.validation_code, validation_code);
+
+			// Nothing is known about other candidates.
+			let (tx, rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOverseer::Communication {
+					msg: DisputeCoordinatorMessage::AssembleEvidence(
+						session,
+						CandidateHash(Hash::repeat_byte(1)),
+						tx,
+					),
+				})
+				.await;
+			assert_eq!(rx.await.unwrap().unwrap_err(), DisputeEvidenceError::UnknownCandidate);
+
+			virtual_overseer.send(FromOverseer::Signal(OverseerSignal::Conclude)).await;
+			assert!(virtual_overseer.try_recv().await.is_none());
+
+			test_state
+		})
+	});
+}
+
+#[test]
+fn dispute_evidence_is_written_on_conclusion() {
+	let session = 1;
+	let dir = std::env::temp_dir().join(format!("dispute-evidence-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+
+	let mut test_state = TestState::default();
+	test_state.config.evidence_path = Some(dir.clone());
+	test_state.resume(|mut test_state, mut virtual_overseer| {
+		let dir = dir.clone();
+		Box::pin(async move {
+			test_state.handle_resume_sync(&mut virtual_overseer, session).await;
+
+			let candidate_receipt = make_invalid_candidate_receipt();
+			let candidate_hash = candidate_receipt.hash();
+
+			test_state.activate_leaf_at_session(&mut virtual_overseer, session, 1).await;
+
+			let supermajority_threshold =
+				polkadot_primitives::v1::supermajority_threshold(test_state.validators.len());
+
+			let valid_vote =
+				test_state.issue_statement_with_index(2, candidate_hash, session, true).await;
+
+			let invalid_vote =
+				test_state.issue_statement_with_index(1, candidate_hash, session, false).await;
+
+			let (pending_confirmation, confirmation_rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOverseer::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_hash,
+						candidate_receipt: candidate_receipt.clone(),
+						session,
+						statements: vec![
+							(valid_vote, ValidatorIndex(2)),
+							(invalid_vote, ValidatorIndex(1)),
+						],
+						pending_confirmation,
+					},
+				})
+				.await;
+			assert_matches!(confirmation_rx.await.unwrap(),
+				ImportStatementsResult::ValidImport => {}
+			);
+
+			participation_with_distribution(&mut virtual_overseer, &candidate_hash).await;
+
+			let mut statements = Vec::new();
+			// minus 2, because of local vote and one previously imported invalid vote.
+			for i in (0..supermajority_threshold - 2).map(|i| i + 3) {
+				let vote =
+					test_state.issue_statement_with_index(i, candidate_hash, session, false).await;
+
+				statements.push((vote, ValidatorIndex(i as _)));
+			}
+
+			let (pending_confirmation, _confirmation_rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOverseer::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_hash,
+						candidate_receipt: candidate_receipt.clone(),
+						session,
+						statements,
+						pending_confirmation,
+					},
+				})
+				.await;
+
+			let available_data = AvailableData {
+				pov: Arc::new(PoV { block_data: BlockData(vec![1, 2, 3]) }),
+				validation_data: PersistedValidationData::default(),
+			};
+			let validation_code = ValidationCode(vec![4, 5, 6]);
+
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::AvailabilityRecovery(AvailabilityRecoveryMessage::RecoverAvailableData(
+					receipt,
+					_,
+					_,
+					_,
+					tx,
+				)) => {
+					assert_eq!(receipt, candidate_receipt);
+					tx.send(Ok(available_data.clone())).unwrap();
+				}
+			);
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_,
+					RuntimeApiRequest::ValidationCodeByHash(_, tx),
+				)) => {
+					tx.send(Ok(Some(validation_code.clone()))).unwrap();
+				}
+			);
+
+			// The evidence is written in the background.
+			let path = dir.join(super::evidence::file_name(session, candidate_hash));
+			let mut evidence = None;
+			for _ in 0..100 {
+				evidence = std::fs::read(&path)
+					.ok()
+					.and_then(|bytes| DisputeEvidence::decode(&mut &bytes[..]).ok());
+				if evidence.is_some() {
+					break
+				}
+				std::thread::sleep(Duration::from_millis(20));
+			}
+			let evidence = evidence.expect("evidence is written");
+			assert_eq!(evidence.session, session);
+			assert_eq!(evidence.candidate_receipt(), &candidate_receipt);
+			assert_eq!(evidence.votes.valid.len(), 1);
+			assert_eq!(evidence.votes.invalid.len(), supermajority_threshold);
+			assert_eq!(evidence.available_data, available_data);
+			assert_eq!(evidence.validation_code, validation_code);
+
+			virtual_overseer.send(FromOverseer::Signal(OverseerSignal::Conclude)).await;
+			assert!(virtual_overseer.try_recv().await.is_none());
+
+			test_state
+		})
+	});
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn replayed_trace_starts_a_dispute() {
+	test_harness(|mut test_state, mut virtual_overseer| {
+		Box::pin(async move {
+			let session = 1;
+
+			test_state.handle_resume_sync(&mut virtual_overseer, session).await;
+
+			let candidate_receipt = make_valid_candidate_receipt();
+			let candidate_hash = candidate_receipt.hash();
+
+			let valid_vote =
+				test_state.issue_statement_with_index(3, candidate_hash, session, true).await;
+			let invalid_vote =
+				test_state.issue_statement_with_index(1, candidate_hash, session, false).await;
+
+			let (pending_confirmation, _confirmation_rx) = oneshot::channel();
+			let trace = make_trace(vec![
+				FromOverseer::Signal(OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(
+					ActivatedLeaf {
+						hash: Hash::repeat_byte(1),
+						span: Arc::new(jaeger::Span::Disabled),
+						number: 1,
+						status: LeafStatus::Fresh,
+					},
+				))),
+				FromOverseer::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_hash,
+						candidate_receipt: candidate_receipt.clone(),
+						session,
+						statements: vec![
+							(valid_vote, ValidatorIndex(3)),
+							(invalid_vote, ValidatorIndex(1)),
+						],
+						pending_confirmation,
+					},
+				},
+			]);
+
+			// Answer the requests of the subsystem the way a node at `session` would and note the
+			// recoveries it starts to participate in disputes.
+			let session_info = test_state.session_info();
+			let mut recovering = Vec::new();
+			let replayed =
+				replay_trace(&mut virtual_overseer, &trace, Duration::from_millis(100), |msg| {
+					match msg {
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							_,
+							RuntimeApiRequest::SessionIndexForChild(tx),
+						)) => tx.send(Ok(session)).unwrap(),
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							_,
+							RuntimeApiRequest::SessionInfo(_, tx),
+						)) => tx.send(Ok(Some(session_info.clone()))).unwrap(),
+						AllMessages::ChainApi(ChainApiMessage::FinalizedBlockNumber(tx)) => {
+							tx.send(Err(ChainApiError::from(""))).unwrap()
+						},
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							_,
+							RuntimeApiRequest::CandidateEvents(tx),
+						)) => tx.send(Ok(Vec::new())).unwrap(),
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							_,
+							RuntimeApiRequest::FetchOnChainVotes(tx),
+						)) => tx
+							.send(Ok(Some(ScrapedOnChainVotes {
+								session,
+								backing_validators_per_candidate: Vec::default(),
+								disputes: MultiDisputeStatementSet::default(),
+							})))
+							.unwrap(),
+						AllMessages::AvailabilityRecovery(
+							AvailabilityRecoveryMessage::RecoverAvailableData(receipt, ..),
+						) => recovering.push(receipt.hash()),
+						msg => panic!("Unexpected message: {:?}", msg),
+					}
+				})
+				.await;
+
+			assert_eq!(replayed, 2);
+			assert_eq!(recovering, vec![candidate_hash]);
+
+			let (tx, rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOverseer::Communication {
+					msg: DisputeCoordinatorMessage::ActiveDisputes(tx),
+				})
+				.await;
+			assert_eq!(rx.await.unwrap(), vec![(session, candidate_hash)]);
+
+			virtual_overseer.send(FromOverseer::Signal(OverseerSignal::Conclude)).await;
+
+			test_state
+		})
+	});
+}
@@ -28,7 +28,9 @@ use futures::{select, FutureExt};
 use polkadot_node_subsystem::{
 	errors::SubsystemError, messages::ProvisionerMessage, overseer::Handle,
 };
-use polkadot_primitives::v1::{Block, Hash, InherentData as ParachainsInherentData};
+use polkadot_primitives::v1::{
+	Block, Hash, InherentData as ParachainsInherentData, MultiApprovalVoteSet,
+};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::generic::BlockId;
 use std::time;
@@ -39,6 +41,7 @@ const PROVISIONER_TIMEOUT: time::Duration = core::time::Duration::from_millis(25
 /// Provides the parachains inherent data.
 pub struct ParachainsInherentDataProvider {
 	inherent_data: ParachainsInherentData,
+	approvals: MultiApprovalVoteSet,
 }
 
 impl ParachainsInherentDataProvider {
@@ -80,28 +83,34 @@ impl ParachainsInherentDataProvider {
 			_ = timeout => Err(Error::Timeout),
 		};
 
-		let inherent_data = match res {
-			Ok(pd) => ParachainsInherentData {
-				bitfields: pd.bitfields.into_iter().map(Into::into).collect(),
-				backed_candidates: pd.backed_candidates,
-				disputes: pd.disputes,
-				parent_header,
-			},
+		let (inherent_data, approvals) = match res {
+			Ok(pd) => (
+				ParachainsInherentData {
+					bitfields: pd.bitfields.into_iter().map(Into::into).collect(),
+					backed_candidates: pd.backed_candidates,
+					disputes: pd.disputes,
+					parent_header,
+				},
+				pd.approvals,
+			),
 			Err(err) => {
 				tracing::debug!(
 					?err,
 					"Could not get provisioner inherent data; injecting default data",
 				);
-				ParachainsInherentData {
-					bitfields: Vec::new(),
-					backed_candidates: Vec::new(),
-					disputes: Vec::new(),
-					parent_header,
-				}
+				(
+					ParachainsInherentData {
+						bitfields: Vec::new(),
+						backed_candidates: Vec::new(),
+						disputes: Vec::new(),
+						parent_header,
+					},
+					Vec::new(),
+				)
 			},
 		};
 
-		Ok(Self { inherent_data })
+		Ok(Self { inherent_data, approvals })
 	}
 }
 
@@ -111,8 +120,12 @@ impl sp_inherents::InherentDataProvider for ParachainsInherentDataProvider {
 		&self,
 		dst_inherent_data: &mut sp_inherents::InherentData,
 	) -> Result<(), sp_inherents::Error> {
+		dst_inherent_data.put_data(
+			polkadot_primitives::v1::PARACHAINS_INHERENT_IDENTIFIER,
+			&self.inherent_data,
+		)?;
 		dst_inherent_data
-			.put_data(polkadot_primitives::v1::PARACHAINS_INHERENT_IDENTIFIER, &self.inherent_data)
+			.put_data(polkadot_primitives::v1::APPROVAL_VOTES_INHERENT_IDENTIFIER, &self.approvals)
 	}
 
 	async fn try_handle_error(
@@ -36,13 +36,15 @@ use polkadot_node_subsystem::{
 	ActivatedLeaf, LeafStatus, PerLeafSpan, SubsystemSender,
 };
 use polkadot_node_subsystem_util::{
-	self as util, request_availability_cores, request_persisted_validation_data, JobSender,
-	JobSubsystem, JobTrait,
+	self as util, request_availability_cores, request_candidate_events,
+	request_persisted_validation_data, request_session_index_for_child, JobSender, JobSubsystem,
+	JobTrait,
 };
 use polkadot_primitives::v1::{
-	BackedCandidate, BlockNumber, CandidateHash, CandidateReceipt, CoreState, DisputeStatement,
-	DisputeStatementSet, Hash, MultiDisputeStatementSet, OccupiedCoreAssumption, SessionIndex,
-	SignedAvailabilityBitfield, ValidatorIndex,
+	ApprovalVoteSet, BackedCandidate, BlockNumber, CandidateEvent, CandidateHash, CandidateReceipt,
+	CoreState, DisputeStatement, DisputeStatementSet, Hash, MultiApprovalVoteSet,
+	MultiDisputeStatementSet, OccupiedCoreAssumption, SessionIndex, SignedAvailabilityBitfield,
+	ValidDisputeStatementKind, ValidatorIndex,
 };
 use std::{
 	collections::{BTreeMap, HashSet},
@@ -62,6 +64,10 @@ const PRE_PROPOSE_TIMEOUT: std::time::Duration = core::time::Duration::from_mill
 
 const LOG_TARGET: &str = "parachain::provisioner";
 
+/// How many ancestors of the leaf are scanned for included candidates, whose approval votes are
+/// then provided to the runtime.
+const APPROVAL_VOTES_LOOKBACK: usize = 16;
+
 enum InherentAfter {
 	Ready,
 	Wait(Delay),
@@ -127,6 +133,15 @@ pub enum Error {
 	#[error("failed to get votes on dispute")]
 	CanceledCandidateVotes(#[source] oneshot::Canceled),
 
+	#[error("failed to get session index")]
+	CanceledSessionIndex(#[source] oneshot::Canceled),
+
+	#[error("failed to get candidate events")]
+	CanceledCandidateEvents(#[source] oneshot::Canceled),
+
+	#[error("failed to get ancestors")]
+	CanceledAncestors(#[source] oneshot::Canceled),
+
 	#[error(transparent)]
 	ChainApi(#[from] ChainApiError),
 
@@ -151,7 +166,8 @@ pub enum Error {
 /// Provisioner run arguments.
 #[derive(Debug, Clone, Copy)]
 pub struct ProvisionerConfig {
-	/// If enabled, dispute votes will be provided to `fn create_inherent`, otherwise not.
+	/// If enabled, dispute votes and approval votes will be provided to `fn create_inherent`,
+	/// otherwise not.
 	/// Long term we will obviously always want disputes to be enabled, this option exists for testing purposes
 	/// and will be removed in the near future.
 	pub disputes_enabled: bool,
@@ -327,6 +343,17 @@ async fn send_inherent_data(
 	let disputes =
 		if disputes_enabled { select_disputes(from_job, metrics).await? } else { vec![] };
 
+	// Approval votes are only known to the dispute coordinator. Rewarding them is not essential
+	// to block production, so failing to collect them must not fail the inherent.
+	let approvals = if disputes_enabled {
+		select_approvals(leaf, from_job, metrics).await.unwrap_or_else(|err| {
+			tracing::warn!(target: LOG_TARGET, err = ?err, "failed to select approval votes");
+			vec![]
+		})
+	} else {
+		vec![]
+	};
+
 	// Only include bitfields on fresh leaves. On chain reversions, we want to make sure that
 	// there will be at least one block, which cannot get disputed, so the chain can make progress.
 	let bitfields = match leaf.status {
@@ -337,7 +364,7 @@ async fn send_inherent_data(
 		select_candidates(&availability_cores, &bitfields, candidates, leaf.hash, from_job).await?;
 
 	let inherent_data =
-		ProvisionerInherentData { bitfields, backed_candidates: candidates, disputes };
+		ProvisionerInherentData { bitfields, backed_candidates: candidates, disputes, approvals };
 
 	for return_sender in return_senders {
 		return_sender
@@ -722,5 +749,94 @@ async fn select_disputes(
 		.collect())
 }
 
+/// Collect the approval votes on the candidates included in the last `APPROVAL_VOTES_LOOKBACK`
+/// blocks, so that the runtime can reward approval checkers.
+///
+/// Only candidates included in the session of the block to be built are considered, since the
+/// runtime rewards approvals only within the session a candidate was included in. Votes that were
+/// already rewarded are filtered out by the runtime.
+async fn select_approvals(
+	leaf: &ActivatedLeaf,
+	sender: &mut impl SubsystemSender,
+	metrics: &metrics::Metrics,
+) -> Result<MultiApprovalVoteSet, Error> {
+	let session = request_session_index_for_child(leaf.hash, sender)
+		.await
+		.await
+		.map_err(|err| Error::CanceledSessionIndex(err))??;
+
+	let (tx, rx) = oneshot::channel();
+	sender
+		.send_message(
+			ChainApiMessage::Ancestors {
+				hash: leaf.hash,
+				// one more, for the session of the oldest block scanned.
+				k: APPROVAL_VOTES_LOOKBACK + 1,
+				response_channel: tx,
+			}
+			.into(),
+		)
+		.await;
+	let ancestors = rx.await.map_err(|err| Error::CanceledAncestors(err))??;
+	let blocks = std::iter::once(leaf.hash).chain(ancestors).collect::<Vec<_>>();
+
+	let mut included = Vec::new();
+	for block in blocks.windows(2) {
+		let (hash, parent) = (block[0], block[1]);
+
+		// A block is built in the session its parent announces for its child. Older blocks can
+		// only be of earlier sessions.
+		let block_session = request_session_index_for_child(parent, sender)
+			.await
+			.await
+			.map_err(|err| Error::CanceledSessionIndex(err))??;
+		if block_session != session {
+			break
+		}
+
+		let events = request_candidate_events(hash, sender)
+			.await
+			.await
+			.map_err(|err| Error::CanceledCandidateEvents(err))??;
+
+		included.extend(events.into_iter().filter_map(|event| match event {
+			CandidateEvent::CandidateIncluded(receipt, ..) => Some((session, receipt.hash())),
+			_ => None,
+		}));
+	}
+
+	let approvals = approval_vote_sets(request_votes(sender, included).await);
+	metrics.inc_approval_votes_by(approvals.iter().map(|set| set.votes.len()).sum());
+
+	Ok(approvals)
+}
+
+/// Extract the approval votes from the given `CandidateVotes`, dropping candidates without any.
+fn approval_vote_sets(
+	candidate_votes: Vec<(SessionIndex, CandidateHash, CandidateVotes)>,
+) -> MultiApprovalVoteSet {
+	candidate_votes
+		.into_iter()
+		.filter_map(|(session, candidate_hash, votes)| {
+			// Votes are sorted by validator index already.
+			let votes = votes
+				.valid
+				.into_iter()
+				.filter_map(|(kind, validator_index, signature)| match kind {
+					ValidDisputeStatementKind::ApprovalChecking =>
+						Some((validator_index, signature)),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+
+			if votes.is_empty() {
+				None
+			} else {
+				Some(ApprovalVoteSet { candidate_hash, session, votes })
+			}
+		})
+		.collect()
+}
+
 /// The provisioner subsystem.
 pub type ProvisionerSubsystem<Spawner> = JobSubsystem<ProvisionerJob, Spawner>;
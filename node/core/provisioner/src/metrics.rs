@@ -28,6 +28,9 @@ struct MetricsInner {
 	/// 4 hours on Polkadot. The metrics are updated only when the node authors a block, so values vary across nodes.
 	inherent_data_dispute_statement_sets: prometheus::Counter<prometheus::U64>,
 	inherent_data_dispute_statements: prometheus::CounterVec<prometheus::U64>,
+
+	/// The number of approval votes provided to the runtime for rewarding approval checkers.
+	inherent_data_approval_votes: prometheus::Counter<prometheus::U64>,
 }
 
 /// Provisioner metrics.
@@ -83,6 +86,12 @@ impl Metrics {
 				.inc_by(disputes.try_into().unwrap_or(0));
 		}
 	}
+
+	pub(crate) fn inc_approval_votes_by(&self, votes: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.inherent_data_approval_votes.inc_by(votes.try_into().unwrap_or(0));
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -129,6 +138,13 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			inherent_data_approval_votes: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_inherent_data_approval_votes",
+					"Number of approval votes passed to `create_inherent()`.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -482,3 +482,157 @@ mod select_candidates {
 		)
 	}
 }
+
+mod select_approvals {
+	use super::*;
+	use polkadot_node_subsystem::messages::{
+		AllMessages, RuntimeApiMessage,
+		RuntimeApiRequest::{CandidateEvents, SessionIndexForChild},
+	};
+	use polkadot_primitives::v1::{
+		CoreIndex, GroupIndex, HeadData, InvalidDisputeStatementKind, ValidatorSignature,
+	};
+	use std::sync::Arc;
+
+	fn signature(byte: u8) -> ValidatorSignature {
+		sp_application_crypto::sr25519::Signature([byte; 64]).into()
+	}
+
+	fn candidate_votes(
+		valid: Vec<(ValidDisputeStatementKind, u32)>,
+		invalid: Vec<u32>,
+	) -> CandidateVotes {
+		CandidateVotes {
+			candidate_receipt: test_helpers::dummy_candidate_receipt(dummy_hash()),
+			valid: valid
+				.into_iter()
+				.map(|(kind, i)| (kind, ValidatorIndex(i), signature(i as u8)))
+				.collect(),
+			invalid: invalid
+				.into_iter()
+				.map(|i| {
+					(InvalidDisputeStatementKind::Explicit, ValidatorIndex(i), signature(i as u8))
+				})
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn only_approval_votes_are_selected() {
+		let candidate_a = CandidateHash(Hash::repeat_byte(1));
+		let candidate_b = CandidateHash(Hash::repeat_byte(2));
+
+		let votes = vec![
+			(
+				1,
+				candidate_a,
+				candidate_votes(
+					vec![
+						(ValidDisputeStatementKind::BackingValid(dummy_hash()), 0),
+						(ValidDisputeStatementKind::ApprovalChecking, 1),
+						(ValidDisputeStatementKind::Explicit, 2),
+						(ValidDisputeStatementKind::ApprovalChecking, 3),
+					],
+					vec![4],
+				),
+			),
+			(
+				1,
+				candidate_b,
+				candidate_votes(vec![(ValidDisputeStatementKind::Explicit, 0)], vec![1]),
+			),
+		];
+
+		assert_eq!(
+			approval_vote_sets(votes),
+			vec![ApprovalVoteSet {
+				candidate_hash: candidate_a,
+				session: 1,
+				votes: vec![(ValidatorIndex(1), signature(1)), (ValidatorIndex(3), signature(3))],
+			}],
+		);
+	}
+
+	#[test]
+	fn only_candidates_of_the_current_session_are_considered() {
+		let leaf = Hash::repeat_byte(1);
+		// `leaf` and `a` were built in session 2, `b` in session 1.
+		let (a, b, c) = (Hash::repeat_byte(2), Hash::repeat_byte(3), Hash::repeat_byte(4));
+		let session_for_child = move |hash: Hash| if hash == c { 1 } else { 2 };
+
+		let included_in = |hash: Hash| {
+			let mut receipt = test_helpers::dummy_candidate_receipt(dummy_hash());
+			receipt.descriptor.relay_parent = hash;
+			receipt
+		};
+		let (candidate_leaf, candidate_a) = (included_in(leaf).hash(), included_in(a).hash());
+
+		let (mut sender, mut receiver) = polkadot_node_subsystem_test_helpers::sender_receiver();
+		let overseer = async move {
+			while let Some(msg) = receiver.next().await {
+				match msg {
+					AllMessages::ChainApi(ChainApiMessage::Ancestors {
+						hash,
+						k,
+						response_channel,
+					}) => {
+						assert_eq!((hash, k), (leaf, APPROVAL_VOTES_LOOKBACK + 1));
+						response_channel.send(Ok(vec![a, b, c])).unwrap();
+					},
+					AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+						hash,
+						SessionIndexForChild(tx),
+					)) => tx.send(Ok(session_for_child(hash))).unwrap(),
+					AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+						hash,
+						CandidateEvents(tx),
+					)) => {
+						assert_ne!(hash, b, "blocks of past sessions are not scanned");
+						let event = CandidateEvent::CandidateIncluded(
+							included_in(hash),
+							HeadData::default(),
+							CoreIndex(0),
+							GroupIndex(0),
+						);
+						tx.send(Ok(vec![event])).unwrap();
+					},
+					AllMessages::DisputeCoordinator(
+						DisputeCoordinatorMessage::QueryCandidateVotes(query, tx),
+					) => {
+						assert_eq!(query, vec![(2, candidate_leaf), (2, candidate_a)]);
+						let votes = query
+							.into_iter()
+							.map(|(session, candidate_hash)| {
+								let votes = candidate_votes(
+									vec![(ValidDisputeStatementKind::ApprovalChecking, 0)],
+									vec![],
+								);
+								(session, candidate_hash, votes)
+							})
+							.collect();
+						tx.send(votes).unwrap();
+					},
+					msg => panic!("Unexpected message: {:?}", msg),
+				}
+			}
+		};
+
+		let test = async move {
+			let leaf = ActivatedLeaf {
+				hash: leaf,
+				number: 10,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			};
+			let approvals =
+				select_approvals(&leaf, &mut sender, &Default::default()).await.unwrap();
+			assert_eq!(
+				approvals.iter().map(|set| set.candidate_hash).collect::<Vec<_>>(),
+				vec![candidate_leaf, candidate_a],
+			);
+		};
+
+		futures::pin_mut!(overseer, test);
+		let _ = futures::executor::block_on(future::select(overseer, test));
+	}
+}
@@ -0,0 +1,89 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Records the versions of the crates that affect the compiled PVF artifacts.
+//!
+//! The resulting string is embedded into the header of every artifact, so that artifacts produced
+//! by a different version of the executor are not picked up after a node restart.
+
+use std::path::{Path, PathBuf};
+
+/// The packages which influence the machine code produced for a PVF.
+const EXECUTOR_PACKAGES: &[&str] =
+	&["sc-executor", "sc-executor-wasmi", "sc-executor-wasmtime", "wasmi", "wasmtime"];
+
+fn main() {
+	let version = match find_lock_file() {
+		Some(lock_file) => {
+			println!("cargo:rerun-if-changed={}", lock_file.display());
+			std::fs::read_to_string(&lock_file)
+				.ok()
+				.and_then(|contents| executor_version(&contents))
+		},
+		None => None,
+	};
+
+	// Fall back to the version of this crate in the unlikely case there is no lock file around.
+	let version = version.unwrap_or_else(|| {
+		format!("polkadot-node-core-pvf {}", std::env::var("CARGO_PKG_VERSION").unwrap_or_default())
+	});
+
+	println!("cargo:rustc-env=PVF_EXECUTOR_VERSION={}", version);
+}
+
+/// Walks up from the manifest directory looking for the `Cargo.lock` of the workspace.
+fn find_lock_file() -> Option<PathBuf> {
+	let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").ok()?);
+	let mut dir: Option<&Path> = Some(&manifest_dir);
+	while let Some(current) = dir {
+		let candidate = current.join("Cargo.lock");
+		if candidate.exists() {
+			return Some(candidate)
+		}
+		dir = current.parent();
+	}
+	None
+}
+
+/// Extracts `<name> <version> (<source>)` of every package in [`EXECUTOR_PACKAGES`] from the
+/// given lock file contents, joined with `;`.
+fn executor_version(lock_file: &str) -> Option<String> {
+	let mut found = Vec::new();
+
+	for package in lock_file.split("[[package]]").skip(1) {
+		let field = |key: &str| {
+			package.lines().find_map(|line| {
+				let value = line.trim().strip_prefix(key)?.trim_start().strip_prefix('=')?;
+				Some(value.trim().trim_matches('"').to_string())
+			})
+		};
+
+		let name = match field("name") {
+			Some(name) if EXECUTOR_PACKAGES.contains(&name.as_str()) => name,
+			_ => continue,
+		};
+		let version = field("version").unwrap_or_default();
+		let source = field("source").unwrap_or_default();
+		found.push(format!("{} {} ({})", name, version, source));
+	}
+
+	if found.is_empty() {
+		return None
+	}
+
+	found.sort();
+	Some(found.join(";"))
+}
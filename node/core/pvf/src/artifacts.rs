@@ -14,62 +14,95 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{error::PrepareError, host::PrepareResultSender};
+use crate::{engine::Engine, error::PrepareError, host::PrepareResultSender, LOG_TARGET};
 use always_assert::always;
-use async_std::path::{Path, PathBuf};
-use parity_scale_codec::{Decode, Encode};
+use async_std::{
+	io::ReadExt as _,
+	path::{Path, PathBuf},
+	stream::StreamExt as _,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
 use polkadot_parachain::primitives::ValidationCodeHash;
 use std::{
 	collections::HashMap,
 	time::{Duration, SystemTime},
 };
 
+/// The header of an artifact that identifies the executor the artifact was compiled with.
+///
+/// Artifacts are kept across node restarts. An artifact is only reused if its header matches the
+/// one of the running executor; otherwise it is discarded and the PVF gets prepared again.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ArtifactHeader {
+	/// The version of `sc-executor` and `wasmtime` that produced the artifact.
+	pub executor_version: String,
+	/// The digest of the executor configuration that was used to produce the artifact.
+	pub config_hash: [u8; 32],
+}
+
+impl ArtifactHeader {
+	/// Returns the header for artifacts produced by the executor of this node.
+	pub fn current() -> Self {
+		Self {
+			executor_version: crate::executor_intf::EXECUTOR_VERSION.to_string(),
+			config_hash: crate::executor_intf::config_hash(),
+		}
+	}
+}
+
 /// A wrapper for the compiled PVF code.
 #[derive(Encode, Decode)]
-pub struct CompiledArtifact(Vec<u8>);
+pub struct CompiledArtifact {
+	header: ArtifactHeader,
+	code: Vec<u8>,
+}
 
 impl CompiledArtifact {
 	pub fn new(code: Vec<u8>) -> Self {
-		Self(code)
+		Self { header: ArtifactHeader::current(), code }
+	}
+
+	/// Returns the header this artifact was compiled with.
+	pub fn header(&self) -> &ArtifactHeader {
+		&self.header
 	}
 }
 
 impl AsRef<[u8]> for CompiledArtifact {
 	fn as_ref(&self) -> &[u8] {
-		self.0.as_slice()
+		self.code.as_slice()
 	}
 }
 
-/// Identifier of an artifact. Right now it only encodes a code hash of the PVF. But if we get to
-/// multiple engine implementations the artifact ID should include the engine type as well.
+/// Identifier of an artifact. Encodes the code hash of the PVF and the engine the artifact is
+/// prepared for.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ArtifactId {
 	pub(crate) code_hash: ValidationCodeHash,
+	pub(crate) engine: Engine,
 }
 
 impl ArtifactId {
-	const PREFIX: &'static str = "wasmtime_";
-
-	/// Creates a new artifact ID with the given hash.
-	pub fn new(code_hash: ValidationCodeHash) -> Self {
-		Self { code_hash }
+	/// Creates a new artifact ID with the given hash for the given engine.
+	pub fn new(code_hash: ValidationCodeHash, engine: Engine) -> Self {
+		Self { code_hash, engine }
 	}
 
 	/// Tries to recover the artifact id from the given file name.
-	#[cfg(test)]
 	pub fn from_file_name(file_name: &str) -> Option<Self> {
 		use polkadot_core_primitives::Hash;
 		use std::str::FromStr as _;
 
-		let file_name = file_name.strip_prefix(Self::PREFIX)?;
-		let code_hash = Hash::from_str(file_name).ok()?.into();
-
-		Some(Self { code_hash })
+		Engine::all().iter().find_map(|&engine| {
+			let file_name = file_name.strip_prefix(engine.artifact_prefix())?;
+			let code_hash = Hash::from_str(file_name).ok()?.into();
+			Some(Self { code_hash, engine })
+		})
 	}
 
 	/// Returns the expected path to this artifact given the root of the cache.
 	pub fn path(&self, cache_path: &Path) -> PathBuf {
-		let file_name = format!("{}{:#x}", Self::PREFIX, self.code_hash);
+		let file_name = format!("{}{:#x}", self.engine.artifact_prefix(), self.code_hash);
 		cache_path.join(file_name)
 	}
 }
@@ -117,18 +150,36 @@ pub struct Artifacts {
 	artifacts: HashMap<ArtifactId, ArtifactState>,
 }
 
+/// The maximum number of bytes read from the beginning of an artifact file to decode its header.
+const HEADER_READ_LIMIT: u64 = 4096;
+
 impl Artifacts {
-	/// Initialize a blank cache at the given path. This will clear everything present at the
-	/// given path, to be populated over time.
+	/// Initialize the cache at the given path, creating the directory if it doesn't exist.
 	///
-	/// The recognized artifacts will be filled in the table and unrecognized will be removed.
+	/// The artifacts left over from the previous runs are scanned: those that are recognized and
+	/// were compiled by the current executor are put in the table as prepared; everything else,
+	/// including stale, corrupted and unrecognized files, is removed.
 	pub async fn new(cache_path: &Path) -> Self {
 		// Make sure that the cache path directory and all it's parents are created.
-		// First delete the entire cache. Nodes are long-running so this should populate shortly.
-		let _ = async_std::fs::remove_dir_all(cache_path).await;
 		let _ = async_std::fs::create_dir_all(cache_path).await;
 
-		Self { artifacts: HashMap::new() }
+		let artifacts = match scan_for_known_artifacts(cache_path).await {
+			Ok(artifacts) => artifacts,
+			Err(err) => {
+				tracing::warn!(
+					target: LOG_TARGET,
+					"failed to scan the artifact cache at {}, clearing it: {:?}",
+					cache_path.display(),
+					err,
+				);
+				// Start from scratch. Nodes are long-running so this should populate shortly.
+				let _ = async_std::fs::remove_dir_all(cache_path).await;
+				let _ = async_std::fs::create_dir_all(cache_path).await;
+				HashMap::new()
+			},
+		};
+
+		Self { artifacts }
 	}
 
 	#[cfg(test)]
@@ -195,10 +246,110 @@ impl Artifacts {
 	}
 }
 
+/// Reads the artifact cache directory, keeping the artifacts that can be reused and removing all
+/// other files.
+async fn scan_for_known_artifacts(
+	cache_path: &Path,
+) -> async_std::io::Result<HashMap<ArtifactId, ArtifactState>> {
+	let mut artifacts = HashMap::new();
+	let current_header = ArtifactHeader::current();
+	let now = SystemTime::now();
+
+	let mut dir = async_std::fs::read_dir(cache_path).await?;
+	while let Some(entry) = dir.next().await {
+		let entry = entry?;
+		let path = entry.path();
+
+		let file_type = entry.file_type().await?;
+		if !file_type.is_file() {
+			tracing::debug!(
+				target: LOG_TARGET,
+				"removing unexpected non-file entry from the artifact cache: {}",
+				path.display(),
+			);
+			let _ = async_std::fs::remove_dir_all(&path).await;
+			continue
+		}
+
+		let artifact_id = match entry.file_name().to_str().and_then(ArtifactId::from_file_name) {
+			Some(artifact_id) => artifact_id,
+			None => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					"removing unrecognized file from the artifact cache: {}",
+					path.display(),
+				);
+				let _ = async_std::fs::remove_file(&path).await;
+				continue
+			},
+		};
+
+		match check_artifact(&path, &current_header).await {
+			Ok(()) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					validation_code_hash = ?artifact_id.code_hash,
+					"reusing artifact {}",
+					path.display(),
+				);
+				artifacts.insert(artifact_id, ArtifactState::Prepared { last_time_needed: now });
+			},
+			Err(reason) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					validation_code_hash = ?artifact_id.code_hash,
+					"removing artifact {}: {}",
+					path.display(),
+					reason,
+				);
+				let _ = async_std::fs::remove_file(&path).await;
+			},
+		}
+	}
+
+	Ok(artifacts)
+}
+
+/// Checks that the artifact at the given path was produced by an executor with the given header
+/// and that the file is not truncated.
+///
+/// Only the beginning of the file is read, so this is cheap even for large artifacts.
+async fn check_artifact(path: &Path, expected_header: &ArtifactHeader) -> Result<(), String> {
+	let file = async_std::fs::File::open(path)
+		.await
+		.map_err(|err| format!("cannot open: {:?}", err))?;
+	let file_len = file.metadata().await.map_err(|err| format!("cannot stat: {:?}", err))?.len();
+
+	let mut prefix = Vec::new();
+	file.take(HEADER_READ_LIMIT)
+		.read_to_end(&mut prefix)
+		.await
+		.map_err(|err| format!("cannot read: {:?}", err))?;
+
+	// The layout of the file follows the encoding of `CompiledArtifact`: the header followed by
+	// the length-prefixed code.
+	let mut input = prefix.as_slice();
+	let header = ArtifactHeader::decode(&mut input).map_err(|_| "corrupted header".to_string())?;
+	if &header != expected_header {
+		return Err(format!("stale header: {:?}", header))
+	}
+
+	let Compact(code_len) =
+		Compact::<u32>::decode(&mut input).map_err(|_| "corrupted code length".to_string())?;
+	let expected_len = (prefix.len() - input.len()) as u64 + code_len as u64;
+	if file_len != expected_len {
+		return Err(format!("unexpected size: {} bytes, expected {}", file_len, expected_len))
+	}
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{ArtifactId, Artifacts};
+	use super::{ArtifactHeader, ArtifactId, ArtifactState, Artifacts, CompiledArtifact};
+	use assert_matches::assert_matches;
 	use async_std::path::Path;
+	use parity_scale_codec::Encode;
 	use sp_core::H256;
 	use std::str::FromStr;
 
@@ -215,7 +366,21 @@ mod tests {
 				hex_literal::hex![
 					"0022800000000000000000000000000000000000000000000000000000000000"
 				]
-				.into()
+				.into(),
+				Engine::Wasmtime,
+			)),
+		);
+
+		assert_eq!(
+			ArtifactId::from_file_name(
+				"wasmi_0x0022800000000000000000000000000000000000000000000000000000000000"
+			),
+			Some(ArtifactId::new(
+				hex_literal::hex![
+					"0022800000000000000000000000000000000000000000000000000000000000"
+				]
+				.into(),
+				Engine::Interpreter,
 			)),
 		);
 	}
@@ -229,32 +394,93 @@ mod tests {
 				.into();
 
 		assert_eq!(
-			ArtifactId::new(hash).path(path).to_str(),
+			ArtifactId::new(hash, Engine::Wasmtime).path(path).to_str(),
 			Some(
 				"/test/wasmtime_0x1234567890123456789012345678901234567890123456789012345678901234"
 			),
 		);
+		assert_eq!(
+			ArtifactId::new(hash, Engine::Interpreter).path(path).to_str(),
+			Some("/test/wasmi_0x1234567890123456789012345678901234567890123456789012345678901234"),
+		);
 	}
 
-	#[test]
-	fn artifacts_removes_cache_on_startup() {
-		let fake_cache_path = async_std::task::block_on(async move {
+	fn fake_cache_path() -> std::path::PathBuf {
+		let path = async_std::task::block_on(async move {
 			crate::worker_common::tmpfile("test-cache").await.unwrap()
 		});
-		let fake_artifact_path = {
-			let mut p = fake_cache_path.clone();
-			p.push("wasmtime_0x1234567890123456789012345678901234567890123456789012345678901234");
-			p
-		};
+		std::fs::create_dir_all(&path).unwrap();
+		path.into()
+	}
+
+	#[test]
+	fn artifacts_keeps_valid_artifacts_on_startup() {
+		let fake_cache_path = fake_cache_path();
+		let file_name =
+			"wasmtime_0x1234567890123456789012345678901234567890123456789012345678901234";
+
+		std::fs::write(
+			fake_cache_path.join(file_name),
+			CompiledArtifact::new(vec![1, 2, 3, 4]).encode(),
+		)
+		.unwrap();
+
+		let p = Path::new(&fake_cache_path);
+		let mut artifacts = async_std::task::block_on(async { Artifacts::new(p).await });
+
+		assert_matches!(
+			artifacts.artifact_state_mut(&ArtifactId::from_file_name(file_name).unwrap()),
+			Some(ArtifactState::Prepared { .. })
+		);
+		assert_eq!(std::fs::read_dir(&fake_cache_path).unwrap().count(), 1);
 
-		// create a tmp cache with 1 artifact.
+		std::fs::remove_dir_all(fake_cache_path).unwrap();
+	}
 
-		std::fs::create_dir_all(&fake_cache_path).unwrap();
-		std::fs::File::create(fake_artifact_path).unwrap();
+	#[test]
+	fn artifacts_removes_stale_and_corrupted_artifacts_on_startup() {
+		let fake_cache_path = fake_cache_path();
+
+		// An artifact produced by a different executor.
+		let stale = CompiledArtifact {
+			header: ArtifactHeader {
+				executor_version: "wasmtime 0.0.1".to_string(),
+				config_hash: [0; 32],
+			},
+			code: vec![1, 2, 3, 4],
+		};
+		std::fs::write(
+			fake_cache_path.join(
+				"wasmtime_0x1234567890123456789012345678901234567890123456789012345678901234",
+			),
+			stale.encode(),
+		)
+		.unwrap();
+
+		// A truncated artifact.
+		let mut truncated = CompiledArtifact::new(vec![1, 2, 3, 4]).encode();
+		truncated.pop();
+		std::fs::write(
+			fake_cache_path.join(
+				"wasmtime_0x0022800000000000000000000000000000000000000000000000000000000000",
+			),
+			truncated,
+		)
+		.unwrap();
+
+		// A file with garbage in it.
+		std::fs::write(
+			fake_cache_path.join(
+				"wasmtime_0x0033800000000000000000000000000000000000000000000000000000000000",
+			),
+			b"garbage",
+		)
+		.unwrap();
 
-		// this should remove it and re-create.
+		// An unrecognized file, e.g. a leftover of an interrupted preparation.
+		std::fs::File::create(fake_cache_path.join("prepare-artifact-abcdefghij")).unwrap();
 
-		let p = &fake_cache_path;
+		let p = Path::new(&fake_cache_path);
 		async_std::task::block_on(async { Artifacts::new(p).await });
 
 		assert_eq!(std::fs::read_dir(&fake_cache_path).unwrap().count(), 0);
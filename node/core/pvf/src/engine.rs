@@ -0,0 +1,54 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::{Decode, Encode};
+
+/// The engine used to execute a PVF.
+///
+/// Each engine has its own artifacts, so the same PVF may be prepared for several engines at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode)]
+pub enum Engine {
+	/// The PVF is compiled ahead of time into machine code by wasmtime.
+	///
+	/// This is the engine used for all the regular validation work.
+	Wasmtime,
+	/// The PVF is run by the wasmi interpreter.
+	///
+	/// This is way slower than [`Engine::Wasmtime`], but it is an independent implementation that
+	/// can be used to cross-check the verdicts of the former.
+	Interpreter,
+}
+
+impl Default for Engine {
+	fn default() -> Self {
+		Engine::Wasmtime
+	}
+}
+
+impl Engine {
+	/// The prefix of the file names of the artifacts prepared for this engine.
+	pub(crate) fn artifact_prefix(self) -> &'static str {
+		match self {
+			Engine::Wasmtime => "wasmtime_",
+			Engine::Interpreter => "wasmi_",
+		}
+	}
+
+	/// All the known engines.
+	pub(crate) fn all() -> [Engine; 2] {
+		[Engine::Wasmtime, Engine::Interpreter]
+	}
+}
@@ -71,9 +71,22 @@ pub enum InvalidCandidate {
 	/// thrown at it and hopefully the operator notices it by decreased reward performance of the
 	/// validator. On the other hand, if the worker died because of (b) we would have better chances
 	/// to stop the attack.
+	///
+	/// Note that running out of the address space limit of the worker is reported separately, see
+	/// [`InvalidCandidate::MemoryExhausted`], whenever the worker survives to report it.
 	AmbiguousWorkerDeath,
-	/// PVF execution (compilation is not included) took more time than was allotted.
-	HardTimeout,
+	/// PVF execution (compilation is not included) consumed more CPU time than was allotted.
+	///
+	/// Only the CPU time is taken into account, so that a validator running on a loaded machine
+	/// does not reject honest candidates. The field contains the CPU time consumed in
+	/// milliseconds.
+	CpuTimeExceeded(u64),
+	/// The execute worker failed to reserve the memory for the PVF instance, most likely because
+	/// of its address space limit. The string contains the error message.
+	///
+	/// The failure doesn't depend on the candidate, so despite being reported here it must not be
+	/// taken as a proof of the candidate being invalid.
+	MemoryExhausted(String),
 }
 
 impl From<PrepareError> for ValidationError {
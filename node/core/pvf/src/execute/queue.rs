@@ -109,6 +109,10 @@ struct Queue {
 
 	program_path: PathBuf,
 	spawn_timeout: Duration,
+	/// The directory the workers are restricted to, if they are sandboxed.
+	sandbox_root: Option<PathBuf>,
+	/// The limit of the address space of each worker in bytes, if any.
+	address_space_limit: Option<u64>,
 
 	/// The queue of jobs that are waiting for a worker to pick up.
 	queue: VecDeque<ExecuteJob>,
@@ -122,12 +126,16 @@ impl Queue {
 		program_path: PathBuf,
 		worker_capacity: usize,
 		spawn_timeout: Duration,
+		sandbox_root: Option<PathBuf>,
+		address_space_limit: Option<u64>,
 		to_queue_rx: mpsc::Receiver<ToQueue>,
 	) -> Self {
 		Self {
 			metrics,
 			program_path,
 			spawn_timeout,
+			sandbox_root,
+			address_space_limit,
 			to_queue_rx,
 			queue: VecDeque::new(),
 			mux: Mux::new(),
@@ -225,9 +233,10 @@ fn handle_job_finish(
 	result_tx: ResultSender,
 ) {
 	let (idle_worker, result) = match outcome {
-		Outcome::Ok { result_descriptor, duration_ms, idle_worker } => {
+		Outcome::Ok { result_descriptor, duration_ms, cpu_time_ms, idle_worker } => {
 			// TODO: propagate the soft timeout
 			drop(duration_ms);
+			queue.metrics.observe_execution_cpu_time(cpu_time_ms);
 
 			(Some(idle_worker), Ok(result_descriptor))
 		},
@@ -235,10 +244,20 @@ fn handle_job_finish(
 			Some(idle_worker),
 			Err(ValidationError::InvalidCandidate(InvalidCandidate::WorkerReportedError(err))),
 		),
+		Outcome::CpuTimeExceeded { cpu_time_ms } => (
+			None,
+			Err(ValidationError::InvalidCandidate(InvalidCandidate::CpuTimeExceeded(cpu_time_ms))),
+		),
+		Outcome::MemoryExhausted { err } =>
+			(None, Err(ValidationError::InvalidCandidate(InvalidCandidate::MemoryExhausted(err)))),
 		Outcome::InternalError { err, idle_worker } =>
 			(Some(idle_worker), Err(ValidationError::InternalError(err))),
-		Outcome::HardTimeout =>
-			(None, Err(ValidationError::InvalidCandidate(InvalidCandidate::HardTimeout))),
+		Outcome::HardTimeout => (
+			None,
+			Err(ValidationError::InternalError(
+				"execution exceeded the wall-clock time limit".to_string(),
+			)),
+		),
 		Outcome::IoErr =>
 			(None, Err(ValidationError::InvalidCandidate(InvalidCandidate::AmbiguousWorkerDeath))),
 	};
@@ -290,17 +309,35 @@ fn spawn_extra_worker(queue: &mut Queue) {
 	queue.metrics.execute_worker().on_begin_spawn();
 	tracing::debug!(target: LOG_TARGET, "spawning an extra worker");
 
-	queue
-		.mux
-		.push(spawn_worker_task(queue.program_path.clone(), queue.spawn_timeout).boxed());
+	queue.mux.push(
+		spawn_worker_task(
+			queue.program_path.clone(),
+			queue.spawn_timeout,
+			queue.sandbox_root.clone(),
+			queue.address_space_limit,
+		)
+		.boxed(),
+	);
 	queue.workers.spawn_inflight += 1;
 }
 
-async fn spawn_worker_task(program_path: PathBuf, spawn_timeout: Duration) -> QueueEvent {
+async fn spawn_worker_task(
+	program_path: PathBuf,
+	spawn_timeout: Duration,
+	sandbox_root: Option<PathBuf>,
+	address_space_limit: Option<u64>,
+) -> QueueEvent {
 	use futures_timer::Delay;
 
 	loop {
-		match super::worker::spawn(&program_path, spawn_timeout).await {
+		match super::worker::spawn(
+			&program_path,
+			spawn_timeout,
+			sandbox_root.clone(),
+			address_space_limit,
+		)
+		.await
+		{
 			Ok((idle, handle)) => break QueueEvent::Spawn(idle, handle),
 			Err(err) => {
 				tracing::warn!(target: LOG_TARGET, "failed to spawn an execute worker: {:?}", err);
@@ -350,8 +387,19 @@ pub fn start(
 	program_path: PathBuf,
 	worker_capacity: usize,
 	spawn_timeout: Duration,
+	sandbox_root: Option<PathBuf>,
+	address_space_limit: Option<u64>,
 ) -> (mpsc::Sender<ToQueue>, impl Future<Output = ()>) {
 	let (to_queue_tx, to_queue_rx) = mpsc::channel(20);
-	let run = Queue::new(metrics, program_path, worker_capacity, spawn_timeout, to_queue_rx).run();
+	let run = Queue::new(
+		metrics,
+		program_path,
+		worker_capacity,
+		spawn_timeout,
+		sandbox_root,
+		address_space_limit,
+		to_queue_rx,
+	)
+	.run();
 	(to_queue_tx, run)
 }
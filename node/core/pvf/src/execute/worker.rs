@@ -15,47 +15,88 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	artifacts::{ArtifactPathId, CompiledArtifact},
-	executor_intf::TaskExecutor,
+	artifacts::{ArtifactHeader, ArtifactPathId, CompiledArtifact},
+	executor_intf::{ExecuteError, TaskExecutor, NATIVE_STACK_MAX},
 	worker_common::{
 		bytes_to_path, framed_recv, framed_send, path_to_bytes, spawn_with_program_path,
 		worker_event_loop, IdleWorker, SpawnErr, WorkerHandle,
 	},
-	LOG_TARGET,
+	Engine, LOG_TARGET,
 };
 use async_std::{
 	io,
 	os::unix::net::UnixStream,
 	path::{Path, PathBuf},
 };
-use futures::FutureExt;
+use futures::{channel::oneshot, FutureExt};
 use futures_timer::Delay;
 use parity_scale_codec::{Decode, Encode};
 use polkadot_parachain::primitives::ValidationResult;
 use std::time::{Duration, Instant};
 
+/// The host kills the worker if it didn't respond within the execution timeout multiplied by this
+/// factor.
+///
+/// The execution timeout itself is enforced by the worker in terms of CPU time. The wall-clock
+/// limit only serves as a safety net in case the worker got stuck.
+const EXECUTION_TIMEOUT_WALL_CLOCK_FACTOR: u32 = 4;
+
+/// How often the worker checks the CPU time consumed by the ongoing execution.
+const CPU_TIME_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The stack size of the thread that executes the PVF. It must be able to fit the native stack
+/// allowed for the wasm code plus some headroom for the executor itself.
+const EXECUTE_THREAD_STACK_SIZE: usize = 2 * 1024 * 1024 + NATIVE_STACK_MAX as usize;
+
 /// Spawns a new worker with the given program path that acts as the worker and the spawn timeout.
 ///
 /// The program should be able to handle `<program-path> execute-worker <socket-path>` invocation.
+///
+/// If `sandbox_root` is specified, the worker only has access to the given directory. If
+/// `address_space_limit` is specified, the address space of the worker is limited to the given
+/// number of bytes.
 pub async fn spawn(
 	program_path: &Path,
 	spawn_timeout: Duration,
+	sandbox_root: Option<PathBuf>,
+	address_space_limit: Option<u64>,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
-	spawn_with_program_path("execute", program_path, &["execute-worker"], spawn_timeout).await
+	spawn_with_program_path(
+		"execute",
+		program_path,
+		&["execute-worker"],
+		spawn_timeout,
+		sandbox_root,
+		address_space_limit,
+	)
+	.await
 }
 
 /// Outcome of PVF execution.
 pub enum Outcome {
 	/// PVF execution completed successfully and the result is returned. The worker is ready for
 	/// another job.
-	Ok { result_descriptor: ValidationResult, duration_ms: u64, idle_worker: IdleWorker },
+	Ok {
+		result_descriptor: ValidationResult,
+		duration_ms: u64,
+		cpu_time_ms: u64,
+		idle_worker: IdleWorker,
+	},
 	/// The candidate validation failed. It may be for example because the wasm execution triggered a trap.
 	/// Errors related to the preparation process are not expected to be encountered by the execution workers.
 	InvalidCandidate { err: String, idle_worker: IdleWorker },
+	/// The execution consumed more CPU time than allotted. The worker has terminated itself.
+	CpuTimeExceeded { cpu_time_ms: u64 },
+	/// The runtime could not be instantiated, most likely because the worker failed to reserve the
+	/// memory within its address space limit. The worker has terminated itself.
+	MemoryExhausted { err: String },
 	/// An internal error happened during the validation. Such an error is most likely related to
 	/// some transient glitch.
 	InternalError { err: String, idle_worker: IdleWorker },
-	/// The execution time exceeded the hard limit. The worker is terminated.
+	/// The worker didn't respond within the wall-clock time limit. The worker is terminated.
+	///
+	/// Since the CPU time limit is enforced by the worker itself, this means that the worker was
+	/// starved or got stuck, which cannot be attributed to the candidate.
 	HardTimeout,
 	/// An I/O error happened during communication with the worker. This may mean that the worker
 	/// process already died. The token is not returned in any case.
@@ -64,6 +105,8 @@ pub enum Outcome {
 
 /// Given the idle token of a worker and parameters of work, communicates with the worker and
 /// returns the outcome.
+///
+/// The `execution_timeout` limits the CPU time the worker may spend executing the PVF.
 pub async fn start_work(
 	worker: IdleWorker,
 	artifact: ArtifactPathId,
@@ -80,7 +123,15 @@ pub async fn start_work(
 		artifact.path.display(),
 	);
 
-	if let Err(error) = send_request(&mut stream, &artifact.path, &validation_params).await {
+	if let Err(error) = send_request(
+		&mut stream,
+		&artifact.path,
+		&validation_params,
+		execution_timeout,
+		artifact.id.engine,
+	)
+	.await
+	{
 		tracing::warn!(
 			target: LOG_TARGET,
 			worker_pid = %pid,
@@ -107,22 +158,37 @@ pub async fn start_work(
 				Ok(response) => response,
 			}
 		},
-		_ = Delay::new(execution_timeout).fuse() => {
+		_ = Delay::new(execution_timeout * EXECUTION_TIMEOUT_WALL_CLOCK_FACTOR).fuse() => {
 			tracing::warn!(
 				target: LOG_TARGET,
 				worker_pid = %pid,
 				validation_code_hash = ?artifact.id.code_hash,
-				"execution worker exceeded alloted time for execution",
+				"execution worker exceeded the wall-clock time limit",
 			);
 			return Outcome::HardTimeout;
 		},
 	};
 
 	match response {
-		Response::Ok { result_descriptor, duration_ms } =>
-			Outcome::Ok { result_descriptor, duration_ms, idle_worker: IdleWorker { stream, pid } },
+		Response::Ok { result_descriptor, duration_ms, cpu_time_ms } => Outcome::Ok {
+			result_descriptor,
+			duration_ms,
+			cpu_time_ms,
+			idle_worker: IdleWorker { stream, pid },
+		},
 		Response::InvalidCandidate(err) =>
 			Outcome::InvalidCandidate { err, idle_worker: IdleWorker { stream, pid } },
+		Response::CpuTimeExceeded { cpu_time_ms } => {
+			tracing::warn!(
+				target: LOG_TARGET,
+				worker_pid = %pid,
+				validation_code_hash = ?artifact.id.code_hash,
+				%cpu_time_ms,
+				"execution worker exceeded allotted CPU time for execution",
+			);
+			Outcome::CpuTimeExceeded { cpu_time_ms }
+		},
+		Response::MemoryExhausted(err) => Outcome::MemoryExhausted { err },
 		Response::InternalError(err) =>
 			Outcome::InternalError { err, idle_worker: IdleWorker { stream, pid } },
 	}
@@ -132,12 +198,15 @@ async fn send_request(
 	stream: &mut UnixStream,
 	artifact_path: &Path,
 	validation_params: &[u8],
+	execution_timeout: Duration,
+	engine: Engine,
 ) -> io::Result<()> {
 	framed_send(stream, path_to_bytes(artifact_path)).await?;
-	framed_send(stream, validation_params).await
+	framed_send(stream, validation_params).await?;
+	framed_send(stream, &(execution_timeout.as_millis() as u64, engine).encode()).await
 }
 
-async fn recv_request(stream: &mut UnixStream) -> io::Result<(PathBuf, Vec<u8>)> {
+async fn recv_request(stream: &mut UnixStream) -> io::Result<(PathBuf, Vec<u8>, Duration, Engine)> {
 	let artifact_path = framed_recv(stream).await?;
 	let artifact_path = bytes_to_path(&artifact_path).ok_or_else(|| {
 		io::Error::new(
@@ -146,7 +215,16 @@ async fn recv_request(stream: &mut UnixStream) -> io::Result<(PathBuf, Vec<u8>)>
 		)
 	})?;
 	let params = framed_recv(stream).await?;
-	Ok((artifact_path, params))
+	let timeout_and_engine = framed_recv(stream).await?;
+	let (execution_timeout, engine) = <(u64, Engine)>::decode(&mut &timeout_and_engine[..])
+		.map_err(|_| {
+			io::Error::new(
+				io::ErrorKind::Other,
+				"execute pvf recv_request: failed to decode execution timeout and engine"
+					.to_string(),
+			)
+		})?;
+	Ok((artifact_path, params, Duration::from_millis(execution_timeout), engine))
 }
 
 async fn send_response(stream: &mut UnixStream, response: Response) -> io::Result<()> {
@@ -165,8 +243,10 @@ async fn recv_response(stream: &mut UnixStream) -> io::Result<Response> {
 
 #[derive(Encode, Decode)]
 enum Response {
-	Ok { result_descriptor: ValidationResult, duration_ms: u64 },
+	Ok { result_descriptor: ValidationResult, duration_ms: u64, cpu_time_ms: u64 },
 	InvalidCandidate(String),
+	CpuTimeExceeded { cpu_time_ms: u64 },
+	MemoryExhausted(String),
 	InternalError(String),
 }
 
@@ -178,6 +258,14 @@ impl Response {
 			Self::InvalidCandidate(format!("{}: {}", ctx, msg))
 		}
 	}
+
+	/// Whether the worker should terminate after sending this response.
+	///
+	/// If the CPU time is exceeded the execution thread may still be running. If the memory is
+	/// exhausted the state of the worker is unreliable.
+	fn terminates_worker(&self) -> bool {
+		matches!(self, Self::CpuTimeExceeded { .. } | Self::MemoryExhausted(_))
+	}
 }
 
 /// The entrypoint that the spawned execute worker should start with. The `socket_path` specifies
@@ -188,22 +276,41 @@ pub fn worker_entrypoint(socket_path: &str) {
 			io::Error::new(io::ErrorKind::Other, format!("cannot create task executor: {}", e))
 		})?;
 		loop {
-			let (artifact_path, params) = recv_request(&mut stream).await?;
+			let (artifact_path, params, execution_timeout, engine) =
+				recv_request(&mut stream).await?;
 			tracing::debug!(
 				target: LOG_TARGET,
 				worker_pid = %std::process::id(),
+				?engine,
 				"worker: validating artifact {}",
 				artifact_path.display(),
 			);
-			let response = validate_using_artifact(&artifact_path, &params, &executor).await;
+			let response = validate_using_artifact(
+				&artifact_path,
+				params,
+				execution_timeout,
+				engine,
+				&executor,
+			)
+			.await;
+			let terminate = response.terminates_worker();
 			send_response(&mut stream, response).await?;
+
+			if terminate {
+				return Err(io::Error::new(
+					io::ErrorKind::Other,
+					"the execution exceeded its resource limits, terminating".to_string(),
+				))
+			}
 		}
 	});
 }
 
 async fn validate_using_artifact(
 	artifact_path: &Path,
-	params: &[u8],
+	params: Vec<u8>,
+	execution_timeout: Duration,
+	engine: Engine,
 	spawner: &TaskExecutor,
 ) -> Response {
 	let artifact_bytes = match async_std::fs::read(artifact_path).await {
@@ -221,26 +328,128 @@ async fn validate_using_artifact(
 		Ok(a) => a,
 	};
 
-	let compiled_artifact = artifact.as_ref();
+	if artifact.header() != &ArtifactHeader::current() {
+		return Response::InternalError(format!(
+			"artifact at {} was compiled by a different executor: {:?}",
+			artifact_path.display(),
+			artifact.header(),
+		))
+	}
 
+	// The execution happens on a separate thread, so that this thread can keep track of the CPU
+	// time consumed and bail out once the limit is exceeded. Only the CPU time of the execution
+	// thread counts, so the work of this thread and any other ones doesn't affect the verdict.
+	let (clock_tx, clock_rx) = oneshot::channel();
+	let (result_tx, result_rx) = oneshot::channel();
+	let spawner = spawner.clone();
 	let validation_started_at = Instant::now();
-	let descriptor_bytes = match unsafe {
-		// SAFETY: this should be safe since the compiled artifact passed here comes from the
-		//         file created by the prepare workers. These files are obtained by calling
-		//         [`executor_intf::prepare`].
-		crate::executor_intf::execute(compiled_artifact, params, spawner.clone())
-	} {
-		Err(err) => return Response::format_invalid("execute", &err.to_string()),
-		Ok(d) => d,
+	let spawn_result = std::thread::Builder::new()
+		.name("pvf-execute".to_string())
+		.stack_size(EXECUTE_THREAD_STACK_SIZE)
+		.spawn(move || {
+			let _ = clock_tx.send(current_thread_cpu_clock());
+			let result = match engine {
+				Engine::Wasmtime => unsafe {
+					// SAFETY: this should be safe since the compiled artifact passed here comes
+					//         from the file created by the prepare workers. These files are
+					//         obtained by calling [`executor_intf::prepare`].
+					crate::executor_intf::execute(artifact.as_ref(), &params, spawner)
+				},
+				Engine::Interpreter =>
+					crate::executor_intf::execute_interpreted(artifact.as_ref(), &params, spawner),
+			};
+			// The thread is dedicated to the execution, so its CPU time is the one of the PVF.
+			let cpu_time = clock_time(libc::CLOCK_THREAD_CPUTIME_ID).unwrap_or_default();
+			let _ = result_tx.send((result, cpu_time));
+		});
+	if let Err(e) = spawn_result {
+		return Response::InternalError(format!("cannot spawn the execution thread: {}", e))
+	}
+
+	// If the clock of the execution thread can't be read from here, the execution is only bounded
+	// by the wall clock timeout of the host.
+	let execution_cpu_clock = clock_rx.await.ok().flatten();
+	let mut result_rx = result_rx.fuse();
+	let result = loop {
+		futures::select! {
+			result = result_rx => break result,
+			_ = Delay::new(CPU_TIME_CHECK_INTERVAL).fuse() => {
+				// The clock can't be read anymore once the thread has exited, in which case the
+				// result is about to be received.
+				let cpu_time_elapsed = execution_cpu_clock.and_then(clock_time).unwrap_or_default();
+				if cpu_time_elapsed > execution_timeout {
+					return Response::CpuTimeExceeded {
+						cpu_time_ms: cpu_time_elapsed.as_millis() as u64,
+					}
+				}
+			},
+		}
 	};
 
 	let duration_ms = validation_started_at.elapsed().as_millis() as u64;
 
+	let (result, cpu_time_elapsed) = match result {
+		Err(oneshot::Canceled) =>
+			return Response::format_invalid("execute", "the execution thread panicked"),
+		Ok(r) => r,
+	};
+
+	// The execution may have finished in between the checks. Only the CPU time counts, so it's
+	// checked once more to keep the verdict deterministic.
+	if cpu_time_elapsed > execution_timeout {
+		return Response::CpuTimeExceeded { cpu_time_ms: cpu_time_elapsed.as_millis() as u64 }
+	}
+
+	let descriptor_bytes = match result {
+		Err(ExecuteError::Instantiation(err)) => return Response::MemoryExhausted(err.to_string()),
+		Err(ExecuteError::Execution(err)) =>
+			return Response::format_invalid("execute", &err.to_string()),
+		Ok(d) => d,
+	};
+
 	let result_descriptor = match ValidationResult::decode(&mut &descriptor_bytes[..]) {
 		Err(err) =>
 			return Response::InvalidCandidate(format!("validation result decoding failed: {}", err)),
 		Ok(r) => r,
 	};
 
-	Response::Ok { result_descriptor, duration_ms }
+	Response::Ok {
+		result_descriptor,
+		duration_ms,
+		cpu_time_ms: cpu_time_elapsed.as_millis() as u64,
+	}
+}
+
+/// Returns the CPU-time clock of the current thread, which can be read from other threads as long
+/// as the current one is running.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+fn current_thread_cpu_clock() -> Option<libc::clockid_t> {
+	let mut clock = std::mem::MaybeUninit::<libc::clockid_t>::uninit();
+	// SAFETY: `pthread_getcpuclockid` fills the provided clock ID of a live thread.
+	unsafe {
+		if libc::pthread_getcpuclockid(libc::pthread_self(), clock.as_mut_ptr()) != 0 {
+			return None
+		}
+		Some(clock.assume_init())
+	}
+}
+
+/// The CPU-time clock of a thread can't be read from other threads on this platform.
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+fn current_thread_cpu_clock() -> Option<libc::clockid_t> {
+	None
+}
+
+/// Returns the CPU time, both user and system, measured by the given clock so far.
+fn clock_time(clock: libc::clockid_t) -> Option<Duration> {
+	let mut time = std::mem::MaybeUninit::<libc::timespec>::uninit();
+	// SAFETY: `clock_gettime` fills the provided struct, unless the clock is invalid.
+	let time = unsafe {
+		if libc::clock_gettime(clock, time.as_mut_ptr()) != 0 {
+			return None
+		}
+		time.assume_init()
+	};
+
+	Some(Duration::from_secs(time.tv_sec as u64) + Duration::from_nanos(time.tv_nsec as u64))
 }
@@ -24,6 +24,10 @@ use sc_executor_wasmtime::{Config, DeterministicStackLimit, Semantics};
 use sp_core::storage::{ChildInfo, TrackedStorageKey};
 use std::any::{Any, TypeId};
 
+/// The maximum amount of the native stack the wasm code is allowed to use, in bytes. See the
+/// `deterministic_stack_limit` below.
+pub(crate) const NATIVE_STACK_MAX: u32 = 256 * 1024 * 1024;
+
 const CONFIG: Config = Config {
 	// Memory configuration
 	//
@@ -64,19 +68,50 @@ const CONFIG: Config = Config {
 		// the stack limit set by the wasmtime.
 		deterministic_stack_limit: Some(DeterministicStackLimit {
 			logical_max: 65536,
-			native_stack_max: 256 * 1024 * 1024,
+			native_stack_max: NATIVE_STACK_MAX,
 		}),
 		canonicalize_nans: true,
-		// Rationale for turning the multi-threaded compilation off is to make the preparation time
-		// easily reproducible and as deterministic as possible.
+		// Rationale for turning the multi-threaded compilation off by default is to make the
+		// preparation time easily reproducible and as deterministic as possible.
 		//
-		// Currently the prepare queue doesn't distinguish between precheck and prepare requests.
-		// On the one hand, it simplifies the code, on the other, however, slows down compile times
-		// for execute requests. This behavior may change in future.
+		// The prepare queue runs the precheck requests single-threaded, but enables the parallel
+		// compilation for the preparation of the PVFs needed for execution, see [`prepare`].
 		parallel_compilation: false,
 	},
 };
 
+/// The version of the executor (that is, `sc-executor` and `wasmtime`) this crate was built
+/// against. Recorded at build time from the workspace lock file.
+pub const EXECUTOR_VERSION: &str = env!("PVF_EXECUTOR_VERSION");
+
+/// Returns a digest of the [`CONFIG`] used to compile and execute PVFs.
+///
+/// Artifacts compiled with a different configuration must not be reused, and so this digest is
+/// recorded in the header of every artifact.
+///
+/// The parallel compilation is not a part of the digest, since it doesn't affect the produced code.
+pub fn config_hash() -> [u8; 32] {
+	use parity_scale_codec::Encode;
+
+	let semantics = &CONFIG.semantics;
+	let stack_limit = semantics
+		.deterministic_stack_limit
+		.as_ref()
+		.map(|limit| (limit.logical_max, limit.native_stack_max));
+
+	let encoded = (
+		CONFIG.max_memory_size.map(|size| size as u64),
+		CONFIG.heap_pages,
+		CONFIG.allow_missing_func_imports,
+		semantics.fast_instance_reuse,
+		stack_limit,
+		semantics.canonicalize_nans,
+	)
+		.encode();
+
+	sp_core::hashing::blake2_256(&encoded)
+}
+
 /// Runs the prevalidation on the given code. Returns a [`RuntimeBlob`] if it succeeds.
 pub fn prevalidate(code: &[u8]) -> Result<RuntimeBlob, sc_executor_common::error::WasmError> {
 	let blob = RuntimeBlob::new(code)?;
@@ -89,8 +124,36 @@ pub fn prevalidate(code: &[u8]) -> Result<RuntimeBlob, sc_executor_common::error
 
 /// Runs preparation on the given runtime blob. If successful, it returns a serialized compiled
 /// artifact which can then be used to pass into [`execute`].
-pub fn prepare(blob: RuntimeBlob) -> Result<Vec<u8>, sc_executor_common::error::WasmError> {
-	sc_executor_wasmtime::prepare_runtime_artifact(blob, &CONFIG.semantics)
+///
+/// `parallel_compilation` allows the compiler to use multiple threads.
+pub fn prepare(
+	blob: RuntimeBlob,
+	parallel_compilation: bool,
+) -> Result<Vec<u8>, sc_executor_common::error::WasmError> {
+	let semantics = Semantics { parallel_compilation, ..CONFIG.semantics };
+	sc_executor_wasmtime::prepare_runtime_artifact(blob, &semantics)
+}
+
+/// Runs preparation of the given runtime blob for the interpreter. Since the interpreter doesn't
+/// compile the code ahead of time, the artifact is just the prevalidated code, which can then be
+/// used to pass into [`execute_interpreted`].
+pub fn prepare_interpreted(
+	blob: RuntimeBlob,
+) -> Result<Vec<u8>, sc_executor_common::error::WasmError> {
+	Ok(blob.serialize())
+}
+
+/// An error that occurred while executing a PVF.
+#[derive(Debug)]
+pub enum ExecuteError {
+	/// The runtime could not be instantiated from the artifact.
+	///
+	/// The artifact has already passed the preparation and the instantiation doesn't depend on
+	/// the candidate, so this is caused by the worker failing to reserve the memory for the
+	/// instance, e.g. because of its address space limit.
+	Instantiation(sc_executor_common::error::Error),
+	/// The validation function failed.
+	Execution(sc_executor_common::error::Error),
 }
 
 /// Executes the given PVF in the form of a compiled artifact and returns the result of execution
@@ -104,7 +167,7 @@ pub unsafe fn execute(
 	compiled_artifact: &[u8],
 	params: &[u8],
 	spawner: impl sp_core::traits::SpawnNamed + 'static,
-) -> Result<Vec<u8>, sc_executor_common::error::Error> {
+) -> Result<Vec<u8>, ExecuteError> {
 	let mut extensions = sp_externalities::Extensions::new();
 
 	extensions.register(sp_core::traits::TaskExecutorExt::new(spawner));
@@ -116,9 +179,51 @@ pub unsafe fn execute(
 		let runtime = sc_executor_wasmtime::create_runtime_from_artifact::<HostFunctions>(
 			compiled_artifact,
 			CONFIG,
-		)?;
-		runtime.new_instance()?.call(InvokeMethod::Export("validate_block"), params)
-	})?
+		)
+		.map_err(|e| ExecuteError::Instantiation(e.into()))?;
+		runtime
+			.new_instance()
+			.map_err(ExecuteError::Instantiation)?
+			.call(InvokeMethod::Export("validate_block"), params)
+			.map_err(ExecuteError::Execution)
+	})
+	.map_err(ExecuteError::Execution)?
+}
+
+/// Executes the given PVF using the interpreter and returns the result of execution upon success.
+///
+/// The artifact should be produced with [`prepare_interpreted`]. The interpreter is independent
+/// from wasmtime and is way slower, so it is only meant to cross-check the verdicts of the latter.
+pub fn execute_interpreted(
+	artifact: &[u8],
+	params: &[u8],
+	spawner: impl sp_core::traits::SpawnNamed + 'static,
+) -> Result<Vec<u8>, ExecuteError> {
+	use sp_wasm_interface::HostFunctions as _;
+
+	let mut extensions = sp_externalities::Extensions::new();
+
+	extensions.register(sp_core::traits::TaskExecutorExt::new(spawner));
+	extensions.register(sp_core::traits::ReadRuntimeVersionExt::new(ReadRuntimeVersion));
+
+	let mut ext = ValidationExternalities(extensions);
+
+	sc_executor::with_externalities_safe(&mut ext, || {
+		let blob = RuntimeBlob::new(artifact).map_err(|e| ExecuteError::Instantiation(e.into()))?;
+		let runtime = sc_executor_wasmi::create_runtime(
+			blob,
+			CONFIG.heap_pages,
+			HostFunctions::host_functions(),
+			CONFIG.allow_missing_func_imports,
+		)
+		.map_err(|e| ExecuteError::Instantiation(e.into()))?;
+		runtime
+			.new_instance()
+			.map_err(ExecuteError::Instantiation)?
+			.call(InvokeMethod::Export("validate_block"), params)
+			.map_err(ExecuteError::Execution)
+	})
+	.map_err(ExecuteError::Execution)?
 }
 
 type HostFunctions = (
@@ -24,15 +24,17 @@ use crate::{
 	artifacts::{ArtifactId, ArtifactPathId, ArtifactState, Artifacts},
 	execute,
 	metrics::Metrics,
-	prepare, PrepareResult, Priority, Pvf, ValidationError, LOG_TARGET,
+	prepare, Engine, InvalidCandidate, PrepareResult, Priority, Pvf, ValidationError, LOG_TARGET,
 };
 use always_assert::never;
 use async_std::path::{Path, PathBuf};
 use futures::{
 	channel::{mpsc, oneshot},
+	future::BoxFuture,
+	stream::FuturesUnordered,
 	Future, FutureExt, SinkExt, StreamExt,
 };
-use polkadot_parachain::primitives::ValidationResult;
+use polkadot_parachain::primitives::{ValidationCodeHash, ValidationResult};
 use std::{
 	collections::HashMap,
 	time::{Duration, SystemTime},
@@ -44,6 +46,10 @@ pub(crate) type ResultSender = oneshot::Sender<Result<ValidationResult, Validati
 /// Transmission end used for sending the PVF preparation result.
 pub(crate) type PrepareResultSender = oneshot::Sender<PrepareResult>;
 
+/// The interpreter is way slower than wasmtime, so when cross-checking it gets this many times
+/// the execution timeout of the original request.
+const INTERPRETER_EXECUTION_TIMEOUT_FACTOR: u32 = 16;
+
 /// A handle to the async process serving the validation host requests.
 #[derive(Clone)]
 pub struct ValidationHost {
@@ -69,8 +75,14 @@ impl ValidationHost {
 			.map_err(|_| "the inner loop hung up".to_string())
 	}
 
-	/// Execute PVF with the given code, execution timeout, parameters and priority.
-	/// The result of execution will be sent to the provided result sender.
+	/// Execute PVF with the given code, execution timeout, parameters and priority using the given
+	/// engine. The result of execution will be sent to the provided result sender.
+	///
+	/// The execution timeout limits the CPU time spent on the execution. Exceeding it makes the
+	/// candidate invalid, see [`crate::InvalidCandidate::CpuTimeExceeded`].
+	///
+	/// If [`Config::dual_execute`] is enabled, the candidates that wasmtime deems invalid are
+	/// re-run on the interpreter before the result is sent.
 	///
 	/// This is async to accommodate the fact a possibility of back-pressure. In the vast majority of
 	/// situations this function should return immediately.
@@ -82,10 +94,18 @@ impl ValidationHost {
 		execution_timeout: Duration,
 		params: Vec<u8>,
 		priority: Priority,
+		engine: Engine,
 		result_tx: ResultSender,
 	) -> Result<(), String> {
 		self.to_host_tx
-			.send(ToHost::ExecutePvf { pvf, execution_timeout, params, priority, result_tx })
+			.send(ToHost::ExecutePvf {
+				pvf,
+				execution_timeout,
+				params,
+				priority,
+				engine,
+				result_tx,
+			})
 			.await
 			.map_err(|_| "the inner loop hung up".to_string())
 	}
@@ -114,6 +134,7 @@ enum ToHost {
 		execution_timeout: Duration,
 		params: Vec<u8>,
 		priority: Priority,
+		engine: Engine,
 		result_tx: ResultSender,
 	},
 	HeadsUp {
@@ -134,12 +155,35 @@ pub struct Config {
 	pub prepare_workers_soft_max_num: usize,
 	/// The absolute number of workers that can be spawned in the prepare pool.
 	pub prepare_workers_hard_max_num: usize,
+	/// The maximum number of workers that can be spawned in the precheck pool. These workers are
+	/// separate from the prepare pool, so that pre-checking doesn't delay the preparation of
+	/// the PVFs needed for execution.
+	pub precheck_workers_max_num: usize,
 	/// The path to the program that can be used to spawn the execute workers.
 	pub execute_worker_program_path: PathBuf,
 	/// The time allotted for an execute worker to spawn and report to the host.
 	pub execute_worker_spawn_timeout: Duration,
 	/// The maximum number of execute workers that can run at the same time.
 	pub execute_workers_max_num: usize,
+	/// The limit of the address space of an execute worker in bytes. Executions that fail to
+	/// reserve the memory for the PVF instance within the limit are reported as
+	/// [`crate::InvalidCandidate::MemoryExhausted`].
+	///
+	/// Note that wasmtime reserves large regions of virtual memory for each instance, so the limit
+	/// must be set generously. `None` means no limit.
+	pub execute_worker_address_space_limit: Option<u64>,
+	/// Whether the workers should be run in a sandbox which only gives them access to the
+	/// artifact cache.
+	///
+	/// Only supported on Linux. Use [`crate::check_sandbox_support`] to check whether the running
+	/// kernel supports the sandbox.
+	pub enable_sandbox: bool,
+	/// Whether the candidates that wasmtime deems invalid should be re-run on the interpreter
+	/// before the verdict is reported.
+	///
+	/// If the interpreter deems such a candidate valid, the disagreement is logged and recorded in
+	/// the metrics, and an internal error is reported instead of the invalid verdict.
+	pub dual_execute: bool,
 }
 
 impl Config {
@@ -155,9 +199,13 @@ impl Config {
 			prepare_worker_spawn_timeout: Duration::from_secs(3),
 			prepare_workers_soft_max_num: 1,
 			prepare_workers_hard_max_num: 1,
+			precheck_workers_max_num: 1,
 			execute_worker_program_path: program_path,
 			execute_worker_spawn_timeout: Duration::from_secs(3),
 			execute_workers_max_num: 2,
+			execute_worker_address_space_limit: None,
+			enable_sandbox: false,
+			dual_execute: false,
 		}
 	}
 }
@@ -170,19 +218,30 @@ impl Config {
 /// The future should not return normally but if it does then that indicates an unrecoverable error.
 /// In that case all pending requests will be canceled, dropping the result senders and new ones
 /// will be rejected.
-pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<Output = ()>) {
+pub fn start(mut config: Config, metrics: Metrics) -> (ValidationHost, impl Future<Output = ()>) {
+	if config.enable_sandbox && config.cache_path.is_relative() {
+		// The sandboxed workers don't share the working directory with the host, so all paths
+		// passed to them have to be absolute.
+		if let Ok(current_dir) = std::env::current_dir() {
+			config.cache_path = PathBuf::from(current_dir).join(&config.cache_path);
+		}
+	}
+
 	let (to_host_tx, to_host_rx) = mpsc::channel(10);
 
 	let validation_host = ValidationHost { to_host_tx };
 
 	let (to_prepare_pool, from_prepare_pool, run_prepare_pool) = prepare::start_pool(
+		prepare::Lane::Prepare,
 		metrics.clone(),
 		config.prepare_worker_program_path.clone(),
 		config.cache_path.clone(),
 		config.prepare_worker_spawn_timeout,
+		config.enable_sandbox,
 	);
 
 	let (to_prepare_queue_tx, from_prepare_queue_rx, run_prepare_queue) = prepare::start_queue(
+		prepare::Lane::Prepare,
 		metrics.clone(),
 		config.prepare_workers_soft_max_num,
 		config.prepare_workers_hard_max_num,
@@ -191,11 +250,32 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 		from_prepare_pool,
 	);
 
+	let (to_precheck_pool, from_precheck_pool, run_precheck_pool) = prepare::start_pool(
+		prepare::Lane::Precheck,
+		metrics.clone(),
+		config.prepare_worker_program_path.clone(),
+		config.cache_path.clone(),
+		config.prepare_worker_spawn_timeout,
+		config.enable_sandbox,
+	);
+
+	let (to_precheck_queue_tx, from_precheck_queue_rx, run_precheck_queue) = prepare::start_queue(
+		prepare::Lane::Precheck,
+		metrics.clone(),
+		config.precheck_workers_max_num,
+		config.precheck_workers_max_num,
+		config.cache_path.clone(),
+		to_precheck_pool,
+		from_precheck_pool,
+	);
+
 	let (to_execute_queue_tx, run_execute_queue) = execute::start(
 		metrics.clone(),
 		config.execute_worker_program_path.to_owned(),
 		config.execute_workers_max_num,
 		config.execute_worker_spawn_timeout,
+		if config.enable_sandbox { Some(config.cache_path.clone()) } else { None },
+		config.execute_worker_address_space_limit,
 	);
 
 	let (to_sweeper_tx, to_sweeper_rx) = mpsc::channel(100);
@@ -204,7 +284,14 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 	let run = async move {
 		let artifacts = Artifacts::new(&config.cache_path).await;
 
-		futures::pin_mut!(run_prepare_queue, run_prepare_pool, run_execute_queue, run_sweeper);
+		futures::pin_mut!(
+			run_prepare_queue,
+			run_prepare_pool,
+			run_precheck_queue,
+			run_precheck_pool,
+			run_execute_queue,
+			run_sweeper
+		);
 
 		run(
 			Inner {
@@ -215,12 +302,19 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 				to_host_rx,
 				to_prepare_queue_tx,
 				from_prepare_queue_rx,
+				to_precheck_queue_tx,
+				from_precheck_queue_rx,
 				to_execute_queue_tx,
 				to_sweeper_tx,
 				awaiting_prepare: AwaitingPrepare::default(),
+				awaiting_precheck: AwaitingPrecheck::default(),
+				metrics,
+				dual_execute: config.dual_execute,
 			},
 			run_prepare_pool,
 			run_prepare_queue,
+			run_precheck_pool,
+			run_precheck_queue,
 			run_execute_queue,
 			run_sweeper,
 		)
@@ -264,6 +358,56 @@ impl AwaitingPrepare {
 	}
 }
 
+/// A mapping from an artifact ID which is being pre-checked to the list of pre-checking requests
+/// waiting for the verdict.
+///
+/// The pre-checking is tracked apart from the artifacts, so that a verdict only ever depends on a
+/// job of the precheck lane and never on a preparation for execution, which is done with different
+/// parameters.
+#[derive(Default)]
+struct AwaitingPrecheck(HashMap<ArtifactId, Vec<PrepareResultSender>>);
+
+impl AwaitingPrecheck {
+	/// Adds the request and returns whether a job needs to be enqueued for it, that is, whether no
+	/// pre-checking of the artifact is in flight yet.
+	fn add(&mut self, artifact_id: ArtifactId, result_sender: PrepareResultSender) -> bool {
+		let waiting_for_response = self.0.entry(artifact_id).or_default();
+		waiting_for_response.push(result_sender);
+		waiting_for_response.len() == 1
+	}
+
+	fn take(&mut self, artifact_id: &ArtifactId) -> Vec<PrepareResultSender> {
+		self.0.remove(artifact_id).unwrap_or_default()
+	}
+}
+
+/// An execution request on wasmtime that is going to be re-run on the interpreter should the
+/// candidate be deemed invalid. See [`Config::dual_execute`].
+struct CrossCheck {
+	pvf: Pvf,
+	execution_timeout: Duration,
+	params: Vec<u8>,
+	priority: Priority,
+	result_tx: ResultSender,
+}
+
+/// The result of an execution as received through the result sender.
+type ReceivedResult = Result<Result<ValidationResult, ValidationError>, oneshot::Canceled>;
+
+enum CrossCheckEvent {
+	/// Wasmtime concluded the execution of the request.
+	WasmtimeConcluded(CrossCheck, ReceivedResult),
+	/// The interpreter concluded the execution of a candidate wasmtime deemed invalid.
+	InterpreterConcluded {
+		code_hash: ValidationCodeHash,
+		wasmtime_error: InvalidCandidate,
+		result_tx: ResultSender,
+		result: ReceivedResult,
+	},
+}
+
+type CrossChecks = FuturesUnordered<BoxFuture<'static, CrossCheckEvent>>;
+
 struct Inner {
 	cache_path: PathBuf,
 	cleanup_pulse_interval: Duration,
@@ -275,10 +419,17 @@ struct Inner {
 	to_prepare_queue_tx: mpsc::Sender<prepare::ToQueue>,
 	from_prepare_queue_rx: mpsc::UnboundedReceiver<prepare::FromQueue>,
 
+	to_precheck_queue_tx: mpsc::Sender<prepare::ToQueue>,
+	from_precheck_queue_rx: mpsc::UnboundedReceiver<prepare::FromQueue>,
+
 	to_execute_queue_tx: mpsc::Sender<execute::ToQueue>,
 	to_sweeper_tx: mpsc::Sender<PathBuf>,
 
 	awaiting_prepare: AwaitingPrepare,
+	awaiting_precheck: AwaitingPrecheck,
+
+	metrics: Metrics,
+	dual_execute: bool,
 }
 
 #[derive(Debug)]
@@ -293,12 +444,19 @@ async fn run(
 		to_host_rx,
 		from_prepare_queue_rx,
 		mut to_prepare_queue_tx,
+		from_precheck_queue_rx,
+		mut to_precheck_queue_tx,
 		mut to_execute_queue_tx,
 		mut to_sweeper_tx,
 		mut awaiting_prepare,
+		mut awaiting_precheck,
+		metrics,
+		dual_execute,
 	}: Inner,
 	prepare_pool: impl Future<Output = ()> + Unpin,
 	prepare_queue: impl Future<Output = ()> + Unpin,
+	precheck_pool: impl Future<Output = ()> + Unpin,
+	precheck_queue: impl Future<Output = ()> + Unpin,
 	execute_queue: impl Future<Output = ()> + Unpin,
 	sweeper: impl Future<Output = ()> + Unpin,
 ) {
@@ -314,12 +472,17 @@ async fn run(
 	let cleanup_pulse = pulse_every(cleanup_pulse_interval).fuse();
 	futures::pin_mut!(cleanup_pulse);
 
+	let mut cross_checks = CrossChecks::new();
+
 	let mut to_host_rx = to_host_rx.fuse();
 	let mut from_prepare_queue_rx = from_prepare_queue_rx.fuse();
+	let mut from_precheck_queue_rx = from_precheck_queue_rx.fuse();
 
 	// Make sure that the task-futures are fused.
 	let mut prepare_queue = prepare_queue.fuse();
 	let mut prepare_pool = prepare_pool.fuse();
+	let mut precheck_queue = precheck_queue.fuse();
+	let mut precheck_pool = precheck_pool.fuse();
 	let mut execute_queue = execute_queue.fuse();
 	let mut sweeper = sweeper.fuse();
 
@@ -334,6 +497,14 @@ async fn run(
 				never!("prepare_pool: long-running task never concludes; qed");
 				break;
 			},
+			_ = precheck_queue => {
+				never!("precheck_queue: long-running task never concludes; qed");
+				break;
+			},
+			_ = precheck_pool => {
+				never!("precheck_pool: long-running task never concludes; qed");
+				break;
+			},
 			_ = execute_queue => {
 				never!("execute_queue: long-running task never concludes; qed");
 				break;
@@ -362,12 +533,29 @@ async fn run(
 					&cache_path,
 					&mut artifacts,
 					&mut to_prepare_queue_tx,
+					&mut to_precheck_queue_tx,
 					&mut to_execute_queue_tx,
 					&mut awaiting_prepare,
+					&mut awaiting_precheck,
+					&mut cross_checks,
+					dual_execute,
 					to_host,
 				)
 				.await);
 			},
+			event = cross_checks.select_next_some() => {
+				break_if_fatal!(handle_cross_check_event(
+					&cache_path,
+					&mut artifacts,
+					&mut to_prepare_queue_tx,
+					&mut to_execute_queue_tx,
+					&mut awaiting_prepare,
+					&mut cross_checks,
+					&metrics,
+					event,
+				)
+				.await);
+			},
 			from_prepare_queue = from_prepare_queue_rx.next() => {
 				let from_queue = break_if_fatal!(from_prepare_queue.ok_or(Fatal));
 
@@ -388,6 +576,11 @@ async fn run(
 					from_queue,
 				).await);
 			},
+			from_precheck_queue = from_precheck_queue_rx.next() => {
+				let from_queue = break_if_fatal!(from_precheck_queue.ok_or(Fatal));
+
+				handle_precheck_done(&mut artifacts, &mut awaiting_precheck, from_queue);
+			},
 		}
 	}
 }
@@ -396,15 +589,33 @@ async fn handle_to_host(
 	cache_path: &Path,
 	artifacts: &mut Artifacts,
 	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
+	precheck_queue: &mut mpsc::Sender<prepare::ToQueue>,
 	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
 	awaiting_prepare: &mut AwaitingPrepare,
+	awaiting_precheck: &mut AwaitingPrecheck,
+	cross_checks: &mut CrossChecks,
+	dual_execute: bool,
 	to_host: ToHost,
 ) -> Result<(), Fatal> {
 	match to_host {
 		ToHost::PrecheckPvf { pvf, result_tx } => {
-			handle_precheck_pvf(artifacts, prepare_queue, pvf, result_tx).await?;
+			handle_precheck_pvf(awaiting_precheck, precheck_queue, pvf, result_tx).await?;
 		},
-		ToHost::ExecutePvf { pvf, execution_timeout, params, priority, result_tx } => {
+		ToHost::ExecutePvf { pvf, execution_timeout, params, priority, engine, result_tx } => {
+			let result_tx = if dual_execute && engine == Engine::Wasmtime {
+				// Note that this copies the params, since they are needed for the second run.
+				let cross_check = CrossCheck {
+					pvf: pvf.clone(),
+					execution_timeout,
+					params: params.clone(),
+					priority,
+					result_tx,
+				};
+				start_cross_check(cross_checks, cross_check)
+			} else {
+				result_tx
+			};
+
 			handle_execute_pvf(
 				cache_path,
 				artifacts,
@@ -415,6 +626,7 @@ async fn handle_to_host(
 				execution_timeout,
 				params,
 				priority,
+				engine,
 				result_tx,
 			)
 			.await?;
@@ -427,34 +639,49 @@ async fn handle_to_host(
 	Ok(())
 }
 
+/// Handles a pre-checking request by enqueueing the PVF into the precheck lane.
+///
+/// The verdict is always given by a job of the precheck lane, even if the artifact is already
+/// prepared or being prepared for execution. Only the requests pre-checking the same PVF share a
+/// job.
 async fn handle_precheck_pvf(
-	artifacts: &mut Artifacts,
-	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
+	awaiting_precheck: &mut AwaitingPrecheck,
+	precheck_queue: &mut mpsc::Sender<prepare::ToQueue>,
 	pvf: Pvf,
 	result_sender: PrepareResultSender,
 ) -> Result<(), Fatal> {
-	let artifact_id = pvf.as_artifact_id();
+	let artifact_id = pvf.as_artifact_id(Engine::Wasmtime);
 
-	if let Some(state) = artifacts.artifact_state_mut(&artifact_id) {
-		match state {
-			ArtifactState::Prepared { last_time_needed } => {
-				*last_time_needed = SystemTime::now();
-				let _ = result_sender.send(Ok(()));
-			},
-			ArtifactState::Preparing { waiting_for_response } =>
-				waiting_for_response.push(result_sender),
-			ArtifactState::FailedToProcess(result) => {
-				let _ = result_sender.send(PrepareResult::Err(result.clone()));
-			},
-		}
-	} else {
-		artifacts.insert_preparing(artifact_id, vec![result_sender]);
-		send_prepare(prepare_queue, prepare::ToQueue::Enqueue { priority: Priority::Normal, pvf })
-			.await?;
+	if awaiting_precheck.add(artifact_id, result_sender) {
+		send_prepare(
+			precheck_queue,
+			prepare::ToQueue::Enqueue { priority: Priority::Normal, pvf, engine: Engine::Wasmtime },
+		)
+		.await?;
 	}
 	Ok(())
 }
 
+/// Handles the verdict of a job of the precheck lane.
+///
+/// The verdict is only reported to the pre-checking requests. A successfully prepared artifact is
+/// however made available for execution, unless the host already knows about the artifact.
+fn handle_precheck_done(
+	artifacts: &mut Artifacts,
+	awaiting_precheck: &mut AwaitingPrecheck,
+	from_queue: prepare::FromQueue,
+) {
+	let prepare::FromQueue { artifact_id, result } = from_queue;
+
+	for result_sender in awaiting_precheck.take(&artifact_id) {
+		let _ = result_sender.send(result.clone());
+	}
+
+	if result.is_ok() && artifacts.artifact_state_mut(&artifact_id).is_none() {
+		artifacts.insert_prepared(artifact_id, SystemTime::now());
+	}
+}
+
 async fn handle_execute_pvf(
 	cache_path: &Path,
 	artifacts: &mut Artifacts,
@@ -465,9 +692,10 @@ async fn handle_execute_pvf(
 	execution_timeout: Duration,
 	params: Vec<u8>,
 	priority: Priority,
+	engine: Engine,
 	result_tx: ResultSender,
 ) -> Result<(), Fatal> {
-	let artifact_id = pvf.as_artifact_id();
+	let artifact_id = pvf.as_artifact_id(engine);
 
 	if let Some(state) = artifacts.artifact_state_mut(&artifact_id) {
 		match state {
@@ -496,7 +724,7 @@ async fn handle_execute_pvf(
 		// Artifact is unknown: register it and enqueue a job with the corresponding priority and
 		//
 		artifacts.insert_preparing(artifact_id.clone(), Vec::new());
-		send_prepare(prepare_queue, prepare::ToQueue::Enqueue { priority, pvf }).await?;
+		send_prepare(prepare_queue, prepare::ToQueue::Enqueue { priority, pvf, engine }).await?;
 
 		awaiting_prepare.add(artifact_id, execution_timeout, params, result_tx);
 	}
@@ -512,7 +740,7 @@ async fn handle_heads_up(
 	let now = SystemTime::now();
 
 	for active_pvf in active_pvfs {
-		let artifact_id = active_pvf.as_artifact_id();
+		let artifact_id = active_pvf.as_artifact_id(Engine::Wasmtime);
 		if let Some(state) = artifacts.artifact_state_mut(&artifact_id) {
 			match state {
 				ArtifactState::Prepared { last_time_needed, .. } => {
@@ -529,7 +757,11 @@ async fn handle_heads_up(
 
 			send_prepare(
 				prepare_queue,
-				prepare::ToQueue::Enqueue { priority: Priority::Normal, pvf: active_pvf },
+				prepare::ToQueue::Enqueue {
+					priority: Priority::Normal,
+					pvf: active_pvf,
+					engine: Engine::Wasmtime,
+				},
 			)
 			.await?;
 		}
@@ -618,6 +850,127 @@ async fn handle_prepare_done(
 	Ok(())
 }
 
+/// Registers the given request to be cross-checked once wasmtime concludes and returns the sender
+/// that wasmtime should report its result to.
+fn start_cross_check(cross_checks: &mut CrossChecks, cross_check: CrossCheck) -> ResultSender {
+	let (wasmtime_tx, wasmtime_rx) = oneshot::channel();
+	cross_checks.push(
+		async move { CrossCheckEvent::WasmtimeConcluded(cross_check, wasmtime_rx.await) }.boxed(),
+	);
+	wasmtime_tx
+}
+
+async fn handle_cross_check_event(
+	cache_path: &Path,
+	artifacts: &mut Artifacts,
+	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
+	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
+	awaiting_prepare: &mut AwaitingPrepare,
+	cross_checks: &mut CrossChecks,
+	metrics: &Metrics,
+	event: CrossCheckEvent,
+) -> Result<(), Fatal> {
+	match event {
+		CrossCheckEvent::WasmtimeConcluded(cross_check, result) => {
+			let CrossCheck { pvf, execution_timeout, params, priority, result_tx } = cross_check;
+
+			let wasmtime_error = match result {
+				Ok(Err(ValidationError::InvalidCandidate(error))) if is_cross_checked(&error) =>
+					error,
+				Ok(result) => {
+					let _ = result_tx.send(result);
+					return Ok(())
+				},
+				// The execution was dropped, and so is the result sender of the requester.
+				Err(oneshot::Canceled) => return Ok(()),
+			};
+
+			if result_tx.is_canceled() {
+				// The requester is not interested in the result anymore.
+				return Ok(())
+			}
+
+			metrics.on_cross_check();
+
+			let (interpreter_tx, interpreter_rx) = oneshot::channel();
+			let code_hash = pvf.code_hash;
+			cross_checks.push(
+				async move {
+					CrossCheckEvent::InterpreterConcluded {
+						code_hash,
+						wasmtime_error,
+						result_tx,
+						result: interpreter_rx.await,
+					}
+				}
+				.boxed(),
+			);
+
+			handle_execute_pvf(
+				cache_path,
+				artifacts,
+				prepare_queue,
+				execute_queue,
+				awaiting_prepare,
+				pvf,
+				execution_timeout * INTERPRETER_EXECUTION_TIMEOUT_FACTOR,
+				params,
+				priority,
+				Engine::Interpreter,
+				interpreter_tx,
+			)
+			.await?;
+		},
+		CrossCheckEvent::InterpreterConcluded { code_hash, wasmtime_error, result_tx, result } => {
+			let verdict = match result {
+				Ok(Ok(_)) => {
+					tracing::warn!(
+						target: LOG_TARGET,
+						validation_code_hash = ?code_hash,
+						?wasmtime_error,
+						"execution engines disagree: the interpreter deems the candidate valid",
+					);
+					metrics.on_engine_disagreement();
+
+					Err(ValidationError::InternalError(format!(
+						"execution engines disagree, wasmtime reported: {:?}",
+						wasmtime_error,
+					)))
+				},
+				Ok(Err(ValidationError::InvalidCandidate(_))) =>
+					Err(ValidationError::InvalidCandidate(wasmtime_error)),
+				Ok(Err(ValidationError::InternalError(error))) => {
+					tracing::debug!(
+						target: LOG_TARGET,
+						validation_code_hash = ?code_hash,
+						?error,
+						"failed to cross-check the candidate on the interpreter",
+					);
+					Err(ValidationError::InvalidCandidate(wasmtime_error))
+				},
+				Err(oneshot::Canceled) => Err(ValidationError::InvalidCandidate(wasmtime_error)),
+			};
+
+			let _ = result_tx.send(verdict);
+		},
+	}
+
+	Ok(())
+}
+
+/// Whether the given verdict of wasmtime should be cross-checked on the interpreter.
+///
+/// Exceeding the resource limits is not cross-checked, since the interpreter consumes the resources
+/// differently.
+fn is_cross_checked(error: &InvalidCandidate) -> bool {
+	match error {
+		InvalidCandidate::PrepareError(_) |
+		InvalidCandidate::WorkerReportedError(_) |
+		InvalidCandidate::AmbiguousWorkerDeath => true,
+		InvalidCandidate::CpuTimeExceeded(_) | InvalidCandidate::MemoryExhausted(_) => false,
+	}
+}
+
 async fn send_prepare(
 	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
 	to_queue: prepare::ToQueue,
@@ -711,7 +1064,7 @@ mod tests {
 
 	/// Creates a new PVF which artifact id can be uniquely identified by the given number.
 	fn artifact_id(descriminator: u32) -> ArtifactId {
-		Pvf::from_discriminator(descriminator).as_artifact_id()
+		Pvf::from_discriminator(descriminator).as_artifact_id(Engine::Wasmtime)
 	}
 
 	fn artifact_path(descriminator: u32) -> PathBuf {
@@ -722,6 +1075,7 @@ mod tests {
 		cleanup_pulse_interval: Duration,
 		artifact_ttl: Duration,
 		artifacts: Artifacts,
+		dual_execute: bool,
 	}
 
 	impl Builder {
@@ -732,6 +1086,7 @@ mod tests {
 				artifact_ttl: Duration::from_secs(3600),
 
 				artifacts: Artifacts::empty(),
+				dual_execute: false,
 			}
 		}
 
@@ -745,6 +1100,8 @@ mod tests {
 
 		to_prepare_queue_rx: mpsc::Receiver<prepare::ToQueue>,
 		from_prepare_queue_tx: mpsc::UnboundedSender<prepare::FromQueue>,
+		to_precheck_queue_rx: mpsc::Receiver<prepare::ToQueue>,
+		from_precheck_queue_tx: mpsc::UnboundedSender<prepare::FromQueue>,
 		to_execute_queue_rx: mpsc::Receiver<execute::ToQueue>,
 		to_sweeper_rx: mpsc::Receiver<PathBuf>,
 
@@ -752,12 +1109,16 @@ mod tests {
 	}
 
 	impl Test {
-		fn new(Builder { cleanup_pulse_interval, artifact_ttl, artifacts }: Builder) -> Self {
+		fn new(
+			Builder { cleanup_pulse_interval, artifact_ttl, artifacts, dual_execute }: Builder,
+		) -> Self {
 			let cache_path = PathBuf::from(std::env::temp_dir());
 
 			let (to_host_tx, to_host_rx) = mpsc::channel(10);
 			let (to_prepare_queue_tx, to_prepare_queue_rx) = mpsc::channel(10);
 			let (from_prepare_queue_tx, from_prepare_queue_rx) = mpsc::unbounded();
+			let (to_precheck_queue_tx, to_precheck_queue_rx) = mpsc::channel(10);
+			let (from_precheck_queue_tx, from_precheck_queue_rx) = mpsc::unbounded();
 			let (to_execute_queue_tx, to_execute_queue_rx) = mpsc::channel(10);
 			let (to_sweeper_tx, to_sweeper_rx) = mpsc::channel(10);
 
@@ -772,14 +1133,20 @@ mod tests {
 					to_host_rx,
 					to_prepare_queue_tx,
 					from_prepare_queue_rx,
+					to_precheck_queue_tx,
+					from_precheck_queue_rx,
 					to_execute_queue_tx,
 					to_sweeper_tx,
 					awaiting_prepare: AwaitingPrepare::default(),
+					metrics: Metrics::default(),
+					dual_execute,
 				},
 				mk_dummy_loop(),
 				mk_dummy_loop(),
 				mk_dummy_loop(),
 				mk_dummy_loop(),
+				mk_dummy_loop(),
+				mk_dummy_loop(),
 			)
 			.boxed();
 
@@ -787,6 +1154,8 @@ mod tests {
 				to_host_tx: Some(to_host_tx),
 				to_prepare_queue_rx,
 				from_prepare_queue_tx,
+				to_precheck_queue_rx,
+				from_precheck_queue_tx,
 				to_execute_queue_rx,
 				to_sweeper_rx,
 				run,
@@ -804,6 +1173,12 @@ mod tests {
 				.await
 		}
 
+		async fn poll_and_recv_to_precheck_queue(&mut self) -> prepare::ToQueue {
+			let to_precheck_queue_rx = &mut self.to_precheck_queue_rx;
+			run_until(&mut self.run, async { to_precheck_queue_rx.next().await.unwrap() }.boxed())
+				.await
+		}
+
 		async fn poll_and_recv_to_execute_queue(&mut self) -> execute::ToQueue {
 			let to_execute_queue_rx = &mut self.to_execute_queue_rx;
 			run_until(&mut self.run, async { to_execute_queue_rx.next().await.unwrap() }.boxed())
@@ -927,6 +1302,7 @@ mod tests {
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf1".to_vec(),
 			Priority::Normal,
+			Engine::Wasmtime,
 			result_tx,
 		)
 		.await
@@ -938,6 +1314,7 @@ mod tests {
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf1".to_vec(),
 			Priority::Critical,
+			Engine::Wasmtime,
 			result_tx,
 		)
 		.await
@@ -949,6 +1326,7 @@ mod tests {
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf2".to_vec(),
 			Priority::Normal,
+			Engine::Wasmtime,
 			result_tx,
 		)
 		.await
@@ -1019,13 +1397,13 @@ mod tests {
 		let (result_tx, result_rx) = oneshot::channel();
 		host.precheck_pvf(Pvf::from_discriminator(1), result_tx).await.unwrap();
 
-		// The queue received the prepare request.
+		// The precheck queue received the prepare request.
 		assert_matches!(
-			test.poll_and_recv_to_prepare_queue().await,
+			test.poll_and_recv_to_precheck_queue().await,
 			prepare::ToQueue::Enqueue { .. }
 		);
 		// Send `Ok` right away and poll the host.
-		test.from_prepare_queue_tx
+		test.from_precheck_queue_tx
 			.send(prepare::FromQueue { artifact_id: artifact_id(1), result: Ok(()) })
 			.await
 			.unwrap();
@@ -1043,10 +1421,10 @@ mod tests {
 		}
 		// Received prepare request.
 		assert_matches!(
-			test.poll_and_recv_to_prepare_queue().await,
+			test.poll_and_recv_to_precheck_queue().await,
 			prepare::ToQueue::Enqueue { .. }
 		);
-		test.from_prepare_queue_tx
+		test.from_precheck_queue_tx
 			.send(prepare::FromQueue {
 				artifact_id: artifact_id(2),
 				result: Err(PrepareError::TimedOut),
@@ -1077,6 +1455,7 @@ mod tests {
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf2".to_vec(),
 			Priority::Critical,
+			Engine::Wasmtime,
 			result_tx,
 		)
 		.await
@@ -1087,11 +1466,17 @@ mod tests {
 			prepare::ToQueue::Enqueue { .. }
 		);
 
-		let (result_tx, result_rx) = oneshot::channel();
+		let (result_tx, mut result_rx) = oneshot::channel();
 		host.precheck_pvf(Pvf::from_discriminator(1), result_tx).await.unwrap();
 
-		// Suppose the preparation failed, the execution queue is empty and both
-		// "clients" receive their results.
+		// The precheck doesn't join the ongoing preparation, but gets a job of its own.
+		assert_matches!(
+			test.poll_and_recv_to_precheck_queue().await,
+			prepare::ToQueue::Enqueue { .. }
+		);
+
+		// Suppose the preparation for the execution failed. Only the execution receives the
+		// result, the execution queue is empty.
 		test.from_prepare_queue_tx
 			.send(prepare::FromQueue {
 				artifact_id: artifact_id(1),
@@ -1100,11 +1485,19 @@ mod tests {
 			.await
 			.unwrap();
 		test.poll_ensure_to_execute_queue_is_empty().await;
-		assert_matches!(result_rx.now_or_never().unwrap().unwrap(), Err(PrepareError::TimedOut));
 		assert_matches!(
 			result_rx_execute.now_or_never().unwrap().unwrap(),
 			Err(ValidationError::InternalError(_))
 		);
+		assert!((&mut result_rx).now_or_never().is_none());
+
+		// The precheck is judged by its own job.
+		test.from_precheck_queue_tx
+			.send(prepare::FromQueue { artifact_id: artifact_id(1), result: Ok(()) })
+			.await
+			.unwrap();
+		test.poll_ensure_to_execute_queue_is_empty().await;
+		assert_matches!(result_rx.now_or_never().unwrap().unwrap(), Ok(()));
 
 		// Reversed case: first send multiple precheck requests, then ask for an execution.
 		let mut precheck_receivers = Vec::new();
@@ -1113,6 +1506,11 @@ mod tests {
 			host.precheck_pvf(Pvf::from_discriminator(2), result_tx).await.unwrap();
 			precheck_receivers.push(result_rx);
 		}
+		// The precheck requests share a single job.
+		assert_matches!(
+			test.poll_and_recv_to_precheck_queue().await,
+			prepare::ToQueue::Enqueue { .. }
+		);
 
 		let (result_tx, _result_rx_execute) = oneshot::channel();
 		host.execute_pvf(
@@ -1120,28 +1518,220 @@ mod tests {
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf2".to_vec(),
 			Priority::Critical,
+			Engine::Wasmtime,
 			result_tx,
 		)
 		.await
 		.unwrap();
-		// Received prepare request.
+		// The execution doesn't join the precheck, but is prepared in the prepare lane.
 		assert_matches!(
 			test.poll_and_recv_to_prepare_queue().await,
 			prepare::ToQueue::Enqueue { .. }
 		);
+
+		// The precheck fails, which only concerns the precheck requests.
+		test.from_precheck_queue_tx
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(2),
+				result: Err(PrepareError::TimedOut),
+			})
+			.await
+			.unwrap();
+		test.poll_ensure_to_execute_queue_is_empty().await;
+		for result_rx in precheck_receivers {
+			assert_matches!(
+				result_rx.now_or_never().unwrap().unwrap(),
+				Err(PrepareError::TimedOut)
+			);
+		}
+
+		// The preparation for the execution succeeds and the execute queue receives the request.
 		test.from_prepare_queue_tx
 			.send(prepare::FromQueue { artifact_id: artifact_id(2), result: Ok(()) })
 			.await
 			.unwrap();
-		// The execute queue receives new request, preckecking is finished and we can
-		// fetch results.
 		assert_matches!(
 			test.poll_and_recv_to_execute_queue().await,
 			execute::ToQueue::Enqueue { .. }
 		);
-		for result_rx in precheck_receivers {
-			assert_matches!(result_rx.now_or_never().unwrap().unwrap(), Ok(()));
-		}
+	}
+
+	#[async_std::test]
+	async fn precheck_is_judged_by_its_own_job_racing_an_execution() {
+		let mut test = Builder::default().build();
+		let mut host = test.host_handle();
+
+		// The PVF is prepared for an execution.
+		let (result_tx, _result_rx_execute) = oneshot::channel();
+		host.execute_pvf(
+			Pvf::from_discriminator(1),
+			TEST_EXECUTION_TIMEOUT,
+			b"pvf1".to_vec(),
+			Priority::Normal,
+			Engine::Wasmtime,
+			result_tx,
+		)
+		.await
+		.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_prepare_queue().await,
+			prepare::ToQueue::Enqueue { .. }
+		);
+		test.from_prepare_queue_tx
+			.send(prepare::FromQueue { artifact_id: artifact_id(1), result: Ok(()) })
+			.await
+			.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { .. }
+		);
+
+		// A precheck of the prepared PVF isn't answered from the artifact, and fails if its own
+		// job fails.
+		let (result_tx, result_rx) = oneshot::channel();
+		host.precheck_pvf(Pvf::from_discriminator(1), result_tx).await.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_precheck_queue().await,
+			prepare::ToQueue::Enqueue { .. }
+		);
+		test.from_precheck_queue_tx
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(1),
+				result: Err(PrepareError::TimedOut),
+			})
+			.await
+			.unwrap();
+		test.poll_ensure_to_execute_queue_is_empty().await;
+		assert_matches!(result_rx.now_or_never().unwrap().unwrap(), Err(PrepareError::TimedOut));
+
+		// The failed precheck doesn't affect the execution of the PVF prepared before.
+		let (result_tx, _result_rx_execute) = oneshot::channel();
+		host.execute_pvf(
+			Pvf::from_discriminator(1),
+			TEST_EXECUTION_TIMEOUT,
+			b"pvf1".to_vec(),
+			Priority::Normal,
+			Engine::Wasmtime,
+			result_tx,
+		)
+		.await
+		.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { .. }
+		);
+
+		// On the other hand, the artifact of a successful precheck is used for the execution.
+		let (result_tx, result_rx) = oneshot::channel();
+		host.precheck_pvf(Pvf::from_discriminator(2), result_tx).await.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_precheck_queue().await,
+			prepare::ToQueue::Enqueue { .. }
+		);
+		test.from_precheck_queue_tx
+			.send(prepare::FromQueue { artifact_id: artifact_id(2), result: Ok(()) })
+			.await
+			.unwrap();
+		test.poll_ensure_to_execute_queue_is_empty().await;
+		assert_matches!(result_rx.now_or_never().unwrap().unwrap(), Ok(()));
+
+		let (result_tx, _result_rx_execute) = oneshot::channel();
+		host.execute_pvf(
+			Pvf::from_discriminator(2),
+			TEST_EXECUTION_TIMEOUT,
+			b"pvf2".to_vec(),
+			Priority::Normal,
+			Engine::Wasmtime,
+			result_tx,
+		)
+		.await
+		.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { .. }
+		);
+	}
+
+	#[async_std::test]
+	async fn dual_execute_reports_disagreement() {
+		let mut builder = Builder::default();
+		builder.dual_execute = true;
+		let mut test = builder.build();
+		let mut host = test.host_handle();
+
+		let (result_tx, result_rx) = oneshot::channel();
+		host.execute_pvf(
+			Pvf::from_discriminator(1),
+			TEST_EXECUTION_TIMEOUT,
+			b"pvf1".to_vec(),
+			Priority::Normal,
+			Engine::Wasmtime,
+			result_tx,
+		)
+		.await
+		.unwrap();
+
+		assert_matches!(
+			test.poll_and_recv_to_prepare_queue().await,
+			prepare::ToQueue::Enqueue { engine: Engine::Wasmtime, .. }
+		);
+		test.from_prepare_queue_tx
+			.send(prepare::FromQueue { artifact_id: artifact_id(1), result: Ok(()) })
+			.await
+			.unwrap();
+
+		// Wasmtime deems the candidate invalid, so the candidate is re-run on the interpreter.
+		let result_tx_wasmtime = assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { result_tx, .. } => result_tx
+		);
+		result_tx_wasmtime
+			.send(Err(ValidationError::InvalidCandidate(InvalidCandidate::WorkerReportedError(
+				"trap".to_string(),
+			))))
+			.unwrap();
+
+		assert_matches!(
+			test.poll_and_recv_to_prepare_queue().await,
+			prepare::ToQueue::Enqueue { engine: Engine::Interpreter, .. }
+		);
+		test.from_prepare_queue_tx
+			.send(prepare::FromQueue {
+				artifact_id: Pvf::from_discriminator(1).as_artifact_id(Engine::Interpreter),
+				result: Ok(()),
+			})
+			.await
+			.unwrap();
+
+		let (execution_timeout, result_tx_interpreter) = assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { artifact, execution_timeout, result_tx, .. } => {
+				assert_eq!(artifact.id.engine, Engine::Interpreter);
+				(execution_timeout, result_tx)
+			}
+		);
+		assert_eq!(
+			execution_timeout,
+			TEST_EXECUTION_TIMEOUT * INTERPRETER_EXECUTION_TIMEOUT_FACTOR
+		);
+
+		// The interpreter disagrees, so no invalid verdict is reported.
+		result_tx_interpreter
+			.send(Ok(ValidationResult {
+				head_data: Default::default(),
+				new_validation_code: None,
+				upward_messages: Vec::new(),
+				horizontal_messages: Vec::new(),
+				processed_downward_messages: 0,
+				hrmp_watermark: 0,
+			}))
+			.unwrap();
+		test.poll_ensure_to_execute_queue_is_empty().await;
+
+		assert_matches!(
+			result_rx.now_or_never().unwrap().unwrap(),
+			Err(ValidationError::InternalError(_))
+		);
 	}
 
 	#[async_std::test]
@@ -1155,6 +1745,7 @@ mod tests {
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf1".to_vec(),
 			Priority::Normal,
+			Engine::Wasmtime,
 			result_tx,
 		)
 		.await
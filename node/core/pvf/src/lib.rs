@@ -52,8 +52,12 @@
 //! dissimilar to actors. Each of such "processes" is a future task that contains an event loop that
 //! processes incoming messages, potentially delegating sub-tasks to other "processes".
 //!
-//! Two of these processes are queues. The first one is for preparation jobs and the second one is for
-//! execution. Both of the queues are backed by separate pools of workers of different kind.
+//! Three of these processes are queues. The first two are for preparation jobs and the third one is
+//! for execution. Each of the queues is backed by a separate pool of workers.
+//!
+//! The preparation is split into two lanes with their own queues, workers and timeouts: one for
+//! the pre-checking requests and one for the preparation of the PVFs needed for execution. This
+//! way a burst of pre-checking requests cannot delay the backing.
 //!
 //! Preparation workers handle preparation requests by preverifying and instrumenting PVF wasm code,
 //! and then passing it into the compiler, to prepare the artifact.
@@ -68,14 +72,28 @@
 //! The artifact is saved on disk and is also tracked by an in memory table. This in memory table
 //! doesn't contain the artifact contents though, only a flag that the given artifact is compiled.
 //!
+//! The artifacts on disk survive node restarts. Each artifact starts with a header that records the
+//! version and the configuration of the executor that produced it. On startup the host rescans the
+//! cache directory and reuses the artifacts whose header matches the running executor, removing
+//! the stale, corrupted and unrecognized files.
+//!
 //! The execute workers will be fed by the requests from the execution queue, which is basically a
 //! combination of a path to the compiled artifact and the
 //! [`params`][`polkadot_parachain::primitives::ValidationParams`].
 //!
+//! A PVF can be executed with one of two [`Engine`]s: the compiling one, used by default, and an
+//! interpreter. With [`Config::dual_execute`] enabled, candidates that the compiling engine failed
+//! to execute are re-executed with the interpreter and any disagreement between the two is
+//! reported instead of an invalid verdict.
+//!
+//! Optionally, the workers can be run in a sandbox that limits their access to the system. See
+//! [`Config::enable_sandbox`] and [`check_sandbox_support`].
+//!
 //! Each fixed interval of time a pruning task will run. This task will remove all artifacts that
 //! weren't used or received a heads up signal for a while.
 
 mod artifacts;
+mod engine;
 mod error;
 mod execute;
 mod executor_intf;
@@ -84,6 +102,7 @@ mod metrics;
 mod prepare;
 mod priority;
 mod pvf;
+mod sandbox;
 mod worker_common;
 
 #[doc(hidden)]
@@ -92,9 +111,11 @@ pub mod testing;
 #[doc(hidden)]
 pub use sp_tracing;
 
+pub use engine::Engine;
 pub use error::{InvalidCandidate, PrepareError, PrepareResult, ValidationError};
 pub use priority::Priority;
 pub use pvf::Pvf;
+pub use sandbox::check_support as check_sandbox_support;
 
 pub use host::{start, Config, ValidationHost};
 pub use metrics::Metrics;
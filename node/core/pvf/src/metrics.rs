@@ -16,6 +16,7 @@
 
 //! Prometheus metrics related to the validation host.
 
+use crate::prepare::Lane;
 use polkadot_node_subsystem_util::metrics::{self, prometheus};
 
 /// Validation host metrics.
@@ -23,9 +24,9 @@ use polkadot_node_subsystem_util::metrics::{self, prometheus};
 pub struct Metrics(Option<MetricsInner>);
 
 impl Metrics {
-	/// Returns a handle to submit prepare workers metrics.
-	pub(crate) fn prepare_worker(&'_ self) -> WorkerRelatedMetrics<'_> {
-		WorkerRelatedMetrics { metrics: self, flavor: WorkerFlavor::Prepare }
+	/// Returns a handle to submit metrics of the prepare workers of the given lane.
+	pub(crate) fn prepare_worker(&'_ self, lane: Lane) -> WorkerRelatedMetrics<'_> {
+		WorkerRelatedMetrics { metrics: self, flavor: WorkerFlavor::Prepare(lane) }
 	}
 
 	/// Returns a handle to submit execute workers metrics.
@@ -33,17 +34,17 @@ impl Metrics {
 		WorkerRelatedMetrics { metrics: self, flavor: WorkerFlavor::Execute }
 	}
 
-	/// When preparation pipeline had a new item enqueued.
-	pub(crate) fn prepare_enqueued(&self) {
+	/// When the given lane of the preparation pipeline had a new item enqueued.
+	pub(crate) fn prepare_enqueued(&self, lane: Lane) {
 		if let Some(metrics) = &self.0 {
-			metrics.prepare_enqueued.inc();
+			metrics.prepare_enqueued.with_label_values(&[lane.as_label()]).inc();
 		}
 	}
 
-	/// When preparation pipeline concluded working on an item.
-	pub(crate) fn prepare_concluded(&self) {
+	/// When the given lane of the preparation pipeline concluded working on an item.
+	pub(crate) fn prepare_concluded(&self, lane: Lane) {
 		if let Some(metrics) = &self.0 {
-			metrics.prepare_concluded.inc();
+			metrics.prepare_concluded.with_label_values(&[lane.as_label()]).inc();
 		}
 	}
 
@@ -61,17 +62,42 @@ impl Metrics {
 		}
 	}
 
-	/// Time between sending preparation request to a worker to having the response.
+	/// Time between sending preparation request to a worker of the given lane to having the
+	/// response.
 	pub(crate) fn time_preparation(
 		&self,
+		lane: Lane,
 	) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
-		self.0.as_ref().map(|metrics| metrics.preparation_time.start_timer())
+		self.0.as_ref().map(|metrics| {
+			metrics.preparation_time.with_label_values(&[lane.as_label()]).start_timer()
+		})
 	}
 
 	/// Time between sending execution request to a worker to having the response.
 	pub(crate) fn time_execution(&self) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
 		self.0.as_ref().map(|metrics| metrics.execution_time.start_timer())
 	}
+
+	/// When a candidate deemed invalid by wasmtime is re-run on the interpreter.
+	pub(crate) fn on_cross_check(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.cross_checks.inc();
+		}
+	}
+
+	/// When the interpreter deems valid a candidate that wasmtime deemed invalid.
+	pub(crate) fn on_engine_disagreement(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.engine_disagreements.inc();
+		}
+	}
+
+	/// CPU time consumed by a successful execution, as measured by the worker.
+	pub(crate) fn observe_execution_cpu_time(&self, cpu_time_ms: u64) {
+		if let Some(metrics) = &self.0 {
+			metrics.execution_cpu_time.observe(cpu_time_ms as f64 / 1000.0);
+		}
+	}
 }
 
 #[derive(Clone)]
@@ -79,12 +105,15 @@ struct MetricsInner {
 	worker_spawning: prometheus::CounterVec<prometheus::U64>,
 	worker_spawned: prometheus::CounterVec<prometheus::U64>,
 	worker_retired: prometheus::CounterVec<prometheus::U64>,
-	prepare_enqueued: prometheus::Counter<prometheus::U64>,
-	prepare_concluded: prometheus::Counter<prometheus::U64>,
+	prepare_enqueued: prometheus::CounterVec<prometheus::U64>,
+	prepare_concluded: prometheus::CounterVec<prometheus::U64>,
 	execute_enqueued: prometheus::Counter<prometheus::U64>,
 	execute_finished: prometheus::Counter<prometheus::U64>,
-	preparation_time: prometheus::Histogram,
+	preparation_time: prometheus::HistogramVec,
 	execution_time: prometheus::Histogram,
+	execution_cpu_time: prometheus::Histogram,
+	cross_checks: prometheus::Counter<prometheus::U64>,
+	engine_disagreements: prometheus::Counter<prometheus::U64>,
 }
 
 impl metrics::Metrics for Metrics {
@@ -121,16 +150,22 @@ impl metrics::Metrics for Metrics {
 				registry,
 			)?,
 			prepare_enqueued: prometheus::register(
-				prometheus::Counter::new(
-					"polkadot_pvf_prepare_enqueued",
-					"The total number of jobs enqueued into the preparation pipeline"
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_pvf_prepare_enqueued",
+						"The total number of jobs enqueued into the preparation pipeline",
+					),
+					&["lane"],
 				)?,
 				registry,
 			)?,
 			prepare_concluded: prometheus::register(
-				prometheus::Counter::new(
-					"polkadot_pvf_prepare_concluded",
-					"The total number of jobs concluded in the preparation pipeline"
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_pvf_prepare_concluded",
+						"The total number of jobs concluded in the preparation pipeline",
+					),
+					&["lane"],
 				)?,
 				registry,
 			)?,
@@ -149,14 +184,14 @@ impl metrics::Metrics for Metrics {
 				registry,
 			)?,
 			preparation_time: prometheus::register(
-				prometheus::Histogram::with_opts(
+				prometheus::HistogramVec::new(
 					prometheus::HistogramOpts::new(
 						"polkadot_pvf_preparation_time",
 						"Time spent in preparing PVF artifacts in seconds",
 					)
 					.buckets(vec![
-						// This is synchronized with COMPILATION_TIMEOUT=60s constant found in
-						// src/prepare/worker.rs
+						// This is synchronized with PRECHECK_PREPARATION_TIMEOUT=60s and
+						// LENIENT_PREPARATION_TIMEOUT=360s constants found in src/prepare/mod.rs
 						0.1,
 						0.5,
 						1.0,
@@ -166,7 +201,11 @@ impl metrics::Metrics for Metrics {
 						40.0,
 						50.0,
 						60.0,
+						120.0,
+						240.0,
+						360.0,
 					]),
+					&["lane"],
 				)?,
 				registry,
 			)?,
@@ -179,20 +218,43 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			execution_cpu_time: prometheus::register(
+				prometheus::Histogram::with_opts(
+					prometheus::HistogramOpts::new(
+						"polkadot_pvf_execution_cpu_time",
+						"CPU time spent in executing PVFs in seconds, as measured by the workers",
+					)
+				)?,
+				registry,
+			)?,
+			cross_checks: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_pvf_cross_checks",
+					"The total number of candidates deemed invalid by wasmtime and re-run on the interpreter"
+				)?,
+				registry,
+			)?,
+			engine_disagreements: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_pvf_engine_disagreements",
+					"The total number of candidates deemed invalid by wasmtime, but valid by the interpreter"
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(inner)))
 	}
 }
 
 enum WorkerFlavor {
-	Prepare,
+	Prepare(Lane),
 	Execute,
 }
 
 impl WorkerFlavor {
 	fn as_label(&self) -> &'static str {
 		match *self {
-			WorkerFlavor::Prepare => "prepare",
+			WorkerFlavor::Prepare(lane) => lane.as_label(),
 			WorkerFlavor::Execute => "execute",
 		}
 	}
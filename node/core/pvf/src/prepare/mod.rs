@@ -21,6 +21,15 @@
 //!
 //! The pool will spawn workers in new processes and those should execute pass control to
 //! [`worker_entrypoint`].
+//!
+//! There are two independent pipelines of a queue and a pool, called lanes (see [`Lane`]). The
+//! precheck lane serves the PVF pre-checking requests, and the prepare lane serves preparation of
+//! the PVFs needed for execution. Each lane has its own workers, so a burst of pre-checking
+//! requests cannot delay the preparation of the PVFs needed for backing and vice versa.
+//!
+//! The jobs of the two lanes are never shared: a pre-checking request doesn't join a preparation
+//! in the prepare lane and vice versa, so the outcome of pre-checking only depends on the
+//! parameters of the precheck lane.
 
 mod pool;
 mod queue;
@@ -29,3 +38,60 @@ mod worker;
 pub use pool::start as start_pool;
 pub use queue::{start as start_queue, FromQueue, ToQueue};
 pub use worker::worker_entrypoint;
+
+use parity_scale_codec::{Decode, Encode};
+use std::time::Duration;
+
+/// The time period after which a precheck preparation is considered to have failed.
+///
+/// The outcome of pre-checking is voted upon on-chain, so this should be kept the same across
+/// validators.
+// NOTE: If you change this make sure to fix the buckets of `pvf_preparation_time` metric.
+const PRECHECK_PREPARATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The time period after which a preparation for execution is considered to have failed.
+///
+/// A PVF that is needed for execution has already passed pre-checking, so the preparation is
+/// expected to succeed eventually and the limit is more lenient. The preparation is also done
+/// with the parallel compilation enabled, so it usually takes way less than that.
+// NOTE: If you change this make sure to fix the buckets of `pvf_preparation_time` metric.
+const LENIENT_PREPARATION_TIMEOUT: Duration = Duration::from_secs(360);
+
+/// The lane of the preparation pipeline a job belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum Lane {
+	/// Preparation requested by the PVF pre-checking.
+	Precheck,
+	/// Preparation of a PVF that is needed for execution, either because there is a pending
+	/// execution request or because the PVF is active.
+	Prepare,
+}
+
+impl Lane {
+	/// The time allotted for a single preparation in this lane.
+	pub(crate) fn preparation_timeout(&self) -> Duration {
+		match *self {
+			Lane::Precheck => PRECHECK_PREPARATION_TIMEOUT,
+			Lane::Prepare => LENIENT_PREPARATION_TIMEOUT,
+		}
+	}
+
+	/// Whether the compilation in this lane may use multiple threads.
+	///
+	/// The pre-checking is done single-threaded to make the preparation time easily reproducible
+	/// and as deterministic as possible across validators.
+	pub(crate) fn parallel_compilation(&self) -> bool {
+		match *self {
+			Lane::Precheck => false,
+			Lane::Prepare => true,
+		}
+	}
+
+	/// The label used for this lane in the metrics.
+	pub(crate) fn as_label(&self) -> &'static str {
+		match *self {
+			Lane::Precheck => "precheck",
+			Lane::Prepare => "prepare",
+		}
+	}
+}
@@ -14,12 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::worker::{self, Outcome};
+use super::{
+	worker::{self, Outcome},
+	Lane,
+};
 use crate::{
 	error::{PrepareError, PrepareResult},
 	metrics::Metrics,
 	worker_common::{IdleWorker, WorkerHandle},
-	LOG_TARGET,
+	Engine, LOG_TARGET,
 };
 use always_assert::never;
 use assert_matches::assert_matches;
@@ -53,7 +56,7 @@ pub enum ToPool {
 	/// this message is processed.
 	Kill(Worker),
 
-	/// Request the given worker to start working on the given code.
+	/// Request the given worker to start preparing the given code for the given engine.
 	///
 	/// Once the job either succeeded or failed, a [`FromPool::Concluded`] message will be sent back.
 	/// It's also possible that the worker dies before handling the message in which case [`FromPool::Rip`]
@@ -61,7 +64,7 @@ pub enum ToPool {
 	///
 	/// In either case, the worker is considered busy and no further `StartWork` messages should be
 	/// sent until either `Concluded` or `Rip` message is received.
-	StartWork { worker: Worker, code: Arc<Vec<u8>>, artifact_path: PathBuf },
+	StartWork { worker: Worker, code: Arc<Vec<u8>>, engine: Engine, artifact_path: PathBuf },
 }
 
 /// A message sent from pool to its client.
@@ -104,9 +107,11 @@ enum PoolEvent {
 type Mux = FuturesUnordered<BoxFuture<'static, PoolEvent>>;
 
 struct Pool {
+	lane: Lane,
 	program_path: PathBuf,
 	cache_path: PathBuf,
 	spawn_timeout: Duration,
+	sandbox: bool,
 	to_pool: mpsc::Receiver<ToPool>,
 	from_pool: mpsc::UnboundedSender<FromPool>,
 	spawned: HopSlotMap<Worker, WorkerData>,
@@ -119,9 +124,11 @@ struct Fatal;
 
 async fn run(
 	Pool {
+		lane,
 		program_path,
 		cache_path,
 		spawn_timeout,
+		sandbox,
 		to_pool,
 		mut from_pool,
 		mut spawned,
@@ -145,25 +152,28 @@ async fn run(
 			to_pool = to_pool.next() => {
 				let to_pool = break_if_fatal!(to_pool.ok_or(Fatal));
 				handle_to_pool(
+					lane,
 					&metrics,
 					&program_path,
 					&cache_path,
 					spawn_timeout,
+					sandbox,
 					&mut spawned,
 					&mut mux,
 					to_pool,
 				)
 			}
 			ev = mux.select_next_some() => {
-				break_if_fatal!(handle_mux(&metrics, &mut from_pool, &mut spawned, ev))
+				break_if_fatal!(handle_mux(lane, &metrics, &mut from_pool, &mut spawned, ev))
 			}
 		}
 
-		break_if_fatal!(purge_dead(&metrics, &mut from_pool, &mut spawned).await);
+		break_if_fatal!(purge_dead(lane, &metrics, &mut from_pool, &mut spawned).await);
 	}
 }
 
 async fn purge_dead(
+	lane: Lane,
 	metrics: &Metrics,
 	from_pool: &mut mpsc::UnboundedSender<FromPool>,
 	spawned: &mut HopSlotMap<Worker, WorkerData>,
@@ -183,7 +193,7 @@ async fn purge_dead(
 		}
 	}
 	for w in to_remove {
-		if attempt_retire(metrics, spawned, w) {
+		if attempt_retire(lane, metrics, spawned, w) {
 			reply(from_pool, FromPool::Rip(w))?;
 		}
 	}
@@ -191,29 +201,36 @@ async fn purge_dead(
 }
 
 fn handle_to_pool(
+	lane: Lane,
 	metrics: &Metrics,
 	program_path: &Path,
 	cache_path: &Path,
 	spawn_timeout: Duration,
+	sandbox: bool,
 	spawned: &mut HopSlotMap<Worker, WorkerData>,
 	mux: &mut Mux,
 	to_pool: ToPool,
 ) {
 	match to_pool {
 		ToPool::Spawn => {
-			tracing::debug!(target: LOG_TARGET, "spawning a new prepare worker");
-			metrics.prepare_worker().on_begin_spawn();
-			mux.push(spawn_worker_task(program_path.to_owned(), spawn_timeout).boxed());
+			tracing::debug!(target: LOG_TARGET, ?lane, "spawning a new prepare worker");
+			metrics.prepare_worker(lane).on_begin_spawn();
+			let sandbox_root = if sandbox { Some(cache_path.to_owned()) } else { None };
+			mux.push(
+				spawn_worker_task(program_path.to_owned(), spawn_timeout, sandbox_root).boxed(),
+			);
 		},
-		ToPool::StartWork { worker, code, artifact_path } => {
+		ToPool::StartWork { worker, code, engine, artifact_path } => {
 			if let Some(data) = spawned.get_mut(worker) {
 				if let Some(idle) = data.idle.take() {
-					let preparation_timer = metrics.time_preparation();
+					let preparation_timer = metrics.time_preparation(lane);
 					mux.push(
 						start_work_task(
+							lane,
 							worker,
 							idle,
 							code,
+							engine,
 							cache_path.to_owned(),
 							artifact_path,
 							preparation_timer,
@@ -234,18 +251,22 @@ fn handle_to_pool(
 			}
 		},
 		ToPool::Kill(worker) => {
-			tracing::debug!(target: LOG_TARGET, ?worker, "killing prepare worker");
+			tracing::debug!(target: LOG_TARGET, ?lane, ?worker, "killing prepare worker");
 			// It may be absent if it were previously already removed by `purge_dead`.
-			let _ = attempt_retire(metrics, spawned, worker);
+			let _ = attempt_retire(lane, metrics, spawned, worker);
 		},
 	}
 }
 
-async fn spawn_worker_task(program_path: PathBuf, spawn_timeout: Duration) -> PoolEvent {
+async fn spawn_worker_task(
+	program_path: PathBuf,
+	spawn_timeout: Duration,
+	sandbox_root: Option<PathBuf>,
+) -> PoolEvent {
 	use futures_timer::Delay;
 
 	loop {
-		match worker::spawn(&program_path, spawn_timeout).await {
+		match worker::spawn(&program_path, spawn_timeout, sandbox_root.clone()).await {
 			Ok((idle, handle)) => break PoolEvent::Spawn(idle, handle),
 			Err(err) => {
 				tracing::warn!(target: LOG_TARGET, "failed to spawn a prepare worker: {:?}", err);
@@ -258,18 +279,21 @@ async fn spawn_worker_task(program_path: PathBuf, spawn_timeout: Duration) -> Po
 }
 
 async fn start_work_task<Timer>(
+	lane: Lane,
 	worker: Worker,
 	idle: IdleWorker,
 	code: Arc<Vec<u8>>,
+	engine: Engine,
 	cache_path: PathBuf,
 	artifact_path: PathBuf,
 	_preparation_timer: Option<Timer>,
 ) -> PoolEvent {
-	let outcome = worker::start_work(idle, code, &cache_path, artifact_path).await;
+	let outcome = worker::start_work(idle, code, engine, &cache_path, artifact_path, lane).await;
 	PoolEvent::StartWork(worker, outcome)
 }
 
 fn handle_mux(
+	lane: Lane,
 	metrics: &Metrics,
 	from_pool: &mut mpsc::UnboundedSender<FromPool>,
 	spawned: &mut HopSlotMap<Worker, WorkerData>,
@@ -277,7 +301,7 @@ fn handle_mux(
 ) -> Result<(), Fatal> {
 	match event {
 		PoolEvent::Spawn(idle, handle) => {
-			metrics.prepare_worker().on_spawned();
+			metrics.prepare_worker(lane).on_spawned();
 
 			let worker = spawned.insert(WorkerData { idle: Some(idle), handle });
 
@@ -307,14 +331,14 @@ fn handle_mux(
 					Ok(())
 				},
 				Outcome::Unreachable => {
-					if attempt_retire(metrics, spawned, worker) {
+					if attempt_retire(lane, metrics, spawned, worker) {
 						reply(from_pool, FromPool::Rip(worker))?;
 					}
 
 					Ok(())
 				},
 				Outcome::DidNotMakeIt => {
-					if attempt_retire(metrics, spawned, worker) {
+					if attempt_retire(lane, metrics, spawned, worker) {
 						reply(
 							from_pool,
 							FromPool::Concluded {
@@ -328,7 +352,7 @@ fn handle_mux(
 					Ok(())
 				},
 				Outcome::TimedOut => {
-					if attempt_retire(metrics, spawned, worker) {
+					if attempt_retire(lane, metrics, spawned, worker) {
 						reply(
 							from_pool,
 							FromPool::Concluded {
@@ -357,12 +381,13 @@ fn reply(from_pool: &mut mpsc::UnboundedSender<FromPool>, m: FromPool) -> Result
 ///
 /// This function takes care about counting the retired workers metric.
 fn attempt_retire(
+	lane: Lane,
 	metrics: &Metrics,
 	spawned: &mut HopSlotMap<Worker, WorkerData>,
 	worker: Worker,
 ) -> bool {
 	if spawned.remove(worker).is_some() {
-		metrics.prepare_worker().on_retired();
+		metrics.prepare_worker(lane).on_retired();
 		true
 	} else {
 		false
@@ -370,20 +395,29 @@ fn attempt_retire(
 }
 
 /// Spins up the pool and returns the future that should be polled to make the pool functional.
+///
+/// The pool only serves the jobs of the given `lane`, which determines the preparation timeout.
+///
+/// If `sandbox` is `true`, the workers are spawned in the sandbox and only have access to the
+/// `cache_path`.
 pub fn start(
+	lane: Lane,
 	metrics: Metrics,
 	program_path: PathBuf,
 	cache_path: PathBuf,
 	spawn_timeout: Duration,
+	sandbox: bool,
 ) -> (mpsc::Sender<ToPool>, mpsc::UnboundedReceiver<FromPool>, impl Future<Output = ()>) {
 	let (to_pool_tx, to_pool_rx) = mpsc::channel(10);
 	let (from_pool_tx, from_pool_rx) = mpsc::unbounded();
 
 	let run = run(Pool {
+		lane,
 		metrics,
 		program_path,
 		cache_path,
 		spawn_timeout,
+		sandbox,
 		to_pool: to_pool_rx,
 		from_pool: from_pool_tx,
 		spawned: HopSlotMap::with_capacity_and_key(20),
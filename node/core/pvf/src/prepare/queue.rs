@@ -16,8 +16,13 @@
 
 //! A queue that handles requests for PVF preparation.
 
-use super::pool::{self, Worker};
-use crate::{artifacts::ArtifactId, metrics::Metrics, PrepareResult, Priority, Pvf, LOG_TARGET};
+use super::{
+	pool::{self, Worker},
+	Lane,
+};
+use crate::{
+	artifacts::ArtifactId, metrics::Metrics, Engine, PrepareResult, Priority, Pvf, LOG_TARGET,
+};
 use always_assert::{always, never};
 use async_std::path::PathBuf;
 use futures::{channel::mpsc, stream::StreamExt as _, Future, SinkExt};
@@ -26,11 +31,11 @@ use std::collections::{HashMap, VecDeque};
 /// A request to pool.
 #[derive(Debug)]
 pub enum ToQueue {
-	/// This schedules preparation of the given PVF.
+	/// This schedules preparation of the given PVF for the given engine.
 	///
-	/// Note that it is incorrect to enqueue the same PVF again without first receiving the
-	/// [`FromQueue`] response.
-	Enqueue { priority: Priority, pvf: Pvf },
+	/// Note that it is incorrect to enqueue the same PVF for the same engine again without first
+	/// receiving the [`FromQueue`] response.
+	Enqueue { priority: Priority, pvf: Pvf, engine: Engine },
 }
 
 /// A response from queue.
@@ -76,6 +81,8 @@ struct JobData {
 	/// The priority of this job. Can be bumped.
 	priority: Priority,
 	pvf: Pvf,
+	/// The engine the PVF is prepared for.
+	engine: Engine,
 	worker: Option<Worker>,
 }
 
@@ -125,6 +132,7 @@ impl Unscheduled {
 }
 
 struct Queue {
+	lane: Lane,
 	metrics: Metrics,
 
 	to_queue_rx: mpsc::Receiver<ToQueue>,
@@ -155,6 +163,7 @@ struct Fatal;
 
 impl Queue {
 	fn new(
+		lane: Lane,
 		metrics: Metrics,
 		soft_capacity: usize,
 		hard_capacity: usize,
@@ -165,6 +174,7 @@ impl Queue {
 		from_pool_rx: mpsc::UnboundedReceiver<pool::FromPool>,
 	) -> Self {
 		Self {
+			lane,
 			metrics,
 			to_queue_rx,
 			from_queue_tx,
@@ -203,23 +213,30 @@ impl Queue {
 
 async fn handle_to_queue(queue: &mut Queue, to_queue: ToQueue) -> Result<(), Fatal> {
 	match to_queue {
-		ToQueue::Enqueue { priority, pvf } => {
-			handle_enqueue(queue, priority, pvf).await?;
+		ToQueue::Enqueue { priority, pvf, engine } => {
+			handle_enqueue(queue, priority, pvf, engine).await?;
 		},
 	}
 	Ok(())
 }
 
-async fn handle_enqueue(queue: &mut Queue, priority: Priority, pvf: Pvf) -> Result<(), Fatal> {
+async fn handle_enqueue(
+	queue: &mut Queue,
+	priority: Priority,
+	pvf: Pvf,
+	engine: Engine,
+) -> Result<(), Fatal> {
 	tracing::debug!(
 		target: LOG_TARGET,
 		validation_code_hash = ?pvf.code_hash,
 		?priority,
+		?engine,
+		lane = ?queue.lane,
 		"PVF is enqueued for preparation.",
 	);
-	queue.metrics.prepare_enqueued();
+	queue.metrics.prepare_enqueued(queue.lane);
 
-	let artifact_id = pvf.as_artifact_id();
+	let artifact_id = pvf.as_artifact_id(engine);
 	if never!(
 		queue.artifact_id_to_job.contains_key(&artifact_id),
 		"second Enqueue sent for a known artifact"
@@ -236,7 +253,7 @@ async fn handle_enqueue(queue: &mut Queue, priority: Priority, pvf: Pvf) -> Resu
 		return Ok(())
 	}
 
-	let job = queue.jobs.insert(JobData { priority, pvf, worker: None });
+	let job = queue.jobs.insert(JobData { priority, pvf, engine, worker: None });
 	queue.artifact_id_to_job.insert(artifact_id, job);
 
 	if let Some(available) = find_idle_worker(queue) {
@@ -283,7 +300,7 @@ async fn handle_worker_concluded(
 	rip: bool,
 	result: PrepareResult,
 ) -> Result<(), Fatal> {
-	queue.metrics.prepare_concluded();
+	queue.metrics.prepare_concluded(queue.lane);
 
 	macro_rules! never_none {
 		($expr:expr) => {
@@ -327,13 +344,14 @@ async fn handle_worker_concluded(
 	// this can't be None;
 	// qed.
 	let job_data = never_none!(queue.jobs.remove(job));
-	let artifact_id = job_data.pvf.as_artifact_id();
+	let artifact_id = job_data.pvf.as_artifact_id(job_data.engine);
 
 	queue.artifact_id_to_job.remove(&artifact_id);
 
 	tracing::debug!(
 		target: LOG_TARGET,
 		validation_code_hash = ?artifact_id.code_hash,
+		lane = ?queue.lane,
 		?worker,
 		?rip,
 		"prepare worker concluded",
@@ -370,7 +388,7 @@ async fn handle_worker_concluded(
 }
 
 async fn handle_worker_rip(queue: &mut Queue, worker: Worker) -> Result<(), Fatal> {
-	tracing::debug!(target: LOG_TARGET, ?worker, "prepare worker ripped");
+	tracing::debug!(target: LOG_TARGET, lane = ?queue.lane, ?worker, "prepare worker ripped");
 
 	let worker_data = queue.workers.remove(worker);
 	if let Some(WorkerData { job: Some(job), .. }) = worker_data {
@@ -415,7 +433,7 @@ async fn spawn_extra_worker(queue: &mut Queue, critical: bool) -> Result<(), Fat
 async fn assign(queue: &mut Queue, worker: Worker, job: Job) -> Result<(), Fatal> {
 	let job_data = &mut queue.jobs[job];
 
-	let artifact_id = job_data.pvf.as_artifact_id();
+	let artifact_id = job_data.pvf.as_artifact_id(job_data.engine);
 	let artifact_path = artifact_id.path(&queue.cache_path);
 
 	job_data.worker = Some(worker);
@@ -424,7 +442,12 @@ async fn assign(queue: &mut Queue, worker: Worker, job: Job) -> Result<(), Fatal
 
 	send_pool(
 		&mut queue.to_pool_tx,
-		pool::ToPool::StartWork { worker, code: job_data.pvf.code.clone(), artifact_path },
+		pool::ToPool::StartWork {
+			worker,
+			code: job_data.pvf.code.clone(),
+			engine: job_data.engine,
+			artifact_path,
+		},
 	)
 	.await?;
 
@@ -449,7 +472,10 @@ async fn send_pool(
 }
 
 /// Spins up the queue and returns the future that should be polled to make the queue functional.
+///
+/// The queue is meant to be paired with a pool serving the same `lane`.
 pub fn start(
+	lane: Lane,
 	metrics: Metrics,
 	soft_capacity: usize,
 	hard_capacity: usize,
@@ -461,6 +487,7 @@ pub fn start(
 	let (from_queue_tx, from_queue_rx) = mpsc::unbounded();
 
 	let run = Queue::new(
+		lane,
 		metrics,
 		soft_capacity,
 		hard_capacity,
@@ -532,6 +559,7 @@ mod tests {
 			let workers: SlotMap<Worker, ()> = SlotMap::with_key();
 
 			let (to_queue_tx, from_queue_rx, run) = start(
+				Lane::Prepare,
 				Metrics::default(),
 				soft_capacity,
 				hard_capacity,
@@ -594,23 +622,42 @@ mod tests {
 	async fn properly_concludes() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			engine: Engine::Wasmtime,
+		});
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
 		let w = test.workers.insert(());
 		test.send_from_pool(pool::FromPool::Spawned(w));
 		test.send_from_pool(pool::FromPool::Concluded { worker: w, rip: false, result: Ok(()) });
 
-		assert_eq!(test.poll_and_recv_from_queue().await.artifact_id, pvf(1).as_artifact_id());
+		assert_eq!(
+			test.poll_and_recv_from_queue().await.artifact_id,
+			pvf(1).as_artifact_id(Engine::Wasmtime)
+		);
 	}
 
 	#[async_std::test]
 	async fn dont_spawn_over_soft_limit_unless_critical() {
 		let mut test = Test::new(2, 3);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(2) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(3) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			engine: Engine::Wasmtime,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(2),
+			engine: Engine::Wasmtime,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(3),
+			engine: Engine::Wasmtime,
+		});
 
 		// Receive only two spawns.
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
@@ -631,7 +678,11 @@ mod tests {
 		assert_matches!(test.poll_and_recv_to_pool().await, pool::ToPool::StartWork { .. });
 
 		// Enqueue a critical job.
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Critical, pvf: pvf(4) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Critical,
+			pvf: pvf(4),
+			engine: Engine::Wasmtime,
+		});
 
 		// 2 out of 2 are working, but there is a critical job incoming. That means that spawning
 		// another worker is warranted.
@@ -642,14 +693,22 @@ mod tests {
 	async fn cull_unwanted() {
 		let mut test = Test::new(1, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			engine: Engine::Wasmtime,
+		});
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 		let w1 = test.workers.insert(());
 		test.send_from_pool(pool::FromPool::Spawned(w1));
 		assert_matches!(test.poll_and_recv_to_pool().await, pool::ToPool::StartWork { .. });
 
 		// Enqueue a critical job, which warrants spawning over the soft limit.
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Critical, pvf: pvf(2) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Critical,
+			pvf: pvf(2),
+			engine: Engine::Wasmtime,
+		});
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
 		// However, before the new worker had a chance to spawn, the first worker finishes with its
@@ -667,9 +726,21 @@ mod tests {
 	async fn worker_mass_die_out_doesnt_stall_queue() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(2) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(3) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			engine: Engine::Wasmtime,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(2),
+			engine: Engine::Wasmtime,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(3),
+			engine: Engine::Wasmtime,
+		});
 
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
@@ -689,14 +760,21 @@ mod tests {
 		// Since there is still work, the queue requested one extra worker to spawn to handle the
 		// remaining enqueued work items.
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
-		assert_eq!(test.poll_and_recv_from_queue().await.artifact_id, pvf(1).as_artifact_id());
+		assert_eq!(
+			test.poll_and_recv_from_queue().await.artifact_id,
+			pvf(1).as_artifact_id(Engine::Wasmtime)
+		);
 	}
 
 	#[async_std::test]
 	async fn doesnt_resurrect_ripped_worker_if_no_work() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			engine: Engine::Wasmtime,
+		});
 
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
@@ -717,7 +795,11 @@ mod tests {
 	async fn rip_for_start_work() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			engine: Engine::Wasmtime,
+		});
 
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
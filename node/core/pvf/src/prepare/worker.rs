@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::Lane;
 use crate::{
 	artifacts::CompiledArtifact,
 	error::{PrepareError, PrepareResult},
@@ -21,7 +22,7 @@ use crate::{
 		bytes_to_path, framed_recv, framed_send, path_to_bytes, spawn_with_program_path,
 		tmpfile_in, worker_event_loop, IdleWorker, SpawnErr, WorkerHandle,
 	},
-	LOG_TARGET,
+	Engine, LOG_TARGET,
 };
 use async_std::{
 	io,
@@ -32,18 +33,25 @@ use parity_scale_codec::{Decode, Encode};
 use sp_core::hexdisplay::HexDisplay;
 use std::{any::Any, panic, sync::Arc, time::Duration};
 
-/// The time period after which the preparation worker is considered unresponsive and will be killed.
-// NOTE: If you change this make sure to fix the buckets of `pvf_preparation_time` metric.
-const COMPILATION_TIMEOUT: Duration = Duration::from_secs(60);
-
 /// Spawns a new worker with the given program path that acts as the worker and the spawn timeout.
 ///
 /// The program should be able to handle `<program-path> prepare-worker <socket-path>` invocation.
+///
+/// If `sandbox_root` is specified, the worker only has access to the given directory.
 pub async fn spawn(
 	program_path: &Path,
 	spawn_timeout: Duration,
+	sandbox_root: Option<PathBuf>,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
-	spawn_with_program_path("prepare", program_path, &["prepare-worker"], spawn_timeout).await
+	spawn_with_program_path(
+		"prepare",
+		program_path,
+		&["prepare-worker"],
+		spawn_timeout,
+		sandbox_root,
+		None,
+	)
+	.await
 }
 
 pub enum Outcome {
@@ -63,24 +71,31 @@ pub enum Outcome {
 }
 
 /// Given the idle token of a worker and parameters of work, communicates with the worker and
-/// returns the outcome.
+/// returns the outcome. The code is prepared for the given `engine`.
+///
+/// The worker is considered unresponsive and the outcome is [`Outcome::TimedOut`] if it doesn't
+/// respond within the preparation timeout of the given lane.
 pub async fn start_work(
 	worker: IdleWorker,
 	code: Arc<Vec<u8>>,
+	engine: Engine,
 	cache_path: &Path,
 	artifact_path: PathBuf,
+	lane: Lane,
 ) -> Outcome {
 	let IdleWorker { mut stream, pid } = worker;
 
 	tracing::debug!(
 		target: LOG_TARGET,
 		worker_pid = %pid,
+		?lane,
+		?engine,
 		"starting prepare for {}",
 		artifact_path.display(),
 	);
 
 	with_tmp_file(pid, cache_path, |tmp_file| async move {
-		if let Err(err) = send_request(&mut stream, code, &tmp_file).await {
+		if let Err(err) = send_request(&mut stream, code, &tmp_file, lane, engine).await {
 			tracing::warn!(
 				target: LOG_TARGET,
 				worker_pid = %pid,
@@ -102,65 +117,65 @@ pub async fn start_work(
 			Deadline,
 		}
 
-		let selected =
-			match async_std::future::timeout(COMPILATION_TIMEOUT, framed_recv(&mut stream)).await {
-				Ok(Ok(response_bytes)) => {
-					// Received bytes from worker within the time limit.
-					// By convention we expect encoded `PrepareResult`.
-					if let Ok(result) = PrepareResult::decode(&mut response_bytes.as_slice()) {
-						if result.is_ok() {
-							tracing::debug!(
-								target: LOG_TARGET,
-								worker_pid = %pid,
-								"promoting WIP artifact {} to {}",
-								tmp_file.display(),
-								artifact_path.display(),
-							);
-
-							async_std::fs::rename(&tmp_file, &artifact_path)
-								.await
-								.map(|_| Selected::Done(result))
-								.unwrap_or_else(|err| {
-									tracing::warn!(
-										target: LOG_TARGET,
-										worker_pid = %pid,
-										"failed to rename the artifact from {} to {}: {:?}",
-										tmp_file.display(),
-										artifact_path.display(),
-										err,
-									);
-									Selected::IoErr
-								})
-						} else {
-							Selected::Done(result)
-						}
-					} else {
-						// We received invalid bytes from the worker.
-						let bound_bytes = &response_bytes[..response_bytes.len().min(4)];
-						tracing::warn!(
+		let timeout = lane.preparation_timeout();
+		let selected = match async_std::future::timeout(timeout, framed_recv(&mut stream)).await {
+			Ok(Ok(response_bytes)) => {
+				// Received bytes from worker within the time limit.
+				// By convention we expect encoded `PrepareResult`.
+				if let Ok(result) = PrepareResult::decode(&mut response_bytes.as_slice()) {
+					if result.is_ok() {
+						tracing::debug!(
 							target: LOG_TARGET,
 							worker_pid = %pid,
-							"received unexpected response from the prepare worker: {}",
-							HexDisplay::from(&bound_bytes),
+							"promoting WIP artifact {} to {}",
+							tmp_file.display(),
+							artifact_path.display(),
 						);
-						Selected::IoErr
+
+						async_std::fs::rename(&tmp_file, &artifact_path)
+							.await
+							.map(|_| Selected::Done(result))
+							.unwrap_or_else(|err| {
+								tracing::warn!(
+									target: LOG_TARGET,
+									worker_pid = %pid,
+									"failed to rename the artifact from {} to {}: {:?}",
+									tmp_file.display(),
+									artifact_path.display(),
+									err,
+								);
+								Selected::IoErr
+							})
+					} else {
+						Selected::Done(result)
 					}
-				},
-				Ok(Err(err)) => {
-					// Communication error within the time limit.
+				} else {
+					// We received invalid bytes from the worker.
+					let bound_bytes = &response_bytes[..response_bytes.len().min(4)];
 					tracing::warn!(
 						target: LOG_TARGET,
 						worker_pid = %pid,
-						"failed to recv a prepare response: {:?}",
-						err,
+						"received unexpected response from the prepare worker: {}",
+						HexDisplay::from(&bound_bytes),
 					);
 					Selected::IoErr
-				},
-				Err(_) => {
-					// Timed out.
-					Selected::Deadline
-				},
-			};
+				}
+			},
+			Ok(Err(err)) => {
+				// Communication error within the time limit.
+				tracing::warn!(
+					target: LOG_TARGET,
+					worker_pid = %pid,
+					"failed to recv a prepare response: {:?}",
+					err,
+				);
+				Selected::IoErr
+			},
+			Err(_) => {
+				// Timed out.
+				Selected::Deadline
+			},
+		};
 
 		match selected {
 			Selected::Done(result) =>
@@ -221,13 +236,16 @@ async fn send_request(
 	stream: &mut UnixStream,
 	code: Arc<Vec<u8>>,
 	tmp_file: &Path,
+	lane: Lane,
+	engine: Engine,
 ) -> io::Result<()> {
 	framed_send(stream, &*code).await?;
 	framed_send(stream, path_to_bytes(tmp_file)).await?;
+	framed_send(stream, &(lane, engine).encode()).await?;
 	Ok(())
 }
 
-async fn recv_request(stream: &mut UnixStream) -> io::Result<(Vec<u8>, PathBuf)> {
+async fn recv_request(stream: &mut UnixStream) -> io::Result<(Vec<u8>, PathBuf, Lane, Engine)> {
 	let code = framed_recv(stream).await?;
 	let tmp_file = framed_recv(stream).await?;
 	let tmp_file = bytes_to_path(&tmp_file).ok_or_else(|| {
@@ -236,7 +254,14 @@ async fn recv_request(stream: &mut UnixStream) -> io::Result<(Vec<u8>, PathBuf)>
 			"prepare pvf recv_request: non utf-8 artifact path".to_string(),
 		)
 	})?;
-	Ok((code, tmp_file))
+	let lane_and_engine = framed_recv(stream).await?;
+	let (lane, engine) = <(Lane, Engine)>::decode(&mut &lane_and_engine[..]).map_err(|_| {
+		io::Error::new(
+			io::ErrorKind::Other,
+			"prepare pvf recv_request: failed to decode the lane and the engine".to_string(),
+		)
+	})?;
+	Ok((code, tmp_file, lane, engine))
 }
 
 /// The entrypoint that the spawned prepare worker should start with. The `socket_path` specifies
@@ -244,15 +269,17 @@ async fn recv_request(stream: &mut UnixStream) -> io::Result<(Vec<u8>, PathBuf)>
 pub fn worker_entrypoint(socket_path: &str) {
 	worker_event_loop("prepare", socket_path, |mut stream| async move {
 		loop {
-			let (code, dest) = recv_request(&mut stream).await?;
+			let (code, dest, lane, engine) = recv_request(&mut stream).await?;
 
 			tracing::debug!(
 				target: LOG_TARGET,
 				worker_pid = %std::process::id(),
+				?lane,
+				?engine,
 				"worker: preparing artifact",
 			);
 
-			let result = match prepare_artifact(&code) {
+			let result = match prepare_artifact(&code, lane.parallel_compilation(), engine) {
 				Err(err) => {
 					// Serialized error will be written into the socket.
 					Err(err)
@@ -284,14 +311,23 @@ pub fn worker_entrypoint(socket_path: &str) {
 	});
 }
 
-fn prepare_artifact(code: &[u8]) -> Result<CompiledArtifact, PrepareError> {
+fn prepare_artifact(
+	code: &[u8],
+	parallel_compilation: bool,
+	engine: Engine,
+) -> Result<CompiledArtifact, PrepareError> {
 	panic::catch_unwind(|| {
 		let blob = match crate::executor_intf::prevalidate(code) {
 			Err(err) => return Err(PrepareError::Prevalidation(format!("{:?}", err))),
 			Ok(b) => b,
 		};
 
-		match crate::executor_intf::prepare(blob) {
+		let prepared = match engine {
+			Engine::Wasmtime => crate::executor_intf::prepare(blob, parallel_compilation),
+			Engine::Interpreter => crate::executor_intf::prepare_interpreted(blob),
+		};
+
+		match prepared {
 			Ok(compiled_artifact) => Ok(CompiledArtifact::new(compiled_artifact)),
 			Err(err) => Err(PrepareError::Preparation(format!("{:?}", err))),
 		}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::artifacts::ArtifactId;
+use crate::{artifacts::ArtifactId, engine::Engine};
 use polkadot_parachain::primitives::ValidationCodeHash;
 use sp_core::blake2_256;
 use std::{fmt, sync::Arc};
@@ -49,8 +49,8 @@ impl Pvf {
 		Pvf::from_code(descriminator_buf)
 	}
 
-	/// Returns the artifact ID that corresponds to this PVF.
-	pub(crate) fn as_artifact_id(&self) -> ArtifactId {
-		ArtifactId::new(self.code_hash)
+	/// Returns the ID of the artifact of this PVF prepared for the given engine.
+	pub(crate) fn as_artifact_id(&self, engine: Engine) -> ArtifactId {
+		ArtifactId::new(self.code_hash, engine)
 	}
 }
@@ -0,0 +1,463 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Opt-in sandboxing of the worker processes.
+//!
+//! The workers handle untrusted PVF code, so an exploit in the compiler or in the executor would
+//! give the attacker the privileges of the worker. With the sandbox enabled, the exposure is
+//! limited as follows:
+//!
+//! 1. Between `fork` and `exec` the child process is moved into fresh user, mount and network
+//!    namespaces (see [`configure_command`]). This step has to happen while the process is still
+//!    single-threaded.
+//!
+//! 2. Once the worker has connected to the host, it replaces its filesystem root with an empty
+//!    `tmpfs` that only contains the artifact cache, bind-mounted at its original path, and then
+//!    installs a seccomp filter that only allows the system calls required for compiling and
+//!    executing PVFs (see [`enter`]). Since the host communicates with the worker through an
+//!    already connected socket, neither the network nor the rest of the filesystem are needed.
+//!
+//! The sandbox is only supported on Linux running on `x86_64` and `aarch64`.
+
+use async_std::{io, path::Path};
+
+/// Checks whether the running kernel supports the facilities required by the sandbox.
+///
+/// Returns a human readable description of the problem otherwise.
+pub fn check_support() -> Result<(), String> {
+	imp::check_support()
+}
+
+/// Configures the given command so that the spawned process is put into the fresh namespaces.
+pub(crate) fn configure_command(command: &mut async_process::Command) -> io::Result<()> {
+	imp::configure_command(command)
+}
+
+/// Restricts the calling worker to the given directory and installs the seccomp filter.
+///
+/// Must be called by a worker process spawned with a command configured by
+/// [`configure_command`].
+pub(crate) fn enter(cache_path: &Path) -> io::Result<()> {
+	imp::enter(cache_path)
+}
+
+#[cfg(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod imp {
+	use async_std::{io, path::Path};
+	use std::{ffi::CString, os::unix::ffi::OsStrExt as _};
+
+	/// The name of the directory the old root is moved to during `pivot_root`.
+	const OLD_ROOT: &str = ".old-root";
+
+	pub fn check_support() -> Result<(), String> {
+		for namespace in &["user", "mnt", "net"] {
+			if !std::path::Path::new("/proc/self/ns").join(namespace).exists() {
+				return Err(format!("the kernel does not support {} namespaces", namespace))
+			}
+		}
+
+		// Debian-based kernels allow to disable unprivileged user namespaces altogether.
+		if let Ok(value) = std::fs::read_to_string("/proc/sys/kernel/unprivileged_userns_clone") {
+			if value.trim() == "0" {
+				return Err("unprivileged user namespaces are disabled by \
+					`kernel.unprivileged_userns_clone`"
+					.to_string())
+			}
+		}
+
+		if let Ok(value) = std::fs::read_to_string("/proc/sys/user/max_user_namespaces") {
+			if value.trim() == "0" {
+				return Err("user namespaces are disabled by `user.max_user_namespaces`".to_string())
+			}
+		}
+
+		match std::fs::read_to_string("/proc/sys/kernel/seccomp/actions_avail") {
+			Ok(actions) if actions.split_whitespace().any(|action| action == "errno") => Ok(()),
+			Ok(_) => Err("the kernel does not support the seccomp errno action".to_string()),
+			Err(_) => Err("the kernel does not support seccomp filters".to_string()),
+		}
+	}
+
+	pub fn configure_command(command: &mut async_process::Command) -> io::Result<()> {
+		use async_process::unix::CommandExt as _;
+
+		// Map the current user to root inside the new user namespace. Only root in the namespace
+		// holds the capabilities required to change the mount table in [`enter`], which happens
+		// after `exec`. The files created by the worker are still owned by the user running the
+		// node outside of the namespace.
+		//
+		// Everything is prepared here because the `pre_exec` closure must not allocate.
+		let uid_map = format!("0 {} 1\n", unsafe { libc::geteuid() });
+		let gid_map = format!("0 {} 1\n", unsafe { libc::getegid() });
+
+		let pre_exec = move || {
+			// SAFETY: the closure is executed in the forked child and only performs system calls.
+			unsafe {
+				if libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNS | libc::CLONE_NEWNET) != 0
+				{
+					return Err(io::Error::last_os_error())
+				}
+				write_proc_file(b"/proc/self/setgroups\0", b"deny")?;
+				write_proc_file(b"/proc/self/uid_map\0", uid_map.as_bytes())?;
+				write_proc_file(b"/proc/self/gid_map\0", gid_map.as_bytes())?;
+			}
+			Ok(())
+		};
+
+		// SAFETY: the closure doesn't allocate and doesn't access any state shared with the
+		//         parent process.
+		unsafe {
+			command.pre_exec(pre_exec);
+		}
+
+		Ok(())
+	}
+
+	/// Writes the given contents into the file at the given nul-terminated path.
+	///
+	/// # Safety
+	///
+	/// The path must be nul-terminated.
+	unsafe fn write_proc_file(path: &[u8], contents: &[u8]) -> io::Result<()> {
+		let fd = libc::open(path.as_ptr() as *const libc::c_char, libc::O_WRONLY | libc::O_CLOEXEC);
+		if fd < 0 {
+			return Err(io::Error::last_os_error())
+		}
+		let written = libc::write(fd, contents.as_ptr() as *const libc::c_void, contents.len());
+		libc::close(fd);
+		if written != contents.len() as isize {
+			return Err(io::Error::last_os_error())
+		}
+		Ok(())
+	}
+
+	pub fn enter(cache_path: &Path) -> io::Result<()> {
+		if !cache_path.is_absolute() {
+			return Err(other_err("the sandbox requires an absolute artifact cache path"))
+		}
+
+		restrict_filesystem(cache_path)?;
+		install_seccomp_filter()
+	}
+
+	/// Replaces the root of the filesystem with a `tmpfs` that contains only the given directory
+	/// mounted at the same path.
+	fn restrict_filesystem(cache_path: &Path) -> io::Result<()> {
+		let cache_path_c = to_cstring(cache_path)?;
+
+		// SAFETY: all pointers passed to the system calls below point to nul-terminated strings
+		//         that outlive the calls.
+		unsafe {
+			// Make sure that none of the changes below propagates to the host's mount namespace.
+			check(libc::mount(
+				std::ptr::null(),
+				b"/\0".as_ptr() as *const libc::c_char,
+				std::ptr::null(),
+				libc::MS_REC | libc::MS_PRIVATE,
+				std::ptr::null(),
+			))?;
+
+			// Keep a handle to the cache directory, since it is going to be shadowed.
+			let cache_fd = libc::open(
+				cache_path_c.as_ptr(),
+				libc::O_PATH | libc::O_DIRECTORY | libc::O_CLOEXEC,
+			);
+			if cache_fd < 0 {
+				return Err(io::Error::last_os_error())
+			}
+			let cache_fd_path = CString::new(format!("/proc/self/fd/{}", cache_fd))
+				.expect("the formatted string doesn't contain nul bytes; qed");
+
+			// The empty `tmpfs` mounted on top of the cache directory becomes the new root.
+			check(libc::mount(
+				b"tmpfs\0".as_ptr() as *const libc::c_char,
+				cache_path_c.as_ptr(),
+				b"tmpfs\0".as_ptr() as *const libc::c_char,
+				libc::MS_NOSUID | libc::MS_NODEV,
+				b"mode=0755\0".as_ptr() as *const libc::c_void,
+			))?;
+
+			// Recreate the path to the cache directory inside the new root and bind the real cache
+			// directory there, so that the paths sent by the host stay valid.
+			let relative_cache_path = cache_path
+				.strip_prefix("/")
+				.map_err(|_| other_err("the cache path is not absolute"))?;
+			let inner_cache_path = cache_path.join(relative_cache_path);
+			std::fs::create_dir_all(&inner_cache_path)?;
+			let inner_cache_path_c = to_cstring(&inner_cache_path)?;
+			let bind_result = libc::mount(
+				cache_fd_path.as_ptr(),
+				inner_cache_path_c.as_ptr(),
+				std::ptr::null(),
+				libc::MS_BIND | libc::MS_REC,
+				std::ptr::null(),
+			);
+			libc::close(cache_fd);
+			check(bind_result)?;
+
+			let old_root = cache_path.join(OLD_ROOT);
+			std::fs::create_dir(&old_root)?;
+			let old_root_c = to_cstring(&old_root)?;
+			check(libc::syscall(libc::SYS_pivot_root, cache_path_c.as_ptr(), old_root_c.as_ptr())
+				as libc::c_int)?;
+			check(libc::chdir(b"/\0".as_ptr() as *const libc::c_char))?;
+
+			let old_root_c = CString::new(format!("/{}", OLD_ROOT))
+				.expect("the formatted string doesn't contain nul bytes; qed");
+			check(libc::umount2(old_root_c.as_ptr(), libc::MNT_DETACH))?;
+			check(libc::rmdir(old_root_c.as_ptr()))?;
+		}
+
+		Ok(())
+	}
+
+	// The subset of the classic BPF used by seccomp. See `linux/filter.h` and `linux/seccomp.h`.
+
+	#[repr(C)]
+	struct SockFilter {
+		code: u16,
+		jt: u8,
+		jf: u8,
+		k: u32,
+	}
+
+	#[repr(C)]
+	struct SockFprog {
+		len: libc::c_ushort,
+		filter: *const SockFilter,
+	}
+
+	const BPF_LD_W_ABS: u16 = 0x00 | 0x00 | 0x20;
+	const BPF_JMP_JEQ_K: u16 = 0x05 | 0x10 | 0x00;
+	const BPF_JMP_JSET_K: u16 = 0x05 | 0x40 | 0x00;
+	const BPF_RET_K: u16 = 0x06 | 0x00;
+
+	const SECCOMP_SET_MODE_FILTER: libc::c_ulong = 1;
+	const SECCOMP_FILTER_FLAG_TSYNC: libc::c_ulong = 1;
+	const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+	const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+	const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+
+	/// Offsets of the fields of `struct seccomp_data`.
+	const SECCOMP_DATA_NR_OFFSET: u32 = 0;
+	const SECCOMP_DATA_ARCH_OFFSET: u32 = 4;
+	/// The lower half of the first argument. Both supported architectures are little-endian.
+	const SECCOMP_DATA_ARG0_OFFSET: u32 = 16;
+
+	#[cfg(target_arch = "x86_64")]
+	const AUDIT_ARCH: u32 = 0xc000_003e;
+	#[cfg(target_arch = "aarch64")]
+	const AUDIT_ARCH: u32 = 0xc000_00b7;
+
+	/// The system calls the workers are allowed to make after entering the sandbox.
+	///
+	/// Notably, this list doesn't contain anything that can be used to create new processes,
+	/// open sockets or change the mount table. `clone` is handled separately by
+	/// [`install_seccomp_filter`], since it is only allowed for spawning threads.
+	const ALLOWED_SYSCALLS: &[libc::c_long] = &[
+		// Files and the socket connected to the host.
+		libc::SYS_read,
+		libc::SYS_write,
+		libc::SYS_readv,
+		libc::SYS_writev,
+		libc::SYS_pread64,
+		libc::SYS_pwrite64,
+		libc::SYS_openat,
+		libc::SYS_close,
+		libc::SYS_fstat,
+		libc::SYS_newfstatat,
+		libc::SYS_statx,
+		libc::SYS_lseek,
+		libc::SYS_fcntl,
+		libc::SYS_ioctl,
+		libc::SYS_fsync,
+		libc::SYS_fdatasync,
+		libc::SYS_ftruncate,
+		libc::SYS_getcwd,
+		libc::SYS_recvfrom,
+		libc::SYS_recvmsg,
+		libc::SYS_sendto,
+		libc::SYS_sendmsg,
+		libc::SYS_shutdown,
+		// Event loop.
+		libc::SYS_epoll_create1,
+		libc::SYS_epoll_ctl,
+		libc::SYS_epoll_pwait,
+		libc::SYS_eventfd2,
+		libc::SYS_pipe2,
+		libc::SYS_ppoll,
+		// Memory management.
+		libc::SYS_mmap,
+		libc::SYS_mprotect,
+		libc::SYS_munmap,
+		libc::SYS_mremap,
+		libc::SYS_madvise,
+		libc::SYS_brk,
+		libc::SYS_membarrier,
+		// Signals, used by wasmtime to handle traps.
+		libc::SYS_rt_sigaction,
+		libc::SYS_rt_sigprocmask,
+		libc::SYS_rt_sigreturn,
+		libc::SYS_sigaltstack,
+		libc::SYS_tgkill,
+		// Threads.
+		libc::SYS_futex,
+		libc::SYS_set_robust_list,
+		libc::SYS_set_tid_address,
+		libc::SYS_rseq,
+		libc::SYS_prctl,
+		libc::SYS_sched_yield,
+		libc::SYS_sched_getaffinity,
+		libc::SYS_getpid,
+		libc::SYS_gettid,
+		libc::SYS_exit,
+		libc::SYS_exit_group,
+		libc::SYS_restart_syscall,
+		// Time and randomness.
+		libc::SYS_clock_gettime,
+		libc::SYS_clock_getres,
+		libc::SYS_clock_nanosleep,
+		libc::SYS_nanosleep,
+		libc::SYS_gettimeofday,
+		libc::SYS_getrandom,
+		// Resource usage.
+		libc::SYS_getrusage,
+		libc::SYS_prlimit64,
+		#[cfg(target_arch = "x86_64")]
+		libc::SYS_open,
+		#[cfg(target_arch = "x86_64")]
+		libc::SYS_stat,
+		#[cfg(target_arch = "x86_64")]
+		libc::SYS_lstat,
+		#[cfg(target_arch = "x86_64")]
+		libc::SYS_access,
+		#[cfg(target_arch = "x86_64")]
+		libc::SYS_poll,
+		#[cfg(target_arch = "x86_64")]
+		libc::SYS_epoll_wait,
+		#[cfg(target_arch = "x86_64")]
+		libc::SYS_arch_prctl,
+	];
+
+	/// Installs a seccomp filter that allows only [`ALLOWED_SYSCALLS`] for all threads of the
+	/// process.
+	///
+	/// `clone` is only allowed with `CLONE_THREAD`, so that the worker can't create new processes.
+	/// `clone3` can't be filtered that way, because its flags are passed in memory. It fails with
+	/// `ENOSYS`, which makes the libc fall back to `clone`.
+	///
+	/// Other system calls fail with `EACCES` rather than killing the worker. This way a missing
+	/// entry leads to an error reported by the worker instead of an ambiguous worker death, which
+	/// would be attributed to the candidate.
+	fn install_seccomp_filter() -> io::Result<()> {
+		let mut filter = Vec::with_capacity(ALLOWED_SYSCALLS.len() * 2 + 12);
+
+		// Kill the process if the system call is made using a foreign calling convention.
+		filter.push(SockFilter { code: BPF_LD_W_ABS, jt: 0, jf: 0, k: SECCOMP_DATA_ARCH_OFFSET });
+		filter.push(SockFilter { code: BPF_JMP_JEQ_K, jt: 1, jf: 0, k: AUDIT_ARCH });
+		filter.push(SockFilter { code: BPF_RET_K, jt: 0, jf: 0, k: SECCOMP_RET_KILL_PROCESS });
+
+		filter.push(SockFilter { code: BPF_LD_W_ABS, jt: 0, jf: 0, k: SECCOMP_DATA_NR_OFFSET });
+
+		filter.push(SockFilter { code: BPF_JMP_JEQ_K, jt: 0, jf: 1, k: libc::SYS_clone3 as u32 });
+		filter.push(SockFilter {
+			code: BPF_RET_K,
+			jt: 0,
+			jf: 0,
+			k: SECCOMP_RET_ERRNO | libc::ENOSYS as u32,
+		});
+
+		// Skip the checks of the flags for any other system call, keeping the number loaded.
+		filter.push(SockFilter { code: BPF_JMP_JEQ_K, jt: 0, jf: 4, k: libc::SYS_clone as u32 });
+		filter.push(SockFilter { code: BPF_LD_W_ABS, jt: 0, jf: 0, k: SECCOMP_DATA_ARG0_OFFSET });
+		filter.push(SockFilter {
+			code: BPF_JMP_JSET_K,
+			jt: 0,
+			jf: 1,
+			k: libc::CLONE_THREAD as u32,
+		});
+		filter.push(SockFilter { code: BPF_RET_K, jt: 0, jf: 0, k: SECCOMP_RET_ALLOW });
+		filter.push(SockFilter {
+			code: BPF_RET_K,
+			jt: 0,
+			jf: 0,
+			k: SECCOMP_RET_ERRNO | libc::EACCES as u32,
+		});
+
+		for syscall in ALLOWED_SYSCALLS {
+			filter.push(SockFilter { code: BPF_JMP_JEQ_K, jt: 0, jf: 1, k: *syscall as u32 });
+			filter.push(SockFilter { code: BPF_RET_K, jt: 0, jf: 0, k: SECCOMP_RET_ALLOW });
+		}
+		filter.push(SockFilter {
+			code: BPF_RET_K,
+			jt: 0,
+			jf: 0,
+			k: SECCOMP_RET_ERRNO | libc::EACCES as u32,
+		});
+
+		let program = SockFprog { len: filter.len() as libc::c_ushort, filter: filter.as_ptr() };
+
+		// SAFETY: `program` points to the filter which is alive for the duration of the calls.
+		unsafe {
+			check(libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0))?;
+			check(libc::syscall(
+				libc::SYS_seccomp,
+				SECCOMP_SET_MODE_FILTER,
+				SECCOMP_FILTER_FLAG_TSYNC,
+				&program as *const SockFprog,
+			) as libc::c_int)?;
+		}
+
+		Ok(())
+	}
+
+	fn to_cstring(path: &Path) -> io::Result<CString> {
+		CString::new(path.as_os_str().as_bytes())
+			.map_err(|_| other_err("the path contains a nul byte"))
+	}
+
+	fn check(result: libc::c_int) -> io::Result<()> {
+		if result != 0 {
+			Err(io::Error::last_os_error())
+		} else {
+			Ok(())
+		}
+	}
+
+	fn other_err(msg: &str) -> io::Error {
+		io::Error::new(io::ErrorKind::Other, msg.to_string())
+	}
+}
+
+#[cfg(not(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64"))))]
+mod imp {
+	use async_std::{io, path::Path};
+
+	const UNSUPPORTED: &str =
+		"the PVF worker sandbox is only supported on Linux x86_64 and aarch64";
+
+	pub fn check_support() -> Result<(), String> {
+		Err(UNSUPPORTED.to_string())
+	}
+
+	pub fn configure_command(_command: &mut async_process::Command) -> io::Result<()> {
+		Err(io::Error::new(io::ErrorKind::Other, UNSUPPORTED))
+	}
+
+	pub fn enter(_cache_path: &Path) -> io::Result<()> {
+		Err(io::Error::new(io::ErrorKind::Other, UNSUPPORTED))
+	}
+}
@@ -35,7 +35,7 @@ pub fn validate_candidate(
 		.expect("Decompressing code failed");
 
 	let blob = prevalidate(&*code)?;
-	let artifact = prepare(blob)?;
+	let artifact = prepare(blob, false)?;
 	let executor = TaskExecutor::new()?;
 	let result = unsafe {
 		// SAFETY: This is trivially safe since the artifact is obtained by calling `prepare`.
@@ -16,7 +16,7 @@
 
 //! Common logic for implementation of worker processes.
 
-use crate::LOG_TARGET;
+use crate::{sandbox, LOG_TARGET};
 use async_std::{
 	io,
 	os::unix::net::{UnixListener, UnixStream},
@@ -26,6 +26,7 @@ use futures::{
 	never::Never, AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _, FutureExt as _,
 };
 use futures_timer::Delay;
+use parity_scale_codec::{Decode, Encode};
 use pin_project::pin_project;
 use rand::Rng;
 use std::{
@@ -35,6 +36,22 @@ use std::{
 	time::Duration,
 };
 
+/// The first message the host sends to a freshly spawned worker.
+#[derive(Encode, Decode)]
+struct Handshake {
+	/// The directory the worker should be restricted to, if the worker is sandboxed. See
+	/// [`sandbox`].
+	sandbox_root: Option<String>,
+	/// The limit of the address space of the worker process in bytes, if any.
+	address_space_limit: Option<u64>,
+}
+
+/// Spawns a worker and waits until it connects to the host.
+///
+/// If `sandbox_root` is specified, the worker is spawned in the [sandbox][`sandbox`] and will only
+/// have access to the given directory. If `address_space_limit` is specified, the worker limits its
+/// address space to the given number of bytes.
+///
 /// This is publicly exposed only for integration tests.
 #[doc(hidden)]
 pub async fn spawn_with_program_path(
@@ -42,6 +59,8 @@ pub async fn spawn_with_program_path(
 	program_path: impl Into<PathBuf>,
 	extra_args: &'static [&'static str],
 	spawn_timeout: Duration,
+	sandbox_root: Option<PathBuf>,
+	address_space_limit: Option<u64>,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
 	let program_path = program_path.into();
 	with_transient_socket_path(debug_id, |socket_path| {
@@ -58,19 +77,20 @@ pub async fn spawn_with_program_path(
 			})?;
 
 			let handle =
-				WorkerHandle::spawn(program_path, extra_args, socket_path).map_err(|err| {
-					tracing::warn!(
-						target: LOG_TARGET,
-						%debug_id,
-						"cannot spawn a worker: {:?}",
-						err,
-					);
-					SpawnErr::ProcessSpawn
-				})?;
+				WorkerHandle::spawn(program_path, extra_args, socket_path, sandbox_root.is_some())
+					.map_err(|err| {
+						tracing::warn!(
+							target: LOG_TARGET,
+							%debug_id,
+							"cannot spawn a worker: {:?}",
+							err,
+						);
+						SpawnErr::ProcessSpawn
+					})?;
 
 			futures::select! {
 				accept_result = listener.accept().fuse() => {
-					let (stream, _) = accept_result.map_err(|err| {
+					let (mut stream, _) = accept_result.map_err(|err| {
 						tracing::warn!(
 							target: LOG_TARGET,
 							%debug_id,
@@ -79,6 +99,21 @@ pub async fn spawn_with_program_path(
 						);
 						SpawnErr::Accept
 					})?;
+
+					let handshake = Handshake {
+						sandbox_root: sandbox_root.map(|root| root.to_string_lossy().into_owned()),
+						address_space_limit,
+					};
+					framed_send(&mut stream, &handshake.encode()).await.map_err(|err| {
+						tracing::warn!(
+							target: LOG_TARGET,
+							%debug_id,
+							"cannot send the handshake to a worker: {:?}",
+							err,
+						);
+						SpawnErr::Handshake
+					})?;
+
 					Ok((IdleWorker { stream, pid: handle.id() }, handle))
 				}
 				_ = Delay::new(spawn_timeout).fuse() => {
@@ -153,9 +188,28 @@ where
 	Fut: futures::Future<Output = io::Result<Never>>,
 {
 	let err = async_std::task::block_on::<_, io::Result<Never>>(async move {
-		let stream = UnixStream::connect(socket_path).await?;
+		let mut stream = UnixStream::connect(socket_path).await?;
 		let _ = async_std::fs::remove_file(socket_path).await;
 
+		let handshake = framed_recv(&mut stream).await?;
+		let handshake = Handshake::decode(&mut handshake.as_slice()).map_err(|e| {
+			io::Error::new(io::ErrorKind::Other, format!("handshake decode error: {:?}", e))
+		})?;
+		if let Some(limit) = handshake.address_space_limit {
+			set_address_space_limit(limit)?;
+		}
+		if let Some(sandbox_root) = handshake.sandbox_root {
+			sandbox::enter(Path::new(&sandbox_root)).map_err(|e| {
+				io::Error::new(io::ErrorKind::Other, format!("cannot enter the sandbox: {}", e))
+			})?;
+			tracing::debug!(
+				target: LOG_TARGET,
+				worker_pid = %std::process::id(),
+				"pvf worker ({}): entered the sandbox",
+				debug_id,
+			);
+		}
+
 		event_loop(stream).await
 	})
 	.unwrap_err(); // it's never `Ok` because it's `Ok(Never)`
@@ -169,6 +223,16 @@ where
 	);
 }
 
+/// Limits the address space of the current process to the given number of bytes.
+fn set_address_space_limit(limit: u64) -> io::Result<()> {
+	let rlimit = libc::rlimit { rlim_cur: limit as libc::rlim_t, rlim_max: limit as libc::rlim_t };
+	// SAFETY: `setrlimit` only reads the provided struct.
+	if unsafe { libc::setrlimit(libc::RLIMIT_AS, &rlimit) } != 0 {
+		return Err(io::Error::last_os_error())
+	}
+	Ok(())
+}
+
 /// A struct that represents an idle worker.
 ///
 /// This struct is supposed to be used as a token that is passed by move into a subroutine that
@@ -195,6 +259,8 @@ pub enum SpawnErr {
 	ProcessSpawn,
 	/// The deadline allotted for the worker spawning and connecting to the socket has elapsed.
 	AcceptTimeout,
+	/// An error happened during sending the handshake to the worker.
+	Handshake,
 }
 
 /// This is a representation of a potentially running worker. Drop it and the process will be killed.
@@ -217,13 +283,18 @@ impl WorkerHandle {
 		program: impl AsRef<Path>,
 		extra_args: &[&str],
 		socket_path: impl AsRef<Path>,
+		sandboxed: bool,
 	) -> io::Result<Self> {
-		let mut child = async_process::Command::new(program.as_ref())
+		let mut command = async_process::Command::new(program.as_ref());
+		command
 			.args(extra_args)
 			.arg(socket_path.as_ref().as_os_str())
 			.stdout(async_process::Stdio::piped())
-			.kill_on_drop(true)
-			.spawn()?;
+			.kill_on_drop(true);
+		if sandboxed {
+			sandbox::configure_command(&mut command)?;
+		}
+		let mut child = command.spawn()?;
 
 		let stdout = child
 			.stdout
@@ -112,3 +112,40 @@ async fn execute_bad_on_parent() {
 		.await
 		.unwrap_err();
 }
+
+// Requires unprivileged user namespaces and seccomp, which are not available in every CI
+// environment. Run with `cargo test -- --ignored` on a host that supports them.
+#[async_std::test]
+#[ignore]
+async fn execute_good_on_parent_sandboxed() {
+	if let Err(reason) = polkadot_node_core_pvf::check_sandbox_support() {
+		panic!("the sandbox is not supported: {}", reason);
+	}
+
+	let parent_head = HeadData { number: 0, parent_hash: [0; 32], post_state: hash_state(0) };
+
+	let block_data = BlockData { state: 0, add: 512 };
+
+	let host = TestHost::new_with_config(|cfg| {
+		cfg.enable_sandbox = true;
+	});
+
+	let ret = host
+		.validate_candidate(
+			adder::wasm_binary_unwrap(),
+			ValidationParams {
+				parent_head: GenericHeadData(parent_head.encode()),
+				block_data: GenericBlockData(block_data.encode()),
+				relay_parent_number: 1,
+				relay_parent_storage_root: Default::default(),
+			},
+		)
+		.await
+		.unwrap();
+
+	let new_head = HeadData::decode(&mut &ret.head_data.0[..]).unwrap();
+
+	assert_eq!(new_head.number, 1);
+	assert_eq!(new_head.parent_hash, parent_head.hash());
+	assert_eq!(new_head.post_state, hash_state(512));
+}
@@ -69,6 +69,7 @@ impl TestHost {
 				TEST_EXECUTION_TIMEOUT,
 				params.encode(),
 				polkadot_node_core_pvf::Priority::Normal,
+				polkadot_node_core_pvf::Engine::Wasmtime,
 				result_tx,
 			)
 			.await
@@ -94,7 +95,7 @@ async fn terminates_on_timeout() {
 		.await;
 
 	match result {
-		Err(ValidationError::InvalidCandidate(InvalidCandidate::HardTimeout)) => {},
+		Err(ValidationError::InvalidCandidate(InvalidCandidate::CpuTimeExceeded(_))) => {},
 		r => panic!("{:?}", r),
 	}
 }
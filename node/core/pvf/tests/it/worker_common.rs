@@ -20,9 +20,15 @@ use std::time::Duration;
 
 #[async_std::test]
 async fn spawn_timeout() {
-	let result =
-		spawn_with_program_path("integration-test", PUPPET_EXE, &["sleep"], Duration::from_secs(2))
-			.await;
+	let result = spawn_with_program_path(
+		"integration-test",
+		PUPPET_EXE,
+		&["sleep"],
+		Duration::from_secs(2),
+		None,
+		None,
+	)
+	.await;
 	assert!(matches!(result, Err(SpawnErr::AcceptTimeout)));
 }
 
@@ -33,6 +39,8 @@ async fn should_connect() {
 		PUPPET_EXE,
 		&["prepare-worker"],
 		Duration::from_secs(2),
+		None,
+		None,
 	)
 	.await
 	.unwrap();
@@ -49,6 +49,7 @@ const CURRENT_BABE_EPOCH_CACHE_SIZE: usize = 64 * 1024;
 const ON_CHAIN_VOTES_CACHE_SIZE: usize = 3 * 1024;
 const PVFS_REQUIRE_PRECHECK_SIZE: usize = 1024;
 const VALIDATION_CODE_HASH_CACHE_SIZE: usize = 64 * 1024;
+const AVAILABILITY_CHUNK_SHUFFLING_CACHE_SIZE: usize = 64 * 1024;
 
 struct ResidentSizeOf<T>(T);
 
@@ -116,6 +117,7 @@ pub(crate) struct RequestResultCache {
 		(Hash, ParaId, OccupiedCoreAssumption),
 		ResidentSizeOf<Option<ValidationCodeHash>>,
 	>,
+	availability_chunk_shuffling: MemoryLruCache<Hash, ResidentSizeOf<bool>>,
 }
 
 impl Default for RequestResultCache {
@@ -142,6 +144,9 @@ impl Default for RequestResultCache {
 			on_chain_votes: MemoryLruCache::new(ON_CHAIN_VOTES_CACHE_SIZE),
 			pvfs_require_precheck: MemoryLruCache::new(PVFS_REQUIRE_PRECHECK_SIZE),
 			validation_code_hash: MemoryLruCache::new(VALIDATION_CODE_HASH_CACHE_SIZE),
+			availability_chunk_shuffling: MemoryLruCache::new(
+				AVAILABILITY_CHUNK_SHUFFLING_CACHE_SIZE,
+			),
 		}
 	}
 }
@@ -399,6 +404,14 @@ impl RequestResultCache {
 	) {
 		self.validation_code_hash.insert(key, ResidentSizeOf(value));
 	}
+
+	pub(crate) fn availability_chunk_shuffling(&mut self, relay_parent: &Hash) -> Option<&bool> {
+		self.availability_chunk_shuffling.get(relay_parent).map(|v| &v.0)
+	}
+
+	pub(crate) fn cache_availability_chunk_shuffling(&mut self, relay_parent: Hash, value: bool) {
+		self.availability_chunk_shuffling.insert(relay_parent, ResidentSizeOf(value));
+	}
 }
 
 pub(crate) enum RequestResult {
@@ -433,4 +446,5 @@ pub(crate) enum RequestResult {
 	// This is a request with side-effects and no result, hence ().
 	SubmitPvfCheckStatement(Hash, PvfCheckStatement, ValidatorSignature, ()),
 	ValidationCodeHash(Hash, ParaId, OccupiedCoreAssumption, Option<ValidationCodeHash>),
+	AvailabilityChunkShuffling(Hash, bool),
 }
@@ -165,6 +165,8 @@ where
 			ValidationCodeHash(relay_parent, para_id, assumption, hash) => self
 				.requests_cache
 				.cache_validation_code_hash((relay_parent, para_id, assumption), hash),
+			AvailabilityChunkShuffling(relay_parent, shuffling) =>
+				self.requests_cache.cache_availability_chunk_shuffling(relay_parent, shuffling),
 		}
 	}
 
@@ -264,6 +266,9 @@ where
 			Request::ValidationCodeHash(para, assumption, sender) =>
 				query!(validation_code_hash(para, assumption), sender)
 					.map(|sender| Request::ValidationCodeHash(para, assumption, sender)),
+			Request::AvailabilityChunkShuffling(sender) =>
+				query!(availability_chunk_shuffling(), sender)
+					.map(|sender| Request::AvailabilityChunkShuffling(sender)),
 		}
 	}
 
@@ -503,6 +508,8 @@ where
 		},
 		Request::ValidationCodeHash(para, assumption, sender) =>
 			query!(ValidationCodeHash, validation_code_hash(para, assumption), ver = 2, sender),
+		Request::AvailabilityChunkShuffling(sender) =>
+			query!(AvailabilityChunkShuffling, availability_chunk_shuffling(), ver = 3, sender),
 	}
 }
 
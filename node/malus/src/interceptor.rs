@@ -51,6 +51,30 @@ where
 	}
 }
 
+/// Chain two filters of the same subsystem, applying the first one before the second one.
+impl<Sender, A, B> MessageInterceptor<Sender> for (A, B)
+where
+	Sender: overseer::SubsystemSender<<A as MessageInterceptor<Sender>>::Message> + Clone + 'static,
+	A: MessageInterceptor<Sender>,
+	B: MessageInterceptor<Sender, Message = <A as MessageInterceptor<Sender>>::Message>,
+{
+	type Message = <A as MessageInterceptor<Sender>>::Message;
+
+	fn intercept_incoming(
+		&self,
+		sender: &mut Sender,
+		msg: FromOverseer<Self::Message>,
+	) -> Option<FromOverseer<Self::Message>> {
+		self.0
+			.intercept_incoming(sender, msg)
+			.and_then(|msg| self.1.intercept_incoming(sender, msg))
+	}
+
+	fn intercept_outgoing(&self, msg: AllMessages) -> Option<AllMessages> {
+		self.0.intercept_outgoing(msg).and_then(|msg| self.1.intercept_outgoing(msg))
+	}
+}
+
 /// A sender with the outgoing messages filtered.
 #[derive(Clone)]
 pub struct InterceptedSender<Sender, Fil> {
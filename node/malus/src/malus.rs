@@ -18,13 +18,16 @@
 
 use color_eyre::eyre;
 use polkadot_cli::{Cli, RunCmd};
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 pub(crate) mod interceptor;
+pub(crate) mod scenario;
 pub(crate) mod shared;
 
 mod variants;
 
+use scenario::Scenario;
 use variants::*;
 
 /// Define the different variants of behavior.
@@ -38,6 +41,14 @@ enum NemesisVariant {
 	BackGarbageCandidate(RunCmd),
 	/// Delayed disputing of ancestors that are perfectly fine.
 	DisputeAncestor(RunCmd),
+	/// Withhold availability chunks while still claiming their availability.
+	WithholdAvailabilityChunks(RunCmd),
+	/// Never send approval votes, turning into a no-show for all assignments.
+	ForceNoShows(RunCmd),
+	/// Second a garbage candidate in addition to each candidate seconded.
+	EquivocateBackingStatements(RunCmd),
+	/// Carry out the attacks described in a scenario file.
+	Scenario(ScenarioCmd),
 
 	#[allow(missing_docs)]
 	#[structopt(name = "prepare-worker", setting = structopt::clap::AppSettings::Hidden)]
@@ -48,6 +59,17 @@ enum NemesisVariant {
 	PvfExecuteWorker(polkadot_cli::ValidationWorkerCommand),
 }
 
+#[derive(Debug, StructOpt)]
+#[allow(missing_docs)]
+struct ScenarioCmd {
+	/// The TOML file describing the attacks to carry out.
+	#[structopt(long, parse(from_os_str))]
+	pub scenario: PathBuf,
+
+	#[structopt(flatten)]
+	pub cmd: RunCmd,
+}
+
 #[derive(Debug, StructOpt)]
 #[allow(missing_docs)]
 struct MalusCli {
@@ -69,6 +91,16 @@ impl MalusCli {
 				polkadot_cli::run_node(run_cmd(cmd), SuggestGarbageCandidate)?,
 			NemesisVariant::DisputeAncestor(cmd) =>
 				polkadot_cli::run_node(run_cmd(cmd), DisputeValidCandidates)?,
+			NemesisVariant::WithholdAvailabilityChunks(cmd) =>
+				polkadot_cli::run_node(run_cmd(cmd), WithholdAvailabilityChunks)?,
+			NemesisVariant::ForceNoShows(cmd) =>
+				polkadot_cli::run_node(run_cmd(cmd), ForceNoShows)?,
+			NemesisVariant::EquivocateBackingStatements(cmd) =>
+				polkadot_cli::run_node(run_cmd(cmd), EquivocateBackingStatements)?,
+			NemesisVariant::Scenario(ScenarioCmd { scenario, cmd }) => {
+				let scenario = Scenario::load(&scenario)?;
+				polkadot_cli::run_node(run_cmd(cmd), RunScenario { scenario })?
+			},
 			NemesisVariant::PvfPrepareWorker(cmd) => {
 				#[cfg(target_os = "android")]
 				{
@@ -123,4 +155,23 @@ mod tests {
 			assert!(run.base.bob);
 		});
 	}
+
+	#[test]
+	fn scenario_subcommand_works() {
+		let cli = MalusCli::from_iter_safe(IntoIterator::into_iter([
+			"malus",
+			"scenario",
+			"--scenario",
+			"attacks.toml",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::Scenario(ScenarioCmd { scenario, cmd }),
+			..
+		} => {
+			assert_eq!(scenario, PathBuf::from("attacks.toml"));
+			assert!(cmd.base.bob);
+		});
+	}
 }
@@ -0,0 +1,325 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scenarios describing which attacks a malus node carries out, on which candidates and when.
+//!
+//! A scenario is read from a TOML file listing attacks:
+//!
+//! ```toml
+//! [[attack]]
+//! variant = "back-garbage-candidate"
+//! para-ids = [100]
+//! percentage = 50
+//! from-block = 20
+//! to-session = 10
+//! ```
+//!
+//! An attack applies to the candidates of the listed paras, or of all paras if none are listed,
+//! and hits the given percentage of them, all by default. Which candidates are hit is derived
+//! from their hash, so all subsystems of a node agree on it. An attack is only carried out while
+//! the number and the session of the latest relay chain leaf the node has seen are within the
+//! given bounds. The bounds are inclusive and omitted ones are unbounded.
+
+use color_eyre::eyre::{self, WrapErr};
+use futures::channel::oneshot;
+use polkadot_node_subsystem::{
+	errors::RuntimeApiError,
+	messages::{AllMessages, RuntimeApiMessage, RuntimeApiRequest},
+	overseer, FromOverseer, OverseerSignal,
+};
+use polkadot_primitives::v1::{BlockNumber, Hash, Id as ParaId, SessionIndex};
+use serde::Deserialize;
+use std::{
+	path::Path,
+	sync::{Arc, Mutex},
+};
+
+/// The kinds of attacks a malus node can carry out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum AttackVariant {
+	/// Second garbage instead of the candidates provided by collators.
+	SuggestGarbageCandidate,
+	/// Back garbage candidates seconded by others.
+	BackGarbageCandidate,
+	/// Dispute valid candidates.
+	DisputeValidCandidates,
+	/// Withhold our availability chunks while still claiming their availability.
+	WithholdAvailabilityChunks,
+	/// Never send approval votes for the candidates we are assigned to.
+	ForceNoShows,
+	/// Second a garbage candidate in addition to the ones provided by collators.
+	EquivocateBackingStatements,
+}
+
+fn all_candidates() -> u8 {
+	100
+}
+
+/// An attack, as described in a scenario.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Attack {
+	/// The kind of attack.
+	pub(crate) variant: AttackVariant,
+	/// The paras whose candidates are attacked, all if empty.
+	#[serde(default)]
+	pub(crate) para_ids: Vec<ParaId>,
+	/// The percentage of candidates attacked.
+	#[serde(default = "all_candidates")]
+	pub(crate) percentage: u8,
+	/// The first block the attack is carried out at.
+	pub(crate) from_block: Option<BlockNumber>,
+	/// The last block the attack is carried out at.
+	pub(crate) to_block: Option<BlockNumber>,
+	/// The first session the attack is carried out in.
+	pub(crate) from_session: Option<SessionIndex>,
+	/// The last session the attack is carried out in.
+	pub(crate) to_session: Option<SessionIndex>,
+}
+
+impl Attack {
+	/// An attack on all candidates, at all times.
+	pub(crate) fn unconditional(variant: AttackVariant) -> Self {
+		Attack {
+			variant,
+			para_ids: Vec::new(),
+			percentage: all_candidates(),
+			from_block: None,
+			to_block: None,
+			from_session: None,
+			to_session: None,
+		}
+	}
+
+	fn is_active(&self, block_number: BlockNumber, session: SessionIndex) -> bool {
+		let within = |value, from: Option<u32>, to: Option<u32>| {
+			from.map_or(true, |from| value >= from) && to.map_or(true, |to| value <= to)
+		};
+		within(block_number, self.from_block, self.to_block) &&
+			within(session, self.from_session, self.to_session)
+	}
+
+	fn hits(&self, para_id: Option<ParaId>, candidate: &Hash) -> bool {
+		let para_matches = self.para_ids.is_empty() ||
+			para_id.map_or(false, |para_id| self.para_ids.contains(&para_id));
+		let selector = u32::from_le_bytes([candidate[0], candidate[1], candidate[2], candidate[3]]);
+		para_matches && selector % 100 < u32::from(self.percentage)
+	}
+}
+
+/// A list of attacks.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Scenario {
+	/// The attacks to carry out.
+	#[serde(default, rename = "attack")]
+	pub(crate) attacks: Vec<Attack>,
+}
+
+impl Scenario {
+	/// Read a scenario from a TOML file.
+	pub(crate) fn load(path: &Path) -> eyre::Result<Self> {
+		let content = std::fs::read_to_string(path)
+			.wrap_err_with(|| format!("failed to read scenario {:?}", path))?;
+		Self::parse(&content).wrap_err_with(|| format!("invalid scenario {:?}", path))
+	}
+
+	fn parse(content: &str) -> eyre::Result<Self> {
+		let scenario: Scenario = toml::from_str(content)?;
+		if let Some(attack) = scenario.attacks.iter().find(|attack| attack.percentage > 100) {
+			eyre::bail!("{:?} attacks more than 100% of the candidates", attack.variant);
+		}
+		Ok(scenario)
+	}
+
+	/// The filter for the attacks of the given variant.
+	pub(crate) fn filter(&self, variant: AttackVariant, clock: &Arc<Clock>) -> AttackFilter {
+		let attacks = self.attacks.iter().filter(|attack| attack.variant == variant);
+		AttackFilter { attacks: Arc::new(attacks.cloned().collect()), clock: clock.clone() }
+	}
+}
+
+#[derive(Default)]
+struct ClockInner {
+	block_number: BlockNumber,
+	session: SessionIndex,
+	pending_session: Option<oneshot::Receiver<Result<SessionIndex, RuntimeApiError>>>,
+}
+
+/// Tracks the number and the session of the latest relay chain leaf, shared by all the
+/// subsystems of a node.
+#[derive(Default)]
+pub(crate) struct Clock {
+	inner: Mutex<ClockInner>,
+}
+
+impl Clock {
+	/// Advance the clock to the leaf activated by `msg`, if any.
+	///
+	/// The session of the leaf is requested from the runtime and picked up once it is known.
+	fn observe<M, Sender>(&self, sender: &mut Sender, msg: &FromOverseer<M>)
+	where
+		Sender: overseer::SubsystemSender<AllMessages>,
+	{
+		let leaf = match msg {
+			FromOverseer::Signal(OverseerSignal::ActiveLeaves(update)) => match &update.activated {
+				Some(leaf) => leaf,
+				None => return,
+			},
+			_ => return,
+		};
+
+		let mut inner = self.inner.lock().expect("clock lock is never poisoned; qed");
+		if leaf.number > inner.block_number {
+			let (tx, rx) = oneshot::channel();
+			sender.send_unbounded_message(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				leaf.hash,
+				RuntimeApiRequest::SessionIndexForChild(tx),
+			)));
+			inner.block_number = leaf.number;
+			inner.pending_session = Some(rx);
+		}
+	}
+
+	fn now(&self) -> (BlockNumber, SessionIndex) {
+		let mut inner = self.inner.lock().expect("clock lock is never poisoned; qed");
+		match inner.pending_session.as_mut().map(|rx| rx.try_recv()) {
+			None | Some(Ok(None)) => {},
+			Some(Ok(Some(Ok(session)))) => {
+				inner.session = session;
+				inner.pending_session = None;
+			},
+			Some(_) => inner.pending_session = None,
+		}
+		(inner.block_number, inner.session)
+	}
+}
+
+/// Decides which candidates the attacks of one variant hit.
+#[derive(Clone)]
+pub(crate) struct AttackFilter {
+	attacks: Arc<Vec<Attack>>,
+	clock: Arc<Clock>,
+}
+
+impl AttackFilter {
+	/// A filter hitting all candidates, at all times.
+	pub(crate) fn unconditional(variant: AttackVariant) -> Self {
+		AttackFilter {
+			attacks: Arc::new(vec![Attack::unconditional(variant)]),
+			clock: Default::default(),
+		}
+	}
+
+	/// Keep track of the time, to be called with all incoming messages of a subsystem.
+	pub(crate) fn observe<M, Sender>(&self, sender: &mut Sender, msg: &FromOverseer<M>)
+	where
+		Sender: overseer::SubsystemSender<AllMessages>,
+	{
+		if !self.attacks.is_empty() {
+			self.clock.observe(sender, msg);
+		}
+	}
+
+	/// Whether any attack is being carried out at the moment.
+	pub(crate) fn is_active(&self) -> bool {
+		if self.attacks.is_empty() {
+			return false
+		}
+		let (block_number, session) = self.clock.now();
+		self.attacks.iter().any(|attack| attack.is_active(block_number, session))
+	}
+
+	/// Whether the given candidate is attacked at the moment. Candidates of unknown paras are
+	/// only hit by attacks on all paras.
+	pub(crate) fn hits(&self, para_id: Option<ParaId>, candidate: &Hash) -> bool {
+		if self.attacks.is_empty() {
+			return false
+		}
+		let (block_number, session) = self.clock.now();
+		self.attacks.iter().any(|attack| {
+			attack.is_active(block_number, session) && attack.hits(para_id, candidate)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn scenario_parsing_works() {
+		let scenario = Scenario::parse(
+			r#"
+			[[attack]]
+			variant = "back-garbage-candidate"
+			para-ids = [100, 200]
+			percentage = 50
+			from-block = 20
+			to-session = 10
+
+			[[attack]]
+			variant = "force-no-shows"
+			"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			scenario.attacks,
+			vec![
+				Attack {
+					para_ids: vec![100.into(), 200.into()],
+					percentage: 50,
+					from_block: Some(20),
+					to_session: Some(10),
+					..Attack::unconditional(AttackVariant::BackGarbageCandidate)
+				},
+				Attack::unconditional(AttackVariant::ForceNoShows),
+			]
+		);
+
+		assert!(
+			Scenario::parse("[[attack]]\nvariant = \"force-no-shows\"\npercentage = 101").is_err()
+		);
+		assert!(Scenario::parse("[[attack]]\nvariant = \"unknown\"").is_err());
+	}
+
+	#[test]
+	fn attacks_hit_the_configured_candidates() {
+		let attack = Attack {
+			para_ids: vec![100.into()],
+			percentage: 50,
+			from_block: Some(10),
+			to_session: Some(2),
+			..Attack::unconditional(AttackVariant::ForceNoShows)
+		};
+		// Selected, as 0 % 100 < 50.
+		let selected = Hash::repeat_byte(0);
+		// Not selected, as 0xffffffff % 100 == 95.
+		let ignored = Hash::repeat_byte(0xff);
+
+		assert!(attack.hits(Some(100.into()), &selected));
+		assert!(!attack.hits(Some(100.into()), &ignored));
+		assert!(!attack.hits(Some(200.into()), &selected));
+		assert!(!attack.hits(None, &selected));
+		assert!(Attack::unconditional(AttackVariant::ForceNoShows).hits(None, &ignored));
+
+		assert!(!attack.is_active(9, 0));
+		assert!(attack.is_active(10, 2));
+		assert!(!attack.is_active(10, 3));
+	}
+}
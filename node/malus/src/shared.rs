@@ -15,7 +15,17 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use futures::prelude::*;
-use polkadot_node_primitives::SpawnNamed;
+use polkadot_node_primitives::{SpawnNamed, Statement};
+use polkadot_node_subsystem::{
+	messages::{AllMessages, StatementDistributionMessage},
+	overseer,
+};
+use polkadot_node_subsystem_util::{self as util, metered};
+use polkadot_primitives::v1::{
+	CandidateCommitments, CandidateReceipt, CommittedCandidateReceipt, CompactStatement, Hash,
+	Signed,
+};
+use sp_keystore::SyncCryptoStorePtr;
 
 pub const MALUS: &str = "MALUS😈😈😈";
 
@@ -47,3 +57,62 @@ where
 		}),
 	);
 }
+
+/// Requests to second a garbage candidate, each with the sender to share the statement with,
+/// the relay parent and the candidate whose descriptor is used.
+pub(crate) type GarbageSecondingQueue<Sender> =
+	metered::UnboundedMeteredSender<(Sender, Hash, CandidateReceipt)>;
+
+/// Launch a task signing and sharing a `Seconded` statement of a garbage candidate for each
+/// request sent to the returned queue.
+///
+/// The garbage candidate has the descriptor of the requested one, but empty commitments.
+pub(crate) fn launch_garbage_seconding<Sender, S>(
+	spawner: &S,
+	keystore: SyncCryptoStorePtr,
+) -> GarbageSecondingQueue<Sender>
+where
+	Sender: overseer::SubsystemSender<AllMessages> + Send + 'static,
+	S: 'static + SpawnNamed + Clone + Unpin,
+{
+	let (sink, source) = metered::unbounded();
+
+	launch_processing_task(
+		spawner,
+		source,
+		move |(mut subsystem_sender, hash, candidate_receipt): (Sender, Hash, CandidateReceipt)| {
+			let keystore = keystore.clone();
+			async move {
+				tracing::info!(target = MALUS, "Seconding a garbage candidate");
+
+				let committed_candidate_receipt = CommittedCandidateReceipt {
+					descriptor: candidate_receipt.descriptor.clone(),
+					commitments: CandidateCommitments::default(),
+				};
+
+				let statement = Statement::Seconded(committed_candidate_receipt);
+
+				if let Ok(validator) =
+					util::Validator::new(hash, keystore.clone(), &mut subsystem_sender).await
+				{
+					let signed_statement: Signed<Statement, CompactStatement> = validator
+						.sign(keystore, statement)
+						.await
+						.expect("Signing works. qed")
+						.expect("Something must come out of this. qed");
+
+					subsystem_sender
+						.send_message(AllMessages::from(StatementDistributionMessage::Share(
+							hash,
+							signed_statement,
+						)))
+						.await;
+				} else {
+					tracing::info!("We are not a validator. Not siging anything.");
+				}
+			}
+		},
+	);
+
+	sink
+}
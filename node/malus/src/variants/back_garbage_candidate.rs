@@ -37,12 +37,16 @@ use polkadot_node_subsystem::messages::{
 use polkadot_node_subsystem_util as util;
 
 // Filter wrapping related types.
-use crate::{interceptor::*, shared::*};
+use crate::{
+	interceptor::*,
+	scenario::{AttackFilter, AttackVariant},
+	shared::*,
+};
 use polkadot_node_primitives::{PoV, ValidationResult};
 
 use polkadot_primitives::v1::{
-	CandidateCommitments, CandidateDescriptor, CandidateReceipt, PersistedValidationData,
-	ValidationCode,
+	BlakeTwo256, CandidateCommitments, CandidateDescriptor, CandidateReceipt, HashT,
+	PersistedValidationData, ValidationCode,
 };
 
 use futures::channel::oneshot;
@@ -57,15 +61,30 @@ struct BribedPassageInner<Spawner> {
 	cache: HashMap<CandidateDescriptor, CandidateReceipt>,
 }
 
-#[derive(Clone, Debug)]
-struct BribedPassage<Spawner> {
+#[derive(Clone)]
+pub(crate) struct BribedPassage<Spawner> {
 	inner: Arc<Mutex<BribedPassageInner<Spawner>>>,
+	filter: AttackFilter,
 }
 
 impl<Spawner> BribedPassage<Spawner>
 where
 	Spawner: SpawnNamed,
 {
+	pub(crate) fn new(spawner: Spawner, filter: AttackFilter) -> Self {
+		BribedPassage {
+			inner: Arc::new(Mutex::new(BribedPassageInner { spawner, cache: Default::default() })),
+			filter,
+		}
+	}
+
+	/// Whether the garbage candidate with the given descriptor is let pass.
+	fn bribed(&self, candidate_descriptor: &CandidateDescriptor) -> bool {
+		// The candidate hash is unknown here, the descriptor identifies the candidate just as well.
+		self.filter
+			.hits(Some(candidate_descriptor.para_id), &BlakeTwo256::hash_of(candidate_descriptor))
+	}
+
 	fn let_pass(
 		persisted_validation_data: PersistedValidationData,
 		validation_code: Option<ValidationCode>,
@@ -101,6 +120,7 @@ where
 		sender: &mut Sender,
 		msg: FromOverseer<Self::Message>,
 	) -> Option<FromOverseer<Self::Message>> {
+		self.filter.observe(sender, &msg);
 		match msg {
 			FromOverseer::Communication {
 				msg:
@@ -112,7 +132,9 @@ where
 						_duration,
 						response_sender,
 					),
-			} if pov.block_data.0.as_slice() == MALICIOUS_POV => {
+			} if pov.block_data.0.as_slice() == MALICIOUS_POV &&
+				self.bribed(&candidate_descriptor) =>
+			{
 				Self::let_pass(
 					persisted_validation_data,
 					Some(validation_code),
@@ -130,7 +152,9 @@ where
 						_duration,
 						response_sender,
 					),
-			} if pov.block_data.0.as_slice() == MALICIOUS_POV => {
+			} if pov.block_data.0.as_slice() == MALICIOUS_POV &&
+				self.bribed(&candidate_descriptor) =>
+			{
 				if let Some(candidate_receipt) =
 					self.inner.lock().unwrap().cache.get(&candidate_descriptor).cloned()
 				{
@@ -156,6 +180,7 @@ where
 										candidate_receipt,
 										session_index,
 										None,
+										None,
 										a_tx,
 									),
 								))
@@ -216,12 +241,10 @@ impl OverseerGen for BackGarbageCandidate {
 						cv.metrics,
 						cv.pvf_metrics,
 					),
-					BribedPassage::<Spawner> {
-						inner: Arc::new(Mutex::new(BribedPassageInner {
-							spawner,
-							cache: Default::default(),
-						})),
-					},
+					BribedPassage::new(
+						spawner,
+						AttackFilter::unconditional(AttackVariant::BackGarbageCandidate),
+					),
 				)
 			})
 			.build_with_connector(connector)
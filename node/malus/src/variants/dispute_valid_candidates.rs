@@ -31,41 +31,84 @@ use polkadot_cli::{
 };
 
 // Filter wrapping related types.
-use crate::interceptor::*;
+use crate::{
+	interceptor::*,
+	scenario::{AttackFilter, AttackVariant},
+};
 
 // Import extra types relevant to the particular
 // subsystem.
-use polkadot_node_core_backing::CandidateBackingSubsystem;
 use polkadot_node_subsystem::messages::{
-	ApprovalDistributionMessage, CandidateBackingMessage, DisputeCoordinatorMessage,
+	ApprovalDistributionMessage, ApprovalVotingMessage, DisputeCoordinatorMessage,
 };
-use sp_keystore::SyncCryptoStorePtr;
+use polkadot_primitives::v1::{CandidateHash, Hash};
+
+use lru::LruCache;
+use std::sync::{Arc, Mutex};
+
+/// The number of blocks whose candidates are remembered.
+const KNOWN_BLOCKS: usize = 256;
+
+/// Replace the outgoing approval messages of approval voting with disputes.
+///
+/// Approvals refer to their candidate by block and candidate index, which are resolved with the
+/// candidates of the blocks approval voting announces to approval distribution.
+#[derive(Clone)]
+pub(crate) struct ReplaceApprovalsWithDisputes {
+	block_candidates: Arc<Mutex<LruCache<Hash, Vec<CandidateHash>>>>,
+	filter: AttackFilter,
+}
 
-use std::sync::Arc;
+impl ReplaceApprovalsWithDisputes {
+	pub(crate) fn new(filter: AttackFilter) -> Self {
+		ReplaceApprovalsWithDisputes {
+			block_candidates: Arc::new(Mutex::new(LruCache::new(KNOWN_BLOCKS))),
+			filter,
+		}
+	}
 
-/// Replace outgoing approval messages with disputes.
-#[derive(Clone, Debug)]
-struct ReplaceApprovalsWithDisputes;
+	fn candidate(&self, block_hash: &Hash, candidate_index: u32) -> Option<CandidateHash> {
+		let mut block_candidates = self.block_candidates.lock().unwrap();
+		block_candidates.get(block_hash)?.get(candidate_index as usize).copied()
+	}
+}
 
 impl<Sender> MessageInterceptor<Sender> for ReplaceApprovalsWithDisputes
 where
-	Sender: overseer::SubsystemSender<CandidateBackingMessage> + Clone + Send + 'static,
+	Sender: overseer::SubsystemSender<ApprovalVotingMessage>
+		+ overseer::SubsystemSender<AllMessages>
+		+ Clone
+		+ Send
+		+ 'static,
 {
-	type Message = CandidateBackingMessage;
+	type Message = ApprovalVotingMessage;
 
 	fn intercept_incoming(
 		&self,
-		_sender: &mut Sender,
+		sender: &mut Sender,
 		msg: FromOverseer<Self::Message>,
 	) -> Option<FromOverseer<Self::Message>> {
+		self.filter.observe(sender, &msg);
 		Some(msg)
 	}
 
 	fn intercept_outgoing(&self, msg: AllMessages) -> Option<AllMessages> {
 		match msg {
-			AllMessages::ApprovalDistribution(ApprovalDistributionMessage::DistributeApproval(
-				_,
+			AllMessages::ApprovalDistribution(ApprovalDistributionMessage::NewBlocks(
+				ref metas,
 			)) => {
+				let mut block_candidates = self.block_candidates.lock().unwrap();
+				for meta in metas {
+					block_candidates.put(meta.hash, meta.candidates.clone());
+				}
+				Some(msg)
+			},
+			AllMessages::ApprovalDistribution(ApprovalDistributionMessage::DistributeApproval(
+				ref vote,
+			)) if self
+				.candidate(&vote.block_hash, vote.candidate_index)
+				.map_or(false, |candidate_hash| self.filter.hits(None, &candidate_hash.0)) =>
+			{
 				// drop the message on the floor
 				None
 			},
@@ -74,7 +117,10 @@ where
 				candidate_receipt,
 				session,
 				..
-			}) => {
+			}) if self
+				.filter
+				.hits(Some(candidate_receipt.descriptor.para_id), &candidate_hash.0) =>
+			{
 				// this would also dispute candidates we were not assigned to approve
 				Some(AllMessages::DisputeCoordinator(
 					DisputeCoordinatorMessage::IssueLocalStatement(
@@ -104,18 +150,78 @@ impl OverseerGen for DisputeValidCandidates {
 		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
 		Spawner: 'static + SpawnNamed + Clone + Unpin,
 	{
-		let spawner = args.spawner.clone();
-		let crypto_store_ptr = args.keystore.clone() as SyncCryptoStorePtr;
-		let filter = ReplaceApprovalsWithDisputes;
+		let filter = ReplaceApprovalsWithDisputes::new(AttackFilter::unconditional(
+			AttackVariant::DisputeValidCandidates,
+		));
 
 		prepared_overseer_builder(args)?
-			.replace_candidate_backing(move |cb| {
-				InterceptedSubsystem::new(
-					CandidateBackingSubsystem::new(spawner, crypto_store_ptr, cb.params.metrics),
-					filter,
-				)
-			})
+			.replace_approval_voting(move |av| InterceptedSubsystem::new(av, filter))
 			.build_with_connector(connector)
 			.map_err(|e| e.into())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::scenario::{Attack, Clock, Scenario};
+	use assert_matches::assert_matches;
+	use polkadot_node_primitives::approval::{BlockApprovalMeta, IndirectSignedApprovalVote};
+	use polkadot_node_subsystem_test_helpers::TestSubsystemSender;
+	use polkadot_primitives::v1::ValidatorIndex;
+
+	fn approval(block_hash: Hash, candidate_index: u32) -> AllMessages {
+		AllMessages::ApprovalDistribution(ApprovalDistributionMessage::DistributeApproval(
+			IndirectSignedApprovalVote {
+				block_hash,
+				candidate_index,
+				validator: ValidatorIndex(0),
+				signature: sp_core::sr25519::Signature::from_raw([0; 64]).into(),
+			},
+		))
+	}
+
+	#[test]
+	fn only_approvals_of_hit_candidates_are_dropped() {
+		let scenario = Scenario {
+			attacks: vec![Attack {
+				percentage: 50,
+				..Attack::unconditional(AttackVariant::DisputeValidCandidates)
+			}],
+		};
+		let filter = ReplaceApprovalsWithDisputes::new(
+			scenario.filter(AttackVariant::DisputeValidCandidates, &Arc::new(Clock::default())),
+		);
+		let intercept =
+			|msg| MessageInterceptor::<TestSubsystemSender>::intercept_outgoing(&filter, msg);
+
+		// Hit, as 0 % 100 < 50.
+		let hit = CandidateHash(Hash::repeat_byte(0));
+		// Missed, as 0xffffffff % 100 == 95.
+		let missed = CandidateHash(Hash::repeat_byte(0xff));
+		let block_hash = Hash::repeat_byte(1);
+
+		let new_blocks =
+			AllMessages::ApprovalDistribution(ApprovalDistributionMessage::NewBlocks(vec![
+				BlockApprovalMeta {
+					hash: block_hash,
+					number: 1,
+					parent_hash: Hash::repeat_byte(2),
+					candidates: vec![hit, missed],
+					slot: Default::default(),
+				},
+			]));
+		assert_matches!(
+			intercept(new_blocks),
+			Some(AllMessages::ApprovalDistribution(ApprovalDistributionMessage::NewBlocks(_)))
+		);
+
+		assert_matches!(intercept(approval(block_hash, 0)), None);
+		assert_matches!(
+			intercept(approval(block_hash, 1)),
+			Some(AllMessages::ApprovalDistribution(
+				ApprovalDistributionMessage::DistributeApproval(_)
+			))
+		);
+	}
+}
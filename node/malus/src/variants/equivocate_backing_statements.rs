@@ -0,0 +1,125 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node equivocating in backing, by seconding a garbage candidate in addition to
+//! each candidate it seconds.
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	prepared_overseer_builder,
+	service::{
+		AuthorityDiscoveryApi, AuxStore, BabeApi, Block, Error, HeaderBackend, Overseer,
+		OverseerConnector, OverseerGen, OverseerGenArgs, OverseerHandle, ParachainHost,
+		ProvideRuntimeApi, SpawnNamed,
+	},
+};
+
+// Import extra types relevant to the particular
+// subsystem.
+use polkadot_node_core_backing::CandidateBackingSubsystem;
+use polkadot_node_subsystem::messages::CandidateBackingMessage;
+use sp_keystore::SyncCryptoStorePtr;
+
+// Filter wrapping related types.
+use crate::{
+	interceptor::*,
+	scenario::{AttackFilter, AttackVariant},
+	shared::*,
+};
+
+use std::sync::Arc;
+
+/// Second a garbage candidate alongside the candidates we are asked to second.
+#[derive(Clone)]
+pub(crate) struct SecondGarbageAlongside<Sender>
+where
+	Sender: Send,
+{
+	pub(crate) queue: GarbageSecondingQueue<Sender>,
+	pub(crate) filter: AttackFilter,
+}
+
+impl<Sender> MessageInterceptor<Sender> for SecondGarbageAlongside<Sender>
+where
+	Sender: overseer::SubsystemSender<CandidateBackingMessage>
+		+ overseer::SubsystemSender<AllMessages>
+		+ Clone
+		+ Send
+		+ 'static,
+{
+	type Message = CandidateBackingMessage;
+
+	fn intercept_incoming(
+		&self,
+		sender: &mut Sender,
+		msg: FromOverseer<Self::Message>,
+	) -> Option<FromOverseer<Self::Message>> {
+		self.filter.observe(sender, &msg);
+		if let FromOverseer::Communication {
+			msg: CandidateBackingMessage::Second(hash, candidate_receipt, _),
+		} = &msg
+		{
+			if self
+				.filter
+				.hits(Some(candidate_receipt.descriptor.para_id), &candidate_receipt.hash().0)
+			{
+				self.queue
+					.unbounded_send((sender.clone(), *hash, candidate_receipt.clone()))
+					.unwrap();
+			}
+		}
+		Some(msg)
+	}
+
+	fn intercept_outgoing(&self, msg: AllMessages) -> Option<AllMessages> {
+		Some(msg)
+	}
+}
+
+/// Generates an overseer that equivocates in backing.
+pub(crate) struct EquivocateBackingStatements;
+
+impl OverseerGen for EquivocateBackingStatements {
+	fn generate<'a, Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'a, Spawner, RuntimeClient>,
+	) -> Result<(Overseer<Spawner, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		let spawner = args.spawner.clone();
+		let keystore = args.keystore.clone() as SyncCryptoStorePtr;
+
+		let filter = SecondGarbageAlongside {
+			queue: launch_garbage_seconding(&spawner, keystore.clone()),
+			filter: AttackFilter::unconditional(AttackVariant::EquivocateBackingStatements),
+		};
+
+		prepared_overseer_builder(args)?
+			.replace_candidate_backing(move |cb| {
+				InterceptedSubsystem::new(
+					CandidateBackingSubsystem::new(spawner, keystore, cb.params.metrics),
+					filter,
+				)
+			})
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
+	}
+}
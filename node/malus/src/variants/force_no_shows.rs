@@ -0,0 +1,114 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node which never sends approval votes for the candidates it is assigned to,
+//! turning into a no-show for them.
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	prepared_overseer_builder,
+	service::{
+		AuthorityDiscoveryApi, AuxStore, BabeApi, Block, Error, HeaderBackend, Overseer,
+		OverseerConnector, OverseerGen, OverseerGenArgs, OverseerHandle, ParachainHost,
+		ProvideRuntimeApi, SpawnNamed,
+	},
+};
+
+// Import extra types relevant to the particular
+// subsystem.
+use polkadot_node_subsystem::messages::{ApprovalVotingMessage, AvailabilityRecoveryMessage};
+
+// Filter wrapping related types.
+use crate::{
+	interceptor::*,
+	scenario::{AttackFilter, AttackVariant},
+	shared::MALUS,
+};
+
+use std::sync::Arc;
+
+/// Drop the availability recovery requests approval checks start with.
+///
+/// The dropped request makes the approval check fail without raising a dispute, so the
+/// assignment has been announced, but no approval vote follows.
+#[derive(Clone)]
+pub(crate) struct SkipApprovalChecks {
+	pub(crate) filter: AttackFilter,
+}
+
+impl<Sender> MessageInterceptor<Sender> for SkipApprovalChecks
+where
+	Sender: overseer::SubsystemSender<ApprovalVotingMessage>
+		+ overseer::SubsystemSender<AllMessages>
+		+ Clone
+		+ Send
+		+ 'static,
+{
+	type Message = ApprovalVotingMessage;
+
+	fn intercept_incoming(
+		&self,
+		sender: &mut Sender,
+		msg: FromOverseer<Self::Message>,
+	) -> Option<FromOverseer<Self::Message>> {
+		self.filter.observe(sender, &msg);
+		Some(msg)
+	}
+
+	fn intercept_outgoing(&self, msg: AllMessages) -> Option<AllMessages> {
+		match msg {
+			AllMessages::AvailabilityRecovery(
+				AvailabilityRecoveryMessage::RecoverAvailableData(candidate_receipt, ..),
+			) if self
+				.filter
+				.hits(Some(candidate_receipt.descriptor.para_id), &candidate_receipt.hash().0) =>
+			{
+				tracing::info!(
+					target = MALUS,
+					candidate_hash = ?candidate_receipt.hash(),
+					"Not checking the candidate, will be a no-show",
+				);
+				None
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+/// Generates an overseer that never sends approval votes.
+pub(crate) struct ForceNoShows;
+
+impl OverseerGen for ForceNoShows {
+	fn generate<'a, Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'a, Spawner, RuntimeClient>,
+	) -> Result<(Overseer<Spawner, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		let filter =
+			SkipApprovalChecks { filter: AttackFilter::unconditional(AttackVariant::ForceNoShows) };
+
+		prepared_overseer_builder(args)?
+			.replace_approval_voting(move |av| InterceptedSubsystem::new(av, filter))
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
+	}
+}
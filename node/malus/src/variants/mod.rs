@@ -18,9 +18,15 @@
 
 mod back_garbage_candidate;
 mod dispute_valid_candidates;
+mod equivocate_backing_statements;
+mod force_no_shows;
+mod scenario;
 mod suggest_garbage_candidate;
+mod withhold_availability_chunks;
 
 pub(crate) use self::{
 	back_garbage_candidate::BackGarbageCandidate, dispute_valid_candidates::DisputeValidCandidates,
-	suggest_garbage_candidate::SuggestGarbageCandidate,
+	equivocate_backing_statements::EquivocateBackingStatements, force_no_shows::ForceNoShows,
+	scenario::RunScenario, suggest_garbage_candidate::SuggestGarbageCandidate,
+	withhold_availability_chunks::WithholdAvailabilityChunks,
 };
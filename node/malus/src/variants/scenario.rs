@@ -0,0 +1,114 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node carrying out the attacks of a scenario, see [`crate::scenario`].
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	prepared_overseer_builder,
+	service::{
+		AuthorityDiscoveryApi, AuxStore, BabeApi, Block, Error, HeaderBackend, Overseer,
+		OverseerConnector, OverseerGen, OverseerGenArgs, OverseerHandle, ParachainHost,
+		ProvideRuntimeApi, SpawnNamed,
+	},
+};
+
+use polkadot_node_core_backing::CandidateBackingSubsystem;
+use polkadot_node_core_candidate_validation::CandidateValidationSubsystem;
+use sp_keystore::SyncCryptoStorePtr;
+
+use crate::{
+	interceptor::*,
+	scenario::{AttackVariant, Clock, Scenario},
+	shared::*,
+	variants::{
+		back_garbage_candidate::BribedPassage,
+		dispute_valid_candidates::ReplaceApprovalsWithDisputes,
+		equivocate_backing_statements::SecondGarbageAlongside, force_no_shows::SkipApprovalChecks,
+		suggest_garbage_candidate::ReplacePoVBytes, withhold_availability_chunks::WithholdChunks,
+	},
+};
+
+use std::sync::Arc;
+
+/// Generates an overseer carrying out the attacks of a scenario.
+pub(crate) struct RunScenario {
+	pub(crate) scenario: Scenario,
+}
+
+impl OverseerGen for RunScenario {
+	fn generate<'a, Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'a, Spawner, RuntimeClient>,
+	) -> Result<(Overseer<Spawner, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		let candidate_validation_config = args.candidate_validation_config.clone();
+		let spawner = args.spawner.clone();
+		let keystore = args.keystore.clone() as SyncCryptoStorePtr;
+		let clock = Arc::new(Clock::default());
+		let filter = |variant| self.scenario.filter(variant, &clock);
+
+		let garbage_seconding = launch_garbage_seconding(&spawner, keystore.clone());
+		let backing_filter = (
+			ReplacePoVBytes {
+				queue: garbage_seconding.clone(),
+				filter: filter(AttackVariant::SuggestGarbageCandidate),
+			},
+			SecondGarbageAlongside {
+				queue: garbage_seconding,
+				filter: filter(AttackVariant::EquivocateBackingStatements),
+			},
+		);
+		let validation_filter =
+			BribedPassage::new(spawner.clone(), filter(AttackVariant::BackGarbageCandidate));
+		let availability_filter =
+			WithholdChunks::new(spawner.clone(), filter(AttackVariant::WithholdAvailabilityChunks));
+		let approval_filter = (
+			SkipApprovalChecks { filter: filter(AttackVariant::ForceNoShows) },
+			ReplaceApprovalsWithDisputes::new(filter(AttackVariant::DisputeValidCandidates)),
+		);
+
+		prepared_overseer_builder(args)?
+			.replace_candidate_backing(move |cb| {
+				InterceptedSubsystem::new(
+					CandidateBackingSubsystem::new(spawner, keystore, cb.params.metrics),
+					backing_filter,
+				)
+			})
+			.replace_candidate_validation(move |cv| {
+				InterceptedSubsystem::new(
+					CandidateValidationSubsystem::with_config(
+						candidate_validation_config,
+						cv.metrics,
+						cv.pvf_metrics,
+					),
+					validation_filter,
+				)
+			})
+			.replace_availability_store(move |av| {
+				InterceptedSubsystem::new(av, availability_filter)
+			})
+			.replace_approval_voting(move |av| InterceptedSubsystem::new(av, approval_filter))
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
+	}
+}
@@ -34,20 +34,13 @@ use polkadot_cli::{
 // Import extra types relevant to the particular
 // subsystem.
 use polkadot_node_core_backing::CandidateBackingSubsystem;
-use polkadot_node_primitives::Statement;
-use polkadot_node_subsystem::{
-	messages::{CandidateBackingMessage, StatementDistributionMessage},
-	overseer::{self, SubsystemSender},
-};
-use polkadot_node_subsystem_util as util;
+use polkadot_node_subsystem::{messages::CandidateBackingMessage, overseer};
 // Filter wrapping related types.
-use crate::interceptor::*;
-use polkadot_primitives::v1::{
-	CandidateCommitments, CandidateReceipt, CommittedCandidateReceipt, CompactStatement, Hash,
-	Signed,
+use crate::{
+	interceptor::*,
+	scenario::{AttackFilter, AttackVariant},
 };
 use sp_keystore::SyncCryptoStorePtr;
-use util::metered;
 
 use std::sync::Arc;
 
@@ -56,16 +49,21 @@ use crate::shared::*;
 /// Replaces the seconded PoV data
 /// of outgoing messages by some garbage data.
 #[derive(Clone)]
-struct ReplacePoVBytes<Sender>
+pub(crate) struct ReplacePoVBytes<Sender>
 where
 	Sender: Send,
 {
-	queue: metered::UnboundedMeteredSender<(Sender, Hash, CandidateReceipt)>,
+	pub(crate) queue: GarbageSecondingQueue<Sender>,
+	pub(crate) filter: AttackFilter,
 }
 
 impl<Sender> MessageInterceptor<Sender> for ReplacePoVBytes<Sender>
 where
-	Sender: overseer::SubsystemSender<CandidateBackingMessage> + Clone + Send + 'static,
+	Sender: overseer::SubsystemSender<CandidateBackingMessage>
+		+ overseer::SubsystemSender<AllMessages>
+		+ Clone
+		+ Send
+		+ 'static,
 {
 	type Message = CandidateBackingMessage;
 
@@ -74,10 +72,14 @@ where
 		sender: &mut Sender,
 		msg: FromOverseer<Self::Message>,
 	) -> Option<FromOverseer<Self::Message>> {
+		self.filter.observe(sender, &msg);
 		match msg {
 			FromOverseer::Communication {
 				msg: CandidateBackingMessage::Second(hash, candidate_receipt, _pov),
-			} => {
+			} if self
+				.filter
+				.hits(Some(candidate_receipt.descriptor.para_id), &candidate_receipt.hash().0) =>
+			{
 				self.queue
 					.unbounded_send((sender.clone(), hash, candidate_receipt.clone()))
 					.unwrap();
@@ -108,64 +110,21 @@ impl OverseerGen for SuggestGarbageCandidate {
 		Spawner: 'static + SpawnNamed + Clone + Unpin,
 	{
 		let spawner = args.spawner.clone();
-		let (sink, source) = metered::unbounded();
 		let keystore = args.keystore.clone() as SyncCryptoStorePtr;
 
-		let filter = ReplacePoVBytes { queue: sink };
-
-		let keystore2 = keystore.clone();
-		let spawner2 = spawner.clone();
+		let filter = ReplacePoVBytes {
+			queue: launch_garbage_seconding(&spawner, keystore.clone()),
+			filter: AttackFilter::unconditional(AttackVariant::SuggestGarbageCandidate),
+		};
 
-		let result = prepared_overseer_builder(args)?
+		prepared_overseer_builder(args)?
 			.replace_candidate_backing(move |cb| {
 				InterceptedSubsystem::new(
-					CandidateBackingSubsystem::new(spawner2, keystore2, cb.params.metrics),
+					CandidateBackingSubsystem::new(spawner, keystore, cb.params.metrics),
 					filter,
 				)
 			})
 			.build_with_connector(connector)
-			.map_err(|e| e.into());
-
-		launch_processing_task(
-			&spawner,
-			source,
-			move |(mut subsystem_sender, hash, candidate_receipt): (_, Hash, CandidateReceipt)| {
-				let keystore = keystore.clone();
-				async move {
-					tracing::info!(
-						target = MALUS,
-						"Replacing seconded candidate pov with something else"
-					);
-
-					let committed_candidate_receipt = CommittedCandidateReceipt {
-						descriptor: candidate_receipt.descriptor.clone(),
-						commitments: CandidateCommitments::default(),
-					};
-
-					let statement = Statement::Seconded(committed_candidate_receipt);
-
-					if let Ok(validator) =
-						util::Validator::new(hash, keystore.clone(), &mut subsystem_sender).await
-					{
-						let signed_statement: Signed<Statement, CompactStatement> = validator
-							.sign(keystore, statement)
-							.await
-							.expect("Signing works. qed")
-							.expect("Something must come out of this. qed");
-
-						subsystem_sender
-							.send_message(StatementDistributionMessage::Share(
-								hash,
-								signed_statement,
-							))
-							.await;
-					} else {
-						tracing::info!("We are not a validator. Not siging anything.");
-					}
-				}
-			},
-		);
-
-		result
+			.map_err(|e| e.into())
 	}
 }
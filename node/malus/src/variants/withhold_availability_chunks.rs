@@ -0,0 +1,175 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node withholding its availability chunks, while still claiming their
+//! availability in its bitfields.
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	prepared_overseer_builder,
+	service::{
+		AuthorityDiscoveryApi, AuxStore, BabeApi, Block, Error, HeaderBackend, Overseer,
+		OverseerConnector, OverseerGen, OverseerGenArgs, OverseerHandle, ParachainHost,
+		ProvideRuntimeApi, SpawnNamed,
+	},
+};
+
+// Import extra types relevant to the particular
+// subsystem.
+use polkadot_node_subsystem::messages::{
+	AvailabilityStoreMessage, RuntimeApiMessage, RuntimeApiRequest,
+};
+use polkadot_primitives::v1::{CandidateEvent, CandidateHash, Id as ParaId};
+
+// Filter wrapping related types.
+use crate::{
+	interceptor::*,
+	scenario::{AttackFilter, AttackVariant},
+	shared::MALUS,
+};
+
+use futures::channel::oneshot;
+use lru::LruCache;
+use std::sync::{Arc, Mutex};
+
+/// The number of backed candidates whose para is remembered.
+const KNOWN_CANDIDATES: usize = 4096;
+
+/// Answer queries for our chunks as if we didn't have them.
+///
+/// The paras of the candidates are learned from the candidate events the availability store
+/// requests for new leaves.
+#[derive(Clone)]
+pub(crate) struct WithholdChunks<Spawner> {
+	spawner: Spawner,
+	paras: Arc<Mutex<LruCache<CandidateHash, ParaId>>>,
+	filter: AttackFilter,
+}
+
+impl<Spawner> WithholdChunks<Spawner> {
+	pub(crate) fn new(spawner: Spawner, filter: AttackFilter) -> Self {
+		WithholdChunks {
+			spawner,
+			paras: Arc::new(Mutex::new(LruCache::new(KNOWN_CANDIDATES))),
+			filter,
+		}
+	}
+
+	fn withholds(&self, candidate_hash: &CandidateHash) -> bool {
+		let para_id = self.paras.lock().unwrap().get(candidate_hash).copied();
+		let withholds = self.filter.hits(para_id, &candidate_hash.0);
+		if withholds {
+			tracing::info!(target = MALUS, ?candidate_hash, "Withholding chunk");
+		}
+		withholds
+	}
+}
+
+impl<Sender, Spawner> MessageInterceptor<Sender> for WithholdChunks<Spawner>
+where
+	Sender: overseer::SubsystemSender<AvailabilityStoreMessage>
+		+ overseer::SubsystemSender<AllMessages>
+		+ Clone
+		+ Send
+		+ 'static,
+	Spawner: SpawnNamed + Clone + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	fn intercept_incoming(
+		&self,
+		sender: &mut Sender,
+		msg: FromOverseer<Self::Message>,
+	) -> Option<FromOverseer<Self::Message>> {
+		self.filter.observe(sender, &msg);
+		match msg {
+			FromOverseer::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, _, tx),
+			} if self.withholds(&candidate_hash) => {
+				let _ = tx.send(None);
+				None
+			},
+			FromOverseer::Communication {
+				msg: AvailabilityStoreMessage::QueryAllChunks(candidate_hash, tx),
+			} if self.withholds(&candidate_hash) => {
+				let _ = tx.send(Vec::new());
+				None
+			},
+			msg => Some(msg),
+		}
+	}
+
+	fn intercept_outgoing(&self, msg: AllMessages) -> Option<AllMessages> {
+		match msg {
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::CandidateEvents(tx),
+			)) => {
+				let (events_tx, events_rx) = oneshot::channel();
+				let paras = self.paras.clone();
+				self.spawner.spawn(
+					"malus-note-candidate-paras",
+					Some("malus"),
+					Box::pin(async move {
+						if let Ok(events) = events_rx.await {
+							if let Ok(events) = &events {
+								let mut paras = paras.lock().unwrap();
+								for event in events {
+									if let CandidateEvent::CandidateBacked(receipt, ..) = event {
+										paras.put(receipt.hash(), receipt.descriptor.para_id);
+									}
+								}
+							}
+							let _ = tx.send(events);
+						}
+					}),
+				);
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					relay_parent,
+					RuntimeApiRequest::CandidateEvents(events_tx),
+				)))
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+/// Generates an overseer that withholds availability chunks.
+pub(crate) struct WithholdAvailabilityChunks;
+
+impl OverseerGen for WithholdAvailabilityChunks {
+	fn generate<'a, Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'a, Spawner, RuntimeClient>,
+	) -> Result<(Overseer<Spawner, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		let filter = WithholdChunks::new(
+			args.spawner.clone(),
+			AttackFilter::unconditional(AttackVariant::WithholdAvailabilityChunks),
+		);
+
+		prepared_overseer_builder(args)?
+			.replace_availability_store(move |av| InterceptedSubsystem::new(av, filter))
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
+	}
+}
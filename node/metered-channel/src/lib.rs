@@ -16,9 +16,12 @@
 
 //! Metered variant of mpsc channels to be able to extract metrics.
 
-use std::sync::{
-	atomic::{AtomicUsize, Ordering},
-	Arc,
+use std::{
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant},
 };
 
 use derive_more::{Add, Display};
@@ -29,6 +32,12 @@ mod unbounded;
 
 pub use self::{bounded::*, unbounded::*};
 
+/// Every n-th message sent on a channel is sampled for its time of flight.
+const TOF_SAMPLE_INTERVAL: usize = 64;
+
+/// Maximum number of time of flight samples retained between two readouts.
+const TOF_SAMPLES_MAX: usize = 256;
+
 /// A peek into the inner state of a meter.
 #[derive(Debug, Clone, Default)]
 pub struct Meter {
@@ -36,6 +45,8 @@ pub struct Meter {
 	sent: Arc<AtomicUsize>,
 	// Number of receives on this channel.
 	received: Arc<AtomicUsize>,
+	// Time of flight of the sampled messages, since the last readout.
+	tof: Arc<Mutex<Vec<Duration>>>,
 }
 
 /// A readout of sizes from the meter. Note that it is possible, due to asynchrony, for received
@@ -60,8 +71,24 @@ impl Meter {
 		}
 	}
 
-	fn note_sent(&self) {
-		self.sent.fetch_add(1, Ordering::Relaxed);
+	/// Take the time of flight samples collected since the last call.
+	///
+	/// Only every n-th message sent on the channel is sampled.
+	pub fn take_tof_samples(&self) -> Vec<Duration> {
+		match self.tof.lock() {
+			Ok(mut tof) => std::mem::take(&mut *tof),
+			Err(_) => Vec::new(),
+		}
+	}
+
+	/// Note a sent item, attaching the time of sending if the item is sampled.
+	fn note_sent<T>(&self, item: T) -> MaybeTimeOfFlight<T> {
+		let nth = self.sent.fetch_add(1, Ordering::Relaxed);
+		if nth % TOF_SAMPLE_INTERVAL == 0 {
+			MaybeTimeOfFlight::WithTimeOfFlight(item, Instant::now())
+		} else {
+			MaybeTimeOfFlight::Bare(item)
+		}
 	}
 
 	fn retract_sent(&self) {
@@ -71,6 +98,40 @@ impl Meter {
 	fn note_received(&self) {
 		self.received.fetch_add(1, Ordering::Relaxed);
 	}
+
+	/// Unwrap a received item, recording its time of flight if it was sampled.
+	fn note_time_of_flight<T>(&self, item: MaybeTimeOfFlight<T>) -> T {
+		match item {
+			MaybeTimeOfFlight::Bare(item) => item,
+			MaybeTimeOfFlight::WithTimeOfFlight(item, sent_at) => {
+				if let Ok(mut tof) = self.tof.lock() {
+					if tof.len() < TOF_SAMPLES_MAX {
+						tof.push(sent_at.elapsed());
+					}
+				}
+				item
+			},
+		}
+	}
+}
+
+/// An item in transit through a metered channel.
+#[derive(Debug)]
+pub enum MaybeTimeOfFlight<T> {
+	/// An item which is not sampled.
+	Bare(T),
+	/// A sampled item, along with the time it was sent.
+	WithTimeOfFlight(T, Instant),
+}
+
+impl<T> MaybeTimeOfFlight<T> {
+	/// Obtain the inner item.
+	pub fn into_inner(self) -> T {
+		match self {
+			Self::Bare(item) => item,
+			Self::WithTimeOfFlight(item, _) => item,
+		}
+	}
 }
 
 #[cfg(test)]
@@ -167,6 +228,20 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn time_of_flight_is_sampled() {
+		let (tx, mut rx) = unbounded::<Msg>();
+
+		for _ in 0..(2 * TOF_SAMPLE_INTERVAL + 1) {
+			tx.unbounded_send(Msg::default()).unwrap();
+		}
+		assert!(tx.meter().take_tof_samples().is_empty());
+
+		while let Ok(Some(_)) = rx.try_next() {}
+		assert_eq!(rx.meter().take_tof_samples().len(), 3);
+		assert!(rx.meter().take_tof_samples().is_empty());
+	}
+
 	#[test]
 	fn failed_send_does_not_inc_sent() {
 		let (mut bounded, _) = channel::<Msg>(5);
@@ -19,6 +19,7 @@
 use std::{
 	ops::Deref,
 	pin::Pin,
+	sync::Mutex,
 	task::{Context, Poll},
 	time::{Duration, Instant},
 };
@@ -30,6 +31,31 @@ use futures::{
 };
 use futures_timer::Delay;
 
+use super::TOF_SAMPLES_MAX;
+
+lazy_static::lazy_static! {
+	/// Time of flight of the completed oneshots since the last readout, along with their name.
+	static ref TOF_SAMPLES: Mutex<Vec<(&'static str, Duration)>> = Mutex::new(Vec::new());
+}
+
+/// Take the time of flight samples of all completed oneshots since the last call.
+///
+/// Each sample is accompanied by the name of the channel it was taken on.
+pub fn take_tof_samples() -> Vec<(&'static str, Duration)> {
+	match TOF_SAMPLES.lock() {
+		Ok(mut tof) => std::mem::take(&mut *tof),
+		Err(_) => Vec::new(),
+	}
+}
+
+fn note_time_of_flight(name: &'static str, tof: Duration) {
+	if let Ok(mut samples) = TOF_SAMPLES.lock() {
+		if samples.len() < TOF_SAMPLES_MAX {
+			samples.push((name, tof));
+		}
+	}
+}
+
 /// Provides the reason for termination.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -46,6 +72,8 @@ pub struct Measurements {
 	first_poll_till_end: Duration,
 	/// Duration starting with creation until polling termination.
 	creation_till_end: Duration,
+	/// Duration between sending the value and receiving it, if the channel completed.
+	time_of_flight: Option<Duration>,
 	/// Reason for resolving the future.
 	reason: Reason,
 }
@@ -63,6 +91,13 @@ impl Measurements {
 		&self.creation_till_end
 	}
 
+	/// Obtain the duration the value was in flight, from being sent until being received.
+	///
+	/// `None` if the channel did not complete.
+	pub fn time_of_flight(&self) -> Option<&Duration> {
+		self.time_of_flight.as_ref()
+	}
+
 	/// Obtain the reason to the channel termination.
 	pub fn reason(&self) -> &Reason {
 		&self.reason
@@ -191,12 +226,22 @@ impl<T> MeteredReceiver<T> {
 	/// Helper to create a measurement.
 	///
 	/// `start` determines the first possible time where poll can resolve with `Ready`.
+	/// The time of flight of completed channels is noted for [`take_tof_samples`].
 	fn create_measurement(&self, start: Instant, reason: Reason) -> Measurements {
 		let end = Instant::now();
+		// on completion, `start` is the point in time the value was sent
+		let time_of_flight = if reason == Reason::Completion {
+			let tof = end.saturating_duration_since(start);
+			note_time_of_flight(self.name, tof);
+			Some(tof)
+		} else {
+			None
+		};
 		Measurements {
 			// negative values are ok, if `send` was called before we poll for the first time.
 			first_poll_till_end: end - start,
 			creation_till_end: end - self.creation_timestamp,
+			time_of_flight,
 			reason,
 		}
 	}
@@ -360,6 +405,7 @@ mod tests {
 				let x = rx.await.unwrap();
 				let measurements = x.measurements();
 				assert_eq!(x.as_ref(), &DummyItem::default());
+				assert!(measurements.time_of_flight().is_some());
 				dbg!(measurements);
 			},
 		);
@@ -414,4 +460,17 @@ mod tests {
 			},
 		);
 	}
+
+	#[test]
+	fn completion_is_sampled() {
+		let (tx, rx) =
+			channel("completion_is_sampled", Duration::from_secs(1), Duration::from_secs(3));
+		tx.send(DummyItem::default()).unwrap();
+		let measurements = futures::executor::block_on(rx).unwrap().measurements();
+
+		let samples = take_tof_samples();
+		assert!(
+			samples.contains(&("completion_is_sampled", *measurements.time_of_flight().unwrap()))
+		);
+	}
 }
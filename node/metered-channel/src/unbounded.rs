@@ -24,11 +24,11 @@ use futures::{
 
 use std::{pin::Pin, result};
 
-use super::Meter;
+use super::{MaybeTimeOfFlight, Meter};
 
 /// Create a wrapped `mpsc::channel` pair of `MeteredSender` and `MeteredReceiver`.
 pub fn unbounded<T>() -> (UnboundedMeteredSender<T>, UnboundedMeteredReceiver<T>) {
-	let (tx, rx) = mpsc::unbounded();
+	let (tx, rx) = mpsc::unbounded::<MaybeTimeOfFlight<T>>();
 	let shared_meter = Meter::default();
 	let tx = UnboundedMeteredSender { meter: shared_meter.clone(), inner: tx };
 	let rx = UnboundedMeteredReceiver { meter: shared_meter, inner: rx };
@@ -40,11 +40,11 @@ pub fn unbounded<T>() -> (UnboundedMeteredSender<T>, UnboundedMeteredReceiver<T>
 pub struct UnboundedMeteredReceiver<T> {
 	// count currently contained messages
 	meter: Meter,
-	inner: mpsc::UnboundedReceiver<T>,
+	inner: mpsc::UnboundedReceiver<MaybeTimeOfFlight<T>>,
 }
 
 impl<T> std::ops::Deref for UnboundedMeteredReceiver<T> {
-	type Target = mpsc::UnboundedReceiver<T>;
+	type Target = mpsc::UnboundedReceiver<MaybeTimeOfFlight<T>>;
 	fn deref(&self) -> &Self::Target {
 		&self.inner
 	}
@@ -62,7 +62,7 @@ impl<T> Stream for UnboundedMeteredReceiver<T> {
 		match mpsc::UnboundedReceiver::poll_next(Pin::new(&mut self.inner), cx) {
 			Poll::Ready(x) => {
 				self.meter.note_received();
-				Poll::Ready(x)
+				Poll::Ready(x.map(|item| self.meter.note_time_of_flight(item)))
 			},
 			other => other,
 		}
@@ -85,7 +85,7 @@ impl<T> UnboundedMeteredReceiver<T> {
 		match self.inner.try_next()? {
 			Some(x) => {
 				self.meter.note_received();
-				Ok(Some(x))
+				Ok(Some(self.meter.note_time_of_flight(x)))
 			},
 			None => Ok(None),
 		}
@@ -103,7 +103,7 @@ impl<T> futures::stream::FusedStream for UnboundedMeteredReceiver<T> {
 #[derive(Debug)]
 pub struct UnboundedMeteredSender<T> {
 	meter: Meter,
-	inner: mpsc::UnboundedSender<T>,
+	inner: mpsc::UnboundedSender<MaybeTimeOfFlight<T>>,
 }
 
 impl<T> Clone for UnboundedMeteredSender<T> {
@@ -113,7 +113,7 @@ impl<T> Clone for UnboundedMeteredSender<T> {
 }
 
 impl<T> std::ops::Deref for UnboundedMeteredSender<T> {
-	type Target = mpsc::UnboundedSender<T>;
+	type Target = mpsc::UnboundedSender<MaybeTimeOfFlight<T>>;
 	fn deref(&self) -> &Self::Target {
 		&self.inner
 	}
@@ -132,8 +132,11 @@ impl<T> UnboundedMeteredSender<T> {
 	}
 
 	/// Attempt to send message or fail immediately.
-	pub fn unbounded_send(&self, msg: T) -> result::Result<(), mpsc::TrySendError<T>> {
-		self.meter.note_sent();
+	pub fn unbounded_send(
+		&self,
+		msg: T,
+	) -> result::Result<(), mpsc::TrySendError<MaybeTimeOfFlight<T>>> {
+		let msg = self.meter.note_sent(msg);
 		self.inner.unbounded_send(msg).map_err(|e| {
 			self.meter.retract_sent();
 			e
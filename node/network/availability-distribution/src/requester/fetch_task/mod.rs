@@ -22,15 +22,15 @@ use futures::{
 	FutureExt, SinkExt,
 };
 
-use polkadot_erasure_coding::branch_hash;
+use polkadot_erasure_coding::{branch_hash, ChunkMapping};
 use polkadot_node_network_protocol::request_response::{
 	outgoing::{OutgoingRequest, Recipient, RequestError, Requests},
 	v1::{ChunkFetchingRequest, ChunkFetchingResponse},
 };
 use polkadot_node_primitives::ErasureChunk;
 use polkadot_primitives::v1::{
-	AuthorityDiscoveryId, BlakeTwo256, CandidateHash, GroupIndex, Hash, HashT, OccupiedCore,
-	SessionIndex,
+	AuthorityDiscoveryId, BlakeTwo256, CandidateHash, CoreIndex, GroupIndex, Hash, HashT,
+	OccupiedCore, SessionIndex,
 };
 use polkadot_subsystem::{
 	jaeger,
@@ -134,9 +134,13 @@ impl FetchTaskConfig {
 	/// Create a new configuration for a [`FetchTask`].
 	///
 	/// The result of this function can be passed into [`FetchTask::start`].
+	///
+	/// `chunk_shuffling` determines whether our chunk is found by the shuffled chunk mapping.
 	pub fn new(
 		leaf: Hash,
 		core: &OccupiedCore,
+		core_index: CoreIndex,
+		chunk_shuffling: bool,
 		sender: mpsc::Sender<FromFetchTask>,
 		metrics: Metrics,
 		session_info: &SessionInfo,
@@ -151,6 +155,13 @@ impl FetchTaskConfig {
 		let span = jaeger::Span::new(core.candidate_hash, "availability-distribution")
 			.with_stage(jaeger::Stage::AvailabilityDistribution);
 
+		let chunk_mapping = ChunkMapping::new(
+			chunk_shuffling,
+			session_info.n_validators,
+			&core.candidate_descriptor.relay_parent,
+			core_index,
+		);
+
 		let prepared_running = RunningTask {
 			session_index: session_info.session_index,
 			group_index: core.group_responsible,
@@ -159,7 +170,7 @@ impl FetchTaskConfig {
 				.clone(),
 			request: ChunkFetchingRequest {
 				candidate_hash: core.candidate_hash,
-				index: session_info.our_index,
+				index: chunk_mapping.chunk_index(session_info.our_index),
 			},
 			erasure_root: core.candidate_descriptor.erasure_root,
 			relay_parent: core.candidate_descriptor.relay_parent,
@@ -32,8 +32,10 @@ use futures::{
 	Stream,
 };
 
-use polkadot_node_subsystem_util::runtime::{get_occupied_cores, RuntimeInfo};
-use polkadot_primitives::v1::{CandidateHash, Hash, OccupiedCore};
+use polkadot_node_subsystem_util::runtime::{
+	get_availability_chunk_shuffling, get_availability_cores, RuntimeInfo,
+};
+use polkadot_primitives::v1::{CandidateHash, CoreIndex, CoreState, Hash, OccupiedCore};
 use polkadot_subsystem::{
 	messages::AllMessages, ActivatedLeaf, ActiveLeavesUpdate, LeafStatus, SubsystemContext,
 };
@@ -120,7 +122,15 @@ impl Requester {
 		Context: SubsystemContext,
 	{
 		for ActivatedLeaf { hash: leaf, .. } in new_heads {
-			let cores = get_occupied_cores(ctx, leaf).await?;
+			let cores: Vec<_> = get_availability_cores(ctx, leaf)
+				.await?
+				.into_iter()
+				.enumerate()
+				.filter_map(|(index, core_state)| match core_state {
+					CoreState::Occupied(occupied) => Some((CoreIndex(index as u32), occupied)),
+					_ => None,
+				})
+				.collect();
 			tracing::trace!(
 				target: LOG_TARGET,
 				occupied_cores = ?cores,
@@ -153,12 +163,12 @@ impl Requester {
 		ctx: &mut Context,
 		runtime: &mut RuntimeInfo,
 		leaf: Hash,
-		cores: impl IntoIterator<Item = OccupiedCore>,
+		cores: impl IntoIterator<Item = (CoreIndex, OccupiedCore)>,
 	) -> super::Result<()>
 	where
 		Context: SubsystemContext,
 	{
-		for core in cores {
+		for (core_index, core) in cores {
 			match self.fetches.entry(core.candidate_hash) {
 				Entry::Occupied(mut e) =>
 				// Just book keeping - we are already requesting that chunk:
@@ -169,6 +179,14 @@ impl Requester {
 					let tx = self.tx.clone();
 					let metrics = self.metrics.clone();
 
+					// All validators need to agree on the chunk mapping, so it is looked up at the
+					// relay parent of the candidate, just like in availability recovery.
+					let chunk_shuffling = get_availability_chunk_shuffling(
+						ctx.sender(),
+						core.candidate_descriptor.relay_parent,
+					)
+					.await?;
+
 					let task_cfg = self
 						.session_cache
 						.with_session_info(
@@ -178,7 +196,17 @@ impl Requester {
 							// leaf. (Cores are dropped at session boundaries.) At the same time,
 							// only leaves are guaranteed to be fetchable by the state trie.
 							leaf,
-							|info| FetchTaskConfig::new(leaf, &core, tx, metrics, info),
+							|info| {
+								FetchTaskConfig::new(
+									leaf,
+									&core,
+									core_index,
+									chunk_shuffling,
+									tx,
+									metrics,
+									info,
+								)
+							},
 						)
 						.await?;
 
@@ -56,6 +56,9 @@ pub struct SessionInfo {
 	/// validators.
 	pub validator_groups: Vec<Vec<AuthorityDiscoveryId>>,
 
+	/// The number of validators in the session, needed for mapping chunks to validators.
+	pub n_validators: usize,
+
 	/// Information about ourselves:
 	pub our_index: ValidatorIndex,
 
@@ -212,7 +215,13 @@ impl SessionCache {
 				})
 				.collect();
 
-			let info = SessionInfo { validator_groups, our_index, session_index, our_group };
+			let info = SessionInfo {
+				validator_groups,
+				n_validators: info.session_info.validators.len(),
+				our_index,
+				session_index,
+				our_group,
+			};
 			return Ok(Some(info))
 		}
 		return Ok(None)
@@ -31,6 +31,18 @@ pub enum Error {
 	#[error("failed to query session info")]
 	CanceledSessionInfo(#[source] oneshot::Canceled),
 
+	#[error("failed to query the session index")]
+	CanceledSessionIndex(#[source] oneshot::Canceled),
+
+	#[error("failed to query the chunk shuffling setting")]
+	CanceledChunkShuffling(#[source] oneshot::Canceled),
+
+	#[error("failed to query availability cores")]
+	CanceledAvailabilityCores(#[source] oneshot::Canceled),
+
+	#[error("the core of the candidate could not be determined")]
+	UnknownCore,
+
 	#[error("failed to send response")]
 	CanceledResponseSender,
 
@@ -19,7 +19,7 @@
 #![warn(missing_docs)]
 
 use std::{
-	collections::{HashMap, VecDeque},
+	collections::{BTreeMap, HashMap, VecDeque},
 	convert::TryFrom,
 	pin::Pin,
 	time::Duration,
@@ -36,7 +36,10 @@ use futures::{
 use lru::LruCache;
 use rand::seq::SliceRandom;
 
-use polkadot_erasure_coding::{branch_hash, branches, obtain_chunks_v1, recovery_threshold};
+use polkadot_erasure_coding::{
+	branch_hash, branches, obtain_chunks_v1, reconstruct_from_systematic_v1, recovery_threshold,
+	systematic_recovery_threshold, ChunkMapping,
+};
 #[cfg(not(test))]
 use polkadot_node_network_protocol::request_response::CHUNK_REQUEST_TIMEOUT;
 use polkadot_node_network_protocol::{
@@ -47,16 +50,19 @@ use polkadot_node_network_protocol::{
 	IfDisconnected, UnifiedReputationChange as Rep,
 };
 use polkadot_node_primitives::{AvailableData, ErasureChunk};
-use polkadot_node_subsystem_util::request_session_info;
+use polkadot_node_subsystem_util::{
+	request_availability_chunk_shuffling, request_availability_cores,
+	request_session_index_for_child, request_session_info,
+};
 use polkadot_primitives::{
 	v1::{
-		AuthorityDiscoveryId, BlakeTwo256, BlockNumber, CandidateHash, CandidateReceipt,
+		AuthorityDiscoveryId, BlakeTwo256, BlockNumber, CandidateHash, CandidateReceipt, CoreIndex,
 		GroupIndex, Hash, HashT, SessionIndex, ValidatorId, ValidatorIndex,
 	},
 	v2::SessionInfo,
 };
 use polkadot_subsystem::{
-	errors::RecoveryError,
+	errors::{RecoveryError, RuntimeApiError},
 	jaeger,
 	messages::{AvailabilityRecoveryMessage, AvailabilityStoreMessage, NetworkBridgeMessage},
 	overseer::{self, Subsystem},
@@ -102,6 +108,8 @@ const TIMEOUT_START_NEW_REQUESTS: Duration = Duration::from_millis(100);
 /// The Availability Recovery Subsystem.
 pub struct AvailabilityRecoverySubsystem {
 	fast_path: bool,
+	/// Whether to request the systematic chunks before requesting any chunks.
+	systematic_chunks: bool,
 	/// Receiver for available data requests.
 	req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 	/// Metrics for this subsystem.
@@ -129,6 +137,16 @@ struct RequestChunksFromValidators {
 	requesting_chunks: FuturesUndead<Result<Option<ErasureChunk>, (ValidatorIndex, RequestError)>>,
 }
 
+struct RequestSystematicChunks {
+	/// The number of systematic chunks, which are the chunks with the lowest indices.
+	threshold: usize,
+	/// Systematic chunks, which have not been requested yet.
+	unrequested: Vec<ValidatorIndex>,
+	received_chunks: BTreeMap<ValidatorIndex, ErasureChunk>,
+	/// Pending chunk requests with soft timeout.
+	requesting_chunks: FuturesUndead<Result<Option<ErasureChunk>, (ValidatorIndex, RequestError)>>,
+}
+
 struct RecoveryParams {
 	/// Discovery ids of `validators`.
 	validator_authority_keys: Vec<AuthorityDiscoveryId>,
@@ -139,9 +157,15 @@ struct RecoveryParams {
 	/// The number of pieces needed.
 	threshold: usize,
 
+	/// The number of systematic pieces needed, if recovery from systematic chunks is enabled.
+	systematic_threshold: Option<usize>,
+
 	/// A hash of the relevant candidate.
 	candidate_hash: CandidateHash,
 
+	/// The assignment of the chunks of the candidate to `validators`.
+	chunk_mapping: ChunkMapping,
+
 	/// The root of the erasure encoding of the para block.
 	erasure_root: Hash,
 
@@ -154,9 +178,23 @@ struct RecoveryParams {
 /// backers (a.k.a. fast-path), or recover from chunks.
 enum Source {
 	RequestFromBackers(RequestFromBackers),
+	RequestSystematicChunks(RequestSystematicChunks),
 	RequestChunks(RequestChunksFromValidators),
 }
 
+impl Source {
+	/// The source to recover from chunks, preferring the systematic chunks if enabled.
+	fn chunks(params: &RecoveryParams) -> Self {
+		match params.systematic_threshold {
+			Some(threshold) =>
+				Source::RequestSystematicChunks(RequestSystematicChunks::new(threshold)),
+			None => Source::RequestChunks(RequestChunksFromValidators::new(
+				params.validators.len() as _,
+			)),
+		}
+	}
+}
+
 /// A stateful reconstruction of availability data in reference to
 /// a candidate hash.
 struct RecoveryTask<S> {
@@ -263,6 +301,20 @@ impl RequestChunksFromValidators {
 		}
 	}
 
+	/// Continue a recovery with chunks which have been received already.
+	fn with_received_chunks(
+		params: &RecoveryParams,
+		received_chunks: impl IntoIterator<Item = (ValidatorIndex, ErasureChunk)>,
+	) -> Self {
+		let mut this = Self::new(params.validators.len() as _);
+		this.received_chunks.extend(received_chunks);
+
+		let received_chunks = &this.received_chunks;
+		this.shuffling
+			.retain(|v| !received_chunks.contains_key(&params.chunk_mapping.chunk_index(*v)));
+		this
+	}
+
 	fn is_unavailable(&self, params: &RecoveryParams) -> bool {
 		is_unavailable(
 			self.received_chunks.len(),
@@ -311,37 +363,12 @@ impl RequestChunksFromValidators {
 
 		while self.requesting_chunks.len() < num_requests {
 			if let Some(validator_index) = self.shuffling.pop_back() {
-				let validator = params.validator_authority_keys[validator_index.0 as usize].clone();
-				tracing::trace!(
-					target: LOG_TARGET,
-					?validator,
-					?validator_index,
-					candidate_hash = ?params.candidate_hash,
-					"Requesting chunk",
-				);
-
-				// Request data.
-				let raw_request = req_res::v1::ChunkFetchingRequest {
-					candidate_hash: params.candidate_hash,
-					index: validator_index,
-				};
-
-				let (req, res) =
-					OutgoingRequest::new(Recipient::Authority(validator), raw_request.clone());
-				requests.push(Requests::ChunkFetching(req));
-
-				params.metrics.on_chunk_request_issued();
-				let timer = params.metrics.time_chunk_request();
-
-				self.requesting_chunks.push(Box::pin(async move {
-					let _timer = timer;
-					match res.await {
-						Ok(req_res::v1::ChunkFetchingResponse::Chunk(chunk)) =>
-							Ok(Some(chunk.recombine_into_chunk(&raw_request))),
-						Ok(req_res::v1::ChunkFetchingResponse::NoSuchChunk) => Ok(None),
-						Err(e) => Err((validator_index, e)),
-					}
-				}));
+				requests.push(request_chunk(
+					params,
+					validator_index,
+					params.chunk_mapping.chunk_index(validator_index),
+					&mut self.requesting_chunks,
+				));
 			} else {
 				break
 			}
@@ -367,49 +394,21 @@ impl RequestChunksFromValidators {
 			self.total_received_responses += 1;
 
 			match request_result {
-				Ok(Some(chunk)) => {
-					// Check merkle proofs of any received chunks.
-
-					let validator_index = chunk.index;
-
-					if let Ok(anticipated_hash) =
-						branch_hash(&params.erasure_root, chunk.proof(), chunk.index.0 as usize)
-					{
-						let erasure_chunk_hash = BlakeTwo256::hash(&chunk.chunk);
-
-						if erasure_chunk_hash != anticipated_hash {
-							metrics.on_chunk_request_invalid();
-							self.error_count += 1;
-
-							tracing::debug!(
-								target: LOG_TARGET,
-								candidate_hash = ?params.candidate_hash,
-								?validator_index,
-								"Merkle proof mismatch",
-							);
-						} else {
-							metrics.on_chunk_request_succeeded();
+				Ok(Some(chunk)) =>
+					if is_chunk_valid(params, &chunk) {
+						metrics.on_chunk_request_succeeded();
 
-							tracing::trace!(
-								target: LOG_TARGET,
-								candidate_hash = ?params.candidate_hash,
-								?validator_index,
-								"Received valid chunk.",
-							);
-							self.received_chunks.insert(validator_index, chunk);
-						}
-					} else {
-						metrics.on_chunk_request_invalid();
-						self.error_count += 1;
-
-						tracing::debug!(
+						tracing::trace!(
 							target: LOG_TARGET,
 							candidate_hash = ?params.candidate_hash,
-							?validator_index,
-							"Invalid Merkle proof",
+							chunk_index = ?chunk.index,
+							"Received valid chunk.",
 						);
-					}
-				},
+						self.received_chunks.insert(chunk.index, chunk);
+					} else {
+						metrics.on_chunk_request_invalid();
+						self.error_count += 1;
+					},
 				Ok(None) => {
 					metrics.on_chunk_request_no_such_chunk();
 					self.error_count += 1;
@@ -474,7 +473,8 @@ impl RequestChunksFromValidators {
 					// This should either be length 1 or 0. If we had the whole data,
 					// we wouldn't have reached this stage.
 					let chunk_indices: Vec<_> = chunks.iter().map(|c| c.index).collect();
-					self.shuffling.retain(|i| !chunk_indices.contains(i));
+					self.shuffling
+						.retain(|v| !chunk_indices.contains(&params.chunk_mapping.chunk_index(*v)));
 
 					for chunk in chunks {
 						self.received_chunks.insert(chunk.index, chunk);
@@ -559,6 +559,254 @@ impl RequestChunksFromValidators {
 	}
 }
 
+impl RequestSystematicChunks {
+	fn new(threshold: usize) -> Self {
+		RequestSystematicChunks {
+			threshold,
+			unrequested: (0..threshold as u32).rev().map(ValidatorIndex).collect(),
+			received_chunks: BTreeMap::new(),
+			requesting_chunks: FuturesUndead::new(),
+		}
+	}
+
+	/// Request the systematic chunks not requested yet, keeping at most `N_PARALLEL` requests in
+	/// flight.
+	async fn launch_parallel_requests(
+		&mut self,
+		params: &RecoveryParams,
+		sender: &mut impl SubsystemSender,
+	) {
+		let mut requests = Vec::new();
+
+		while self.requesting_chunks.len() < N_PARALLEL {
+			match self.unrequested.pop() {
+				Some(chunk_index) => requests.push(request_chunk(
+					params,
+					params.chunk_mapping.validator_index(chunk_index),
+					chunk_index,
+					&mut self.requesting_chunks,
+				)),
+				None => break,
+			}
+		}
+
+		if !requests.is_empty() {
+			sender
+				.send_message(
+					NetworkBridgeMessage::SendRequests(requests, IfDisconnected::ImmediateError)
+						.into(),
+				)
+				.await;
+		}
+	}
+
+	/// Run this phase to completion.
+	///
+	/// Slow requests are kept alive past `TIMEOUT_START_NEW_REQUESTS`, just like in
+	/// `RequestChunksFromValidators`. Fails with `RecoveryError::Unavailable` as soon as any
+	/// systematic chunk can not be obtained, in which case recovery should continue from any
+	/// chunks.
+	async fn run(
+		&mut self,
+		params: &RecoveryParams,
+		sender: &mut impl SubsystemSender,
+	) -> Result<AvailableData, RecoveryError> {
+		let metrics = &params.metrics;
+
+		// First query the store for any systematic chunks we've got.
+		{
+			let (tx, rx) = oneshot::channel();
+			sender
+				.send_message(
+					AvailabilityStoreMessage::QueryAllChunks(params.candidate_hash, tx).into(),
+				)
+				.await;
+
+			match rx.await {
+				Ok(chunks) => {
+					let threshold = self.threshold;
+					for chunk in chunks.into_iter().filter(|c| (c.index.0 as usize) < threshold) {
+						self.unrequested.retain(|i| *i != chunk.index);
+						self.received_chunks.insert(chunk.index, chunk);
+					}
+				},
+				Err(oneshot::Canceled) => {
+					tracing::warn!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						"Failed to reach the availability store"
+					);
+				},
+			}
+		}
+
+		while self.received_chunks.len() < self.threshold {
+			self.launch_parallel_requests(params, sender).await;
+
+			let validator_index =
+				match self.requesting_chunks.next_with_timeout(TIMEOUT_START_NEW_REQUESTS).await {
+					Some(Ok(Some(chunk))) if is_chunk_valid(params, &chunk) => {
+						metrics.on_chunk_request_succeeded();
+						self.received_chunks.insert(chunk.index, chunk);
+						continue
+					},
+					Some(Ok(Some(chunk))) => {
+						metrics.on_chunk_request_invalid();
+						chunk.index
+					},
+					Some(Ok(None)) => {
+						metrics.on_chunk_request_no_such_chunk();
+						return Err(RecoveryError::Unavailable)
+					},
+					Some(Err((validator_index, e))) => {
+						match e {
+							RequestError::NetworkError(RequestFailure::Network(
+								OutboundFailure::Timeout,
+							)) => metrics.on_chunk_request_timeout(),
+							RequestError::InvalidResponse(_) => metrics.on_chunk_request_invalid(),
+							_ => metrics.on_chunk_request_error(),
+						}
+						validator_index
+					},
+					// Soft timeout, keep waiting for the pending requests.
+					None if self.requesting_chunks.total_len() > 0 => continue,
+					None => {
+						tracing::debug!(
+							target: LOG_TARGET,
+							candidate_hash = ?params.candidate_hash,
+							received = %self.received_chunks.len(),
+							threshold = %self.threshold,
+							"No more systematic chunks to wait for",
+						);
+						return Err(RecoveryError::Unavailable)
+					},
+				};
+
+			tracing::debug!(
+				target: LOG_TARGET,
+				candidate_hash = ?params.candidate_hash,
+				?validator_index,
+				"Failed to obtain systematic chunk",
+			);
+			return Err(RecoveryError::Unavailable)
+		}
+
+		let chunks: Vec<&[u8]> = self.received_chunks.values().map(|c| &c.chunk[..]).collect();
+		match reconstruct_from_systematic_v1(params.validators.len(), &chunks) {
+			Ok(data) =>
+				if reconstructed_data_matches_root(
+					params.validators.len(),
+					&params.erasure_root,
+					&data,
+				) {
+					tracing::trace!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						erasure_root = ?params.erasure_root,
+						"Data recovery from systematic chunks complete",
+					);
+
+					Ok(data)
+				} else {
+					tracing::trace!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						erasure_root = ?params.erasure_root,
+						"Data recovery from systematic chunks - root mismatch",
+					);
+
+					Err(RecoveryError::Invalid)
+				},
+			Err(err) => {
+				tracing::trace!(
+					target: LOG_TARGET,
+					candidate_hash = ?params.candidate_hash,
+					erasure_root = ?params.erasure_root,
+					?err,
+					"Data recovery from systematic chunks error",
+				);
+
+				Err(RecoveryError::Invalid)
+			},
+		}
+	}
+}
+
+/// Issue a request for the chunk with the given index to the given validator.
+///
+/// The response is pushed to `requesting_chunks`, the returned request still needs to be sent.
+fn request_chunk(
+	params: &RecoveryParams,
+	validator_index: ValidatorIndex,
+	chunk_index: ValidatorIndex,
+	requesting_chunks: &mut FuturesUndead<
+		Result<Option<ErasureChunk>, (ValidatorIndex, RequestError)>,
+	>,
+) -> Requests {
+	let validator = params.validator_authority_keys[validator_index.0 as usize].clone();
+	tracing::trace!(
+		target: LOG_TARGET,
+		?validator,
+		?validator_index,
+		?chunk_index,
+		candidate_hash = ?params.candidate_hash,
+		"Requesting chunk",
+	);
+
+	// Request data.
+	let raw_request = req_res::v1::ChunkFetchingRequest {
+		candidate_hash: params.candidate_hash,
+		index: chunk_index,
+	};
+
+	let (req, res) = OutgoingRequest::new(Recipient::Authority(validator), raw_request.clone());
+
+	params.metrics.on_chunk_request_issued();
+	let timer = params.metrics.time_chunk_request();
+
+	requesting_chunks.push(Box::pin(async move {
+		let _timer = timer;
+		match res.await {
+			Ok(req_res::v1::ChunkFetchingResponse::Chunk(chunk)) =>
+				Ok(Some(chunk.recombine_into_chunk(&raw_request))),
+			Ok(req_res::v1::ChunkFetchingResponse::NoSuchChunk) => Ok(None),
+			Err(e) => Err((validator_index, e)),
+		}
+	}));
+
+	Requests::ChunkFetching(req)
+}
+
+/// Check the Merkle proof of a received chunk against the erasure root.
+fn is_chunk_valid(params: &RecoveryParams, chunk: &ErasureChunk) -> bool {
+	let anticipated_hash =
+		match branch_hash(&params.erasure_root, chunk.proof(), chunk.index.0 as usize) {
+			Ok(hash) => hash,
+			Err(_) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					candidate_hash = ?params.candidate_hash,
+					validator_index = ?chunk.index,
+					"Invalid Merkle proof",
+				);
+				return false
+			},
+		};
+
+	let erasure_chunk_hash = BlakeTwo256::hash(&chunk.chunk);
+	if anticipated_hash != erasure_chunk_hash {
+		tracing::debug!(
+			target: LOG_TARGET,
+			candidate_hash = ?params.candidate_hash,
+			validator_index = ?chunk.index,
+			"Merkle proof mismatch",
+		);
+		return false
+	}
+
+	true
+}
+
 const fn is_unavailable(
 	received_chunks: usize,
 	requesting_chunks: usize,
@@ -638,10 +886,22 @@ impl<S: SubsystemSender> RecoveryTask<S> {
 					match from_backers.run(&self.params, &mut self.sender).await {
 						Ok(data) => break Ok(data),
 						Err(RecoveryError::Invalid) => break Err(RecoveryError::Invalid),
-						Err(RecoveryError::Unavailable) =>
-							self.source = Source::RequestChunks(RequestChunksFromValidators::new(
-								self.params.validators.len() as _,
-							)),
+						Err(RecoveryError::Unavailable) => self.source = Source::chunks(&self.params),
+					}
+				},
+				Source::RequestSystematicChunks(ref mut systematic) => {
+					match systematic.run(&self.params, &mut self.sender).await {
+						Ok(data) => break Ok(data),
+						Err(RecoveryError::Invalid) => break Err(RecoveryError::Invalid),
+						Err(RecoveryError::Unavailable) => {
+							let received_chunks = std::mem::take(&mut systematic.received_chunks);
+							self.source = Source::RequestChunks(
+								RequestChunksFromValidators::with_received_chunks(
+									&self.params,
+									received_chunks,
+								),
+							);
+						},
 					}
 				},
 				Source::RequestChunks(ref mut from_all) =>
@@ -792,9 +1052,12 @@ async fn handle_signal(state: &mut State, signal: OverseerSignal) -> SubsystemRe
 async fn launch_recovery_task<Context>(
 	state: &mut State,
 	ctx: &mut Context,
+	session_index: SessionIndex,
 	session_info: SessionInfo,
 	receipt: CandidateReceipt,
 	backing_group: Option<GroupIndex>,
+	core_index: Option<CoreIndex>,
+	systematic_chunks: bool,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
 ) -> error::Result<()>
@@ -803,12 +1066,28 @@ where
 	Context: overseer::SubsystemContext<Message = AvailabilityRecoveryMessage>,
 {
 	let candidate_hash = receipt.hash();
+	let n_validators = session_info.validators.len();
+	let chunk_mapping = request_chunk_mapping(
+		ctx,
+		state.live_block.1,
+		&receipt,
+		session_index,
+		core_index,
+		n_validators,
+	)
+	.await?;
 
 	let params = RecoveryParams {
 		validator_authority_keys: session_info.discovery_keys.clone(),
 		validators: session_info.validators.clone(),
-		threshold: recovery_threshold(session_info.validators.len())?,
+		threshold: recovery_threshold(n_validators)?,
+		systematic_threshold: if systematic_chunks {
+			Some(systematic_recovery_threshold(n_validators)?)
+		} else {
+			None
+		},
 		candidate_hash,
+		chunk_mapping,
 		erasure_root: receipt.descriptor.erasure_root,
 		metrics: metrics.clone(),
 	};
@@ -816,9 +1095,7 @@ where
 	let phase = backing_group
 		.and_then(|g| session_info.validator_groups.get(g.0 as usize))
 		.map(|group| Source::RequestFromBackers(RequestFromBackers::new(group.clone())))
-		.unwrap_or_else(|| {
-			Source::RequestChunks(RequestChunksFromValidators::new(params.validators.len() as _))
-		});
+		.unwrap_or_else(|| Source::chunks(&params));
 
 	let recovery_task = RecoveryTask { sender: ctx.sender().clone(), params, source: phase };
 
@@ -848,6 +1125,8 @@ async fn handle_recover<Context>(
 	receipt: CandidateReceipt,
 	session_index: SessionIndex,
 	backing_group: Option<GroupIndex>,
+	core_index: Option<CoreIndex>,
+	systematic_chunks: bool,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
 ) -> error::Result<()>
@@ -892,9 +1171,12 @@ where
 			launch_recovery_task(
 				state,
 				ctx,
+				session_index,
 				session_info,
 				receipt,
 				backing_group,
+				core_index,
+				systematic_chunks,
 				response_sender,
 				metrics,
 			)
@@ -909,6 +1191,82 @@ where
 	}
 }
 
+/// Determine which validator holds which chunk of the given candidate.
+///
+/// If the core the candidate occupied is not known, it is taken to be the core the para of the
+/// candidate was scheduled on at the relay parent.
+async fn request_chunk_mapping<Context>(
+	ctx: &mut Context,
+	live_block: Hash,
+	receipt: &CandidateReceipt,
+	session_index: SessionIndex,
+	core_index: Option<CoreIndex>,
+	n_validators: usize,
+) -> error::Result<ChunkMapping>
+where
+	Context: SubsystemContext<Message = AvailabilityRecoveryMessage>,
+	Context: overseer::SubsystemContext<Message = AvailabilityRecoveryMessage>,
+{
+	let relay_parent = receipt.descriptor.relay_parent;
+	let chunk_shuffling = match request_chunk_shuffling(ctx, relay_parent).await? {
+		Ok(chunk_shuffling) => chunk_shuffling,
+		// The state of the relay parent might have been pruned already. The setting only changes
+		// on session boundaries, so the live block agrees with the relay parent as long as it is
+		// still in the session of the candidate.
+		Err(err) => {
+			let live_session = request_session_index_for_child(live_block, ctx.sender())
+				.await
+				.await
+				.map_err(error::Error::CanceledSessionIndex)??;
+
+			if live_session != session_index {
+				return Err(err.into())
+			}
+
+			request_chunk_shuffling(ctx, live_block).await??
+		},
+	};
+
+	if !chunk_shuffling {
+		return Ok(ChunkMapping::identity(n_validators))
+	}
+
+	let core_index = match core_index {
+		Some(core_index) => core_index,
+		None => request_availability_cores(relay_parent, ctx.sender())
+			.await
+			.await
+			.map_err(error::Error::CanceledAvailabilityCores)??
+			.iter()
+			.position(|core| core.para_id() == Some(receipt.descriptor.para_id))
+			.map(|i| CoreIndex(i as u32))
+			.ok_or(error::Error::UnknownCore)?,
+	};
+
+	Ok(ChunkMapping::new(chunk_shuffling, n_validators, &relay_parent, core_index))
+}
+
+/// Whether chunks are shuffled according to the state at the given block.
+///
+/// Runtimes which don't support the setting yet never shuffle.
+async fn request_chunk_shuffling<Context>(
+	ctx: &mut Context,
+	block: Hash,
+) -> error::Result<Result<bool, RuntimeApiError>>
+where
+	Context: SubsystemContext<Message = AvailabilityRecoveryMessage>,
+	Context: overseer::SubsystemContext<Message = AvailabilityRecoveryMessage>,
+{
+	match request_availability_chunk_shuffling(block, ctx.sender())
+		.await
+		.await
+		.map_err(error::Error::CanceledChunkShuffling)?
+	{
+		Err(RuntimeApiError::NotSupported { .. }) => Ok(Ok(false)),
+		res => Ok(res),
+	}
+}
+
 /// Queries a chunk from av-store.
 async fn query_full_data<Context>(
 	ctx: &mut Context,
@@ -932,7 +1290,7 @@ impl AvailabilityRecoverySubsystem {
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { fast_path: true, req_receiver, metrics }
+		Self { fast_path: true, systematic_chunks: false, req_receiver, metrics }
 	}
 
 	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests only chunks
@@ -940,7 +1298,16 @@ impl AvailabilityRecoverySubsystem {
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { fast_path: false, req_receiver, metrics }
+		Self { fast_path: false, systematic_chunks: false, req_receiver, metrics }
+	}
+
+	/// Create a new instance of `AvailabilityRecoverySubsystem` which first requests the
+	/// systematic chunks, falling back to requesting any chunks.
+	pub fn with_systematic_chunks(
+		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		metrics: Metrics,
+	) -> Self {
+		Self { fast_path: false, systematic_chunks: true, req_receiver, metrics }
 	}
 
 	async fn run<Context>(self, mut ctx: Context) -> SubsystemResult<()>
@@ -949,7 +1316,7 @@ impl AvailabilityRecoverySubsystem {
 		Context: overseer::SubsystemContext<Message = AvailabilityRecoveryMessage>,
 	{
 		let mut state = State::default();
-		let Self { fast_path, mut req_receiver, metrics } = self;
+		let Self { fast_path, systematic_chunks, mut req_receiver, metrics } = self;
 
 		loop {
 			let recv_req = req_receiver.recv(|| vec![COST_INVALID_REQUEST]).fuse();
@@ -969,6 +1336,7 @@ impl AvailabilityRecoverySubsystem {
 									receipt,
 									session_index,
 									maybe_backing_group,
+									maybe_core_index,
 									response_sender,
 								) => {
 									if let Err(e) = handle_recover(
@@ -977,6 +1345,8 @@ impl AvailabilityRecoverySubsystem {
 										receipt,
 										session_index,
 										maybe_backing_group.filter(|_| fast_path),
+										maybe_core_index,
+										systematic_chunks,
 										response_sender,
 										&metrics,
 									).await {
@@ -110,6 +110,41 @@ fn test_harness_chunks_only<T: Future<Output = (VirtualOverseer, RequestResponse
 	.unwrap();
 }
 
+fn test_harness_systematic_chunks<T: Future<Output = (VirtualOverseer, RequestResponseConfig)>>(
+	test: impl FnOnce(VirtualOverseer, RequestResponseConfig) -> T,
+) {
+	let _ = env_logger::builder()
+		.is_test(true)
+		.filter(Some("polkadot_availability_recovery"), log::LevelFilter::Trace)
+		.try_init();
+
+	let pool = sp_core::testing::TaskExecutor::new();
+
+	let (context, virtual_overseer) = make_subsystem_context(pool.clone());
+
+	let (collation_req_receiver, req_cfg) = IncomingRequest::get_config_receiver();
+	let subsystem = AvailabilityRecoverySubsystem::with_systematic_chunks(
+		collation_req_receiver,
+		Metrics::new_dummy(),
+	);
+	let subsystem = subsystem.run(context);
+
+	let test_fut = test(virtual_overseer, req_cfg);
+
+	futures::pin_mut!(test_fut);
+	futures::pin_mut!(subsystem);
+
+	executor::block_on(future::join(
+		async move {
+			let (mut overseer, _req_cfg) = test_fut.await;
+			overseer_signal(&mut overseer, OverseerSignal::Conclude).await;
+		},
+		subsystem,
+	))
+	.1
+	.unwrap();
+}
+
 const TIMEOUT: Duration = Duration::from_millis(300);
 
 macro_rules! delay {
@@ -184,6 +219,9 @@ struct TestState {
 
 	available_data: AvailableData,
 	chunks: Vec<ErasureChunk>,
+
+	chunk_shuffling: bool,
+	core_index: CoreIndex,
 }
 
 impl TestState {
@@ -191,11 +229,39 @@ impl TestState {
 		recovery_threshold(self.validators.len()).unwrap()
 	}
 
+	fn systematic_threshold(&self) -> usize {
+		systematic_recovery_threshold(self.validators.len()).unwrap()
+	}
+
 	fn impossibility_threshold(&self) -> usize {
 		self.validators.len() - self.threshold() + 1
 	}
 
+	fn chunk_mapping(&self) -> ChunkMapping {
+		ChunkMapping::new(
+			self.chunk_shuffling,
+			self.validators.len(),
+			&self.candidate.descriptor.relay_parent,
+			self.core_index,
+		)
+	}
+
 	async fn test_runtime_api(&self, virtual_overseer: &mut VirtualOverseer) {
+		self.test_session_info(virtual_overseer).await;
+
+		assert_matches!(
+			overseer_recv(virtual_overseer).await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::AvailabilityChunkShuffling(tx)
+			)) => {
+				assert_eq!(relay_parent, self.candidate.descriptor.relay_parent);
+				tx.send(Ok(self.chunk_shuffling)).unwrap();
+			}
+		);
+	}
+
+	async fn test_session_info(&self, virtual_overseer: &mut VirtualOverseer) {
 		assert_matches!(
 			overseer_recv(virtual_overseer).await,
 			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
@@ -228,6 +294,65 @@ impl TestState {
 		);
 	}
 
+	/// Answer the chunk shuffling query as if the state of the relay parent was pruned, with the
+	/// live block being in the given session.
+	async fn test_runtime_api_pruned_relay_parent(
+		&self,
+		virtual_overseer: &mut VirtualOverseer,
+		live_session: SessionIndex,
+	) {
+		self.test_session_info(virtual_overseer).await;
+
+		#[derive(Debug)]
+		struct StatePruned;
+		impl std::error::Error for StatePruned {}
+		impl std::fmt::Display for StatePruned {
+			fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(f, "State already discarded")
+			}
+		}
+
+		assert_matches!(
+			overseer_recv(virtual_overseer).await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::AvailabilityChunkShuffling(tx)
+			)) => {
+				assert_eq!(relay_parent, self.candidate.descriptor.relay_parent);
+				tx.send(Err(RuntimeApiError::Execution {
+					runtime_api_name: "availability_chunk_shuffling",
+					source: Arc::new(StatePruned),
+				})).unwrap();
+			}
+		);
+
+		assert_matches!(
+			overseer_recv(virtual_overseer).await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::SessionIndexForChild(tx)
+			)) => {
+				assert_eq!(relay_parent, self.current);
+				tx.send(Ok(live_session)).unwrap();
+			}
+		);
+
+		if live_session != self.session_index {
+			return
+		}
+
+		assert_matches!(
+			overseer_recv(virtual_overseer).await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::AvailabilityChunkShuffling(tx)
+			)) => {
+				assert_eq!(relay_parent, self.current);
+				tx.send(Ok(self.chunk_shuffling)).unwrap();
+			}
+		);
+	}
+
 	async fn respond_to_available_data_query(
 		&self,
 		virtual_overseer: &mut VirtualOverseer,
@@ -295,10 +420,19 @@ impl TestState {
 							Requests::ChunkFetching(req) => {
 								assert_eq!(req.payload.candidate_hash, candidate_hash);
 
-								let validator_index = req.payload.index.0 as usize;
-								let available_data = match who_has(validator_index) {
+								let chunk_index = req.payload.index.0 as usize;
+								let validator_index = self.chunk_mapping().validator_index(req.payload.index);
+								assert_eq!(
+									req.peer,
+									Recipient::Authority(
+										self.validator_authority_id[validator_index.0 as usize].clone()
+									),
+									"Chunk is requested from the validator holding it",
+								);
+
+								let available_data = match who_has(chunk_index) {
 									Has::No => Ok(None),
-									Has::Yes => Ok(Some(self.chunks[validator_index].clone().into())),
+									Has::Yes => Ok(Some(self.chunks[chunk_index].clone().into())),
 									Has::NetworkError(e) => Err(e),
 									Has::DoesNotReturn => {
 										senders.push(req.pending_response);
@@ -463,6 +597,8 @@ impl Default for TestState {
 			persisted_validation_data,
 			available_data,
 			chunks,
+			chunk_shuffling: false,
+			core_index: CoreIndex(0),
 		}
 	}
 }
@@ -491,6 +627,7 @@ fn availability_is_recovered_from_chunks_if_no_group_provided() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -528,6 +665,7 @@ fn availability_is_recovered_from_chunks_if_no_group_provided() {
 				new_candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -577,6 +715,7 @@ fn availability_is_recovered_from_chunks_even_if_backing_group_supplied_if_chunk
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -614,6 +753,7 @@ fn availability_is_recovered_from_chunks_even_if_backing_group_supplied_if_chunk
 				new_candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -663,6 +803,7 @@ fn bad_merkle_path_leads_to_recovery_error() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -721,6 +862,7 @@ fn wrong_chunk_index_leads_to_recovery_error() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -794,6 +936,7 @@ fn invalid_erasure_coding_leads_to_invalid_error() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -843,6 +986,7 @@ fn fast_path_backing_group_recovers() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -893,6 +1037,7 @@ fn no_answers_in_fast_path_causes_chunk_requests() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -955,6 +1100,7 @@ fn task_canceled_when_receivers_dropped() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -997,6 +1143,7 @@ fn chunks_retry_until_all_nodes_respond() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1058,6 +1205,7 @@ fn not_returning_requests_wont_stall_retrieval() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1130,6 +1278,7 @@ fn all_not_returning_requests_still_recovers_on_return() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1207,6 +1356,7 @@ fn returns_early_if_we_have_the_data() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -1244,6 +1394,7 @@ fn does_not_query_local_validator() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -1309,3 +1460,331 @@ fn parallel_request_calculation_works_as_expected() {
 	// With error count zero - we should fetch exactly as needed:
 	assert_eq!(phase.get_desired_request_count(threshold), threshold - phase.received_chunks.len());
 }
+
+#[test]
+fn availability_is_recovered_from_systematic_chunks() {
+	let test_state = TestState::default();
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				None,
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		let systematic_threshold = test_state.systematic_threshold();
+		test_state
+			.test_chunk_requests(candidate_hash, &mut virtual_overseer, systematic_threshold, |i| {
+				assert!(i < systematic_threshold, "Only systematic chunks are requested");
+				Has::Yes
+			})
+			.await;
+
+		// Recovered data should match the original one.
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		(virtual_overseer, req_cfg)
+	});
+}
+
+#[test]
+fn systematic_recovery_falls_back_to_regular_chunks() {
+	let test_state = TestState::default();
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				None,
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		// None of the systematic chunks are available.
+		test_state
+			.test_chunk_requests(
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.systematic_threshold(),
+				|_| Has::No,
+			)
+			.await;
+
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		test_state
+			.test_chunk_requests(
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.threshold(),
+				|_| Has::Yes,
+			)
+			.await;
+
+		// Recovered data should match the original one.
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		(virtual_overseer, req_cfg)
+	});
+}
+
+#[test]
+fn systematic_recovery_waits_for_slow_chunks() {
+	let test_state = TestState::default();
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				None,
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		let mut senders = test_state
+			.test_chunk_requests(
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.systematic_threshold(),
+				|i| if i == 0 { Has::DoesNotReturn } else { Has::Yes },
+			)
+			.await;
+
+		// Passing the soft timeout doesn't make recovery fall back to regular chunks.
+		assert!(virtual_overseer.recv().timeout(TIMEOUT_START_NEW_REQUESTS * 3).await.is_none());
+
+		let response = req_res::v1::ChunkFetchingResponse::from(Some(test_state.chunks[0].clone()));
+		let _ = senders.pop().unwrap().send(Ok(response.encode()));
+
+		// Recovered data should match the original one.
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		(virtual_overseer, req_cfg)
+	});
+}
+
+#[test]
+fn availability_is_recovered_from_shuffled_systematic_chunks() {
+	let mut test_state = TestState::default();
+	test_state.chunk_shuffling = true;
+	test_state.core_index = CoreIndex(1);
+	assert_ne!(test_state.chunk_mapping(), ChunkMapping::identity(test_state.validators.len()));
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				Some(test_state.core_index),
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		let systematic_threshold = test_state.systematic_threshold();
+		test_state
+			.test_chunk_requests(candidate_hash, &mut virtual_overseer, systematic_threshold, |i| {
+				assert!(i < systematic_threshold, "Only systematic chunks are requested");
+				Has::Yes
+			})
+			.await;
+
+		// Recovered data should match the original one.
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		(virtual_overseer, req_cfg)
+	});
+}
+
+#[test]
+fn chunk_shuffling_is_read_at_the_live_block_of_the_same_session() {
+	let mut test_state = TestState::default();
+	test_state.chunk_shuffling = true;
+	test_state.core_index = CoreIndex(1);
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				Some(test_state.core_index),
+				tx,
+			),
+		)
+		.await;
+
+		test_state
+			.test_runtime_api_pruned_relay_parent(&mut virtual_overseer, test_state.session_index)
+			.await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		// Chunks are requested according to the shuffled mapping.
+		test_state
+			.test_chunk_requests(
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.systematic_threshold(),
+				|_| Has::Yes,
+			)
+			.await;
+
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		(virtual_overseer, req_cfg)
+	});
+}
+
+#[test]
+fn chunk_shuffling_is_not_guessed_for_pruned_relay_parent_of_past_session() {
+	let mut test_state = TestState::default();
+	test_state.chunk_shuffling = true;
+	test_state.core_index = CoreIndex(1);
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				Some(test_state.core_index),
+				tx,
+			),
+		)
+		.await;
+
+		test_state
+			.test_runtime_api_pruned_relay_parent(
+				&mut virtual_overseer,
+				test_state.session_index + 1,
+			)
+			.await;
+
+		// The mapping can't be determined, so the request is dropped.
+		assert!(rx.await.is_err());
+		(virtual_overseer, req_cfg)
+	});
+}
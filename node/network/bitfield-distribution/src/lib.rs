@@ -157,6 +157,7 @@ impl PerRelayParentData {
 const LOG_TARGET: &str = "parachain::bitfield-distribution";
 
 /// The bitfield distribution subsystem.
+#[derive(Clone)]
 pub struct BitfieldDistribution {
 	metrics: Metrics,
 }
@@ -20,7 +20,7 @@
 #![deny(missing_docs, unused_crate_dependencies)]
 #![recursion_limit = "256"]
 
-use std::time::Duration;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
 
 use futures::{FutureExt, TryFutureExt};
 
@@ -30,7 +30,7 @@ use polkadot_node_network_protocol::{
 	request_response::{v1 as request_v1, IncomingRequestReceiver},
 	PeerId, UnifiedReputationChange as Rep,
 };
-use polkadot_primitives::v1::CollatorPair;
+use polkadot_primitives::v1::{CollatorId, CollatorPair, Id as ParaId};
 
 use polkadot_subsystem::{
 	errors::SubsystemError,
@@ -72,6 +72,10 @@ pub enum ProtocolSide {
 		keystore: SyncCryptoStorePtr,
 		/// An eviction policy for inactive peers or validators.
 		eviction_policy: CollatorEvictionPolicy,
+		/// Where to keep the scores of collators across restarts, if at all.
+		scoreboard_path: Option<PathBuf>,
+		/// Collators per para whose advertisements are fetched before all others.
+		preferred_collators: HashMap<ParaId, Vec<CollatorId>>,
 		/// Prometheus metrics for validators.
 		metrics: validator_side::Metrics,
 	},
@@ -104,8 +108,22 @@ impl CollatorProtocolSubsystem {
 		Context: SubsystemContext<Message = CollatorProtocolMessage>,
 	{
 		match self.protocol_side {
-			ProtocolSide::Validator { keystore, eviction_policy, metrics } =>
-				validator_side::run(ctx, keystore, eviction_policy, metrics).await,
+			ProtocolSide::Validator {
+				keystore,
+				eviction_policy,
+				scoreboard_path,
+				preferred_collators,
+				metrics,
+			} =>
+				validator_side::run(
+					ctx,
+					keystore,
+					eviction_policy,
+					scoreboard_path,
+					preferred_collators,
+					metrics,
+				)
+				.await,
 			ProtocolSide::Collator(local_peer_id, collator_pair, req_receiver, metrics) =>
 				collator_side::run(ctx, local_peer_id, collator_pair, req_receiver, metrics).await,
 		}
@@ -25,6 +25,7 @@ use futures::{
 use futures_timer::Delay;
 use std::{
 	collections::{hash_map::Entry, HashMap, HashSet},
+	path::PathBuf,
 	sync::Arc,
 	task::Poll,
 	time::{Duration, Instant},
@@ -58,6 +59,9 @@ use crate::error::FatalResult;
 
 use super::{modify_reputation, Result, LOG_TARGET};
 
+mod scoreboard;
+use scoreboard::{ScoreEvent, Scoreboard};
+
 #[cfg(test)]
 mod tests;
 
@@ -517,10 +521,29 @@ struct CollationsPerRelayParent {
 	waiting_collation: Option<CollatorId>,
 	/// Collation that were advertised to us, but we did not yet fetch.
 	unfetched_collations: Vec<(PendingCollation, CollatorId)>,
+	/// Collations that were advertised to us by preferred collators, but we did not yet fetch.
+	///
+	/// These are fetched before any of the `unfetched_collations`.
+	unfetched_preferred_collations: Vec<(PendingCollation, CollatorId)>,
 }
 
 impl CollationsPerRelayParent {
-	/// Returns the next collation to fetch from the `unfetched_collations`.
+	/// Note an advertised collation we are not fetching yet.
+	fn add_unfetched_collation(
+		&mut self,
+		pending_collation: PendingCollation,
+		collator_id: CollatorId,
+		preferred: bool,
+	) {
+		if preferred {
+			self.unfetched_preferred_collations.push((pending_collation, collator_id));
+		} else {
+			self.unfetched_collations.push((pending_collation, collator_id));
+		}
+	}
+
+	/// Returns the next collation to fetch from the `unfetched_collations`, preferring the ones
+	/// advertised by preferred collators.
 	///
 	/// This will reset the status back to `Waiting` using [`CollationStatus::back_to_waiting`].
 	///
@@ -547,7 +570,10 @@ impl CollationsPerRelayParent {
 			// We don't need to fetch any other collation when we already have seconded one.
 			CollationStatus::Seconded => None,
 			CollationStatus::Waiting => {
-				let next = self.unfetched_collations.pop();
+				let next = self
+					.unfetched_preferred_collations
+					.pop()
+					.or_else(|| self.unfetched_collations.pop());
 				self.waiting_collation = next.as_ref().map(|(_, collator_id)| collator_id.clone());
 				next
 			},
@@ -597,6 +623,21 @@ struct State {
 
 	/// Keep track of all pending candidate collations
 	pending_candidates: HashMap<Hash, CollationEvent>,
+
+	/// Scores of the collators we fetched collations from.
+	scoreboard: Scoreboard,
+
+	/// Collators per para whose advertisements are fetched first.
+	preferred_collators: HashMap<ParaId, HashSet<CollatorId>>,
+}
+
+impl State {
+	/// Whether the collator is one of the preferred collators of the para.
+	fn is_preferred(&self, para_id: &ParaId, collator_id: &CollatorId) -> bool {
+		self.preferred_collators
+			.get(para_id)
+			.map_or(false, |collators| collators.contains(collator_id))
+	}
 }
 
 // O(n) search for collator ID by iterating through the peers map. This should be fast enough
@@ -826,6 +867,7 @@ async fn process_incoming_peer_message<Context>(
 					peer_id = ?origin,
 					?collator_id,
 					?para_id,
+					score = ?state.scoreboard.score(&para_id, &collator_id),
 					"Declared as collator for current para",
 				);
 
@@ -880,13 +922,14 @@ async fn process_incoming_peer_message<Context>(
 					);
 
 					let pending_collation = PendingCollation::new(relay_parent, &para_id, &origin);
+					let preferred = state.is_preferred(&para_id, &id);
 
 					let collations =
 						state.collations_per_relay_parent.entry(relay_parent).or_default();
 
 					match collations.status {
 						CollationStatus::Fetching | CollationStatus::WaitingOnValidation =>
-							collations.unfetched_collations.push((pending_collation, id)),
+							collations.add_unfetched_collation(pending_collation, id, preferred),
 						CollationStatus::Waiting => {
 							collations.status = CollationStatus::Fetching;
 							collations.waiting_collation = Some(id.clone());
@@ -1067,7 +1110,8 @@ async fn process_msg<Context>(
 		Seconded(parent, stmt) => {
 			if let Some(collation_event) = state.pending_candidates.remove(&parent) {
 				let (collator_id, pending_collation) = collation_event;
-				let PendingCollation { relay_parent, peer_id, .. } = pending_collation;
+				let PendingCollation { relay_parent, para_id, peer_id, .. } = pending_collation;
+				state.scoreboard.note(para_id, collator_id.clone(), ScoreEvent::Seconded);
 				note_good_collation(ctx, &state.peer_data, collator_id).await;
 				notify_collation_seconded(ctx, peer_id, relay_parent, stmt).await;
 
@@ -1083,11 +1127,11 @@ async fn process_msg<Context>(
 			}
 		},
 		Invalid(parent, candidate_receipt) => {
-			let id = match state.pending_candidates.entry(parent) {
+			let (id, pending_collation) = match state.pending_candidates.entry(parent) {
 				Entry::Occupied(entry)
 					if entry.get().1.commitments_hash ==
 						Some(candidate_receipt.commitments_hash) =>
-					entry.remove().0,
+					entry.remove(),
 				Entry::Occupied(_) => {
 					tracing::error!(
 						target: LOG_TARGET,
@@ -1100,6 +1144,9 @@ async fn process_msg<Context>(
 				Entry::Vacant(_) => return,
 			};
 
+			state
+				.scoreboard
+				.note(pending_collation.para_id, id.clone(), ScoreEvent::Invalid);
 			report_collator(ctx, &state.peer_data, id.clone()).await;
 
 			dequeue_next_collation_and_fetch(ctx, state, parent, id).await;
@@ -1131,13 +1178,23 @@ pub(crate) async fn run<Context>(
 	mut ctx: Context,
 	keystore: SyncCryptoStorePtr,
 	eviction_policy: crate::CollatorEvictionPolicy,
+	scoreboard_path: Option<PathBuf>,
+	preferred_collators: HashMap<ParaId, Vec<CollatorId>>,
 	metrics: Metrics,
 ) -> FatalResult<()>
 where
 	Context: overseer::SubsystemContext<Message = CollatorProtocolMessage>,
 	Context: SubsystemContext<Message = CollatorProtocolMessage>,
 {
-	let mut state = State { metrics, ..Default::default() };
+	let mut state = State {
+		metrics,
+		scoreboard: Scoreboard::load(scoreboard_path),
+		preferred_collators: preferred_collators
+			.into_iter()
+			.map(|(para_id, collators)| (para_id, collators.into_iter().collect()))
+			.collect(),
+		..Default::default()
+	};
 
 	let next_inactivity_stream = infinite_stream(ACTIVITY_POLL);
 	futures::pin_mut!(next_inactivity_stream);
@@ -1164,6 +1221,7 @@ where
 			}
 			_ = next_inactivity_stream.next() => {
 				disconnect_inactive_peers(&mut ctx, &eviction_policy, &state.peer_data).await;
+				state.scoreboard.persist();
 			}
 			res = state.collation_fetches.select_next_some() => {
 				handle_collation_fetched_result(&mut ctx, &mut state, res).await;
@@ -1183,6 +1241,8 @@ where
 					&mut state.requested_collations,
 					&state.metrics,
 					&state.span_per_relay_parent,
+					&state.peer_data,
+					&mut state.scoreboard,
 				).await;
 
 				for (peer_id, rep) in reputation_changes {
@@ -1192,6 +1252,8 @@ where
 		}
 	}
 
+	state.scoreboard.persist();
+
 	Ok(())
 }
 
@@ -1199,6 +1261,8 @@ async fn poll_requests(
 	requested_collations: &mut HashMap<PendingCollation, PerRequest>,
 	metrics: &Metrics,
 	span_per_relay_parent: &HashMap<Hash, PerLeafSpan>,
+	peer_data: &HashMap<PeerId, PeerData>,
+	scoreboard: &mut Scoreboard,
 ) -> Vec<(PeerId, Rep)> {
 	let mut retained_requested = HashSet::new();
 	let mut reputation_changes = Vec::new();
@@ -1211,8 +1275,21 @@ async fn poll_requests(
 		if !result.is_ready() {
 			retained_requested.insert(pending_collation.clone());
 		}
-		if let CollationFetchResult::Error(Some(rep)) = result {
-			reputation_changes.push((pending_collation.peer_id.clone(), rep));
+		match result {
+			CollationFetchResult::Error(Some(rep)) =>
+				reputation_changes.push((pending_collation.peer_id.clone(), rep)),
+			CollationFetchResult::TimedOut => {
+				let collator_id =
+					peer_data.get(&pending_collation.peer_id).and_then(|d| d.collator_id());
+				if let Some(collator_id) = collator_id {
+					scoreboard.note(
+						pending_collation.para_id,
+						collator_id.clone(),
+						ScoreEvent::TimedOut,
+					);
+				}
+			},
+			_ => {},
 		}
 	}
 	requested_collations.retain(|k, _| retained_requested.contains(k));
@@ -1331,6 +1408,8 @@ enum CollationFetchResult {
 	Pending,
 	/// The collation was fetched successfully.
 	Success,
+	/// Fetching the collation timed out.
+	TimedOut,
 	/// An error occurred when fetching a collation or it was invalid.
 	/// A given reputation change should be applied to the peer.
 	Error(Option<Rep>),
@@ -1391,7 +1470,7 @@ async fn poll_collation_response(
 				);
 				// For now we don't want to change reputation on timeout, to mitigate issues like
 				// this: https://github.com/paritytech/polkadot/issues/4617
-				CollationFetchResult::Error(None)
+				CollationFetchResult::TimedOut
 			},
 			Err(RequestError::NetworkError(err)) => {
 				tracing::debug!(
@@ -0,0 +1,163 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per para scores of the collators we fetched collations from.
+//!
+//! Unlike peer set reputation, the scores are kept across restarts by writing them to a file.
+//! They are informational only, i.e. logged and available to operators, but they don't affect
+//! which collations are fetched.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use parity_scale_codec::{Decode, Encode};
+
+use polkadot_primitives::v1::{CollatorId, Id as ParaId};
+
+use crate::LOG_TARGET;
+
+/// The maximum number of collators we keep scores for per para.
+///
+/// Creating collator keys is free, so without a limit a spammer could grow the scoreboard
+/// indefinitely.
+pub const MAX_COLLATORS_PER_PARA: usize = 1000;
+
+/// What became of the collations fetched from a collator.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct CollatorScore {
+	/// Number of collations we seconded.
+	pub seconded: u32,
+	/// Number of collations found to be invalid.
+	pub invalid: u32,
+	/// Number of collation fetches that timed out.
+	pub timed_out: u32,
+}
+
+impl CollatorScore {
+	/// The seconded collations minus the invalid collations and the timed out fetches.
+	pub fn net(&self) -> i64 {
+		i64::from(self.seconded) - i64::from(self.invalid) - i64::from(self.timed_out)
+	}
+}
+
+/// An event changing the score of a collator.
+#[derive(Debug, Clone, Copy)]
+pub enum ScoreEvent {
+	/// A collation of the collator got seconded.
+	Seconded,
+	/// A collation of the collator was found to be invalid.
+	Invalid,
+	/// Fetching a collation from the collator timed out.
+	TimedOut,
+}
+
+/// The scores of collators, by para.
+#[derive(Default)]
+pub struct Scoreboard {
+	scores: BTreeMap<ParaId, BTreeMap<CollatorId, CollatorScore>>,
+	/// Where to persist the scores, if at all.
+	path: Option<PathBuf>,
+	/// Whether the scores changed since they were last persisted.
+	dirty: bool,
+}
+
+impl Scoreboard {
+	/// Load the scores persisted at `path`, if any.
+	///
+	/// A missing or unreadable file results in an empty scoreboard.
+	pub fn load(path: Option<PathBuf>) -> Self {
+		let scores = match path.as_ref().map(std::fs::read) {
+			None => Default::default(),
+			Some(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => Default::default(),
+			Some(Err(e)) => {
+				tracing::warn!(
+					target: LOG_TARGET,
+					err = ?e,
+					?path,
+					"Failed to read collator scores, starting afresh",
+				);
+				Default::default()
+			},
+			Some(Ok(bytes)) => Decode::decode(&mut &bytes[..]).unwrap_or_else(|e| {
+				tracing::warn!(
+					target: LOG_TARGET,
+					err = ?e,
+					?path,
+					"Failed to decode collator scores, starting afresh",
+				);
+				Default::default()
+			}),
+		};
+
+		Scoreboard { scores, path, dirty: false }
+	}
+
+	/// The score of a collator of the given para.
+	pub fn score(&self, para_id: &ParaId, collator_id: &CollatorId) -> CollatorScore {
+		self.scores
+			.get(para_id)
+			.and_then(|scores| scores.get(collator_id))
+			.copied()
+			.unwrap_or_default()
+	}
+
+	/// Note an event changing the score of a collator of the given para.
+	pub fn note(&mut self, para_id: ParaId, collator_id: CollatorId, event: ScoreEvent) {
+		let scores = self.scores.entry(para_id).or_default();
+		if scores.len() >= MAX_COLLATORS_PER_PARA && !scores.contains_key(&collator_id) {
+			// Make room by forgetting the collator with the worst score.
+			if let Some(evicted) = collator_with_worst_score(scores) {
+				scores.remove(&evicted);
+			}
+		}
+		let score = scores.entry(collator_id).or_default();
+
+		match event {
+			ScoreEvent::Seconded => score.seconded = score.seconded.saturating_add(1),
+			ScoreEvent::Invalid => score.invalid = score.invalid.saturating_add(1),
+			ScoreEvent::TimedOut => score.timed_out = score.timed_out.saturating_add(1),
+		}
+		self.dirty = true;
+	}
+
+	/// Write the scores to disk, if they changed since the last time.
+	pub fn persist(&mut self) {
+		let path = match (&self.path, self.dirty) {
+			(Some(path), true) => path,
+			_ => return,
+		};
+
+		// Write to a temporary file first, so a crash never leaves a truncated file behind.
+		let tmp_path = path.with_extension("tmp");
+		let result = std::fs::write(&tmp_path, self.scores.encode())
+			.and_then(|()| std::fs::rename(&tmp_path, path));
+		match result {
+			Ok(()) => self.dirty = false,
+			Err(e) => tracing::warn!(
+				target: LOG_TARGET,
+				err = ?e,
+				?path,
+				"Failed to persist collator scores",
+			),
+		}
+	}
+}
+
+fn collator_with_worst_score(scores: &BTreeMap<CollatorId, CollatorScore>) -> Option<CollatorId> {
+	scores
+		.iter()
+		.min_by_key(|(_, score)| score.net())
+		.map(|(collator_id, _)| collator_id.clone())
+}
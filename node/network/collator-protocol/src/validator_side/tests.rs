@@ -119,6 +119,14 @@ struct TestHarness {
 }
 
 fn test_harness<T: Future<Output = VirtualOverseer>>(test: impl FnOnce(TestHarness) -> T) {
+	test_harness_with_config(None, HashMap::new(), test)
+}
+
+fn test_harness_with_config<T: Future<Output = VirtualOverseer>>(
+	scoreboard_path: Option<PathBuf>,
+	preferred_collators: HashMap<ParaId, Vec<CollatorId>>,
+	test: impl FnOnce(TestHarness) -> T,
+) {
 	let _ = env_logger::builder()
 		.is_test(true)
 		.filter(Some("polkadot_collator_protocol"), log::LevelFilter::Trace)
@@ -144,6 +152,8 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(test: impl FnOnce(TestHarne
 			inactive_collator: ACTIVITY_TIMEOUT,
 			undeclared: DECLARE_TIMEOUT,
 		},
+		scoreboard_path,
+		preferred_collators,
 		Metrics::default(),
 	);
 
@@ -1063,3 +1073,205 @@ fn view_change_clears_old_collators() {
 		virtual_overseer
 	})
 }
+
+#[test]
+fn preferred_collators_are_fetched_first() {
+	let test_state = TestState::default();
+	let preferred_collators =
+		iter::once((test_state.chain_ids[0], vec![test_state.collators[2].public()])).collect();
+
+	test_harness_with_config(None, preferred_collators, |test_harness| async move {
+		let TestHarness { mut virtual_overseer } = test_harness;
+
+		overseer_send(
+			&mut virtual_overseer,
+			CollatorProtocolMessage::NetworkBridgeUpdateV1(NetworkBridgeEvent::OurViewChange(
+				our_view![test_state.relay_parent],
+			)),
+		)
+		.await;
+
+		respond_to_core_info_queries(&mut virtual_overseer, &test_state).await;
+
+		let peer_b = PeerId::random();
+		let peer_c = PeerId::random();
+		let peer_d = PeerId::random();
+
+		for (peer, collator) in [(peer_b, 0), (peer_c, 1), (peer_d, 2)] {
+			connect_and_declare_collator(
+				&mut virtual_overseer,
+				peer,
+				test_state.collators[collator].clone(),
+				test_state.chain_ids[0],
+			)
+			.await;
+		}
+
+		advertise_collation(&mut virtual_overseer, peer_b, test_state.relay_parent).await;
+
+		let response_channel = assert_fetch_collation_request(
+			&mut virtual_overseer,
+			test_state.relay_parent,
+			test_state.chain_ids[0],
+		)
+		.await;
+
+		// Without a preference, the latest advertisement would be fetched next.
+		advertise_collation(&mut virtual_overseer, peer_d, test_state.relay_parent).await;
+		advertise_collation(&mut virtual_overseer, peer_c, test_state.relay_parent).await;
+
+		// Failing the first fetch leads to fetching the collation of the preferred collator.
+		drop(response_channel);
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::NetworkBridge(NetworkBridgeMessage::SendRequests(reqs, IfDisconnected::ImmediateError)
+		) => {
+			assert_matches!(
+				&reqs[..],
+				[Requests::CollationFetching(req)] => {
+					assert_eq!(req.peer, Recipient::Peer(peer_d));
+				}
+			);
+		});
+
+		virtual_overseer
+	});
+}
+
+#[test]
+fn collator_scores_are_persisted() {
+	let test_state = TestState::default();
+	let dir = tempfile::tempdir().unwrap();
+	let scoreboard_path = dir.path().join("collator-scores");
+
+	test_harness_with_config(
+		Some(scoreboard_path.clone()),
+		HashMap::new(),
+		|test_harness| async move {
+			let TestHarness { mut virtual_overseer } = test_harness;
+
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::NetworkBridgeUpdateV1(NetworkBridgeEvent::OurViewChange(
+					our_view![test_state.relay_parent],
+				)),
+			)
+			.await;
+
+			respond_to_core_info_queries(&mut virtual_overseer, &test_state).await;
+
+			let peer_b = PeerId::random();
+			let peer_c = PeerId::random();
+
+			connect_and_declare_collator(
+				&mut virtual_overseer,
+				peer_b,
+				test_state.collators[0].clone(),
+				test_state.chain_ids[0],
+			)
+			.await;
+
+			connect_and_declare_collator(
+				&mut virtual_overseer,
+				peer_c,
+				test_state.collators[1].clone(),
+				test_state.chain_ids[0],
+			)
+			.await;
+
+			advertise_collation(&mut virtual_overseer, peer_b, test_state.relay_parent).await;
+			advertise_collation(&mut virtual_overseer, peer_c, test_state.relay_parent).await;
+
+			let response_channel = assert_fetch_collation_request(
+				&mut virtual_overseer,
+				test_state.relay_parent,
+				test_state.chain_ids[0],
+			)
+			.await;
+
+			let pov = PoV { block_data: BlockData(vec![]) };
+			let mut candidate_a =
+				dummy_candidate_receipt_bad_sig(dummy_hash(), Some(Default::default()));
+			candidate_a.descriptor.para_id = test_state.chain_ids[0];
+			candidate_a.descriptor.relay_parent = test_state.relay_parent;
+			response_channel
+				.send(Ok(CollationFetchingResponse::Collation(candidate_a, pov.clone()).encode()))
+				.expect("Sending response should succeed");
+
+			let receipt = assert_candidate_backing_second(
+				&mut virtual_overseer,
+				test_state.relay_parent,
+				test_state.chain_ids[0],
+				&pov,
+			)
+			.await;
+
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::Invalid(test_state.relay_parent, receipt),
+			)
+			.await;
+
+			assert_matches!(
+				overseer_recv(&mut virtual_overseer).await,
+				AllMessages::NetworkBridge(NetworkBridgeMessage::ReportPeer(peer, rep)) => {
+					assert_eq!(peer, peer_b);
+					assert_eq!(rep, COST_REPORT_BAD);
+				}
+			);
+
+			// The fetch of the second collation times out.
+			let response_channel = assert_fetch_collation_request(
+				&mut virtual_overseer,
+				test_state.relay_parent,
+				test_state.chain_ids[0],
+			)
+			.await;
+			drop(response_channel);
+			Delay::new(CHECK_COLLATIONS_POLL * 4).await;
+
+			virtual_overseer
+		},
+	);
+
+	// The scores are written when the subsystem concludes.
+	let scoreboard = Scoreboard::load(Some(scoreboard_path));
+	assert_eq!(
+		scoreboard.score(&test_state.chain_ids[0], &test_state.collators[0].public()),
+		scoreboard::CollatorScore { invalid: 1, ..Default::default() },
+	);
+	assert_eq!(
+		scoreboard.score(&test_state.chain_ids[0], &test_state.collators[1].public()),
+		scoreboard::CollatorScore { timed_out: 1, ..Default::default() },
+	);
+}
+
+#[test]
+fn scoreboard_evicts_the_worst_collator() {
+	let para_id = ParaId::from(1);
+	let collator = |i: usize| {
+		let mut raw = [0u8; 32];
+		raw[..8].copy_from_slice(&(i as u64).to_le_bytes());
+		CollatorId::from(sp_core::sr25519::Public::from_raw(raw))
+	};
+
+	let mut scoreboard = Scoreboard::load(None);
+	for i in 0..scoreboard::MAX_COLLATORS_PER_PARA - 1 {
+		scoreboard.note(para_id, collator(i), ScoreEvent::Seconded);
+	}
+	let worst = collator(scoreboard::MAX_COLLATORS_PER_PARA - 1);
+	scoreboard.note(para_id, worst.clone(), ScoreEvent::Invalid);
+
+	let newcomer = collator(scoreboard::MAX_COLLATORS_PER_PARA);
+	scoreboard.note(para_id, newcomer.clone(), ScoreEvent::TimedOut);
+
+	assert_eq!(scoreboard.score(&para_id, &worst), Default::default());
+	assert_eq!(
+		scoreboard.score(&para_id, &newcomer),
+		scoreboard::CollatorScore { timed_out: 1, ..Default::default() },
+	);
+	for i in 0..scoreboard::MAX_COLLATORS_PER_PARA - 1 {
+		assert_eq!(scoreboard.score(&para_id, &collator(i)).seconded, 1);
+	}
+}
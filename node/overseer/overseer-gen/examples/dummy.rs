@@ -4,7 +4,7 @@ use polkadot_node_network_protocol::WrongVariant;
 use polkadot_overseer_gen::*;
 
 /// Concrete subsystem implementation for `MsgStrukt` msg type.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct AwesomeSubSys;
 
 impl ::polkadot_overseer_gen::Subsystem<XxxSubsystemContext<MsgStrukt>, Yikes> for AwesomeSubSys {
@@ -89,7 +89,7 @@ impl NetworkMsg {
 
 #[overlord(signal=SigSigSig, event=EvX, error=Yikes, network=NetworkMsg, gen=AllMessages)]
 struct Xxx {
-	#[subsystem(MsgStrukt)]
+	#[subsystem(restart(max_restarts(3)), MsgStrukt)]
 	sub0: AwesomeSubSys,
 
 	#[subsystem(no_dispatch, blocking, Plinko)]
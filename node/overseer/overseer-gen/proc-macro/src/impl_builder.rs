@@ -73,6 +73,11 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 		.iter()
 		.map(|subsystem_name| format_ident!("replace_{}", subsystem_name))
 		.collect::<Vec<_>>();
+	let subsystem_name_record = &info
+		.subsystem_names_without_wip()
+		.iter()
+		.map(|subsystem_name| format_ident!("record_{}", subsystem_name))
+		.collect::<Vec<_>>();
 
 	let builder_generic_ty = &info.builder_generic_types();
 
@@ -92,9 +97,12 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 	let subsystem_ctx_name = format_ident!("{}SubsystemContext", overseer_name);
 
 	let error_ty = &info.extern_error_ty;
+	let signal = &info.extern_signal_ty;
 
 	let support_crate = info.support_crate_name();
 
+	let consumes_without_wip = &info.consumes_without_wip();
+
 	let blocking = &info
 		.subsystems()
 		.iter()
@@ -107,6 +115,54 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 		})
 		.collect::<Vec<_>>();
 
+	// Restartable subsystems are started anew from a clone of the
+	// instance passed to the builder, hence they must be `Clone`.
+	let restartable_generic_ty = &info
+		.subsystems()
+		.iter()
+		.filter(|ssf| !ssf.wip && ssf.is_restartable())
+		.map(|ssf| ssf.generic.clone())
+		.collect::<Vec<_>>();
+
+	let restart_setup = &info
+		.subsystems()
+		.iter()
+		.filter(|ssf| !ssf.wip)
+		.map(|ssf| match ssf.restart {
+			Some(ref policy) if policy.is_restartable() => {
+				let subsystem_name = &ssf.name;
+				let consumes = &ssf.consumes;
+				let blocking = ssf.blocking;
+				let policy = policy.to_policy_expr(support_crate);
+				quote! {
+					let (recycle_tx, restart) = {
+						let (recycle_tx, recycle_rx) = #support_crate ::oneshot::channel();
+						let template = #subsystem_name .clone();
+						let restart = SubsystemRestart::< #consumes > {
+							policy: #policy,
+							restarts: 0,
+							start: Box::new(move |ctx| {
+								#support_crate ::Subsystem::<
+									#subsystem_ctx_name< #consumes >, #error_ty
+								>::start(template.clone(), ctx)
+							}),
+							blocking: #blocking,
+							subsystem_name: subsystem_static_str,
+							channels_out: channels_out.clone(),
+							to_overseer: to_overseer_tx.clone(),
+							recycle: Some(recycle_rx),
+							recorder: recorder.clone(),
+						};
+						(Some(recycle_tx), Some(restart))
+					};
+				}
+			},
+			_ => quote! {
+				let (recycle_tx, restart) = (None, None);
+			},
+		})
+		.collect::<Vec<_>>();
+
 	let generics = quote! {
 		< S, #( #baggage_generic_ty, )* >
 	};
@@ -136,6 +192,9 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 		#(
 			#builder_generic_ty : Subsystem<#subsyste_ctx_name< #consumes >, #error_ty>,
 		)*
+		#(
+			#restartable_generic_ty : Clone + Send + 'static,
+		)*
 	};
 
 	let event = &info.extern_event_ty;
@@ -212,6 +271,16 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 			}
 		}
 
+		/// Recorders of the signals and messages delivered to the subsystems.
+		#[derive(Default)]
+		struct SubsystemRecorders {
+			#(
+				#subsystem_name : ::std::option::Option<
+					::std::sync::Arc<dyn #support_crate ::MessageRecorder< #consumes_without_wip, #signal >>
+				>,
+			)*
+		}
+
 		#[allow(missing_docs)]
 		pub struct #builder #builder_generics {
 			#(
@@ -221,6 +290,7 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 				#baggage_name : ::std::option::Option< #baggage_ty >,
 			)*
 			spawner: ::std::option::Option< S >,
+			recorders: SubsystemRecorders,
 		}
 
 		impl #builder_generics Default for #builder #builder_generics {
@@ -241,6 +311,7 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 					#baggage_name: None,
 				)*
 					spawner: None,
+					recorders: Default::default(),
 				}
 			}
 		}
@@ -274,6 +345,17 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 				}
 			)*
 
+			#(
+				/// Record all signals and messages delivered to the particular subsystem.
+				pub fn #subsystem_name_record (
+					mut self,
+					recorder: ::std::sync::Arc<dyn #support_crate ::MessageRecorder< #consumes_without_wip, #signal >>,
+				) -> Self {
+					self.recorders. #subsystem_name = Some(recorder);
+					self
+				}
+			)*
+
 			#(
 				/// Attach the user defined addendum type.
 				pub fn #baggage_name (mut self, baggage: #baggage_ty ) -> Self {
@@ -330,7 +412,11 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 				let mut spawner = self.spawner.expect("Spawner is set. qed");
 
 				let mut running_subsystems = #support_crate ::FuturesUnordered::<
-						BoxFuture<'static, ::std::result::Result<(), #error_ty > >
+						BoxFuture<'static, &'static str>
+					>::new();
+
+				let pending_restarts = #support_crate ::FuturesUnordered::<
+						BoxFuture<'static, &'static str>
 					>::new();
 
 				#(
@@ -350,18 +436,24 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 					);
 					let (signal_tx, signal_rx) = #support_crate ::metered::channel(SIGNAL_CHANNEL_CAPACITY);
 
+					let recorder = self.recorders. #subsystem_name;
+
 					// Generate subsystem name based on overseer field name.
 					let mut subsystem_string = String::from(stringify!(#subsystem_name));
 					// Convert owned `snake case` string to a `kebab case` static str.
 					let subsystem_static_str = Box::leak(subsystem_string.replace("_", "-").into_boxed_str());
 
-					let ctx = #subsyste_ctx_name::< #consumes >::new(
+					#restart_setup
+
+					let mut ctx = #subsyste_ctx_name::< #consumes >::new(
 						signal_rx,
 						message_rx,
 						channels_out.clone(),
 						to_overseer_tx.clone(),
 						subsystem_static_str
 					);
+					ctx.recycle = recycle_tx;
+					ctx.recorder = recorder;
 
 					let #subsystem_name: OverseenSubsystem< #consumes > =
 						spawn::<_,_, #blocking, _, _, _>(
@@ -373,6 +465,7 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 							#subsystem_name,
 							subsystem_static_str,
 							&mut running_subsystems,
+							restart,
 						)?;
 				)*
 
@@ -399,6 +492,7 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 
 					spawner,
 					running_subsystems,
+					pending_restarts,
 					events_rx,
 					to_overseer_rx,
 				};
@@ -410,22 +504,38 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 
 	let mut acc = TokenStream::new();
 
+	let restartable = info
+		.subsystems()
+		.iter()
+		.filter(|ssf| !ssf.wip)
+		.map(|ssf| ssf.is_restartable())
+		.collect::<Vec<_>>();
+
 	for (
 		(
 			(
-				ref modified_generics,
-				ref to_be_replaced_ty,
-				ref to_be_replaced_name,
-				ref to_keep_name,
+				(
+					ref modified_generics,
+					ref to_be_replaced_ty,
+					ref to_be_replaced_name,
+					ref to_keep_name,
+				),
+				subsystem_name_replace_with,
 			),
-			subsystem_name_replace_with,
+			consumes,
 		),
-		consumes,
+		restartable,
 	) in derive_replacable_generic_lists(info)
 		.into_iter()
 		.zip(subsystem_name_replace_with.iter())
 		.zip(consumes.iter())
+		.zip(restartable.into_iter())
 	{
+		let restartable_bound = if restartable {
+			quote! { NEW: Clone + Send + 'static, }
+		} else {
+			TokenStream::new()
+		};
 		let replace1 = quote! {
 			/// Replace a subsystem by another implementation for the
 			/// consumable message type.
@@ -435,6 +545,7 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 				#to_be_replaced_ty: 'static,
 				F: 'static + FnOnce(#to_be_replaced_ty) -> NEW,
 				NEW: #support_crate ::Subsystem<#subsystem_ctx_name< #consumes >, #error_ty>,
+				#restartable_bound
 			{
 
 				let Self {
@@ -446,6 +557,7 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 						#baggage_name,
 					)*
 					spawner,
+					recorders,
 				} = self;
 
 				// Some cases require that parts of the original are copied
@@ -470,6 +582,7 @@ pub(crate) fn impl_builder(info: &OverseerInfo) -> proc_macro2::TokenStream {
 						#baggage_name,
 					)*
 					spawner,
+					recorders,
 				}
 			}
 		};
@@ -516,27 +629,22 @@ pub(crate) fn impl_task_kind(info: &OverseerInfo) -> proc_macro2::TokenStream {
 			}
 		}
 
-		/// Spawn task of kind `self` using spawner `S`.
-		pub fn spawn<S, M, TK, Ctx, E, SubSys>(
+		/// Launch the future of a started subsystem as task of kind `TK`.
+		///
+		/// Once the subsystem exits, the corresponding entry of `futures`
+		/// resolves to `subsystem_name`.
+		fn launch<S, TK, E>(
 			spawner: &mut S,
-			message_tx: #support_crate ::metered::MeteredSender<MessagePacket<M>>,
-			signal_tx: #support_crate ::metered::MeteredSender< #signal >,
-			// meter for the unbounded channel
-			unbounded_meter: #support_crate ::metered::Meter,
-			ctx: Ctx,
-			s: SubSys,
+			spawned: #support_crate ::SpawnedSubsystem<E>,
 			subsystem_name: &'static str,
-			futures: &mut #support_crate ::FuturesUnordered<BoxFuture<'static, ::std::result::Result<(), #error_ty> >>,
-		) -> ::std::result::Result<OverseenSubsystem<M>, #error_ty >
+			futures: &mut #support_crate ::FuturesUnordered<BoxFuture<'static, &'static str>>,
+		) -> &'static str
 		where
 			S: #support_crate ::SpawnNamed,
-			M: std::fmt::Debug + Send + 'static,
 			TK: TaskKind,
-			Ctx: #support_crate ::SubsystemContext<Message=M>,
 			E: std::error::Error + Send + Sync + 'static + From<#support_crate ::OverseerError>,
-			SubSys: #support_crate ::Subsystem<Ctx, E>,
 		{
-			let #support_crate ::SpawnedSubsystem::<E> { future, name } = s.start(ctx);
+			let #support_crate ::SpawnedSubsystem::<E> { future, name } = spawned;
 
 			let (tx, rx) = #support_crate ::oneshot::channel();
 
@@ -552,12 +660,40 @@ pub(crate) fn impl_task_kind(info: &OverseerInfo) -> proc_macro2::TokenStream {
 			<TK as TaskKind>::launch_task(spawner, name, subsystem_name, fut);
 
 			futures.push(Box::pin(
-				rx.map(|e| {
-					tracing::warn!(err = ?e, "dropping error");
-					Ok(())
+				rx.map(move |e| {
+					if let Err(e) = e {
+						tracing::warn!(err = ?e, subsystem = subsystem_name, "subsystem exited without notice");
+					}
+					subsystem_name
 				})
 			));
 
+			name
+		}
+
+		/// Spawn task of kind `self` using spawner `S`.
+		pub fn spawn<S, M, TK, Ctx, E, SubSys>(
+			spawner: &mut S,
+			message_tx: #support_crate ::metered::MeteredSender<MessagePacket<M>>,
+			signal_tx: #support_crate ::metered::MeteredSender< #signal >,
+			// meter for the unbounded channel
+			unbounded_meter: #support_crate ::metered::Meter,
+			ctx: Ctx,
+			s: SubSys,
+			subsystem_name: &'static str,
+			futures: &mut #support_crate ::FuturesUnordered<BoxFuture<'static, &'static str>>,
+			restart: Option<SubsystemRestart<M>>,
+		) -> ::std::result::Result<OverseenSubsystem<M>, #error_ty >
+		where
+			S: #support_crate ::SpawnNamed,
+			M: std::fmt::Debug + Send + 'static,
+			TK: TaskKind,
+			Ctx: #support_crate ::SubsystemContext<Message=M>,
+			E: std::error::Error + Send + Sync + 'static + From<#support_crate ::OverseerError>,
+			SubSys: #support_crate ::Subsystem<Ctx, E>,
+		{
+			let name = launch::<_, TK, E>(spawner, s.start(ctx), subsystem_name, futures);
+
 			let instance = Some(SubsystemInstance {
 				meters: #support_crate ::SubsystemMeters {
 					unbounded: unbounded_meter,
@@ -572,6 +708,7 @@ pub(crate) fn impl_task_kind(info: &OverseerInfo) -> proc_macro2::TokenStream {
 
 			Ok(OverseenSubsystem {
 				instance,
+				restart,
 			})
 		}
 	};
@@ -112,7 +112,14 @@ pub(crate) fn impl_misc(info: &OverseerInfo) -> proc_macro2::TokenStream {
 				>,
 			signals_received: SignalsReceived,
 			pending_incoming: Option<(usize, M)>,
-			name: &'static str
+			name: &'static str,
+			/// Hands back the receivers once the context is dropped,
+			/// only present for restartable subsystems.
+			recycle: Option<#support_crate ::oneshot::Sender<
+				#support_crate ::SubsystemReceivers<M, #signal>
+			>>,
+			/// Observes everything received, if recording was requested.
+			recorder: Option<::std::sync::Arc<dyn #support_crate ::MessageRecorder<M, #signal>>>,
 		}
 
 		impl<M> #subsystem_ctx_name<M> {
@@ -124,7 +131,31 @@ pub(crate) fn impl_misc(info: &OverseerInfo) -> proc_macro2::TokenStream {
 				to_overseer: #support_crate ::metered::UnboundedMeteredSender<#support_crate:: ToOverseer>,
 				name: &'static str
 			) -> Self {
-				let signals_received = SignalsReceived::default();
+				let receivers = #support_crate ::SubsystemReceivers {
+					signals,
+					messages,
+					signals_received: SignalsReceived::default(),
+					pending_incoming: None,
+				};
+				Self::with_receivers(receivers, to_subsystems, to_overseer, name, None)
+			}
+
+			/// Create a new context, continuing on the receivers of a previous one.
+			fn with_receivers(
+				receivers: #support_crate ::SubsystemReceivers<M, #signal>,
+				to_subsystems: ChannelsOut,
+				to_overseer: #support_crate ::metered::UnboundedMeteredSender<#support_crate:: ToOverseer>,
+				name: &'static str,
+				recycle: Option<#support_crate ::oneshot::Sender<
+					#support_crate ::SubsystemReceivers<M, #signal>
+				>>,
+			) -> Self {
+				let #support_crate ::SubsystemReceivers {
+					signals,
+					messages,
+					signals_received,
+					pending_incoming,
+				} = receivers;
 				#subsystem_ctx_name {
 					signals,
 					messages,
@@ -134,8 +165,10 @@ pub(crate) fn impl_misc(info: &OverseerInfo) -> proc_macro2::TokenStream {
 					},
 					to_overseer,
 					signals_received,
-					pending_incoming: None,
-					name
+					pending_incoming,
+					name,
+					recycle,
+					recorder: None,
 				}
 			}
 
@@ -144,26 +177,9 @@ pub(crate) fn impl_misc(info: &OverseerInfo) -> proc_macro2::TokenStream {
 			}
 		}
 
-		#[#support_crate ::async_trait]
-		impl<M: std::fmt::Debug + Send + 'static> #support_crate ::SubsystemContext for #subsystem_ctx_name<M>
-		where
-			#subsystem_sender_name: #support_crate ::SubsystemSender< #wrapper_message >,
-			#wrapper_message: From<M>,
-		{
-			type Message = M;
-			type Signal = #signal;
-			type Sender = #subsystem_sender_name;
-			type AllMessages = #wrapper_message;
-			type Error = #error_ty;
-
-			async fn try_recv(&mut self) -> ::std::result::Result<Option<FromOverseer<M, #signal>>, ()> {
-				match #support_crate ::poll!(self.recv()) {
-					#support_crate ::Poll::Ready(msg) => Ok(Some(msg.map_err(|_| ())?)),
-					#support_crate ::Poll::Pending => Ok(None),
-				}
-			}
-
-			async fn recv(&mut self) -> ::std::result::Result<FromOverseer<M, #signal>, #error_ty> {
+		impl<M: std::fmt::Debug + Send + 'static> #subsystem_ctx_name<M> {
+			/// Receive the next signal or message, respecting the order of signals and messages.
+			async fn next_from_overseer(&mut self) -> ::std::result::Result<FromOverseer<M, #signal>, #error_ty> {
 				loop {
 					// If we have a message pending an overseer signal, we only poll for signals
 					// in the meantime.
@@ -226,6 +242,56 @@ pub(crate) fn impl_misc(info: &OverseerInfo) -> proc_macro2::TokenStream {
 					return Ok(from_overseer);
 				}
 			}
+		}
+
+		impl<M> Drop for #subsystem_ctx_name<M> {
+			fn drop(&mut self) {
+				if let Some(recycle) = self.recycle.take() {
+					// The receivers can not be moved out of `self`,
+					// swap in disconnected ones instead.
+					let (_, signals) = #support_crate ::metered::channel(1);
+					let (_, bounded) = #support_crate ::metered::channel(1);
+					let (_, unbounded) = #support_crate ::metered::unbounded();
+					let receivers = #support_crate ::SubsystemReceivers {
+						signals: ::std::mem::replace(&mut self.signals, signals),
+						messages: ::std::mem::replace(
+							&mut self.messages,
+							#support_crate ::select(bounded, unbounded),
+						),
+						signals_received: self.signals_received.clone(),
+						pending_incoming: self.pending_incoming.take(),
+					};
+					let _ = recycle.send(receivers);
+				}
+			}
+		}
+
+		#[#support_crate ::async_trait]
+		impl<M: std::fmt::Debug + Send + 'static> #support_crate ::SubsystemContext for #subsystem_ctx_name<M>
+		where
+			#subsystem_sender_name: #support_crate ::SubsystemSender< #wrapper_message >,
+			#wrapper_message: From<M>,
+		{
+			type Message = M;
+			type Signal = #signal;
+			type Sender = #subsystem_sender_name;
+			type AllMessages = #wrapper_message;
+			type Error = #error_ty;
+
+			async fn try_recv(&mut self) -> ::std::result::Result<Option<FromOverseer<M, #signal>>, ()> {
+				match #support_crate ::poll!(self.recv()) {
+					#support_crate ::Poll::Ready(msg) => Ok(Some(msg.map_err(|_| ())?)),
+					#support_crate ::Poll::Pending => Ok(None),
+				}
+			}
+
+			async fn recv(&mut self) -> ::std::result::Result<FromOverseer<M, #signal>, #error_ty> {
+				let from_overseer = self.next_from_overseer().await?;
+				if let Some(ref recorder) = self.recorder {
+					recorder.record(&from_overseer);
+				}
+				Ok(from_overseer)
+			}
 
 			fn sender(&mut self) -> &mut Self::Sender {
 				&mut self.to_subsystems
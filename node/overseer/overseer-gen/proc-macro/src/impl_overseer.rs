@@ -22,6 +22,11 @@ pub(crate) fn impl_overseer_struct(info: &OverseerInfo) -> proc_macro2::TokenStr
 	let message_wrapper = &info.message_wrapper.clone();
 	let overseer_name = info.overseer_name.clone();
 	let subsystem_name = &info.subsystem_names_without_wip();
+	// Mirrors the `kebab case` name the builder assigns to each subsystem.
+	let subsystem_str = &subsystem_name
+		.iter()
+		.map(|name| syn::LitStr::new(&name.to_string().replace("_", "-"), name.span()))
+		.collect::<Vec<_>>();
 	let support_crate = info.support_crate_name();
 
 	let baggage_decl = &info.baggage_decl();
@@ -82,9 +87,15 @@ pub(crate) fn impl_overseer_struct(info: &OverseerInfo) -> proc_macro2::TokenStr
 			/// Responsible for driving the subsystem futures.
 			spawner: S,
 
-			/// The set of running subsystems.
+			/// The set of running subsystems, each resolves to
+			/// the name of the subsystem once it exits.
 			running_subsystems: #support_crate ::FuturesUnordered<
-				BoxFuture<'static, ::std::result::Result<(), #error_ty>>
+				BoxFuture<'static, &'static str>
+			>,
+
+			/// Subsystems waiting for their restart delay to elapse.
+			pending_restarts: #support_crate ::FuturesUnordered<
+				BoxFuture<'static, &'static str>
 			>,
 
 			/// Gather running subsystems' outbound streams into one.
@@ -152,6 +163,50 @@ pub(crate) fn impl_overseer_struct(info: &OverseerInfo) -> proc_macro2::TokenStr
 				Ok(())
 			}
 
+			/// Schedule the restart of an exited subsystem according to its restart policy.
+			///
+			/// Returns `false` if the subsystem must not be restarted, the exit
+			/// of the subsystem is then fatal.
+			pub fn schedule_restart(&mut self, subsystem: &'static str) -> bool {
+				let delay = match subsystem {
+					#(
+						#subsystem_str => self. #subsystem_name .next_restart_delay(),
+					)*
+					_ => None,
+				};
+
+				match delay {
+					Some(delay) => {
+						self.pending_restarts.push(Box::pin(
+							#support_crate ::Delay::new(delay).map(move |_| subsystem)
+						));
+						true
+					}
+					None => false,
+				}
+			}
+
+			/// Restart a subsystem, whose restart was scheduled before,
+			/// and send it the given `signals`.
+			///
+			/// Returns `false` if the subsystem could not be restarted.
+			pub async fn restart_subsystem(&mut self, subsystem: &'static str, signals: Vec< #signal_ty >) -> ::std::result::Result<bool, #error_ty > {
+				match subsystem {
+					#(
+						#subsystem_str => {
+							if !self. #subsystem_name .restart(&mut self.spawner, &mut self.running_subsystems).await {
+								return Ok(false)
+							}
+							for signal in signals {
+								self. #subsystem_name .send_signal(signal).await?;
+							}
+							Ok(true)
+						}
+					)*
+					_ => Ok(false),
+				}
+			}
+
 			/// Extract information from each subsystem.
 			pub fn map_subsystems<'a, Mapper, Output>(&'a self, mapper: Mapper)
 			-> Vec<Output>
@@ -182,6 +237,10 @@ pub(crate) fn impl_overseen_subsystem(info: &OverseerInfo) -> proc_macro2::Token
 	let signal = &info.extern_signal_ty;
 	let error_ty = &info.extern_error_ty;
 	let support_crate = info.support_crate_name();
+	let subsystem_ctx_name = syn::Ident::new(
+		&(info.overseer_name.to_string() + "SubsystemContext"),
+		info.overseer_name.span(),
+	);
 
 	let ts = quote::quote! {
 		/// A subsystem that the overseer oversees.
@@ -196,9 +255,105 @@ pub(crate) fn impl_overseen_subsystem(info: &OverseerInfo) -> proc_macro2::Token
 			pub instance: std::option::Option<
 				#support_crate ::SubsystemInstance<M, #signal>
 			>,
+			/// Means to restart the subsystem, `None` if it is never restarted.
+			restart: std::option::Option<SubsystemRestart<M>>,
+		}
+
+		/// Everything required to restart a subsystem on its existing channels.
+		pub struct SubsystemRestart<M> {
+			/// The restart policy of the subsystem.
+			policy: #support_crate ::RestartPolicy,
+			/// Number of restarts so far.
+			restarts: u32,
+			/// Starts a fresh instance of the subsystem.
+			start: Box<
+				dyn FnMut(#subsystem_ctx_name<M>) -> #support_crate ::SpawnedSubsystem< #error_ty > + Send
+			>,
+			/// If the subsystem has to be spawned as blocking task.
+			blocking: bool,
+			/// Name of the subsystem.
+			subsystem_name: &'static str,
+			/// Channels to all subsystems, for the context of a fresh instance.
+			channels_out: ChannelsOut,
+			/// Channel to the overseer, for the context of a fresh instance.
+			to_overseer: #support_crate ::metered::UnboundedMeteredSender< #support_crate ::ToOverseer >,
+			/// Receives the channels of the running instance, once it exits.
+			recycle: std::option::Option<
+				#support_crate ::oneshot::Receiver< #support_crate ::SubsystemReceivers<M, #signal> >
+			>,
+			/// Records the signals and messages delivered to the subsystem.
+			recorder: std::option::Option<
+				::std::sync::Arc<dyn #support_crate ::MessageRecorder<M, #signal>>
+			>,
 		}
 
 		impl<M> OverseenSubsystem<M> {
+			/// The delay before the next restart of the subsystem.
+			///
+			/// Returns `None` if the restart policy does not permit another restart.
+			pub fn next_restart_delay(&mut self) -> std::option::Option<Duration> {
+				let restart = self.restart.as_mut()?;
+				let delay = restart.policy.restart_delay(restart.restarts)?;
+				restart.restarts += 1;
+				Some(delay)
+			}
+
+			/// Start a new instance of the subsystem on the channels of the exited one.
+			///
+			/// Returns `false` if the subsystem is not restartable or the channels
+			/// of the exited instance were not handed back in time.
+			pub async fn restart<S: #support_crate ::SpawnNamed>(
+				&mut self,
+				spawner: &mut S,
+				futures: &mut #support_crate ::FuturesUnordered<BoxFuture<'static, &'static str>>,
+			) -> bool {
+				const RECYCLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+				let restart = match self.restart {
+					Some(ref mut restart) => restart,
+					None => return false,
+				};
+
+				let receivers = match restart.recycle.take() {
+					Some(recycle) => recycle.timeout(RECYCLE_TIMEOUT).await,
+					None => None,
+				};
+				let receivers = match receivers {
+					Some(Ok(receivers)) => receivers,
+					_ => {
+						#support_crate ::tracing::error!(
+							target: LOG_TARGET,
+							subsystem = restart.subsystem_name,
+							"Channels of the exited subsystem were not handed back.",
+						);
+						return false
+					}
+				};
+
+				let (recycle_tx, recycle_rx) = #support_crate ::oneshot::channel();
+				restart.recycle = Some(recycle_rx);
+
+				let mut ctx = #subsystem_ctx_name::<M>::with_receivers(
+					receivers,
+					restart.channels_out.clone(),
+					restart.to_overseer.clone(),
+					restart.subsystem_name,
+					Some(recycle_tx),
+				);
+				ctx.recorder = restart.recorder.clone();
+				let spawned = (restart.start)(ctx);
+				let name = if restart.blocking {
+					launch::<_, Blocking, _>(spawner, spawned, restart.subsystem_name, futures)
+				} else {
+					launch::<_, Regular, _>(spawner, spawned, restart.subsystem_name, futures)
+				};
+
+				if let Some(ref mut instance) = self.instance {
+					instance.name = name;
+				}
+				true
+			}
+
 			/// Send a message to the wrapped subsystem.
 			///
 			/// If the inner `instance` is `None`, nothing is happening.
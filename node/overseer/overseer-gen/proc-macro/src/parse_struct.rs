@@ -20,8 +20,8 @@ use syn::{
 	parse::{Parse, ParseStream},
 	punctuated::Punctuated,
 	spanned::Spanned,
-	AttrStyle, Attribute, Error, Field, FieldsNamed, GenericParam, Ident, ItemStruct, Path, Result,
-	Token, Type, Visibility,
+	AttrStyle, Attribute, Error, Field, FieldsNamed, GenericParam, Ident, ItemStruct, LitInt, Path,
+	Result, Token, Type, Visibility,
 };
 
 use quote::{quote, ToTokens};
@@ -30,6 +30,100 @@ mod kw {
 	syn::custom_keyword!(wip);
 	syn::custom_keyword!(no_dispatch);
 	syn::custom_keyword!(blocking);
+	syn::custom_keyword!(restart);
+	syn::custom_keyword!(never);
+	syn::custom_keyword!(backoff);
+	syn::custom_keyword!(max_restarts);
+}
+
+/// Restart policy of a subsystem, as declared with `restart(..)`.
+#[derive(Clone, Debug)]
+pub(crate) enum RestartPolicy {
+	/// `restart(never)`, identical to not declaring a policy at all.
+	Never(kw::never),
+	/// `restart(backoff)` or `restart(backoff(<initial ms>, <max ms>))`.
+	Backoff(kw::backoff, Option<(LitInt, LitInt)>),
+	/// `restart(max_restarts(<n>))`.
+	MaxRestarts(kw::max_restarts, LitInt),
+}
+
+impl RestartPolicy {
+	/// Whether the subsystem is restarted at all.
+	pub(crate) fn is_restartable(&self) -> bool {
+		!matches!(self, Self::Never(_))
+	}
+
+	/// Expression evaluating to the matching `RestartPolicy` of the support crate.
+	pub(crate) fn to_policy_expr(&self, support_crate: &TokenStream) -> TokenStream {
+		match self {
+			Self::Never(_) => quote! { #support_crate ::RestartPolicy::Never },
+			Self::Backoff(_, None) => quote! { #support_crate ::RestartPolicy::default_backoff() },
+			Self::Backoff(_, Some((initial, max))) => quote! {
+				#support_crate ::RestartPolicy::Backoff {
+					initial: #support_crate ::Duration::from_millis(#initial),
+					max: #support_crate ::Duration::from_millis(#max),
+				}
+			},
+			Self::MaxRestarts(_, n) => quote! { #support_crate ::RestartPolicy::MaxRestarts(#n) },
+		}
+	}
+}
+
+impl Parse for RestartPolicy {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let lookahead = input.lookahead1();
+		if lookahead.peek(kw::never) {
+			Ok(Self::Never(input.parse::<kw::never>()?))
+		} else if lookahead.peek(kw::backoff) {
+			let backoff = input.parse::<kw::backoff>()?;
+			let delays = if input.peek(syn::token::Paren) {
+				let content;
+				let _ = syn::parenthesized!(content in input);
+				let initial = content.parse::<LitInt>()?;
+				let _ = content.parse::<Token![,]>()?;
+				let max = content.parse::<LitInt>()?;
+				if initial.base10_parse::<u64>()? > max.base10_parse::<u64>()? {
+					return Err(Error::new(
+						initial.span(),
+						"Initial backoff delay must not exceed the maximum delay.",
+					))
+				}
+				Some((initial, max))
+			} else {
+				None
+			};
+			Ok(Self::Backoff(backoff, delays))
+		} else if lookahead.peek(kw::max_restarts) {
+			let max_restarts = input.parse::<kw::max_restarts>()?;
+			let content;
+			let _ = syn::parenthesized!(content in input);
+			let n = content.parse::<LitInt>()?;
+			let _ = n.base10_parse::<u32>()?;
+			Ok(Self::MaxRestarts(max_restarts, n))
+		} else {
+			Err(lookahead.error())
+		}
+	}
+}
+
+impl ToTokens for RestartPolicy {
+	fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+		let ts = match self {
+			Self::Never(never) => {
+				quote! { #never }
+			},
+			Self::Backoff(backoff, None) => {
+				quote! { #backoff }
+			},
+			Self::Backoff(backoff, Some((initial, max))) => {
+				quote! { #backoff(#initial, #max) }
+			},
+			Self::MaxRestarts(max_restarts, n) => {
+				quote! { #max_restarts(#n) }
+			},
+		};
+		tokens.extend(ts.into_iter());
+	}
 }
 
 #[derive(Clone, Debug)]
@@ -43,6 +137,9 @@ enum SubSysAttrItem {
 	/// External messages should not be - after being converted -
 	/// be dispatched to the annotated subsystem.
 	NoDispatch(kw::no_dispatch),
+	/// The subsystem is restarted according to the given policy
+	/// once it exits, instead of bringing down the overseer.
+	Restart(kw::restart, RestartPolicy),
 }
 
 impl Parse for SubSysAttrItem {
@@ -54,6 +151,11 @@ impl Parse for SubSysAttrItem {
 			Self::Blocking(input.parse::<kw::blocking>()?)
 		} else if lookahead.peek(kw::no_dispatch) {
 			Self::NoDispatch(input.parse::<kw::no_dispatch>()?)
+		} else if lookahead.peek(kw::restart) {
+			let restart = input.parse::<kw::restart>()?;
+			let content;
+			let _ = syn::parenthesized!(content in input);
+			Self::Restart(restart, content.parse::<RestartPolicy>()?)
 		} else {
 			return Err(lookahead.error())
 		})
@@ -72,6 +174,9 @@ impl ToTokens for SubSysAttrItem {
 			Self::NoDispatch(no_dispatch) => {
 				quote! { #no_dispatch }
 			},
+			Self::Restart(restart, policy) => {
+				quote! { #restart(#policy) }
+			},
 		};
 		tokens.extend(ts.into_iter());
 	}
@@ -99,6 +204,15 @@ pub(crate) struct SubSysField {
 	/// Avoids dispatching `Wrapper` type messages, but generates the variants.
 	/// Does not require the subsystem to be instantiated with the builder pattern.
 	pub(crate) wip: bool,
+	/// The policy to apply once the subsystem exits, `None` if it was not declared.
+	pub(crate) restart: Option<RestartPolicy>,
+}
+
+impl SubSysField {
+	/// If the subsystem is restarted once it exits.
+	pub(crate) fn is_restartable(&self) -> bool {
+		self.restart.as_ref().map_or(false, |policy| policy.is_restartable())
+	}
 }
 
 fn try_type_to_path(ty: Type, span: Span) -> Result<Path> {
@@ -135,6 +249,7 @@ pub(crate) struct SubSystemTags {
 	/// and also not include the subsystem in the list of subsystems.
 	pub(crate) wip: bool,
 	pub(crate) blocking: bool,
+	pub(crate) restart: Option<RestartPolicy>,
 	pub(crate) consumes: Path,
 }
 
@@ -147,7 +262,16 @@ impl Parse for SubSystemTags {
 		let _ = syn::parenthesized!(content in input);
 
 		let mut items = Punctuated::new();
-		while let Ok(tag) = content.call(SubSysAttrItem::parse) {
+		loop {
+			let tag = if content.peek(kw::restart) {
+				// Report errors within the restart policy, instead of
+				// mistaking them for the end of the attribute list.
+				content.call(SubSysAttrItem::parse)?
+			} else if let Ok(tag) = content.call(SubSysAttrItem::parse) {
+				tag
+			} else {
+				break
+			};
 			items.push_value(tag);
 			items.push_punct(content.call(<Token![,]>::parse)?);
 		}
@@ -175,8 +299,15 @@ impl Parse for SubSystemTags {
 		let no_dispatch = extract_variant!(unique, NoDispatch; default = false);
 		let blocking = extract_variant!(unique, Blocking; default = false);
 		let wip = extract_variant!(unique, Wip; default = false);
+		let restart = unique.values().find_map(|item| {
+			if let SubSysAttrItem::Restart(_, policy) = item {
+				Some(policy.clone())
+			} else {
+				None
+			}
+		});
 
-		Ok(Self { attrs, no_dispatch, blocking, consumes, wip })
+		Ok(Self { attrs, no_dispatch, blocking, restart, consumes, wip })
 	}
 }
 
@@ -389,6 +520,7 @@ impl OverseerGuts {
 					no_dispatch: variant.no_dispatch,
 					wip: variant.wip,
 					blocking: variant.blocking,
+					restart: variant.restart,
 				});
 			} else {
 				let field_ty = try_type_to_path(ty, ident.span())?;
@@ -35,13 +35,13 @@ fn print() {
 			#[subsystem(no_dispatch, Foo)]
 			sub0: FooSubsystem,
 
-			#[subsystem(blocking, Bar)]
+			#[subsystem(blocking, restart(backoff), Bar)]
 			yyy: BaersBuyBilliardBalls,
 
 			#[subsystem(no_dispatch, blocking, Twain)]
 			fff: Beeeeep,
 
-			#[subsystem(Rope)]
+			#[subsystem(restart(max_restarts(3)), Rope)]
 			mc: MountainCave,
 
 			metrics: Metrics,
@@ -60,13 +60,13 @@ fn struct_parse_full() {
 			#[subsystem(no_dispatch, Foo)]
 			sub0: FooSubsystem,
 
-			#[subsystem(blocking, Bar)]
+			#[subsystem(blocking, restart(backoff), Bar)]
 			yyy: BaersBuyBilliardBalls,
 
 			#[subsystem(no_dispatch, blocking, Twain)]
 			fff: Beeeeep,
 
-			#[subsystem(Rope)]
+			#[subsystem(restart(max_restarts(3)), Rope)]
 			mc: MountainCave,
 
 			metrics: Metrics,
@@ -86,6 +86,41 @@ fn struct_parse_basic() {
 	let _ = dbg!(item);
 }
 
+#[test]
+fn struct_parse_restart() {
+	let item: OverseerGuts = parse_quote! {
+		pub struct Ooooh {
+			#[subsystem(Foo)]
+			sub0: FooSubsystem,
+
+			#[subsystem(restart(never), Bar)]
+			sub1: BarSubsystem,
+
+			#[subsystem(no_dispatch, restart(backoff(100, 2000)), Baz)]
+			sub2: BazSubsystem,
+
+			#[subsystem(restart(max_restarts(3)), blocking, Qux)]
+			sub3: QuxSubsystem,
+		}
+	};
+	let restartable = item.subsystems.iter().map(|ssf| ssf.is_restartable()).collect::<Vec<_>>();
+	assert_eq!(restartable, vec![false, false, true, true]);
+	assert_matches!(item.subsystems[2].restart, Some(RestartPolicy::Backoff(_, Some(_))));
+	assert_matches!(item.subsystems[3].restart, Some(RestartPolicy::MaxRestarts(_, _)));
+	assert!(item.subsystems[3].blocking);
+}
+
+#[test]
+fn struct_parse_restart_rejects_inverted_backoff() {
+	let item = quote! {
+		pub struct Ooooh {
+			#[subsystem(restart(backoff(2000, 100)), Foo)]
+			sub0: FooSubsystem,
+		}
+	};
+	assert!(syn::parse2::<OverseerGuts>(item).is_err());
+}
+
 #[test]
 fn attr_full() {
 	let attr: AttrArgs = parse_quote! {
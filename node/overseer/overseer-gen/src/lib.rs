@@ -206,6 +206,83 @@ impl SignalsReceived {
 	}
 }
 
+/// Initial delay of the default [`RestartPolicy::Backoff`].
+pub const DEFAULT_RESTART_BACKOFF_INITIAL: Duration = Duration::from_millis(500);
+
+/// Upper bound for the delay of the default [`RestartPolicy::Backoff`].
+pub const DEFAULT_RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// What to do once a subsystem exits, be it by returning an error or by panicking.
+///
+/// Declared per subsystem with `#[subsystem(restart(..), ..)]`, where
+/// `restart(never)`, `restart(backoff)`, `restart(backoff(<initial ms>, <max ms>))`
+/// and `restart(max_restarts(<n>))` map to the respective variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+	/// Never restart the subsystem, its exit brings down the overseer.
+	Never,
+	/// Restart the subsystem indefinitely, the delay before each restart
+	/// doubles with every restart, starting at `initial` and capped at `max`.
+	Backoff {
+		/// Delay before the first restart.
+		initial: Duration,
+		/// Upper bound of the delay.
+		max: Duration,
+	},
+	/// Restart the subsystem immediately, but at most the given number of times.
+	MaxRestarts(u32),
+}
+
+impl Default for RestartPolicy {
+	fn default() -> Self {
+		Self::Never
+	}
+}
+
+impl RestartPolicy {
+	/// A backoff policy with the default delays.
+	pub const fn default_backoff() -> Self {
+		Self::Backoff { initial: DEFAULT_RESTART_BACKOFF_INITIAL, max: DEFAULT_RESTART_BACKOFF_MAX }
+	}
+
+	/// The delay before restarting a subsystem that was already restarted `restarts` times.
+	///
+	/// Returns `None` if the subsystem must not be restarted (anymore).
+	pub fn restart_delay(&self, restarts: u32) -> Option<Duration> {
+		match *self {
+			Self::Never => None,
+			Self::Backoff { initial, max } => Some(
+				initial
+					.checked_mul(2u32.saturating_pow(restarts))
+					.map_or(max, |delay| delay.min(max)),
+			),
+			Self::MaxRestarts(max_restarts) =>
+				if restarts < max_restarts {
+					Some(Duration::ZERO)
+				} else {
+					None
+				},
+		}
+	}
+}
+
+/// The receiving ends of the channels of a subsystem.
+///
+/// Handed back to the overseer once an instance of a restartable subsystem
+/// exits, such that the restarted instance continues on the very same channels
+/// and nothing sent to the subsystem in the meantime is lost.
+#[derive(Debug)]
+pub struct SubsystemReceivers<Message, Signal> {
+	/// Signals sent by the overseer.
+	pub signals: metered::MeteredReceiver<Signal>,
+	/// Messages sent by other subsystems.
+	pub messages: SubsystemIncomingMessages<Message>,
+	/// Watermark of the signals consumed so far.
+	pub signals_received: SignalsReceived,
+	/// A message that was received, but still waits for a signal to be consumed first.
+	pub pending_incoming: Option<(usize, Message)>,
+}
+
 /// A trait to support the origin annotation
 /// such that errors across subsystems can be easier tracked.
 pub trait AnnotateErrorOrigin: 'static + Send + Sync + std::error::Error {
@@ -287,11 +364,15 @@ pub struct SubsystemMeters {
 
 impl SubsystemMeters {
 	/// Read the values of all subsystem `Meter`s.
+	///
+	/// Takes the time of flight samples of the message channels collected since the last readout.
 	pub fn read(&self) -> SubsystemMeterReadouts {
 		SubsystemMeterReadouts {
 			bounded: self.bounded.read(),
 			unbounded: self.unbounded.read(),
 			signals: self.signals.read(),
+			bounded_tof: self.bounded.take_tof_samples(),
+			unbounded_tof: self.unbounded.take_tof_samples(),
 		}
 	}
 }
@@ -304,6 +385,10 @@ pub struct SubsystemMeterReadouts {
 	pub unbounded: metered::Readout,
 	#[allow(missing_docs)]
 	pub signals: metered::Readout,
+	/// Sampled time of flight of messages through the bounded channel.
+	pub bounded_tof: Vec<Duration>,
+	/// Sampled time of flight of messages through the unbounded channel.
+	pub unbounded_tof: Vec<Duration>,
 }
 
 /// A running instance of some [`Subsystem`].
@@ -349,6 +434,21 @@ impl<Signal, Message> From<Signal> for FromOverseer<Message, Signal> {
 	}
 }
 
+/// Observes the signals and messages delivered to a subsystem,
+/// for example to write them to a trace that can be replayed later on.
+///
+/// Attached to a particular subsystem with the `record_*` methods of the builder.
+pub trait MessageRecorder<Message, Signal>: Send + Sync {
+	/// Called with every signal and message, right before the subsystem receives it.
+	fn record(&self, msg: &FromOverseer<Message, Signal>);
+}
+
+impl<Message, Signal> fmt::Debug for dyn MessageRecorder<Message, Signal> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("MessageRecorder")
+	}
+}
+
 /// A context type that is given to the [`Subsystem`] upon spawning.
 /// It can be used by [`Subsystem`] to communicate with other [`Subsystem`]s
 /// or spawn jobs.
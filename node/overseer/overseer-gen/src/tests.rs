@@ -7,3 +7,23 @@ fn ui_compile_fail() {
 	let t = trybuild::TestCases::new();
 	t.compile_fail("tests/ui/err-*.rs");
 }
+
+#[test]
+fn restart_delays_follow_policy() {
+	use super::{Duration, RestartPolicy};
+
+	assert_eq!(RestartPolicy::Never.restart_delay(0), None);
+
+	let backoff =
+		RestartPolicy::Backoff { initial: Duration::from_millis(100), max: Duration::from_secs(1) };
+	assert_eq!(backoff.restart_delay(0), Some(Duration::from_millis(100)));
+	assert_eq!(backoff.restart_delay(1), Some(Duration::from_millis(200)));
+	assert_eq!(backoff.restart_delay(3), Some(Duration::from_millis(800)));
+	assert_eq!(backoff.restart_delay(4), Some(Duration::from_secs(1)));
+	assert_eq!(backoff.restart_delay(u32::MAX), Some(Duration::from_secs(1)));
+
+	let max_restarts = RestartPolicy::MaxRestarts(2);
+	assert_eq!(max_restarts.restart_delay(0), Some(Duration::ZERO));
+	assert_eq!(max_restarts.restart_delay(1), Some(Duration::ZERO));
+	assert_eq!(max_restarts.restart_delay(2), None);
+}
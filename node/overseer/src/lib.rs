@@ -99,6 +99,8 @@ pub use self::metrics::Metrics as OverseerMetrics;
 pub mod dummy;
 pub use self::dummy::DummySubsystem;
 
+pub mod trace;
+
 pub use polkadot_node_metrics::{
 	metrics::{prometheus, Metrics as MetricsTrait},
 	Metronome,
@@ -438,7 +440,7 @@ pub struct Overseer<SupportsParachains> {
 	#[subsystem(blocking, no_dispatch, BitfieldSigningMessage)]
 	bitfield_signing: BitfieldSigning,
 
-	#[subsystem(BitfieldDistributionMessage)]
+	#[subsystem(restart(backoff), BitfieldDistributionMessage)]
 	bitfield_distribution: BitfieldDistribution,
 
 	#[subsystem(no_dispatch, ProvisionerMessage)]
@@ -568,6 +570,7 @@ where
 				.filter_map(|x| x)
 				.map(|(name, ref meters)| (name, meters.read())),
 		);
+		metronome_metrics.oneshot_tof_snapshot(gen::metered::oneshot::take_tof_samples());
 
 		futures::future::ready(())
 	});
@@ -636,14 +639,36 @@ where
 						}
 					}
 				},
-				res = self.running_subsystems.select_next_some() => {
-					tracing::error!(
-						target: LOG_TARGET,
-						subsystem = ?res,
-						"subsystem finished unexpectedly",
-					);
-					self.stop().await;
-					return res;
+				subsystem = self.running_subsystems.select_next_some() => {
+					if self.schedule_restart(subsystem) {
+						tracing::warn!(
+							target: LOG_TARGET,
+							subsystem,
+							"subsystem finished unexpectedly, restart scheduled",
+						);
+					} else {
+						tracing::error!(
+							target: LOG_TARGET,
+							subsystem,
+							"subsystem finished unexpectedly",
+						);
+						self.stop().await;
+						return Ok(());
+					}
+				},
+				subsystem = self.pending_restarts.select_next_some() => {
+					let signals = self.active_leaves_signals();
+					if self.restart_subsystem(subsystem, signals).await? {
+						self.metrics.on_subsystem_restarted(subsystem);
+					} else {
+						tracing::error!(
+							target: LOG_TARGET,
+							subsystem,
+							"failed to restart subsystem",
+						);
+						self.stop().await;
+						return Ok(());
+					}
 				},
 			}
 		}
@@ -750,6 +775,26 @@ where
 		Some((span, status))
 	}
 
+	/// One `ActiveLeaves` signal per currently active leaf, to bring
+	/// a restarted subsystem up to date.
+	///
+	/// The leaves are marked as fresh, since the restarted instance
+	/// never encountered them.
+	fn active_leaves_signals(&self) -> Vec<OverseerSignal> {
+		self.active_leaves
+			.iter()
+			.filter_map(|(hash, number)| {
+				let span = self.span_per_active_leaf.get(hash)?.clone();
+				Some(OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+					hash: *hash,
+					number: *number,
+					status: LeafStatus::Fresh,
+					span,
+				})))
+			})
+			.collect()
+	}
+
 	fn on_head_deactivated(&mut self, hash: &Hash) {
 		self.metrics.on_head_deactivated();
 		self.activation_external_listeners.remove(hash);
@@ -21,6 +21,10 @@ pub use polkadot_node_metrics::metrics::{self, prometheus, Metrics as MetricsTra
 
 use parity_util_mem::MemoryAllocationSnapshot;
 
+/// Histogram buckets for the time of flight of messages, in seconds.
+const TOF_BUCKETS: [f64; 12] =
+	[0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0];
+
 /// Overseer Prometheus metrics.
 #[derive(Clone)]
 struct MetricsInner {
@@ -33,6 +37,10 @@ struct MetricsInner {
 	to_subsystem_unbounded_received: prometheus::GaugeVec<prometheus::U64>,
 	signals_sent: prometheus::GaugeVec<prometheus::U64>,
 	signals_received: prometheus::GaugeVec<prometheus::U64>,
+	to_subsystem_bounded_tof: prometheus::HistogramVec,
+	to_subsystem_unbounded_tof: prometheus::HistogramVec,
+	oneshot_tof: prometheus::HistogramVec,
+	subsystem_restarts_total: prometheus::CounterVec<prometheus::U64>,
 
 	memory_stats_resident: prometheus::Gauge<prometheus::U64>,
 	memory_stats_allocated: prometheus::Gauge<prometheus::U64>,
@@ -61,6 +69,12 @@ impl Metrics {
 		}
 	}
 
+	pub(crate) fn on_subsystem_restarted(&self, subsystem: &'static str) {
+		if let Some(metrics) = &self.0 {
+			metrics.subsystem_restarts_total.with_label_values(&[subsystem]).inc();
+		}
+	}
+
 	pub(crate) fn memory_stats_snapshot(&self, memory_stats: MemoryAllocationSnapshot) {
 		if let Some(metrics) = &self.0 {
 			metrics.memory_stats_allocated.set(memory_stats.allocated);
@@ -105,9 +119,33 @@ impl Metrics {
 						.signals_received
 						.with_label_values(&[name])
 						.set(readouts.signals.received as u64);
+
+					let bounded_tof = metrics.to_subsystem_bounded_tof.with_label_values(&[name]);
+					readouts
+						.bounded_tof
+						.iter()
+						.for_each(|tof| bounded_tof.observe(tof.as_secs_f64()));
+
+					let unbounded_tof =
+						metrics.to_subsystem_unbounded_tof.with_label_values(&[name]);
+					readouts
+						.unbounded_tof
+						.iter()
+						.for_each(|tof| unbounded_tof.observe(tof.as_secs_f64()));
 				});
 		}
 	}
+
+	pub(crate) fn oneshot_tof_snapshot(
+		&self,
+		samples: impl IntoIterator<Item = (&'static str, Duration)>,
+	) {
+		if let Some(metrics) = &self.0 {
+			samples.into_iter().for_each(|(name, tof)| {
+				metrics.oneshot_tof.with_label_values(&[name]).observe(tof.as_secs_f64())
+			});
+		}
+	}
 }
 
 impl MetricsTrait for Metrics {
@@ -194,6 +232,49 @@ impl MetricsTrait for Metrics {
 				)?,
 				registry,
 			)?,
+			to_subsystem_bounded_tof: prometheus::register(
+				prometheus::HistogramVec::new(
+					prometheus::HistogramOpts::new(
+						"polkadot_parachain_subsystem_bounded_tof",
+						"Sampled time messages spent in subsystems' bounded queues, in seconds",
+					)
+					.buckets(TOF_BUCKETS.to_vec()),
+					&["subsystem_name"],
+				)?,
+				registry,
+			)?,
+			to_subsystem_unbounded_tof: prometheus::register(
+				prometheus::HistogramVec::new(
+					prometheus::HistogramOpts::new(
+						"polkadot_parachain_subsystem_unbounded_tof",
+						"Sampled time messages spent in subsystems' unbounded queues, in seconds",
+					)
+					.buckets(TOF_BUCKETS.to_vec()),
+					&["subsystem_name"],
+				)?,
+				registry,
+			)?,
+			oneshot_tof: prometheus::register(
+				prometheus::HistogramVec::new(
+					prometheus::HistogramOpts::new(
+						"polkadot_parachain_oneshot_tof",
+						"Time responses spent in metered oneshot channels, in seconds",
+					)
+					.buckets(TOF_BUCKETS.to_vec()),
+					&["channel_name"],
+				)?,
+				registry,
+			)?,
+			subsystem_restarts_total: prometheus::register(
+				prometheus::CounterVec::<prometheus::U64>::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_subsystem_restarts_total",
+						"Number of times a subsystem was restarted after it exited",
+					),
+					&["subsystem_name"],
+				)?,
+				registry,
+			)?,
 
 			memory_stats_allocated: prometheus::register(
 				prometheus::Gauge::<prometheus::U64>::new(
@@ -308,6 +308,134 @@ fn overseer_ends_on_subsystem_exit() {
 	})
 }
 
+// Fails the first instance on its first signal, every instance reports
+// the signals it receives along with the number of the instance.
+#[derive(Clone)]
+struct FailOnFirstSignal {
+	starts: Arc<atomic::AtomicUsize>,
+	sender: metered::MeteredSender<(usize, OverseerSignal)>,
+}
+
+impl<C> overseer::Subsystem<C, SubsystemError> for FailOnFirstSignal
+where
+	C: overseer::SubsystemContext<
+		Message = BitfieldDistributionMessage,
+		Signal = OverseerSignal,
+		AllMessages = AllMessages,
+	>,
+{
+	fn start(self, mut ctx: C) -> SpawnedSubsystem {
+		let instance = self.starts.fetch_add(1, atomic::Ordering::SeqCst);
+		let mut sender = self.sender;
+
+		SpawnedSubsystem {
+			name: "test-subsystem-fail-on-first-signal",
+			future: Box::pin(async move {
+				loop {
+					match ctx.recv().await {
+						Ok(FromOverseer::Signal(OverseerSignal::Conclude)) => return Ok(()),
+						Ok(FromOverseer::Signal(s)) => {
+							sender.send((instance, s)).await.unwrap();
+							if instance == 0 {
+								return Err(SubsystemError::Context("failing on purpose".into()))
+							}
+						},
+						Ok(_) => continue,
+						Err(_) => return Ok(()),
+					}
+				}
+			}),
+		}
+	}
+}
+
+// A restartable subsystem that fails is restarted on its existing channels
+// and receives the currently active leaves again.
+#[test]
+fn overseer_restarts_failed_subsystem() {
+	let spawner = sp_core::testing::TaskExecutor::new();
+
+	executor::block_on(async move {
+		let first_block_hash = [1; 32].into();
+		let second_block_hash = [2; 32].into();
+
+		let first_block =
+			BlockInfo { hash: first_block_hash, parent_hash: [0; 32].into(), number: 1 };
+		let second_block =
+			BlockInfo { hash: second_block_hash, parent_hash: first_block_hash, number: 2 };
+
+		let (tx, mut rx) = metered::channel(64);
+		let starts = Arc::new(atomic::AtomicUsize::new(0));
+
+		let registry = prometheus::Registry::new();
+		let (overseer, handle) =
+			dummy_overseer_builder(spawner, MockSupportsParachains, Some(&registry))
+				.unwrap()
+				.replace_bitfield_distribution({
+					let starts = starts.clone();
+					move |_| FailOnFirstSignal { starts, sender: tx }
+				})
+				.leaves(block_info_to_pair(vec![first_block]))
+				.build()
+				.unwrap();
+		let mut handle = Handle::new(handle);
+
+		let overseer_fut = overseer.run().fuse();
+		pin_mut!(overseer_fut);
+
+		let mut results = Vec::new();
+
+		loop {
+			select! {
+				res = overseer_fut => {
+					assert!(res.is_ok());
+					break;
+				},
+				res = rx.next() => {
+					if let Some(res) = res {
+						results.push(res);
+						match results.len() {
+							// The restarted instance caught up on the active leaves.
+							2 => handle.block_imported(second_block.clone()).await,
+							3 => handle.stop().await,
+							_ => {},
+						}
+					}
+				}
+				complete => break,
+			}
+		}
+
+		let first_leaf =
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: first_block_hash,
+				number: 1,
+				span: Arc::new(jaeger::Span::Disabled),
+				status: LeafStatus::Fresh,
+			}));
+		let second_leaf = OverseerSignal::ActiveLeaves(ActiveLeavesUpdate {
+			activated: Some(ActivatedLeaf {
+				hash: second_block_hash,
+				number: 2,
+				span: Arc::new(jaeger::Span::Disabled),
+				status: LeafStatus::Fresh,
+			}),
+			deactivated: [first_block_hash].as_ref().into(),
+		});
+
+		assert_eq!(results, vec![(0, first_leaf.clone()), (1, first_leaf), (1, second_leaf)]);
+		assert_eq!(starts.load(atomic::Ordering::SeqCst), 2);
+
+		let restarts = registry
+			.gather()
+			.into_iter()
+			.find(|mf| mf.get_name() == "polkadot_parachain_subsystem_restarts_total")
+			.expect("A restart was recorded. qed");
+		assert_eq!(restarts.get_metric()[0].get_label()[0].get_value(), "bitfield-distribution");
+		assert_eq!(restarts.get_metric()[0].get_counter().get_value() as u64, 1);
+	});
+}
+
 struct TestSubsystem5(metered::MeteredSender<OverseerSignal>);
 
 impl<C> overseer::Subsystem<C, SubsystemError> for TestSubsystem5
@@ -848,6 +976,7 @@ fn test_availability_recovery_msg() -> AvailabilityRecoveryMessage {
 		dummy_candidate_receipt(dummy_hash()),
 		Default::default(),
 		None,
+		None,
 		sender,
 	)
 }
@@ -0,0 +1,496 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recording of the signals and messages delivered to a subsystem.
+//!
+//! A [`TraceRecorder`] can be attached to any subsystem via the `record_*` methods
+//! of the overseer builder. It writes a SCALE encoded [`TraceEntry`] for every signal
+//! and message the subsystem receives, which allows to replay a production
+//! incident against a standalone instance of the subsystem later on.
+//!
+//! Entries are written by a dedicated thread, so that recording does not block the
+//! subsystem on file I/O.
+//!
+//! Response channels contained in messages can not be recorded, they are replaced
+//! with fresh channels when decoding a trace.
+
+use std::{
+	fs::File,
+	io::{self, BufWriter, Write},
+	path::Path,
+	sync::Arc,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures::{
+	channel::{mpsc, oneshot},
+	executor, StreamExt,
+};
+use parity_scale_codec::{Decode, Encode};
+
+use polkadot_node_primitives::{
+	approval::{IndirectAssignmentCert, IndirectSignedApprovalVote},
+	SignedDisputeStatement,
+};
+use polkadot_node_subsystem_types::messages::{
+	ApprovalVotingMessage, BlockDescription, DisputeCoordinatorMessage,
+};
+use polkadot_primitives::v1::{
+	BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt, DisputeStatement, Hash,
+	SessionIndex, ValidatorId, ValidatorIndex, ValidatorSignature,
+};
+
+use crate::{
+	gen::MessageRecorder, jaeger, ActivatedLeaf, ActiveLeavesUpdate, FromOverseer, LeafStatus,
+	OverseerSignal,
+};
+
+const LOG_TARGET: &str = "parachain::overseer-trace";
+
+/// A single recorded signal or message.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TraceEntry {
+	/// Milliseconds since the UNIX epoch, at the time the subsystem received the event.
+	pub timestamp: u64,
+	/// The received event.
+	pub event: TraceEvent,
+}
+
+/// A recorded event.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum TraceEvent {
+	/// A signal.
+	Signal(TraceSignal),
+	/// A message, encoded via [`TraceableMessage::encode_trace`].
+	Message(Vec<u8>),
+}
+
+/// A recorded [`OverseerSignal`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum TraceSignal {
+	/// See [`OverseerSignal::ActiveLeaves`].
+	ActiveLeaves {
+		/// The activated leaf, if any.
+		activated: Option<TraceLeaf>,
+		/// The deactivated leaves.
+		deactivated: Vec<Hash>,
+	},
+	/// See [`OverseerSignal::BlockFinalized`].
+	BlockFinalized(Hash, BlockNumber),
+	/// See [`OverseerSignal::Conclude`].
+	Conclude,
+}
+
+/// A recorded [`ActivatedLeaf`], without its span.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TraceLeaf {
+	/// The block hash.
+	pub hash: Hash,
+	/// The block number.
+	pub number: BlockNumber,
+	/// Whether the leaf was fresh.
+	pub fresh: bool,
+}
+
+impl From<&OverseerSignal> for TraceSignal {
+	fn from(signal: &OverseerSignal) -> Self {
+		match signal {
+			OverseerSignal::ActiveLeaves(update) => TraceSignal::ActiveLeaves {
+				activated: update.activated.as_ref().map(|leaf| TraceLeaf {
+					hash: leaf.hash,
+					number: leaf.number,
+					fresh: matches!(leaf.status, LeafStatus::Fresh),
+				}),
+				deactivated: update.deactivated.to_vec(),
+			},
+			OverseerSignal::BlockFinalized(hash, number) =>
+				TraceSignal::BlockFinalized(*hash, *number),
+			OverseerSignal::Conclude => TraceSignal::Conclude,
+		}
+	}
+}
+
+impl From<TraceSignal> for OverseerSignal {
+	fn from(signal: TraceSignal) -> Self {
+		match signal {
+			TraceSignal::ActiveLeaves { activated, deactivated } =>
+				OverseerSignal::ActiveLeaves(ActiveLeavesUpdate {
+					activated: activated.map(|leaf| ActivatedLeaf {
+						hash: leaf.hash,
+						number: leaf.number,
+						status: if leaf.fresh { LeafStatus::Fresh } else { LeafStatus::Stale },
+						span: Arc::new(jaeger::Span::Disabled),
+					}),
+					deactivated: deactivated.into_iter().collect(),
+				}),
+			TraceSignal::BlockFinalized(hash, number) =>
+				OverseerSignal::BlockFinalized(hash, number),
+			TraceSignal::Conclude => OverseerSignal::Conclude,
+		}
+	}
+}
+
+impl TraceEvent {
+	/// Record what a subsystem received.
+	pub fn recorded<M: TraceableMessage>(msg: &FromOverseer<M, OverseerSignal>) -> Self {
+		match msg {
+			FromOverseer::Signal(signal) => TraceEvent::Signal(signal.into()),
+			FromOverseer::Communication { msg } => TraceEvent::Message(msg.encode_trace()),
+		}
+	}
+}
+
+impl TraceEntry {
+	/// Convert the entry back into what the subsystem originally received.
+	pub fn into_from_overseer<M: TraceableMessage>(
+		self,
+	) -> Result<FromOverseer<M, OverseerSignal>, parity_scale_codec::Error> {
+		Ok(match self.event {
+			TraceEvent::Signal(signal) => FromOverseer::Signal(signal.into()),
+			TraceEvent::Message(encoded) =>
+				FromOverseer::Communication { msg: M::decode_trace(&mut &encoded[..])? },
+		})
+	}
+}
+
+/// A message that can be recorded in a trace.
+pub trait TraceableMessage: Sized {
+	/// Encode the message, omitting any response channels.
+	fn encode_trace(&self) -> Vec<u8>;
+
+	/// Decode a message, creating fresh response channels.
+	///
+	/// The receiving ends of the response channels are dropped.
+	fn decode_trace(input: &mut &[u8]) -> Result<Self, parity_scale_codec::Error>;
+}
+
+/// Create a response channel, the receiving end of which is dropped.
+fn detached_sender<T>() -> oneshot::Sender<T> {
+	oneshot::channel().0
+}
+
+#[derive(Encode, Decode)]
+enum TracedApprovalVotingMessage {
+	CheckAndImportAssignment(IndirectAssignmentCert, CandidateIndex),
+	CheckAndImportApproval(IndirectSignedApprovalVote),
+	ApprovedAncestor(Hash, BlockNumber),
+}
+
+impl TraceableMessage for ApprovalVotingMessage {
+	fn encode_trace(&self) -> Vec<u8> {
+		let traced = match self {
+			ApprovalVotingMessage::CheckAndImportAssignment(cert, candidate_index, _) =>
+				TracedApprovalVotingMessage::CheckAndImportAssignment(
+					cert.clone(),
+					*candidate_index,
+				),
+			ApprovalVotingMessage::CheckAndImportApproval(vote, _) =>
+				TracedApprovalVotingMessage::CheckAndImportApproval(vote.clone()),
+			ApprovalVotingMessage::ApprovedAncestor(hash, number, _) =>
+				TracedApprovalVotingMessage::ApprovedAncestor(*hash, *number),
+		};
+		traced.encode()
+	}
+
+	fn decode_trace(input: &mut &[u8]) -> Result<Self, parity_scale_codec::Error> {
+		Ok(match TracedApprovalVotingMessage::decode(input)? {
+			TracedApprovalVotingMessage::CheckAndImportAssignment(cert, candidate_index) =>
+				ApprovalVotingMessage::CheckAndImportAssignment(
+					cert,
+					candidate_index,
+					detached_sender(),
+				),
+			TracedApprovalVotingMessage::CheckAndImportApproval(vote) =>
+				ApprovalVotingMessage::CheckAndImportApproval(vote, detached_sender()),
+			TracedApprovalVotingMessage::ApprovedAncestor(hash, number) =>
+				ApprovalVotingMessage::ApprovedAncestor(hash, number, detached_sender()),
+		})
+	}
+}
+
+#[derive(Encode, Decode)]
+struct TracedDisputeStatement {
+	statement: DisputeStatement,
+	candidate_hash: CandidateHash,
+	session_index: SessionIndex,
+	validator_public: ValidatorId,
+	validator_signature: ValidatorSignature,
+}
+
+impl From<&SignedDisputeStatement> for TracedDisputeStatement {
+	fn from(statement: &SignedDisputeStatement) -> Self {
+		TracedDisputeStatement {
+			statement: statement.statement().clone(),
+			candidate_hash: *statement.candidate_hash(),
+			session_index: statement.session_index(),
+			validator_public: statement.validator_public().clone(),
+			validator_signature: statement.validator_signature().clone(),
+		}
+	}
+}
+
+impl From<TracedDisputeStatement> for SignedDisputeStatement {
+	fn from(traced: TracedDisputeStatement) -> Self {
+		SignedDisputeStatement::new_unchecked_from_trusted_source(
+			traced.statement,
+			traced.candidate_hash,
+			traced.session_index,
+			traced.validator_public,
+			traced.validator_signature,
+		)
+	}
+}
+
+#[derive(Encode, Decode)]
+enum TracedDisputeCoordinatorMessage {
+	ImportStatements {
+		candidate_hash: CandidateHash,
+		candidate_receipt: CandidateReceipt,
+		session: SessionIndex,
+		statements: Vec<(TracedDisputeStatement, ValidatorIndex)>,
+	},
+	RecentDisputes,
+	ActiveDisputes,
+	QueryCandidateVotes(Vec<(SessionIndex, CandidateHash)>),
+	IssueLocalStatement(SessionIndex, CandidateHash, CandidateReceipt, bool),
+	DetermineUndisputedChain {
+		base: (BlockNumber, Hash),
+		block_descriptions: Vec<(Hash, SessionIndex, Vec<CandidateHash>)>,
+	},
+	AssembleEvidence(SessionIndex, CandidateHash),
+}
+
+impl TraceableMessage for DisputeCoordinatorMessage {
+	fn encode_trace(&self) -> Vec<u8> {
+		let traced = match self {
+			DisputeCoordinatorMessage::ImportStatements {
+				candidate_hash,
+				candidate_receipt,
+				session,
+				statements,
+				..
+			} => TracedDisputeCoordinatorMessage::ImportStatements {
+				candidate_hash: *candidate_hash,
+				candidate_receipt: candidate_receipt.clone(),
+				session: *session,
+				statements: statements
+					.iter()
+					.map(|(statement, index)| (statement.into(), *index))
+					.collect(),
+			},
+			DisputeCoordinatorMessage::RecentDisputes(_) =>
+				TracedDisputeCoordinatorMessage::RecentDisputes,
+			DisputeCoordinatorMessage::ActiveDisputes(_) =>
+				TracedDisputeCoordinatorMessage::ActiveDisputes,
+			DisputeCoordinatorMessage::QueryCandidateVotes(query, _) =>
+				TracedDisputeCoordinatorMessage::QueryCandidateVotes(query.clone()),
+			DisputeCoordinatorMessage::IssueLocalStatement(
+				session,
+				candidate_hash,
+				candidate_receipt,
+				valid,
+			) => TracedDisputeCoordinatorMessage::IssueLocalStatement(
+				*session,
+				*candidate_hash,
+				candidate_receipt.clone(),
+				*valid,
+			),
+			DisputeCoordinatorMessage::DetermineUndisputedChain {
+				base,
+				block_descriptions,
+				..
+			} => TracedDisputeCoordinatorMessage::DetermineUndisputedChain {
+				base: *base,
+				block_descriptions: block_descriptions
+					.iter()
+					.map(|d| (d.block_hash, d.session, d.candidates.clone()))
+					.collect(),
+			},
+			DisputeCoordinatorMessage::AssembleEvidence(session, candidate_hash, _) =>
+				TracedDisputeCoordinatorMessage::AssembleEvidence(*session, *candidate_hash),
+		};
+		traced.encode()
+	}
+
+	fn decode_trace(input: &mut &[u8]) -> Result<Self, parity_scale_codec::Error> {
+		Ok(match TracedDisputeCoordinatorMessage::decode(input)? {
+			TracedDisputeCoordinatorMessage::ImportStatements {
+				candidate_hash,
+				candidate_receipt,
+				session,
+				statements,
+			} => DisputeCoordinatorMessage::ImportStatements {
+				candidate_hash,
+				candidate_receipt,
+				session,
+				statements: statements
+					.into_iter()
+					.map(|(statement, index)| (statement.into(), index))
+					.collect(),
+				pending_confirmation: detached_sender(),
+			},
+			TracedDisputeCoordinatorMessage::RecentDisputes =>
+				DisputeCoordinatorMessage::RecentDisputes(detached_sender()),
+			TracedDisputeCoordinatorMessage::ActiveDisputes =>
+				DisputeCoordinatorMessage::ActiveDisputes(detached_sender()),
+			TracedDisputeCoordinatorMessage::QueryCandidateVotes(query) =>
+				DisputeCoordinatorMessage::QueryCandidateVotes(query, detached_sender()),
+			TracedDisputeCoordinatorMessage::IssueLocalStatement(
+				session,
+				candidate_hash,
+				candidate_receipt,
+				valid,
+			) => DisputeCoordinatorMessage::IssueLocalStatement(
+				session,
+				candidate_hash,
+				candidate_receipt,
+				valid,
+			),
+			TracedDisputeCoordinatorMessage::DetermineUndisputedChain {
+				base,
+				block_descriptions,
+			} => DisputeCoordinatorMessage::DetermineUndisputedChain {
+				base,
+				block_descriptions: block_descriptions
+					.into_iter()
+					.map(|(block_hash, session, candidates)| BlockDescription {
+						block_hash,
+						session,
+						candidates,
+					})
+					.collect(),
+				tx: detached_sender(),
+			},
+			TracedDisputeCoordinatorMessage::AssembleEvidence(session, candidate_hash) =>
+				DisputeCoordinatorMessage::AssembleEvidence(
+					session,
+					candidate_hash,
+					detached_sender(),
+				),
+		})
+	}
+}
+
+enum WriterCommand {
+	/// Write an encoded entry.
+	Write(Vec<u8>),
+	/// Flush the written entries and acknowledge it.
+	Flush(oneshot::Sender<()>),
+}
+
+/// Writes the signals and messages delivered to a subsystem to a trace.
+///
+/// The entries are handed to a writer thread, which flushes them whenever it runs out of
+/// entries to write. The thread exits once the recorder is dropped.
+pub struct TraceRecorder {
+	to_writer: mpsc::UnboundedSender<WriterCommand>,
+}
+
+impl TraceRecorder {
+	/// Create a recorder writing to the given writer.
+	pub fn new(writer: impl Write + Send + 'static) -> io::Result<Self> {
+		let (to_writer, from_recorder) = mpsc::unbounded();
+		std::thread::Builder::new()
+			.name("trace-recorder".into())
+			.spawn(move || run_writer(writer, from_recorder))?;
+		Ok(TraceRecorder { to_writer })
+	}
+
+	/// Create a recorder writing to a new file at the given path.
+	pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+		Self::new(BufWriter::new(File::create(path)?))
+	}
+
+	/// Block until all entries recorded so far have been written and flushed.
+	pub fn flush(&self) {
+		let (tx, rx) = oneshot::channel();
+		if self.to_writer.unbounded_send(WriterCommand::Flush(tx)).is_ok() {
+			let _ = executor::block_on(rx);
+		}
+	}
+
+	fn write_entry(&self, entry: TraceEntry) {
+		if self.to_writer.unbounded_send(WriterCommand::Write(entry.encode())).is_err() {
+			tracing::warn!(target: LOG_TARGET, "Trace writer is gone, dropping trace entry");
+		}
+	}
+}
+
+fn flush(writer: &mut impl Write) {
+	if let Err(err) = writer.flush() {
+		tracing::warn!(target: LOG_TARGET, ?err, "Failed to flush trace");
+	}
+}
+
+fn run_writer(mut writer: impl Write, mut commands: mpsc::UnboundedReceiver<WriterCommand>) {
+	// a partially written entry corrupts the rest of the trace, so writing stops on failure.
+	let mut failed = false;
+
+	loop {
+		let command = match commands.try_next() {
+			Ok(Some(command)) => command,
+			// all recorders are gone.
+			Ok(None) => break,
+			// nothing to write right now, make what was written so far durable.
+			Err(_) => {
+				flush(&mut writer);
+				match executor::block_on(commands.next()) {
+					Some(command) => command,
+					None => break,
+				}
+			},
+		};
+
+		match command {
+			WriterCommand::Write(_) if failed => {},
+			WriterCommand::Write(entry) =>
+				if let Err(err) = writer.write_all(&entry) {
+					tracing::warn!(target: LOG_TARGET, ?err, "Failed to write trace entry, stopping");
+					failed = true;
+				},
+			WriterCommand::Flush(ack) => {
+				flush(&mut writer);
+				let _ = ack.send(());
+			},
+		}
+	}
+
+	flush(&mut writer);
+}
+
+impl<M: TraceableMessage> MessageRecorder<M, OverseerSignal> for TraceRecorder {
+	fn record(&self, msg: &FromOverseer<M, OverseerSignal>) {
+		let timestamp = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_millis() as u64)
+			.unwrap_or_default();
+		self.write_entry(TraceEntry { timestamp, event: TraceEvent::recorded(msg) });
+	}
+}
+
+/// Encode the given entries as a trace.
+pub fn write_trace(entries: impl IntoIterator<Item = TraceEntry>) -> Vec<u8> {
+	entries.into_iter().flat_map(|entry| entry.encode()).collect()
+}
+
+/// Decode all entries of a trace.
+pub fn read_trace(mut input: &[u8]) -> Result<Vec<TraceEntry>, parity_scale_codec::Error> {
+	let mut entries = Vec::new();
+	while !input.is_empty() {
+		entries.push(TraceEntry::decode(&mut input)?);
+	}
+	Ok(entries)
+}
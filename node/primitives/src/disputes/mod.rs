@@ -21,10 +21,11 @@ use parity_scale_codec::{Decode, Encode};
 use sp_application_crypto::AppKey;
 use sp_keystore::{CryptoStore, Error as KeystoreError, SyncCryptoStorePtr};
 
-use super::{Statement, UncheckedSignedFullStatement};
+use super::{AvailableData, Statement, UncheckedSignedFullStatement};
 use polkadot_primitives::v1::{
 	CandidateHash, CandidateReceipt, DisputeStatement, InvalidDisputeStatementKind, SessionIndex,
-	SigningContext, ValidDisputeStatementKind, ValidatorId, ValidatorIndex, ValidatorSignature,
+	SigningContext, ValidDisputeStatementKind, ValidationCode, ValidatorId, ValidatorIndex,
+	ValidatorSignature,
 };
 
 /// `DisputeMessage` and related types.
@@ -42,7 +43,7 @@ pub struct SignedDisputeStatement {
 }
 
 /// Tracked votes on candidates, for the purposes of dispute resolution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Encode, Decode)]
 pub struct CandidateVotes {
 	/// The receipt of the candidate itself.
 	pub candidate_receipt: CandidateReceipt,
@@ -65,6 +66,26 @@ impl CandidateVotes {
 	}
 }
 
+/// Everything needed to re-examine a disputed candidate without access to the network.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct DisputeEvidence {
+	/// The session the dispute was raised in.
+	pub session: SessionIndex,
+	/// All votes on the candidate, including the candidate receipt.
+	pub votes: CandidateVotes,
+	/// The data recovered from availability.
+	pub available_data: AvailableData,
+	/// The validation code referenced by the candidate descriptor.
+	pub validation_code: ValidationCode,
+}
+
+impl DisputeEvidence {
+	/// The receipt of the disputed candidate.
+	pub fn candidate_receipt(&self) -> &CandidateReceipt {
+		&self.votes.candidate_receipt
+	}
+}
+
 impl SignedDisputeStatement {
 	/// Create a new `SignedDisputeStatement` from information
 	/// that is available on-chain, and hence already can be trusted.
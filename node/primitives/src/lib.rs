@@ -48,7 +48,7 @@ pub mod approval;
 /// Disputes related types.
 pub mod disputes;
 pub use disputes::{
-	CandidateVotes, DisputeMessage, DisputeMessageCheckError, InvalidDisputeVote,
+	CandidateVotes, DisputeEvidence, DisputeMessage, DisputeMessageCheckError, InvalidDisputeVote,
 	SignedDisputeStatement, UncheckedDisputeMessage, ValidDisputeVote,
 };
 
@@ -27,7 +27,9 @@ mod relay_chain_selection;
 pub mod overseer;
 
 #[cfg(feature = "full-node")]
-pub use self::overseer::{OverseerGen, OverseerGenArgs, RealOverseerGen};
+pub use self::overseer::{
+	OverseerGen, OverseerGenArgs, RealOverseerGen, SubsystemTrace, TracedSubsystem,
+};
 
 #[cfg(test)]
 mod tests;
@@ -50,6 +52,9 @@ use {
 	tracing::info,
 };
 
+#[cfg(feature = "full-node")]
+pub use parachains_db::inspect as parachains_db_inspect;
+pub use parachains_db::Backend as ParachainsDbBackend;
 pub use sp_core::traits::SpawnNamed;
 #[cfg(feature = "full-node")]
 pub use {
@@ -94,7 +99,7 @@ pub use polkadot_client::{
 	AbstractClient, Client, ClientHandle, ExecuteWithClient, FullBackend, FullClient,
 	RuntimeApiCollection,
 };
-pub use polkadot_primitives::v1::{Block, BlockId, CollatorPair, Hash, Id as ParaId};
+pub use polkadot_primitives::v1::{Block, BlockId, CollatorId, CollatorPair, Hash, Id as ParaId};
 pub use sc_client_api::{Backend, CallExecutor, ExecutionStrategy};
 pub use sc_consensus::{BlockImport, LongestChain};
 use sc_executor::NativeElseWasmExecutor;
@@ -398,6 +403,7 @@ fn new_partial<RuntimeApi, ExecutorDispatch, ChainSelection>(
 		ExecutorDispatch,
 	>,
 	select_chain: ChainSelection,
+	overseer_handle: Option<Handle>,
 ) -> Result<
 	service::PartialComponents<
 		FullClient<RuntimeApi, ExecutorDispatch>,
@@ -537,8 +543,11 @@ where
 				beefy: polkadot_rpc::BeefyDeps {
 					beefy_commitment_stream: beefy_commitment_stream.clone(),
 					beefy_best_block_stream: beefy_best_block_stream.clone(),
-					subscription_executor,
+					subscription_executor: subscription_executor.clone(),
 				},
+				parachain: overseer_handle.clone().map(|overseer_handle| {
+					polkadot_rpc::ParachainDeps { overseer_handle, subscription_executor }
+				}),
 			};
 
 			polkadot_rpc::create_full(deps).map_err(Into::into)
@@ -674,6 +683,13 @@ pub fn new_full<RuntimeApi, ExecutorDispatch, OverseerGenerator>(
 	jaeger_agent: Option<std::net::SocketAddr>,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
 	program_path: Option<std::path::PathBuf>,
+	enable_pvf_sandbox: bool,
+	pvf_execute_address_space_limit: Option<u64>,
+	enable_pvf_dual_execute: bool,
+	parachains_db_backend: ParachainsDbBackend,
+	dispute_evidence_path: Option<std::path::PathBuf>,
+	preferred_collators: std::collections::HashMap<ParaId, Vec<CollatorId>>,
+	subsystem_trace: Option<SubsystemTrace>,
 	overseer_gen: OverseerGenerator,
 ) -> Result<NewFull<Arc<FullClient<RuntimeApi, ExecutorDispatch>>>, Error>
 where
@@ -761,6 +777,8 @@ where
 		&mut config,
 		basics,
 		select_chain,
+		// The parachain RPC is only served if the overseer is actually running.
+		requires_overseer_for_chain_sel.then(|| overseer_handle.clone()),
 	)?;
 
 	let shared_voter_state = rpc_setup;
@@ -853,6 +871,7 @@ where
 
 	let parachains_db = crate::parachains_db::open_creating(
 		config.database.path().ok_or(Error::DatabasePathRequired)?.into(),
+		parachains_db_backend,
 		crate::parachains_db::CacheSizes::default(),
 	)?;
 
@@ -876,6 +895,9 @@ where
 			None => std::env::current_exe()?,
 			Some(p) => p,
 		},
+		enable_sandbox: enable_pvf_sandbox,
+		execute_worker_address_space_limit: pvf_execute_address_space_limit,
+		dual_execute: enable_pvf_dual_execute,
 	};
 
 	let chain_selection_config = ChainSelectionConfig {
@@ -885,8 +907,12 @@ where
 
 	let dispute_coordinator_config = DisputeCoordinatorConfig {
 		col_data: crate::parachains_db::REAL_COLUMNS.col_dispute_coordinator_data,
+		evidence_path: dispute_evidence_path,
 	};
 
+	let collator_scoreboard_path =
+		config.database.path().ok_or(Error::DatabasePathRequired)?.join("collator-scoreboard");
+
 	let rpc_handlers = service::spawn_tasks(service::SpawnTasksParams {
 		config,
 		backend: backend.clone(),
@@ -982,6 +1008,9 @@ where
 					dispute_coordinator_config,
 					disputes_enabled,
 					pvf_checker_enabled,
+					collator_scoreboard_path: Some(collator_scoreboard_path),
+					preferred_collators,
+					subsystem_trace,
 				},
 			)
 			.map_err(|e| {
@@ -1203,6 +1232,7 @@ macro_rules! chain_ops {
 				&mut config,
 				basics,
 				chain_selection,
+				None,
 			)?;
 		Ok((Arc::new(Client::$variant(client)), backend, import_queue, task_manager))
 	}};
@@ -1260,6 +1290,13 @@ pub fn build_full(
 	enable_beefy: bool,
 	jaeger_agent: Option<std::net::SocketAddr>,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
+	enable_pvf_sandbox: bool,
+	pvf_execute_address_space_limit: Option<u64>,
+	enable_pvf_dual_execute: bool,
+	parachains_db_backend: ParachainsDbBackend,
+	dispute_evidence_path: Option<std::path::PathBuf>,
+	preferred_collators: std::collections::HashMap<ParaId, Vec<CollatorId>>,
+	subsystem_trace: Option<SubsystemTrace>,
 	overseer_gen: impl OverseerGen,
 ) -> Result<NewFull<Client>, Error> {
 	#[cfg(feature = "rococo-native")]
@@ -1275,6 +1312,13 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			pvf_execute_address_space_limit,
+			enable_pvf_dual_execute,
+			parachains_db_backend,
+			dispute_evidence_path,
+			preferred_collators,
+			subsystem_trace,
 			overseer_gen,
 		)
 		.map(|full| full.with_client(Client::Rococo))
@@ -1290,6 +1334,13 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			pvf_execute_address_space_limit,
+			enable_pvf_dual_execute,
+			parachains_db_backend,
+			dispute_evidence_path,
+			preferred_collators,
+			subsystem_trace,
 			overseer_gen,
 		)
 		.map(|full| full.with_client(Client::Kusama))
@@ -1305,6 +1356,13 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			pvf_execute_address_space_limit,
+			enable_pvf_dual_execute,
+			parachains_db_backend,
+			dispute_evidence_path,
+			preferred_collators,
+			subsystem_trace,
 			overseer_gen,
 		)
 		.map(|full| full.with_client(Client::Westend))
@@ -1320,6 +1378,13 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			pvf_execute_address_space_limit,
+			enable_pvf_dual_execute,
+			parachains_db_backend,
+			dispute_evidence_path,
+			preferred_collators,
+			subsystem_trace,
 			overseer_gen,
 		)
 		.map(|full| full.with_client(Client::Polkadot))
@@ -30,18 +30,21 @@ pub use polkadot_overseer::{
 	HeadSupportsParachains,
 };
 use polkadot_overseer::{
-	metrics::Metrics as OverseerMetrics, BlockInfo, MetricsTrait, Overseer, OverseerBuilder,
-	OverseerConnector, OverseerHandle,
+	metrics::Metrics as OverseerMetrics, trace::TraceRecorder, BlockInfo, MetricsTrait, Overseer,
+	OverseerBuilder, OverseerConnector, OverseerHandle,
 };
 
-use polkadot_primitives::v2::ParachainHost;
+use polkadot_primitives::{
+	v1::{CollatorId, Id as ParaId},
+	v2::ParachainHost,
+};
 use sc_authority_discovery::Service as AuthorityDiscoveryService;
 use sc_client_api::AuxStore;
 use sc_keystore::LocalKeystore;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_consensus_babe::BabeApi;
-use std::sync::Arc;
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 pub use polkadot_approval_distribution::ApprovalDistribution as ApprovalDistributionSubsystem;
 pub use polkadot_availability_bitfield_distribution::BitfieldDistribution as BitfieldDistributionSubsystem;
@@ -112,6 +115,30 @@ where
 	pub disputes_enabled: bool,
 	/// Enable PVF pre-checking
 	pub pvf_checker_enabled: bool,
+	/// Where the collator protocol keeps the scores of collators, if at all.
+	pub collator_scoreboard_path: Option<PathBuf>,
+	/// Collators per para whose advertisements are fetched before all others.
+	pub preferred_collators: HashMap<ParaId, Vec<CollatorId>>,
+	/// Record the signals and messages delivered to a subsystem, if any.
+	pub subsystem_trace: Option<SubsystemTrace>,
+}
+
+/// The subsystems whose signals and messages can be recorded.
+#[derive(Debug, Clone, Copy)]
+pub enum TracedSubsystem {
+	/// The approval voting subsystem.
+	ApprovalVoting,
+	/// The dispute coordinator subsystem.
+	DisputeCoordinator,
+}
+
+/// Where to record the signals and messages delivered to a subsystem.
+#[derive(Debug, Clone)]
+pub struct SubsystemTrace {
+	/// The subsystem to record.
+	pub subsystem: TracedSubsystem,
+	/// The trace file, which is truncated if it exists.
+	pub path: PathBuf,
 }
 
 /// Obtain a prepared `OverseerBuilder`, that is initialized
@@ -140,6 +167,9 @@ pub fn prepared_overseer_builder<'a, Spawner, RuntimeClient>(
 		dispute_coordinator_config,
 		disputes_enabled,
 		pvf_checker_enabled,
+		collator_scoreboard_path,
+		preferred_collators,
+		subsystem_trace,
 	}: OverseerGenArgs<'a, Spawner, RuntimeClient>,
 ) -> Result<
 	OverseerBuilder<
@@ -188,7 +218,7 @@ where
 			IncomingRequestReceivers { pov_req_receiver, chunk_req_receiver },
 			Metrics::register(registry)?,
 		))
-		.availability_recovery(AvailabilityRecoverySubsystem::with_chunks_only(
+		.availability_recovery(AvailabilityRecoverySubsystem::with_systematic_chunks(
 			available_data_req_receiver,
 			Metrics::register(registry)?,
 		))
@@ -231,6 +261,8 @@ where
 				IsCollator::No => ProtocolSide::Validator {
 					keystore: keystore.clone(),
 					eviction_policy: Default::default(),
+					scoreboard_path: collator_scoreboard_path,
+					preferred_collators,
 					metrics: Metrics::register(registry)?,
 				},
 			};
@@ -299,6 +331,17 @@ where
 		.known_leaves(LruCache::new(KNOWN_LEAVES_CACHE_SIZE))
 		.metrics(metrics)
 		.spawner(spawner);
+
+	let builder = match subsystem_trace {
+		None => builder,
+		Some(SubsystemTrace { subsystem, path }) => {
+			let recorder = Arc::new(TraceRecorder::create(&path)?);
+			match subsystem {
+				TracedSubsystem::ApprovalVoting => builder.record_approval_voting(recorder),
+				TracedSubsystem::DisputeCoordinator => builder.record_dispute_coordinator(recorder),
+			}
+		},
+	};
 	Ok(builder)
 }
 
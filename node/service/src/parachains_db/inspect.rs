@@ -0,0 +1,116 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline inspection of the parachains DB.
+
+#![cfg(feature = "full-node")]
+
+pub use polkadot_node_subsystem_util::inspection::Filter;
+pub use polkadot_primitives::v1::CandidateHash;
+
+use polkadot_node_core_approval_voting as approval_voting;
+use polkadot_node_core_av_store as av_store;
+use polkadot_node_core_chain_selection as chain_selection;
+use polkadot_node_core_dispute_coordinator as dispute_coordinator;
+use serde_json::{Map, Value};
+use std::{io, path::PathBuf};
+
+use super::{other_io_error, Backend, REAL_COLUMNS};
+
+/// The records of a single subsystem in the parachains DB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+	/// The candidate meta information of the availability store.
+	AvailabilityStore,
+	/// The block and candidate entries of approval voting.
+	ApprovalVoting,
+	/// The block tree of chain selection.
+	ChainSelection,
+	/// The recent disputes and the votes of the dispute coordinator.
+	DisputeCoordinator,
+}
+
+impl Section {
+	/// All the sections of the parachains DB.
+	pub fn all() -> &'static [Section] {
+		&[
+			Section::AvailabilityStore,
+			Section::ApprovalVoting,
+			Section::ChainSelection,
+			Section::DisputeCoordinator,
+		]
+	}
+
+	fn key(&self) -> &'static str {
+		match self {
+			Section::AvailabilityStore => "availability_store",
+			Section::ApprovalVoting => "approval_voting",
+			Section::ChainSelection => "chain_selection",
+			Section::DisputeCoordinator => "dispute_coordinator",
+		}
+	}
+}
+
+/// Opens the parachains DB under `root` read-only and decodes the records of the given sections
+/// selected by the filter.
+///
+/// The result is a JSON object with an entry for every section.
+pub fn inspect(
+	root: PathBuf,
+	backend: Backend,
+	sections: &[Section],
+	filter: &Filter,
+) -> io::Result<Value> {
+	let read_only = super::open_read_only(root, backend)?;
+	let db = read_only.db();
+
+	let mut result = Map::new();
+	for section in sections {
+		let value = match section {
+			Section::AvailabilityStore => {
+				let config = av_store::Config {
+					col_data: REAL_COLUMNS.col_availability_data,
+					col_meta: REAL_COLUMNS.col_availability_meta,
+				};
+				av_store::inspect::candidate_meta(&**db, &config, filter)
+					.map(Value::Array)
+					.map_err(|e| inspection_error(section, e))?
+			},
+			Section::ApprovalVoting =>
+				approval_voting::inspect::entries(&**db, REAL_COLUMNS.col_approval_data, filter)
+					.map_err(|e| inspection_error(section, e))?,
+			Section::ChainSelection => chain_selection::inspect::tree(
+				db.clone(),
+				REAL_COLUMNS.col_chain_selection_data,
+				filter,
+			)
+			.map_err(|e| inspection_error(section, e))?,
+			Section::DisputeCoordinator => dispute_coordinator::inspect::disputes(
+				&**db,
+				REAL_COLUMNS.col_dispute_coordinator_data,
+				filter,
+			)
+			.map_err(|e| inspection_error(section, e))?,
+		};
+		let _ = result.insert(section.key().into(), value);
+	}
+
+	Ok(Value::Object(result))
+}
+
+fn inspection_error(section: &Section, err: impl std::fmt::Display) -> io::Error {
+	other_io_error(format!("Failed to decode the {:?} records: {}", section, err))
+}
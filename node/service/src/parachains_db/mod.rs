@@ -11,11 +11,21 @@
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
 // GNU General Public License for more details.
 
-//! A `RocksDB` instance for storing parachain data; availability data, and approvals.
+//! A `RocksDB` or `ParityDB` instance for storing parachain data; availability data, and
+//! approvals.
 
 #[cfg(feature = "full-node")]
-use {kvdb::KeyValueDB, std::io, std::path::PathBuf, std::sync::Arc};
+use {
+	kvdb::KeyValueDB,
+	std::io,
+	std::path::{Path, PathBuf},
+	std::sync::Arc,
+};
 
+#[cfg(feature = "full-node")]
+pub mod inspect;
+#[cfg(feature = "full-node")]
+mod paritydb;
 #[cfg(feature = "full-node")]
 mod upgrade;
 
@@ -31,6 +41,14 @@ pub(crate) mod columns {
 	pub const COL_APPROVAL_DATA: u32 = 2;
 	pub const COL_CHAIN_SELECTION_DATA: u32 = 3;
 	pub const COL_DISPUTE_COORDINATOR_DATA: u32 = 4;
+
+	/// The columns which are iterated over and hence need to be ordered when backed by ParityDB.
+	pub const ORDERED_COL: &[u32] = &[
+		COL_AVAILABILITY_META,
+		COL_APPROVAL_DATA,
+		COL_CHAIN_SELECTION_DATA,
+		COL_DISPUTE_COORDINATOR_DATA,
+	];
 }
 
 /// Columns used by different subsystems.
@@ -59,6 +77,23 @@ pub const REAL_COLUMNS: ColumnsConfig = ColumnsConfig {
 	col_dispute_coordinator_data: columns::COL_DISPUTE_COORDINATOR_DATA,
 };
 
+/// The database backend of the parachains DB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+	/// Store the data in RocksDB.
+	RocksDb,
+	/// Store the data in ParityDB.
+	///
+	/// An existing RocksDB parachains DB is migrated to ParityDB on the first start.
+	ParityDb,
+}
+
+impl Default for Backend {
+	fn default() -> Self {
+		Backend::RocksDb
+	}
+}
+
 /// The cache size for each column, in megabytes.
 #[derive(Debug, Clone)]
 pub struct CacheSizes {
@@ -81,12 +116,39 @@ pub(crate) fn other_io_error(err: String) -> io::Error {
 	io::Error::new(io::ErrorKind::Other, err)
 }
 
-/// Open the database on disk, creating it if it doesn't exist.
+/// Open the database on disk with the given backend, creating it if it doesn't exist.
 #[cfg(feature = "full-node")]
-pub fn open_creating(root: PathBuf, cache_sizes: CacheSizes) -> io::Result<Arc<dyn KeyValueDB>> {
+pub fn open_creating(
+	root: PathBuf,
+	backend: Backend,
+	cache_sizes: CacheSizes,
+) -> io::Result<Arc<dyn KeyValueDB>> {
+	match backend {
+		Backend::RocksDb => open_creating_rocksdb(root, cache_sizes),
+		Backend::ParityDb => open_creating_paritydb(root),
+	}
+}
+
+/// The path of the RocksDB parachains DB.
+#[cfg(feature = "full-node")]
+fn rocksdb_path(root: &Path) -> PathBuf {
+	root.join("parachains").join("db")
+}
+
+/// The path of the ParityDB parachains DB.
+#[cfg(feature = "full-node")]
+fn paritydb_path(root: &Path) -> PathBuf {
+	root.join("parachains").join("paritydb")
+}
+
+#[cfg(feature = "full-node")]
+fn open_creating_rocksdb(
+	root: PathBuf,
+	cache_sizes: CacheSizes,
+) -> io::Result<Arc<dyn KeyValueDB>> {
 	use kvdb_rocksdb::{Database, DatabaseConfig};
 
-	let path = root.join("parachains").join("db");
+	let path = rocksdb_path(&root);
 
 	let mut db_config = DatabaseConfig::with_columns(columns::NUM_COLUMNS);
 
@@ -105,8 +167,108 @@ pub fn open_creating(root: PathBuf, cache_sizes: CacheSizes) -> io::Result<Arc<d
 		.ok_or_else(|| other_io_error(format!("Bad database path: {:?}", path)))?;
 
 	std::fs::create_dir_all(&path_str)?;
-	upgrade::try_upgrade_db(&path)?;
+	upgrade::try_upgrade_db(&path, Backend::RocksDb)?;
 	let db = Database::open(&db_config, &path_str)?;
 
 	Ok(Arc::new(db))
 }
+
+#[cfg(feature = "full-node")]
+fn open_creating_paritydb(root: PathBuf) -> io::Result<Arc<dyn KeyValueDB>> {
+	let path = paritydb_path(&root);
+	let rocksdb_path = rocksdb_path(&root);
+
+	if !upgrade::is_empty(&path) || upgrade::is_empty(&rocksdb_path) {
+		std::fs::create_dir_all(&path)?;
+	} else {
+		upgrade::migrate_rocksdb_to_paritydb(&rocksdb_path, &path)?;
+	}
+
+	upgrade::try_upgrade_db(&path, Backend::ParityDb)?;
+	let db = paritydb::open_creating(&path)?;
+
+	Ok(Arc::new(paritydb::DbAdapter::new(db)))
+}
+
+/// A database opened with [`open_read_only`].
+#[cfg(feature = "full-node")]
+pub struct ReadOnlyDb {
+	db: Arc<dyn KeyValueDB>,
+	// The files of the RocksDB secondary instance, removed after the database is closed.
+	_secondary_dir: Option<tempfile::TempDir>,
+}
+
+#[cfg(feature = "full-node")]
+impl ReadOnlyDb {
+	/// The opened database.
+	pub fn db(&self) -> &Arc<dyn KeyValueDB> {
+		&self.db
+	}
+}
+
+/// Open the existing database on disk read-only.
+///
+/// A RocksDB database is opened as a secondary instance, so it can be inspected while the node is
+/// running.
+#[cfg(feature = "full-node")]
+pub fn open_read_only(root: PathBuf, backend: Backend) -> io::Result<ReadOnlyDb> {
+	let path = match backend {
+		Backend::RocksDb => rocksdb_path(&root),
+		Backend::ParityDb => paritydb_path(&root),
+	};
+	if upgrade::is_empty(&path) {
+		return Err(other_io_error(format!("No {:?} parachains DB found at {:?}", backend, path)))
+	}
+	upgrade::ensure_current_version(&path)?;
+
+	match backend {
+		Backend::RocksDb => {
+			use kvdb_rocksdb::{Database, DatabaseConfig};
+
+			let secondary_dir =
+				tempfile::Builder::new().prefix("polkadot-parachains-db-secondary").tempdir()?;
+			let path_str = path
+				.to_str()
+				.ok_or_else(|| other_io_error(format!("Bad database path: {:?}", path)))?;
+
+			let mut db_config = DatabaseConfig::with_columns(columns::NUM_COLUMNS);
+			// Required by RocksDB for secondary instances.
+			db_config.max_open_files = -1;
+			db_config.secondary = Some(secondary_dir.path().to_string_lossy().into_owned());
+
+			let db = Database::open(&db_config, path_str)?;
+			Ok(ReadOnlyDb { db: Arc::new(db), _secondary_dir: Some(secondary_dir) })
+		},
+		Backend::ParityDb => {
+			let db = parity_db::Db::open_read_only(&paritydb::options(&path))
+				.map_err(paritydb::paritydb_error)?;
+
+			Ok(ReadOnlyDb { db: Arc::new(paritydb::DbAdapter::new(db)), _secondary_dir: None })
+		},
+	}
+}
+
+#[cfg(all(test, feature = "full-node"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn read_only_db_sees_written_data() {
+		for backend in [Backend::RocksDb, Backend::ParityDb] {
+			let root = tempfile::tempdir().unwrap();
+			{
+				let db =
+					open_creating(root.path().to_owned(), backend, CacheSizes::default()).unwrap();
+				let mut transaction = kvdb::DBTransaction::new();
+				transaction.put(columns::COL_APPROVAL_DATA, b"key", b"value");
+				db.write(transaction).unwrap();
+			}
+
+			let read_only = open_read_only(root.path().to_owned(), backend).unwrap();
+			assert_eq!(
+				read_only.db().get(columns::COL_APPROVAL_DATA, b"key").unwrap(),
+				Some(b"value".to_vec()),
+			);
+		}
+	}
+}
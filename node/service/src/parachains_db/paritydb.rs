@@ -0,0 +1,238 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A [`KeyValueDB`] implementation on top of ParityDB.
+
+#![cfg(feature = "full-node")]
+
+use kvdb::{DBOp, DBTransaction, DBValue, KeyValueDB, KeyValuePair};
+use parity_db::{ColId, Db, Options};
+use std::{io, path::Path};
+
+use super::{columns, other_io_error};
+
+const LOG_TARGET: &str = "parachain::db";
+
+/// The options the ParityDB parachains database at `path` is opened with.
+///
+/// ParityDB only supports iteration over the columns that use the btree index, so it is enabled
+/// for every column listed in [`columns::ORDERED_COL`].
+pub(crate) fn options(path: &Path) -> Options {
+	let mut options = Options::with_columns(path, columns::NUM_COLUMNS as u8);
+	for col in columns::ORDERED_COL {
+		options.columns[*col as usize].btree_index = true;
+	}
+	options
+}
+
+/// Opens the ParityDB database at `path`, creating it if it doesn't exist.
+pub(crate) fn open_creating(path: &Path) -> io::Result<Db> {
+	Db::open_or_create(&options(path)).map_err(paritydb_error)
+}
+
+/// Converts an error of ParityDB into an I/O error.
+pub(crate) fn paritydb_error(err: parity_db::Error) -> io::Error {
+	other_io_error(format!("ParityDB error: {:?}", err))
+}
+
+/// Exposes a ParityDB database through the [`KeyValueDB`] interface used by the subsystems.
+pub struct DbAdapter {
+	db: Db,
+}
+
+impl DbAdapter {
+	/// Wraps the given database.
+	pub fn new(db: Db) -> Self {
+		DbAdapter { db }
+	}
+
+	fn ensure_ordered(&self, col: u32) -> io::Result<()> {
+		if columns::ORDERED_COL.contains(&col) {
+			Ok(())
+		} else {
+			Err(other_io_error(format!("Column {} of the parachains DB is not ordered", col)))
+		}
+	}
+}
+
+impl parity_util_mem::MallocSizeOf for DbAdapter {
+	fn size_of(&self, _ops: &mut parity_util_mem::MallocSizeOfOps) -> usize {
+		// ParityDB doesn't report its memory usage.
+		0
+	}
+}
+
+impl KeyValueDB for DbAdapter {
+	fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<DBValue>> {
+		self.db.get(col as ColId, key).map_err(paritydb_error)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Option<Box<[u8]>> {
+		self.iter_with_prefix(col, prefix).next().map(|(_, value)| value)
+	}
+
+	fn write(&self, transaction: DBTransaction) -> io::Result<()> {
+		let mut ops = Vec::with_capacity(transaction.ops.len());
+		for op in transaction.ops {
+			match op {
+				DBOp::Insert { col, key, value } =>
+					ops.push((col as ColId, key.to_vec(), Some(value))),
+				DBOp::Delete { col, key } => ops.push((col as ColId, key.to_vec(), None)),
+				DBOp::DeletePrefix { col, prefix } => {
+					// ParityDB can't delete a range of keys, so they are looked up one by one.
+					self.ensure_ordered(col)?;
+					ops.extend(
+						self.iter_with_prefix(col, &prefix)
+							.map(|(key, _)| (col as ColId, key.into_vec(), None)),
+					);
+				},
+			}
+		}
+
+		self.db.commit(ops).map_err(paritydb_error)
+	}
+
+	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = KeyValuePair> + 'a> {
+		self.iter_with_prefix(col, &[])
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = KeyValuePair> + 'a> {
+		if let Err(err) = self.ensure_ordered(col) {
+			tracing::warn!(target: LOG_TARGET, ?err, "Attempted to iterate the parachains DB");
+			return Box::new(std::iter::empty())
+		}
+
+		let mut iter = match self.db.iter(col as ColId) {
+			Ok(iter) => iter,
+			Err(err) => {
+				tracing::warn!(
+					target: LOG_TARGET,
+					?err,
+					col,
+					"Failed to iterate the parachains DB",
+				);
+				return Box::new(std::iter::empty())
+			},
+		};
+		if let Err(err) = iter.seek(prefix) {
+			tracing::warn!(target: LOG_TARGET, ?err, col, "Failed to iterate the parachains DB");
+			return Box::new(std::iter::empty())
+		}
+
+		Box::new(std::iter::from_fn(move || match iter.next() {
+			Ok(Some((key, value))) if key.starts_with(prefix) =>
+				Some((key.into_boxed_slice(), value.into_boxed_slice())),
+			Ok(_) => None,
+			Err(err) => {
+				tracing::warn!(
+					target: LOG_TARGET,
+					?err,
+					col,
+					"Failed to iterate the parachains DB",
+				);
+				None
+			},
+		}))
+	}
+
+	fn restore(&self, _new_db: &str) -> io::Result<()> {
+		Err(other_io_error("Restoring the ParityDB parachains DB is not supported".into()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use columns::{COL_AVAILABILITY_DATA, COL_AVAILABILITY_META};
+
+	fn open_test_db() -> (tempfile::TempDir, DbAdapter) {
+		let dir = tempfile::tempdir().unwrap();
+		let db = open_creating(dir.path()).unwrap();
+		(dir, DbAdapter::new(db))
+	}
+
+	fn insert(db: &DbAdapter, col: u32, entries: &[&[u8]]) {
+		let mut transaction = DBTransaction::new();
+		for key in entries {
+			transaction.put(col, key, key);
+		}
+		db.write(transaction).unwrap();
+	}
+
+	fn keys(iter: impl Iterator<Item = KeyValuePair>) -> Vec<Vec<u8>> {
+		iter.map(|(key, _)| key.into_vec()).collect()
+	}
+
+	#[test]
+	fn iter_with_prefix_returns_matching_entries_in_order() {
+		let (_dir, db) = open_test_db();
+		insert(&db, COL_AVAILABILITY_META, &[b"ab2", b"a", b"ab1", b"b", b"ac"]);
+
+		assert_eq!(
+			keys(db.iter_with_prefix(COL_AVAILABILITY_META, b"ab")),
+			vec![b"ab1".to_vec(), b"ab2".to_vec()],
+		);
+		assert_eq!(
+			keys(db.iter(COL_AVAILABILITY_META)),
+			vec![b"a".to_vec(), b"ab1".to_vec(), b"ab2".to_vec(), b"ac".to_vec(), b"b".to_vec()],
+		);
+		assert!(db.iter_with_prefix(COL_AVAILABILITY_META, b"c").next().is_none());
+		assert_eq!(
+			db.get_by_prefix(COL_AVAILABILITY_META, b"ab").map(|value| value.into_vec()),
+			Some(b"ab1".to_vec()),
+		);
+	}
+
+	#[test]
+	fn iter_over_unordered_column_is_empty() {
+		let (_dir, db) = open_test_db();
+		insert(&db, COL_AVAILABILITY_DATA, &[b"a", b"b"]);
+
+		assert!(db.iter(COL_AVAILABILITY_DATA).next().is_none());
+		assert_eq!(db.get(COL_AVAILABILITY_DATA, b"a").unwrap(), Some(b"a".to_vec()));
+	}
+
+	#[test]
+	fn delete_prefix_removes_only_matching_entries() {
+		let (_dir, db) = open_test_db();
+		insert(&db, COL_AVAILABILITY_META, &[b"a", b"ab1", b"ab2", b"b"]);
+
+		let mut transaction = DBTransaction::new();
+		transaction.delete_prefix(COL_AVAILABILITY_META, b"ab");
+		transaction.put(COL_AVAILABILITY_META, b"c", b"c");
+		db.write(transaction).unwrap();
+
+		assert_eq!(
+			keys(db.iter(COL_AVAILABILITY_META)),
+			vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()],
+		);
+	}
+
+	#[test]
+	fn delete_prefix_in_unordered_column_fails() {
+		let (_dir, db) = open_test_db();
+		insert(&db, COL_AVAILABILITY_DATA, &[b"a"]);
+
+		let mut transaction = DBTransaction::new();
+		transaction.delete_prefix(COL_AVAILABILITY_DATA, b"a");
+		assert!(db.write(transaction).is_err());
+		assert_eq!(db.get(COL_AVAILABILITY_DATA, b"a").unwrap(), Some(b"a".to_vec()));
+	}
+}
@@ -21,14 +21,23 @@ use std::{
 	str::FromStr,
 };
 
+use kvdb::KeyValueDB;
+
+use super::{columns, Backend};
+
 type Version = u32;
 
+const LOG_TARGET: &str = "parachain::db";
+
 /// Version file name.
 const VERSION_FILE_NAME: &'static str = "parachain_db_version";
 
 /// Current db version.
 const CURRENT_VERSION: Version = 1;
 
+/// The amount of data copied between the databases in a single ParityDB commit, in bytes.
+const MIGRATION_BATCH_SIZE: usize = 64 * 1024 * 1024;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
 	#[error("I/O error when reading/writing the version")]
@@ -37,6 +46,12 @@ pub enum Error {
 	CorruptedVersionFile,
 	#[error("Future version (expected {current:?}, found {got:?})")]
 	FutureVersion { current: Version, got: Version },
+	#[error("Outdated version (expected {current:?}, found {got:?}), start the node to upgrade")]
+	OutdatedVersion { current: Version, got: Version },
+	#[error("Version {got:?} is not supported by the {backend:?} backend")]
+	UnsupportedVersion { backend: Backend, got: Version },
+	#[error("The data of column {col:?} differs after the migration to ParityDB")]
+	MigrationMismatch { col: u32 },
 }
 
 impl From<Error> for io::Error {
@@ -49,19 +64,104 @@ impl From<Error> for io::Error {
 }
 
 /// Try upgrading parachain's database to the current version.
-pub fn try_upgrade_db(db_path: &Path) -> Result<(), Error> {
-	let is_empty = db_path.read_dir().map_or(true, |mut d| d.next().is_none());
-	if !is_empty {
-		match current_version(db_path)? {
-			0 => migrate_from_version_0_to_1(db_path)?,
-			CURRENT_VERSION => (),
-			v => return Err(Error::FutureVersion { current: CURRENT_VERSION, got: v }),
+pub fn try_upgrade_db(db_path: &Path, backend: Backend) -> Result<(), Error> {
+	if !is_empty(db_path) {
+		match (current_version(db_path)?, backend) {
+			(0, Backend::RocksDb) => migrate_from_version_0_to_1(db_path)?,
+			(CURRENT_VERSION, _) => (),
+			(v, _) if v > CURRENT_VERSION =>
+				return Err(Error::FutureVersion { current: CURRENT_VERSION, got: v }),
+			// The ParityDB backend was introduced with version 1.
+			(v, backend) => return Err(Error::UnsupportedVersion { backend, got: v }),
 		}
 	}
 
 	update_version(db_path)
 }
 
+/// Ensures the database at `db_path` has the current version, without upgrading it.
+pub fn ensure_current_version(db_path: &Path) -> Result<(), Error> {
+	match current_version(db_path)? {
+		CURRENT_VERSION => Ok(()),
+		v if v > CURRENT_VERSION => Err(Error::FutureVersion { current: CURRENT_VERSION, got: v }),
+		v => Err(Error::OutdatedVersion { current: CURRENT_VERSION, got: v }),
+	}
+}
+
+/// Returns `true` if the directory at `path` doesn't exist or has no entries.
+pub fn is_empty(path: &Path) -> bool {
+	path.read_dir().map_or(true, |mut d| d.next().is_none())
+}
+
+/// Copies the RocksDB parachains database at `rocksdb_path` into a new ParityDB database at
+/// `paritydb_path`, removing the former once done.
+///
+/// The data is copied into a temporary directory first, so that an interrupted migration is
+/// started over on the next launch rather than leaving a partial database behind. Every column is
+/// compared with the original before the latter is removed.
+pub fn migrate_rocksdb_to_paritydb(rocksdb_path: &Path, paritydb_path: &Path) -> Result<(), Error> {
+	use kvdb_rocksdb::{Database, DatabaseConfig};
+
+	try_upgrade_db(rocksdb_path, Backend::RocksDb)?;
+
+	tracing::info!(
+		target: LOG_TARGET,
+		from = ?rocksdb_path,
+		to = ?paritydb_path,
+		"Migrating the parachains DB from RocksDB to ParityDB",
+	);
+
+	let tmp_path = paritydb_path.with_extension("migrating");
+	if tmp_path.exists() {
+		fs::remove_dir_all(&tmp_path)?;
+	}
+	fs::create_dir_all(&tmp_path)?;
+
+	{
+		let rocksdb_path_str = rocksdb_path
+			.to_str()
+			.ok_or_else(|| super::other_io_error("Invalid database path".into()))?;
+		let rocksdb =
+			Database::open(&DatabaseConfig::with_columns(columns::NUM_COLUMNS), rocksdb_path_str)?;
+		let paritydb = super::paritydb::open_creating(&tmp_path)?;
+
+		for col in 0..columns::NUM_COLUMNS {
+			let mut batch = Vec::new();
+			let mut batch_size = 0;
+			for (key, value) in rocksdb.iter(col) {
+				batch_size += key.len() + value.len();
+				batch.push((col as u8, key, Some(value.into_vec())));
+				if batch_size >= MIGRATION_BATCH_SIZE {
+					paritydb.commit(batch.drain(..)).map_err(super::paritydb::paritydb_error)?;
+					batch_size = 0;
+				}
+			}
+			paritydb.commit(batch).map_err(super::paritydb::paritydb_error)?;
+		}
+
+		for col in 0..columns::NUM_COLUMNS {
+			for (key, value) in rocksdb.iter(col) {
+				let migrated =
+					paritydb.get(col as u8, &key).map_err(super::paritydb::paritydb_error)?;
+				if migrated.as_deref() != Some(&*value) {
+					return Err(Error::MigrationMismatch { col })
+				}
+			}
+		}
+	}
+
+	update_version(&tmp_path)?;
+	if paritydb_path.exists() {
+		fs::remove_dir_all(paritydb_path)?;
+	}
+	fs::rename(&tmp_path, paritydb_path)?;
+	fs::remove_dir_all(rocksdb_path)?;
+
+	tracing::info!(target: LOG_TARGET, "Migration of the parachains DB to ParityDB complete");
+
+	Ok(())
+}
+
 /// Reads current database version from the file at given path.
 /// If the file does not exist, assumes the current version.
 fn current_version(path: &Path) -> Result<Version, Error> {
@@ -94,7 +194,7 @@ fn migrate_from_version_0_to_1(path: &Path) -> Result<(), Error> {
 	let db_path = path
 		.to_str()
 		.ok_or_else(|| super::other_io_error("Invalid database path".into()))?;
-	let db_cfg = DatabaseConfig::with_columns(super::columns::v0::NUM_COLUMNS);
+	let db_cfg = DatabaseConfig::with_columns(columns::v0::NUM_COLUMNS);
 	let db = Database::open(&db_cfg, db_path)?;
 
 	db.add_column()?;
@@ -102,3 +202,79 @@ fn migrate_from_version_0_to_1(path: &Path) -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{super::paritydb::DbAdapter, *};
+	use kvdb::DBTransaction;
+	use kvdb_rocksdb::{Database, DatabaseConfig};
+
+	const ENTRIES_PER_COLUMN: u8 = 10;
+
+	#[test]
+	fn migration_to_paritydb_preserves_every_column() {
+		let dir = tempfile::tempdir().unwrap();
+		let rocksdb_path = dir.path().join("db");
+		let paritydb_path = dir.path().join("paritydb");
+
+		{
+			let db = Database::open(
+				&DatabaseConfig::with_columns(columns::NUM_COLUMNS),
+				rocksdb_path.to_str().unwrap(),
+			)
+			.unwrap();
+			let mut transaction = DBTransaction::new();
+			for col in 0..columns::NUM_COLUMNS {
+				for i in 0..ENTRIES_PER_COLUMN {
+					transaction.put(col, &[col as u8, i], &[i; 3]);
+				}
+			}
+			db.write(transaction).unwrap();
+		}
+
+		migrate_rocksdb_to_paritydb(&rocksdb_path, &paritydb_path).unwrap();
+
+		assert!(!rocksdb_path.exists());
+		assert!(!paritydb_path.with_extension("migrating").exists());
+		ensure_current_version(&paritydb_path).unwrap();
+
+		let db = DbAdapter::new(super::super::paritydb::open_creating(&paritydb_path).unwrap());
+		for col in 0..columns::NUM_COLUMNS {
+			for i in 0..ENTRIES_PER_COLUMN {
+				assert_eq!(db.get(col, &[col as u8, i]).unwrap(), Some(vec![i; 3]));
+			}
+		}
+		for col in columns::ORDERED_COL {
+			assert_eq!(db.iter(*col).count(), ENTRIES_PER_COLUMN as usize);
+		}
+	}
+
+	#[test]
+	fn interrupted_migration_is_started_over() {
+		let dir = tempfile::tempdir().unwrap();
+		let rocksdb_path = dir.path().join("db");
+		let paritydb_path = dir.path().join("paritydb");
+
+		{
+			let db = Database::open(
+				&DatabaseConfig::with_columns(columns::NUM_COLUMNS),
+				rocksdb_path.to_str().unwrap(),
+			)
+			.unwrap();
+			let mut transaction = DBTransaction::new();
+			transaction.put(columns::COL_APPROVAL_DATA, b"key", b"value");
+			db.write(transaction).unwrap();
+		}
+
+		// Leftovers of a previous attempt.
+		let tmp_path = paritydb_path.with_extension("migrating");
+		fs::create_dir_all(&tmp_path).unwrap();
+		fs::write(tmp_path.join("garbage"), b"garbage").unwrap();
+
+		migrate_rocksdb_to_paritydb(&rocksdb_path, &paritydb_path).unwrap();
+
+		assert!(!paritydb_path.join("garbage").exists());
+		let db = DbAdapter::new(super::super::paritydb::open_creating(&paritydb_path).unwrap());
+		assert_eq!(db.get(columns::COL_APPROVAL_DATA, b"key").unwrap(), Some(b"value".to_vec()));
+	}
+}
@@ -0,0 +1,190 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replay of traces recorded with [`TraceRecorder`](overseer::trace::TraceRecorder).
+
+use polkadot_node_subsystem::{messages::AllMessages, overseer, FromOverseer};
+use polkadot_node_subsystem_util::TimeoutExt;
+
+use futures::prelude::*;
+use overseer::trace::{read_trace, write_trace, TraceEntry, TraceEvent, TraceableMessage};
+
+use std::time::Duration;
+
+use crate::TestSubsystemContextHandle;
+
+/// Build a trace of the given signals and messages, as if a subsystem had received them.
+///
+/// Allows to replay hand-crafted scenarios with [`replay_trace`].
+pub fn make_trace<M: TraceableMessage>(
+	received: impl IntoIterator<Item = FromOverseer<M>>,
+) -> Vec<u8> {
+	write_trace(
+		received
+			.into_iter()
+			.map(|msg| TraceEntry { timestamp: 0, event: TraceEvent::recorded(&msg) }),
+	)
+}
+
+/// Replay a recorded trace into the subsystem behind `handle`.
+///
+/// Every recorded signal and message is sent to the subsystem in order. After each of them,
+/// the messages sent by the subsystem are passed to `on_message`, until the subsystem stays
+/// quiet for `settle`. `on_message` can be used to assert on the behavior of the subsystem
+/// and to answer its requests.
+///
+/// Returns the number of replayed entries.
+pub async fn replay_trace<M, F>(
+	handle: &mut TestSubsystemContextHandle<M>,
+	trace: &[u8],
+	settle: Duration,
+	mut on_message: F,
+) -> usize
+where
+	M: TraceableMessage,
+	F: FnMut(AllMessages),
+{
+	let entries = read_trace(trace).expect("Trace is not decodable");
+	let replayed = entries.len();
+
+	for entry in entries {
+		let from_overseer = entry.into_from_overseer().expect("Message is not decodable");
+		handle.send(from_overseer).await;
+
+		while let Some(Some(msg)) = handle.rx.next().timeout(settle).await {
+			on_message(msg);
+		}
+	}
+
+	replayed
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{make_subsystem_context, ForwardSubsystem};
+	use futures::{channel::mpsc, executor::block_on};
+	use parking_lot::Mutex;
+	use polkadot_node_subsystem::{
+		messages::{ApprovalVotingMessage, ChainApiMessage},
+		FromOverseer, OverseerSignal,
+	};
+	use polkadot_overseer::{
+		dummy::dummy_overseer_builder,
+		trace::{TraceEvent, TraceRecorder},
+		Handle, HeadSupportsParachains, SubsystemContext as _,
+	};
+	use polkadot_primitives::v1::Hash;
+	use std::{io, sync::Arc};
+
+	struct AlwaysSupportsParachains;
+	impl HeadSupportsParachains for AlwaysSupportsParachains {
+		fn head_supports_parachains(&self, _head: &Hash) -> bool {
+			true
+		}
+	}
+
+	#[derive(Clone, Default)]
+	struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+	impl io::Write for SharedBuffer {
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+			self.0.lock().extend_from_slice(buf);
+			Ok(buf.len())
+		}
+
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	fn record_approved_ancestor(buffer: SharedBuffer, hash: Hash) {
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let (tx, mut rx) = mpsc::channel(2);
+		let recorder = Arc::new(TraceRecorder::new(buffer).unwrap());
+		let (overseer, handle) =
+			dummy_overseer_builder(spawner.clone(), AlwaysSupportsParachains, None)
+				.unwrap()
+				.replace_approval_voting(|_| ForwardSubsystem(tx))
+				.record_approval_voting(recorder.clone())
+				.leaves(vec![])
+				.build()
+				.unwrap();
+
+		let mut handle = Handle::new(handle);
+
+		spawner.spawn("overseer", None, overseer.run().then(|_| async { () }).boxed());
+
+		let (response_tx, _response_rx) = futures::channel::oneshot::channel();
+		block_on(handle.send_msg_anon(ApprovalVotingMessage::ApprovedAncestor(
+			hash,
+			1,
+			response_tx,
+		)));
+		assert!(matches!(block_on(rx.next()), Some(ApprovalVotingMessage::ApprovedAncestor(..))));
+
+		// the message is recorded before it is delivered.
+		recorder.flush();
+	}
+
+	#[test]
+	fn recorded_messages_are_replayed() {
+		let buffer = SharedBuffer::default();
+		let hash = Hash::repeat_byte(0x42);
+		record_approved_ancestor(buffer.clone(), hash);
+
+		let trace = buffer.0.lock().clone();
+		let entries = read_trace(&trace).unwrap();
+		assert_eq!(entries.len(), 1);
+		assert!(matches!(entries[0].event, TraceEvent::Message(_)));
+
+		let pool = sp_core::testing::TaskExecutor::new();
+		let (mut ctx, mut handle) = make_subsystem_context::<ApprovalVotingMessage, _>(pool);
+
+		let subsystem = async move {
+			loop {
+				match ctx.recv().await {
+					Ok(FromOverseer::Communication {
+						msg: ApprovalVotingMessage::ApprovedAncestor(hash, _, _),
+					}) => {
+						let (tx, _rx) = futures::channel::oneshot::channel();
+						ctx.send_message(ChainApiMessage::BlockNumber(hash, tx)).await;
+					},
+					Ok(FromOverseer::Signal(OverseerSignal::Conclude)) | Err(_) => break,
+					_ => {},
+				}
+			}
+		};
+
+		let replay = async move {
+			let mut requested = Vec::new();
+			let replayed =
+				replay_trace(&mut handle, &trace, Duration::from_millis(100), |msg| match msg {
+					AllMessages::ChainApi(ChainApiMessage::BlockNumber(hash, _)) => {
+						requested.push(hash)
+					},
+					msg => panic!("Unexpected message: {:?}", msg),
+				})
+				.await;
+			assert_eq!(replayed, 1);
+			assert_eq!(requested, vec![hash]);
+
+			handle.send(FromOverseer::Signal(OverseerSignal::Conclude)).await;
+		};
+
+		block_on(future::join(subsystem, replay));
+	}
+}
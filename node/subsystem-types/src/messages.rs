@@ -35,15 +35,15 @@ use polkadot_node_network_protocol::{
 use polkadot_node_primitives::{
 	approval::{BlockApprovalMeta, IndirectAssignmentCert, IndirectSignedApprovalVote},
 	AvailableData, BabeEpoch, BlockWeight, CandidateVotes, CollationGenerationConfig,
-	CollationSecondedSignal, DisputeMessage, ErasureChunk, PoV, SignedDisputeStatement,
-	SignedFullStatement, ValidationResult,
+	CollationSecondedSignal, DisputeEvidence, DisputeMessage, ErasureChunk, PoV,
+	SignedDisputeStatement, SignedFullStatement, ValidationResult,
 };
 use polkadot_primitives::{
 	v1::{
 		AuthorityDiscoveryId, BackedCandidate, BlockNumber, CandidateDescriptor, CandidateEvent,
 		CandidateHash, CandidateIndex, CandidateReceipt, CollatorId, CommittedCandidateReceipt,
-		CoreState, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader, Id as ParaId,
-		InboundDownwardMessage, InboundHrmpMessage, MultiDisputeStatementSet,
+		CoreIndex, CoreState, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader, Id as ParaId,
+		InboundDownwardMessage, InboundHrmpMessage, MultiApprovalVoteSet, MultiDisputeStatementSet,
 		OccupiedCoreAssumption, PersistedValidationData, SessionIndex, SignedAvailabilityBitfield,
 		SignedAvailabilityBitfields, ValidationCode, ValidationCodeHash, ValidatorId,
 		ValidatorIndex, ValidatorSignature,
@@ -302,6 +302,29 @@ pub enum DisputeCoordinatorMessage {
 		/// The block to vote on, might be base in case there is no better.
 		tx: oneshot::Sender<(BlockNumber, Hash)>,
 	},
+	/// Assemble the evidence of a dispute, for examining it offline.
+	///
+	/// Besides the votes known to the coordinator, this recovers the available data of the
+	/// candidate and fetches its validation code, so the response may take a while.
+	AssembleEvidence(
+		SessionIndex,
+		CandidateHash,
+		oneshot::Sender<Result<DisputeEvidence, DisputeEvidenceError>>,
+	),
+}
+
+/// The reasons `DisputeCoordinatorMessage::AssembleEvidence` can fail for.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum DisputeEvidenceError {
+	/// No votes are known for the candidate.
+	#[error("No votes are known for the candidate")]
+	UnknownCandidate,
+	/// The available data of the candidate could not be recovered.
+	#[error("Recovering the available data failed: {0:?}")]
+	Unavailable(crate::errors::RecoveryError),
+	/// The validation code of the candidate could not be fetched.
+	#[error("The validation code is not available")]
+	ValidationCodeUnavailable,
 }
 
 /// The result of `DisputeCoordinatorMessage::ImportStatements`.
@@ -435,6 +458,7 @@ pub enum AvailabilityRecoveryMessage {
 		CandidateReceipt,
 		SessionIndex,
 		Option<GroupIndex>, // Optional backing group to request from first.
+		Option<CoreIndex>,  // Optional core the candidate occupied, needed for chunk shuffling.
 		oneshot::Sender<Result<AvailableData, crate::errors::RecoveryError>>,
 	),
 }
@@ -683,6 +707,9 @@ pub enum RuntimeApiRequest {
 		OccupiedCoreAssumption,
 		RuntimeApiSender<Option<ValidationCodeHash>>,
 	),
+	/// Whether the erasure chunks of candidates with this relay parent are assigned to validators
+	/// by a shuffled mapping.
+	AvailabilityChunkShuffling(RuntimeApiSender<bool>),
 }
 
 /// A message to the Runtime API subsystem.
@@ -735,6 +762,8 @@ pub struct ProvisionerInherentData {
 	pub backed_candidates: Vec<BackedCandidate>,
 	/// Dispute statement sets.
 	pub disputes: MultiDisputeStatementSet,
+	/// Approval votes on included candidates.
+	pub approvals: MultiApprovalVoteSet,
 }
 
 /// Message to the Provisioner.
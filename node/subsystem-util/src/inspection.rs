@@ -0,0 +1,72 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The subsystems storing data in the parachains DB expose a read-only view of their records for
+//! debugging purposes. The records to be inspected are selected with a [`Filter`].
+
+use polkadot_primitives::v1::{CandidateHash, Hash, SessionIndex};
+
+/// Criteria a record of the parachains DB has to meet to be inspected.
+///
+/// Every criterion that is set has to be met. A record which doesn't carry the information a
+/// criterion is about, e.g. a record without a session for a session filter, is not selected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+	/// Only select the records concerning the given session.
+	pub session: Option<SessionIndex>,
+	/// Only select the records concerning the given relay chain block.
+	pub block: Option<Hash>,
+	/// Only select the records concerning the given candidate.
+	pub candidate: Option<CandidateHash>,
+}
+
+impl Filter {
+	/// Whether a record concerning the given session, relay chain blocks and candidates is
+	/// selected.
+	pub fn matches(
+		&self,
+		session: Option<SessionIndex>,
+		blocks: &[Hash],
+		candidates: &[CandidateHash],
+	) -> bool {
+		self.session.map_or(true, |s| session == Some(s)) &&
+			self.block.map_or(true, |b| blocks.contains(&b)) &&
+			self.candidate.map_or(true, |c| candidates.contains(&c))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn records_must_meet_every_criterion() {
+		let block = Hash::repeat_byte(1);
+		let candidate = CandidateHash(Hash::repeat_byte(2));
+
+		assert!(Filter::default().matches(None, &[], &[]));
+
+		let filter = Filter { session: Some(1), block: Some(block), candidate: None };
+		assert!(filter.matches(Some(1), &[block], &[candidate]));
+		assert!(!filter.matches(Some(2), &[block], &[candidate]));
+		assert!(!filter.matches(None, &[block], &[candidate]));
+		assert!(!filter.matches(Some(1), &[], &[candidate]));
+
+		let filter = Filter { candidate: Some(candidate), ..Default::default() };
+		assert!(filter.matches(None, &[], &[candidate]));
+		assert!(!filter.matches(Some(1), &[block], &[]));
+	}
+}
@@ -82,6 +82,8 @@ pub mod reexports {
 	pub use polkadot_overseer::gen::{SpawnNamed, SpawnedSubsystem, Subsystem, SubsystemContext};
 }
 
+/// Selection of the records of the parachains DB for offline inspection.
+pub mod inspection;
 /// A rolling session window cache.
 pub mod rolling_session_window;
 /// Convenient and efficient runtime info access.
@@ -217,6 +219,7 @@ specialize_requests! {
 	fn request_session_info(index: SessionIndex) -> Option<SessionInfo>; SessionInfo;
 	fn request_validation_code_hash(para_id: ParaId, assumption: OccupiedCoreAssumption)
 		-> Option<ValidationCodeHash>; ValidationCodeHash;
+	fn request_availability_chunk_shuffling() -> bool; AvailabilityChunkShuffling;
 }
 
 /// From the given set of validators, find the first key we can sign with, if any.
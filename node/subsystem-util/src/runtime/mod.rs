@@ -25,7 +25,7 @@ use sp_application_crypto::AppKey;
 use sp_core::crypto::ByteArray;
 use sp_keystore::{CryptoStore, SyncCryptoStorePtr};
 
-use polkadot_node_subsystem::{SubsystemContext, SubsystemSender};
+use polkadot_node_subsystem::{errors::RuntimeApiError, SubsystemContext, SubsystemSender};
 use polkadot_primitives::{
 	v1::{
 		CandidateEvent, CoreState, EncodeAs, GroupIndex, GroupRotationInfo, Hash, OccupiedCore,
@@ -36,8 +36,9 @@ use polkadot_primitives::{
 };
 
 use crate::{
-	request_availability_cores, request_candidate_events, request_session_index_for_child,
-	request_session_info, request_validation_code_by_hash, request_validator_groups,
+	request_availability_chunk_shuffling, request_availability_cores, request_candidate_events,
+	request_session_index_for_child, request_session_info, request_validation_code_by_hash,
+	request_validator_groups,
 };
 
 /// Errors that can happen on runtime fetches.
@@ -328,3 +329,22 @@ where
 	recv_runtime(request_validation_code_by_hash(relay_parent, validation_code_hash, sender).await)
 		.await
 }
+
+/// Whether the erasure chunks of candidates with the given `relay_parent` are assigned to
+/// validators by a shuffled mapping.
+///
+/// Runtimes which don't support the query never shuffle chunks.
+pub async fn get_availability_chunk_shuffling<Sender>(
+	sender: &mut Sender,
+	relay_parent: Hash,
+) -> Result<bool>
+where
+	Sender: SubsystemSender,
+{
+	match recv_runtime(request_availability_chunk_shuffling(relay_parent, sender).await).await {
+		Err(Error::NonFatal(NonFatal::RuntimeRequest(RuntimeApiError::NotSupported {
+			..
+		}))) => Ok(false),
+		result => result,
+	}
+}
@@ -64,9 +64,9 @@ pub fn measure_pvf_prepare(wasm_code: &[u8]) -> Result<Duration, PerfCheckError>
 	let code = sp_maybe_compressed_blob::decompress(wasm_code, VALIDATION_CODE_BOMB_LIMIT)
 		.or(Err(PerfCheckError::CodeDecompressionFailed))?;
 
-	// Recreate the pipeline from the pvf prepare worker.
+	// Recreate the pipeline from the pvf prepare worker, as it is run for the pre-checking.
 	let blob = polkadot_node_core_pvf::prevalidate(code.as_ref()).map_err(PerfCheckError::from)?;
-	polkadot_node_core_pvf::prepare(blob).map_err(PerfCheckError::from)?;
+	polkadot_node_core_pvf::prepare(blob, false).map_err(PerfCheckError::from)?;
 
 	Ok(start.elapsed())
 }
@@ -95,6 +95,13 @@ pub fn new_full(
 		None,
 		None,
 		worker_program_path,
+		false,
+		None,
+		false,
+		polkadot_service::ParachainsDbBackend::RocksDb,
+		None,
+		Default::default(),
+		None,
 		polkadot_service::RealOverseerGen,
 	)
 }
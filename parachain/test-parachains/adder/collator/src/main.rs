@@ -68,6 +68,13 @@ fn main() -> Result<()> {
 							true,
 							None,
 							None,
+							false,
+							None,
+							false,
+							polkadot_service::ParachainsDbBackend::RocksDb,
+							None,
+							Default::default(),
+							None,
 							polkadot_service::RealOverseerGen,
 						)
 						.map_err(|e| e.to_string())?;
@@ -230,6 +230,9 @@ pub mod well_known_keys {
 /// Unique identifier for the Parachains Inherent
 pub const PARACHAINS_INHERENT_IDENTIFIER: InherentIdentifier = *b"parachn0";
 
+/// Unique identifier for the approval votes inherent.
+pub const APPROVAL_VOTES_INHERENT_IDENTIFIER: InherentIdentifier = *b"apprvls0";
+
 /// The key type ID for parachain assignment key.
 pub const ASSIGNMENT_KEY_TYPE_ID: KeyTypeId = KeyTypeId(*b"asgn");
 
@@ -985,6 +988,20 @@ impl ApprovalVote {
 	}
 }
 
+/// A set of approval votes on a specific included candidate.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ApprovalVoteSet {
+	/// The candidate referenced by this set.
+	pub candidate_hash: CandidateHash,
+	/// The session index of the candidate.
+	pub session: SessionIndex,
+	/// The approval votes on the candidate, sorted by validator index.
+	pub votes: Vec<(ValidatorIndex, ValidatorSignature)>,
+}
+
+/// A set of approval votes on multiple candidates.
+pub type MultiApprovalVoteSet = Vec<ApprovalVoteSet>;
+
 /// Custom validity errors used in Polkadot while validating transactions.
 #[repr(u8)]
 pub enum ValidityError {
@@ -18,7 +18,7 @@
 
 use crate::v1;
 
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Codec, Decode, Encode};
 use primitives::RuntimeDebug;
 use scale_info::TypeInfo;
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
@@ -122,6 +122,19 @@ pub struct PvfCheckStatement {
 	pub validator_index: v1::ValidatorIndex,
 }
 
+/// An order for a parathread block, placed with the on-demand order pallet.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParathreadOrder<AccountId> {
+	/// The parathread a block is ordered for.
+	pub para_id: v1::Id,
+	/// The collator allowed to provide the block.
+	pub collator: v1::CollatorId,
+	/// The account that placed the order.
+	pub who: AccountId,
+	/// The amount reserved from `who` to pay for the order.
+	pub price: v1::Balance,
+}
+
 impl PvfCheckStatement {
 	/// Produce the payload used for signing this type of statement.
 	///
@@ -135,7 +148,7 @@ impl PvfCheckStatement {
 
 sp_api::decl_runtime_apis! {
 	/// The API for querying the state of parachains on-chain.
-	#[api_version(2)]
+	#[api_version(3)]
 	pub trait ParachainHost<H: Encode + Decode = v1::Hash, N: Encode + Decode = v1::BlockNumber> {
 		/// Get the current validators.
 		fn validators() -> Vec<v1::ValidatorId>;
@@ -228,5 +241,27 @@ sp_api::decl_runtime_apis! {
 		/// NOTE: This function is only available since parachain host version 2.
 		fn validation_code_hash(para_id: v1::Id, assumption: v1::OccupiedCoreAssumption)
 			-> Option<v1::ValidationCodeHash>;
+
+		/***** Added in v3 *****/
+
+		/// Whether the erasure chunks of candidates with this block as relay parent are assigned to
+		/// validators by a shuffled mapping.
+		///
+		/// NOTE: This function is only available since parachain host version 3.
+		fn availability_chunk_shuffling() -> bool;
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API for querying the on-demand parathread order queue.
+	pub trait OnDemandApi<AccountId: Codec> {
+		/// The orders waiting for room in the parathread claim queue, oldest first.
+		fn order_queue() -> Vec<ParathreadOrder<AccountId>>;
+
+		/// The orders that have been handed to the scheduler and are waiting to be fulfilled.
+		fn active_orders() -> Vec<ParathreadOrder<AccountId>>;
+
+		/// The price an order placed in this block would pay.
+		fn spot_price() -> v1::Balance;
 	}
 }
@@ -34,6 +34,9 @@ use sp_consensus_babe::BabeApi;
 use sp_keystore::SyncCryptoStorePtr;
 use txpool_api::TransactionPool;
 
+pub mod parachain;
+pub mod xcm;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
 
@@ -72,6 +75,14 @@ pub struct BeefyDeps {
 	pub subscription_executor: sc_rpc::SubscriptionTaskExecutor,
 }
 
+/// Dependencies for the parachain RPC.
+pub struct ParachainDeps {
+	/// Handle to the overseer the requests are forwarded to.
+	pub overseer_handle: polkadot_overseer::Handle,
+	/// Executor to drive the subscription manager in the parachain RPC handler.
+	pub subscription_executor: sc_rpc::SubscriptionTaskExecutor,
+}
+
 /// Full client dependencies
 pub struct FullDeps<C, P, SC, B> {
 	/// The client instance to use.
@@ -90,6 +101,8 @@ pub struct FullDeps<C, P, SC, B> {
 	pub grandpa: GrandpaDeps<B>,
 	/// BEEFY specific dependencies.
 	pub beefy: BeefyDeps,
+	/// Parachain specific dependencies, if the node runs the parachain subsystems.
+	pub parachain: Option<ParachainDeps>,
 }
 
 /// Instantiate all RPC extensions.
@@ -108,6 +121,7 @@ where
 	C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BabeApi<Block>,
+	C::Api: pallet_xcm::dry_run::XcmDryRunApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 	SC: SelectChain<Block> + 'static,
@@ -121,8 +135,17 @@ where
 	use sc_finality_grandpa_rpc::{GrandpaApi, GrandpaRpcHandler};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, select_chain, chain_spec, deny_unsafe, babe, grandpa, beefy } =
-		deps;
+	let FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		deny_unsafe,
+		babe,
+		grandpa,
+		beefy,
+		parachain,
+	} = deps;
 	let BabeDeps { keystore, babe_config, shared_epoch_changes } = babe;
 	let GrandpaDeps {
 		shared_voter_state,
@@ -135,6 +158,7 @@ where
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 	io.extend_with(MmrApi::to_delegate(Mmr::new(client.clone())));
+	io.extend_with(xcm::XcmApi::to_delegate(xcm::XcmRpcHandler::new(client.clone())));
 	io.extend_with(sc_consensus_babe_rpc::BabeApi::to_delegate(BabeRpcHandler::new(
 		client.clone(),
 		shared_epoch_changes.clone(),
@@ -165,5 +189,13 @@ where
 	)?;
 	io.extend_with(beefy_gadget_rpc::BeefyApi::to_delegate(handler));
 
+	if let Some(ParachainDeps { overseer_handle, subscription_executor }) = parachain {
+		io.extend_with(parachain::ParachainApi::to_delegate(parachain::ParachainRpcHandler::new(
+			overseer_handle,
+			subscription_executor,
+			deny_unsafe,
+		)));
+	}
+
 	Ok(io)
 }
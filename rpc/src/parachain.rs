@@ -0,0 +1,523 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `parachain_*` RPC namespace.
+//!
+//! Exposes the view of the parachain subsystems on disputes, approvals and availability by
+//! proxying requests to the overseer. All of the methods are unsafe, as they put load on
+//! subsystems a validator relies on.
+
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use futures::{
+	channel::oneshot,
+	future::{self, Either},
+	FutureExt, TryFutureExt,
+};
+use jsonrpc_core::{Error as RpcError, ErrorCode};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use polkadot_overseer::{
+	AllMessages, ApprovalVotingMessage, AvailabilityStoreMessage, ChainSelectionMessage,
+	DisputeCoordinatorMessage, Handle,
+};
+use polkadot_primitives::v1::{BlockNumber, CandidateHash, Hash, SessionIndex};
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+
+const LOG_TARGET: &str = "parachain::rpc";
+
+/// How often the dispute coordinator is polled for subscriptions to dispute events.
+const DISPUTE_POLL_INTERVAL: Duration = Duration::from_secs(6);
+
+/// How long to wait for the overseer to answer a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The origin the requests to the overseer are sent with.
+const ORIGIN: &str = "parachain-rpc";
+
+/// Error code returned when the overseer dropped a request.
+const SUBSYSTEM_UNAVAILABLE: i64 = 9000;
+
+/// Error code returned when the overseer didn't answer a request within [`REQUEST_TIMEOUT`].
+const SUBSYSTEM_TIMEOUT: i64 = 9001;
+
+type FutureResult<T> = jsonrpc_core::BoxFuture<Result<T, RpcError>>;
+
+/// A dispute, identified by the session it was raised in and the disputed candidate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisputeId {
+	/// The session the candidate was included in.
+	pub session: SessionIndex,
+	/// The hash of the disputed candidate.
+	pub candidate_hash: Hash,
+}
+
+impl From<(SessionIndex, CandidateHash)> for DisputeId {
+	fn from((session, candidate_hash): (SessionIndex, CandidateHash)) -> Self {
+		DisputeId { session, candidate_hash: candidate_hash.0 }
+	}
+}
+
+/// The votes cast on a disputed candidate.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateVotes {
+	/// The dispute the votes were cast in.
+	pub dispute: DisputeId,
+	/// The relay parent of the candidate.
+	pub relay_parent: Hash,
+	/// The parachain the candidate was produced for.
+	pub para_id: u32,
+	/// Indices of the validators that voted for the validity of the candidate.
+	pub valid: Vec<u32>,
+	/// Indices of the validators that voted against the validity of the candidate.
+	pub invalid: Vec<u32>,
+}
+
+/// The highest ancestor of a block approval checking considers finalizable.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovedAncestor {
+	/// The hash of the ancestor.
+	pub hash: Hash,
+	/// The number of the ancestor.
+	pub number: BlockNumber,
+}
+
+/// A change in the set of disputes known to the node.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum DisputeEvent {
+	/// A dispute the node didn't know about before has been raised.
+	Started(DisputeId),
+	/// A dispute has concluded.
+	///
+	/// Concluded disputes are reported once the dispute coordinator stops considering them
+	/// active, which happens a few minutes after they actually concluded.
+	Concluded(DisputeId),
+}
+
+/// Parachain RPC methods.
+#[rpc]
+pub trait ParachainApi {
+	/// RPC metadata
+	type Metadata;
+
+	/// Returns the disputes which are either unconcluded or recently concluded.
+	#[rpc(name = "parachain_activeDisputes")]
+	fn active_disputes(&self) -> FutureResult<Vec<DisputeId>>;
+
+	/// Returns all disputes the dispute coordinator still keeps track of.
+	#[rpc(name = "parachain_recentDisputes")]
+	fn recent_disputes(&self) -> FutureResult<Vec<DisputeId>>;
+
+	/// Returns the votes cast in the given disputes. Unknown disputes are omitted.
+	#[rpc(name = "parachain_candidateVotes")]
+	fn candidate_votes(&self, disputes: Vec<DisputeId>) -> FutureResult<Vec<CandidateVotes>>;
+
+	/// Returns the highest ancestor of the given block which can be finalized as far as approval
+	/// checking is concerned, or `null` if the block is unknown.
+	#[rpc(name = "parachain_approvedAncestor")]
+	fn approved_ancestor(
+		&self,
+		hash: Hash,
+		lower_bound: BlockNumber,
+	) -> FutureResult<Option<ApprovedAncestor>>;
+
+	/// Returns whether the available data of the given candidate is stored by the node.
+	#[rpc(name = "parachain_isDataAvailable")]
+	fn is_data_available(&self, candidate_hash: Hash) -> FutureResult<bool>;
+
+	/// Returns the leaves of the relay chain in descending order by score.
+	#[rpc(name = "parachain_leaves")]
+	fn leaves(&self) -> FutureResult<Vec<Hash>>;
+
+	/// Subscribe to disputes being raised and concluded.
+	///
+	/// The subscription keeps going if the dispute coordinator fails to answer, the failure is
+	/// reported as an error notification instead.
+	#[pubsub(subscription = "parachain_disputes", subscribe, name = "parachain_subscribeDisputes")]
+	fn subscribe_disputes(&self, metadata: Self::Metadata, subscriber: Subscriber<DisputeEvent>);
+
+	/// Unsubscribe from dispute events.
+	#[pubsub(
+		subscription = "parachain_disputes",
+		unsubscribe,
+		name = "parachain_unsubscribeDisputes"
+	)]
+	fn unsubscribe_disputes(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> jsonrpc_core::Result<bool>;
+}
+
+/// Implements the [`ParachainApi`] RPC trait by forwarding requests to the overseer.
+pub struct ParachainRpcHandler {
+	overseer_handle: Handle,
+	manager: SubscriptionManager,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl ParachainRpcHandler {
+	/// Creates a new handler forwarding requests through the given overseer handle.
+	pub fn new(
+		overseer_handle: Handle,
+		subscription_executor: sc_rpc::SubscriptionTaskExecutor,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		let manager = SubscriptionManager::new(Arc::new(subscription_executor));
+		ParachainRpcHandler { overseer_handle, manager, deny_unsafe }
+	}
+
+	fn request<T, M>(&self, message: impl FnOnce(oneshot::Sender<T>) -> M) -> FutureResult<T>
+	where
+		T: Send + 'static,
+		M: Into<AllMessages>,
+	{
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return Box::pin(futures::future::err(err.into()))
+		}
+
+		request(self.overseer_handle.clone(), message)
+	}
+}
+
+impl ParachainApi for ParachainRpcHandler {
+	type Metadata = sc_rpc::Metadata;
+
+	fn active_disputes(&self) -> FutureResult<Vec<DisputeId>> {
+		self.request(DisputeCoordinatorMessage::ActiveDisputes)
+			.map_ok(|disputes| disputes.into_iter().map(Into::into).collect())
+			.boxed()
+	}
+
+	fn recent_disputes(&self) -> FutureResult<Vec<DisputeId>> {
+		self.request(DisputeCoordinatorMessage::RecentDisputes)
+			.map_ok(|disputes| disputes.into_iter().map(Into::into).collect())
+			.boxed()
+	}
+
+	fn candidate_votes(&self, disputes: Vec<DisputeId>) -> FutureResult<Vec<CandidateVotes>> {
+		let query = disputes
+			.into_iter()
+			.map(|dispute| (dispute.session, CandidateHash(dispute.candidate_hash)))
+			.collect();
+
+		self.request(|tx| DisputeCoordinatorMessage::QueryCandidateVotes(query, tx))
+			.map_ok(|votes| {
+				votes
+					.into_iter()
+					.map(|(session, candidate_hash, votes)| CandidateVotes {
+						dispute: (session, candidate_hash).into(),
+						relay_parent: votes.candidate_receipt.descriptor.relay_parent,
+						para_id: votes.candidate_receipt.descriptor.para_id.into(),
+						valid: votes.valid.iter().map(|(_, index, _)| index.0).collect(),
+						invalid: votes.invalid.iter().map(|(_, index, _)| index.0).collect(),
+					})
+					.collect()
+			})
+			.boxed()
+	}
+
+	fn approved_ancestor(
+		&self,
+		hash: Hash,
+		lower_bound: BlockNumber,
+	) -> FutureResult<Option<ApprovedAncestor>> {
+		self.request(|tx| ApprovalVotingMessage::ApprovedAncestor(hash, lower_bound, tx))
+			.map_ok(|ancestor| {
+				ancestor.map(|block| ApprovedAncestor { hash: block.hash, number: block.number })
+			})
+			.boxed()
+	}
+
+	fn is_data_available(&self, candidate_hash: Hash) -> FutureResult<bool> {
+		self.request(|tx| {
+			AvailabilityStoreMessage::QueryDataAvailability(CandidateHash(candidate_hash), tx)
+		})
+	}
+
+	fn leaves(&self) -> FutureResult<Vec<Hash>> {
+		self.request(ChainSelectionMessage::Leaves)
+	}
+
+	fn subscribe_disputes(&self, _metadata: Self::Metadata, subscriber: Subscriber<DisputeEvent>) {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			let _ = subscriber.reject(err.into());
+			return
+		}
+
+		let overseer_handle = self.overseer_handle.clone();
+		self.manager.add(subscriber, move |sink| async move {
+			let mut watcher = DisputeWatcher::default();
+			loop {
+				let notifications = match watcher.poll(overseer_handle.clone()).await {
+					Ok(events) => events.into_iter().map(Ok).collect(),
+					Err(err) => {
+						tracing::warn!(target: LOG_TARGET, ?err, "Failed to poll disputes");
+						vec![Err(err)]
+					},
+				};
+
+				for notification in notifications {
+					if sink.notify(notification).is_err() {
+						// The subscriber has gone away.
+						return
+					}
+				}
+
+				futures_timer::Delay::new(DISPUTE_POLL_INTERVAL).await;
+			}
+		});
+	}
+
+	fn unsubscribe_disputes(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> jsonrpc_core::Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
+
+/// Keeps track of the disputes seen by a subscription, so that only changes are reported.
+#[derive(Default)]
+struct DisputeWatcher {
+	/// The recent and active disputes as of the last poll. `None` before the first poll.
+	last: Option<(HashSet<DisputeId>, HashSet<DisputeId>)>,
+}
+
+impl DisputeWatcher {
+	/// Queries the disputes known to the dispute coordinator and returns what changed since the
+	/// last poll. Disputes known at the first poll are not reported.
+	async fn poll(&mut self, overseer_handle: Handle) -> Result<Vec<DisputeEvent>, RpcError> {
+		let recent: HashSet<DisputeId> =
+			request(overseer_handle.clone(), DisputeCoordinatorMessage::RecentDisputes)
+				.await?
+				.into_iter()
+				.map(Into::into)
+				.collect();
+		let active: HashSet<DisputeId> =
+			request(overseer_handle, DisputeCoordinatorMessage::ActiveDisputes)
+				.await?
+				.into_iter()
+				.map(Into::into)
+				.collect();
+
+		let events = match self.last {
+			None => Vec::new(),
+			Some((ref last_recent, ref last_active)) =>
+				diff(last_recent, last_active, &recent, &active),
+		};
+		self.last = Some((recent, active));
+
+		Ok(events)
+	}
+}
+
+/// Computes the dispute events between two polls of the dispute coordinator.
+fn diff(
+	last_recent: &HashSet<DisputeId>,
+	last_active: &HashSet<DisputeId>,
+	recent: &HashSet<DisputeId>,
+	active: &HashSet<DisputeId>,
+) -> Vec<DisputeEvent> {
+	let started = recent.difference(last_recent).map(|dispute| DisputeEvent::Started(*dispute));
+	// Disputes which stopped being active but are still known have concluded, pruned disputes
+	// are not reported.
+	let concluded = recent
+		.iter()
+		.filter(|dispute| !active.contains(dispute))
+		.filter(|dispute| last_active.contains(dispute) || !last_recent.contains(dispute))
+		.map(|dispute| DisputeEvent::Concluded(*dispute));
+
+	started.chain(concluded).collect()
+}
+
+/// Sends a request to the overseer and waits for the response for at most [`REQUEST_TIMEOUT`].
+fn request<T, M>(
+	overseer_handle: Handle,
+	message: impl FnOnce(oneshot::Sender<T>) -> M,
+) -> FutureResult<T>
+where
+	T: Send + 'static,
+	M: Into<AllMessages>,
+{
+	request_with_timeout(overseer_handle, message, REQUEST_TIMEOUT)
+}
+
+fn request_with_timeout<T, M>(
+	mut overseer_handle: Handle,
+	message: impl FnOnce(oneshot::Sender<T>) -> M,
+	timeout: Duration,
+) -> FutureResult<T>
+where
+	T: Send + 'static,
+	M: Into<AllMessages>,
+{
+	let (tx, rx) = oneshot::channel();
+	let message = message(tx).into();
+
+	async move {
+		let response = async move {
+			overseer_handle.send_msg(message, ORIGIN).await;
+			rx.await
+		}
+		.boxed();
+
+		match future::select(response, futures_timer::Delay::new(timeout)).await {
+			Either::Left((Ok(response), _)) => Ok(response),
+			Either::Left((Err(oneshot::Canceled), _)) => Err(RpcError {
+				code: ErrorCode::ServerError(SUBSYSTEM_UNAVAILABLE),
+				message: "The parachain subsystems did not answer the request".into(),
+				data: None,
+			}),
+			Either::Right(_) => Err(RpcError {
+				code: ErrorCode::ServerError(SUBSYSTEM_TIMEOUT),
+				message: "The parachain subsystems did not answer the request in time".into(),
+				data: None,
+			}),
+		}
+	}
+	.boxed()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{executor::block_on, StreamExt};
+	use polkadot_overseer::{gen::metered, Event};
+
+	type EventReceiver = metered::MeteredReceiver<Event>;
+
+	fn test_handle() -> (Handle, EventReceiver) {
+		let (tx, rx) = metered::channel(8);
+		(Handle::new(tx), rx)
+	}
+
+	/// Answers the next request to the dispute coordinator for the recent or active disputes.
+	async fn answer_disputes(rx: &mut EventReceiver, recent: &[DisputeId], active: &[DisputeId]) {
+		let to_query = |disputes: &[DisputeId]| {
+			disputes
+				.iter()
+				.map(|dispute| (dispute.session, CandidateHash(dispute.candidate_hash)))
+				.collect()
+		};
+
+		match rx.next().await {
+			Some(Event::MsgToSubsystem {
+				msg: AllMessages::DisputeCoordinator(DisputeCoordinatorMessage::RecentDisputes(tx)),
+				..
+			}) => tx.send(to_query(recent)).unwrap(),
+			Some(Event::MsgToSubsystem {
+				msg: AllMessages::DisputeCoordinator(DisputeCoordinatorMessage::ActiveDisputes(tx)),
+				..
+			}) => tx.send(to_query(active)).unwrap(),
+			_ => panic!("Unexpected request"),
+		}
+	}
+
+	fn dispute(n: u8) -> DisputeId {
+		DisputeId { session: 1, candidate_hash: Hash::repeat_byte(n) }
+	}
+
+	fn set(disputes: &[DisputeId]) -> HashSet<DisputeId> {
+		disputes.iter().cloned().collect()
+	}
+
+	#[test]
+	fn diff_reports_started_and_concluded_disputes() {
+		let (a, b, c) = (dispute(1), dispute(2), dispute(3));
+
+		let mut events = diff(&set(&[a, b]), &set(&[a, b]), &set(&[a, b, c]), &set(&[b, c]));
+		events.sort_by_key(|event| format!("{:?}", event));
+
+		assert_eq!(events, vec![DisputeEvent::Concluded(a), DisputeEvent::Started(c)]);
+	}
+
+	#[test]
+	fn diff_ignores_pruned_and_already_concluded_disputes() {
+		let (a, b) = (dispute(1), dispute(2));
+
+		// `a` concluded before the last poll, `b` has been pruned since.
+		let events = diff(&set(&[a, b]), &set(&[]), &set(&[a]), &set(&[]));
+
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn watcher_reports_changes_between_polls() {
+		let (handle, mut rx) = test_handle();
+		let (a, b) = (dispute(1), dispute(2));
+		let mut watcher = DisputeWatcher::default();
+
+		let (events, _) = block_on(future::join(watcher.poll(handle.clone()), async {
+			answer_disputes(&mut rx, &[a], &[a]).await;
+			answer_disputes(&mut rx, &[a], &[a]).await;
+		}));
+		assert!(events.unwrap().is_empty());
+
+		let (events, _) = block_on(future::join(watcher.poll(handle), async {
+			answer_disputes(&mut rx, &[a, b], &[b]).await;
+			answer_disputes(&mut rx, &[a, b], &[b]).await;
+		}));
+		let mut events = events.unwrap();
+		events.sort_by_key(|event| format!("{:?}", event));
+		assert_eq!(events, vec![DisputeEvent::Concluded(a), DisputeEvent::Started(b)]);
+	}
+
+	#[test]
+	fn watcher_keeps_its_state_on_failure() {
+		let (handle, mut rx) = test_handle();
+		let a = dispute(1);
+		let mut watcher = DisputeWatcher::default();
+
+		let (events, _) = block_on(future::join(watcher.poll(handle.clone()), async {
+			answer_disputes(&mut rx, &[], &[]).await;
+			answer_disputes(&mut rx, &[], &[]).await;
+		}));
+		assert!(events.unwrap().is_empty());
+
+		// The request is dropped without an answer.
+		let (events, _) = block_on(future::join(watcher.poll(handle.clone()), async {
+			drop(rx.next().await);
+		}));
+		assert_eq!(events.unwrap_err().code, ErrorCode::ServerError(SUBSYSTEM_UNAVAILABLE));
+
+		let (events, _) = block_on(future::join(watcher.poll(handle), async {
+			answer_disputes(&mut rx, &[a], &[a]).await;
+			answer_disputes(&mut rx, &[a], &[a]).await;
+		}));
+		assert_eq!(events.unwrap(), vec![DisputeEvent::Started(a)]);
+	}
+
+	#[test]
+	fn unanswered_request_times_out() {
+		let (handle, rx) = test_handle();
+
+		let result = block_on(request_with_timeout(
+			handle,
+			DisputeCoordinatorMessage::ActiveDisputes,
+			Duration::from_millis(10),
+		));
+
+		assert_eq!(result.unwrap_err().code, ErrorCode::ServerError(SUBSYSTEM_TIMEOUT));
+		drop(rx);
+	}
+}
@@ -0,0 +1,120 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `xcm_*` RPC namespace.
+//!
+//! Exposes the XCM dry-run runtime API, which allows to preview the effects of executing an XCM
+//! message, e.g. to estimate its fees or to find out why it fails.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_xcm::dry_run::XcmDryRunApi as XcmDryRunRuntimeApi;
+use parity_scale_codec::{Decode, Encode};
+use polkadot_primitives::v0::{Block, Hash};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::generic::BlockId;
+
+/// Error code returned when the runtime API call failed.
+const RUNTIME_ERROR: i64 = 9100;
+
+/// Error code returned when the dry run could not be performed.
+const DRY_RUN_ERROR: i64 = 9101;
+
+/// The highest `max_weight` a message may be dry-run with, the weight of a full relay chain block.
+///
+/// This bounds the work a single call can cause, so the method can be exposed publicly.
+pub const MAX_WEIGHT: u64 = 2_000_000_000_000;
+
+/// XCM RPC methods.
+#[rpc]
+pub trait XcmApi {
+	/// Dry-runs the SCALE encoded `VersionedXcm` `message` with the SCALE encoded
+	/// `VersionedMultiLocation` `origin` on top of the given block, or the best block if none is
+	/// given. `max_weight` is both the weight limit of the execution and the weight credit it
+	/// starts with, and must not exceed [`MAX_WEIGHT`].
+	///
+	/// Returns the SCALE encoded `XcmDryRunEffects`. Nothing is applied to the chain.
+	#[rpc(name = "xcm_dryRun")]
+	fn dry_run(
+		&self,
+		origin: Bytes,
+		message: Bytes,
+		max_weight: u64,
+		at: Option<Hash>,
+	) -> Result<Bytes>;
+}
+
+/// Implements the [`XcmApi`] RPC trait by calling into the runtime.
+pub struct XcmRpcHandler<C> {
+	client: Arc<C>,
+}
+
+impl<C> XcmRpcHandler<C> {
+	/// Creates a new handler calling into the runtime of the given client.
+	pub fn new(client: Arc<C>) -> Self {
+		XcmRpcHandler { client }
+	}
+}
+
+impl<C> XcmApi for XcmRpcHandler<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XcmDryRunRuntimeApi<Block>,
+{
+	fn dry_run(
+		&self,
+		origin: Bytes,
+		message: Bytes,
+		max_weight: u64,
+		at: Option<Hash>,
+	) -> Result<Bytes> {
+		if max_weight > MAX_WEIGHT {
+			return Err(RpcError {
+				code: ErrorCode::InvalidParams,
+				message: format!("The max weight must not exceed {}.", MAX_WEIGHT),
+				data: None,
+			})
+		}
+
+		let origin = Decode::decode(&mut &origin[..]).map_err(|e| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: "Unable to decode the origin.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let effects = self
+			.client
+			.runtime_api()
+			.dry_run_xcm(&at, origin, message.to_vec(), max_weight)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(RUNTIME_ERROR),
+				message: "Unable to dry-run the message.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(DRY_RUN_ERROR),
+				message: "The message could not be dry-run.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		Ok(effects.encode().into())
+	}
+}
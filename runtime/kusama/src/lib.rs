@@ -41,7 +41,8 @@ use sp_core::u32_trait::{_1, _2, _3, _5};
 use sp_std::{cmp::Ordering, collections::btree_map::BTreeMap, prelude::*};
 
 use runtime_parachains::{
-	configuration as parachains_configuration, disputes as parachains_disputes,
+	approval_rewards as parachains_approval_rewards, configuration as parachains_configuration,
+	disputes as parachains_disputes, disputes::slashing as parachains_slashing,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
 	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
@@ -1236,7 +1237,9 @@ impl parachains_paras_inherent::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
 
-impl parachains_scheduler::Config for Runtime {}
+impl parachains_scheduler::Config for Runtime {
+	type OnClaimEnded = ();
+}
 
 impl parachains_initializer::Config for Runtime {
 	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
@@ -1247,10 +1250,43 @@ impl parachains_initializer::Config for Runtime {
 impl parachains_disputes::Config for Runtime {
 	type Event = Event;
 	type RewardValidators = ();
-	type PunishValidators = ();
+	type PunishValidators = ParasSlashing;
 	type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	// Slashing any side of disputes stays disabled until validators run a release which
+	// abstains from, rather than votes against, candidates exhausting the memory limit of PVF
+	// execution. Otherwise nodes on a tighter limit could be slashed for honest votes, or for
+	// taking part in disputes which never conclude because of them.
+	pub const SlashForInvalid: Perbill = Perbill::zero();
+	pub const SlashAgainstValid: Perbill = Perbill::zero();
+	pub const SlashInconclusive: Perbill = Perbill::zero();
+	pub const DisputeSlashDeferDuration: BlockNumber = 1 * DAYS;
+	// Each offender may take about a tenth of a second to slash along with its nominators.
+	pub const MaxDisputeOffendersPerBlock: u32 = 5;
+}
+
+impl parachains_slashing::Config for Runtime {
+	type Event = Event;
+	type ValidatorSet = Historical;
+	type HistoricalIdentification = parachains_slashing::EraExposure<Runtime>;
+	type HandleReports = Offences;
+	type SlashForInvalid = SlashForInvalid;
+	type SlashAgainstValid = SlashAgainstValid;
+	type SlashInconclusive = SlashInconclusive;
+	type SlashDeferDuration = DisputeSlashDeferDuration;
+	type CancelOrigin = SlashCancelOrigin;
+	type MaxOffendersPerBlock = MaxDisputeOffendersPerBlock;
+	type WeightInfo = parachains_slashing::StakingOffenceWeights<Runtime>;
+}
+
+impl parachains_approval_rewards::Config for Runtime {
+	type Event = Event;
+	type RewardApprovals = parachains_reward_points::RewardValidatorsWithEraPoints<Runtime>;
+	type WeightInfo = parachains_approval_rewards::DisputeStatementWeights<Runtime>;
+}
+
 parameter_types! {
 	pub const ParaDeposit: Balance = 40 * UNITS;
 }
@@ -1451,6 +1487,8 @@ construct_runtime! {
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>, Config} = 60,
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage} = 61,
 		ParasDisputes: parachains_disputes::{Pallet, Call, Storage, Event<T>} = 62,
+		ParasSlashing: parachains_slashing::{Pallet, Call, Storage, Event<T>} = 63,
+		ParasApprovalRewards: parachains_approval_rewards::{Pallet, Call, Storage, Event<T>, Inherent} = 64,
 
 		// Parachain Onboarding Pallets. Start indices at 70 to leave room.
 		Registrar: paras_registrar::{Pallet, Call, Storage, Event<T>} = 70,
@@ -3102,6 +3140,10 @@ sp_api::impl_runtime_apis! {
 		{
 			parachains_runtime_api_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn availability_chunk_shuffling() -> bool {
+			parachains_runtime_api_impl::availability_chunk_shuffling::<Runtime>()
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -3261,6 +3303,16 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: Vec<u8>,
+			max_weight: Weight,
+		) -> Result<pallet_xcm::dry_run::XcmDryRunEffects, pallet_xcm::dry_run::XcmDryRunError> {
+			XcmPallet::dry_run_xcm(origin, message, max_weight)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade() -> (Weight, Weight) {
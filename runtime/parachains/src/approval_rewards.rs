@@ -0,0 +1,704 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rewards for approval checking.
+//!
+//! Approval votes are cast off-chain and, unlike backing votes, only ever reach the chain when a
+//! candidate gets disputed. This module accepts an optional inherent through which block authors
+//! bring the approval votes on candidates included in the current session on chain. Every vote is
+//! checked against the validator set of the session and is credited at most once with the
+//! `approval_reward_points` of the host configuration.
+//!
+//! Inclusion of a candidate is looked up through the disputes handler, so approval checking is
+//! only rewarded in runtimes that track disputes.
+//!
+//! # Known limitations
+//!
+//! Assignments are not checked on chain: the relay VRF assignment certificates which entitle a
+//! validator to check a candidate never reach the chain, so any validator of the session may get
+//! an approval vote rewarded, whether it was assigned to the candidate or not. At most
+//! `needed_approvals` of the session are rewarded per candidate, which is as many checkers as
+//! approval voting requires, so validators cannot farm points beyond that. Within the cap,
+//! however, the first votes brought on chain win, and a block author may prefer its own votes,
+//! or those of validators it colludes with, over those of the assigned checkers. Rewarding only
+//! assigned checkers requires the assignments to be brought on chain and checked alongside the
+//! votes, which is left as a follow-up.
+
+use crate::{
+	configuration, disputes::DisputesHandler, inclusion, paras_inherent, session_info, shared,
+};
+use bitvec::{bitvec, order::Lsb0 as BitOrderLsb0, vec::BitVec};
+use frame_support::{
+	inherent::{InherentData, InherentIdentifier, MakeFatalError, ProvideInherent},
+	pallet_prelude::*,
+};
+use frame_system::pallet_prelude::*;
+use primitives::v1::{
+	ApprovalVote, ApprovalVoteSet, CandidateHash, MultiApprovalVoteSet, SessionIndex, ValidatorId,
+	ValidatorIndex, ValidatorSignature, APPROVAL_VOTES_INHERENT_IDENTIFIER,
+};
+use sp_runtime::{traits::AppVerify, Perbill};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+const LOG_TARGET: &str = "runtime::approval-rewards";
+
+/// The share of the block weight the approval votes inherent may take up.
+const MAX_APPROVALS_BLOCK_WEIGHT: Perbill = Perbill::from_percent(10);
+
+/// Reward hooks for approval checking.
+pub trait RewardApprovals {
+	/// Reward the given validators of the current session with `points` each.
+	fn reward_approvals(validators: impl IntoIterator<Item = ValidatorIndex>, points: u32);
+}
+
+impl RewardApprovals for () {
+	fn reward_approvals(_: impl IntoIterator<Item = ValidatorIndex>, _: u32) {}
+}
+
+pub trait WeightInfo {
+	/// Variant over `v`, the count of approval votes in an approval vote set. This gives the
+	/// weight of a single approval vote set.
+	fn note_approvals(v: u32) -> Weight;
+}
+
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn note_approvals(_v: u32) -> Weight {
+		0
+	}
+}
+
+/// Weights of approval vote sets derived from the benchmarked weights of dispute statement sets
+/// in the paras inherent, which cost about the same to check.
+pub struct DisputeStatementWeights<T>(sp_std::marker::PhantomData<T>);
+impl<T: paras_inherent::Config> WeightInfo for DisputeStatementWeights<T> {
+	fn note_approvals(v: u32) -> Weight {
+		<<T as paras_inherent::Config>::WeightInfo as paras_inherent::WeightInfo>::enter_variable_disputes(v)
+	}
+}
+
+/// The total weight of checking and rewarding the given approval vote sets.
+pub fn approval_vote_sets_weight<T: Config>(approvals: &[ApprovalVoteSet]) -> Weight {
+	approvals.iter().fold(0, |weight, set| {
+		weight.saturating_add(<T as Config>::WeightInfo::note_approvals(set.votes.len() as u32))
+	})
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + inclusion::Config + session_info::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Credits the era points for approval votes.
+		type RewardApprovals: RewardApprovals;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Approval votes on an included candidate have been rewarded.
+		/// `[session, candidate hash, rewarded votes]`
+		ApprovalsRewarded(SessionIndex, CandidateHash, u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Approval votes inherent called more than once per block.
+		TooManyApprovalInherents,
+		/// The approval votes are not for a candidate of the current session.
+		NotCurrentSession,
+		/// The candidate is not known to be included or was concluded invalid.
+		CandidateNotIncluded,
+		/// The approval votes were not sorted by validator index or contained duplicates.
+		ApprovalVotesUnsortedOrDuplicates,
+		/// An approval vote was cast by a validator unknown to the session.
+		ValidatorIndexOutOfBounds,
+		/// An approval vote had an invalid signature.
+		InvalidApprovalSignature,
+		/// An approval vote was already rewarded.
+		ApprovalAlreadyRewarded,
+		/// More approval votes than `needed_approvals` of the session would be rewarded for the
+		/// candidate.
+		TooManyApprovals,
+	}
+
+	/// Whether the approval votes inherent was included within this block.
+	#[pallet::storage]
+	pub(crate) type Included<T> = StorageValue<_, ()>;
+
+	/// The validators whose approval votes on an included candidate were already rewarded, as a
+	/// bitfield indexed by validator index.
+	#[pallet::storage]
+	#[pallet::getter(fn rewarded_approvals)]
+	pub(crate) type RewardedApprovals<T> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Blake2_128Concat,
+		CandidateHash,
+		BitVec<BitOrderLsb0, u8>,
+	>;
+
+	/// The session for which approvals are currently rewarded.
+	#[pallet::storage]
+	pub(crate) type RewardedSession<T> = StorageValue<_, SessionIndex, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: T::BlockNumber) -> Weight {
+			// Reads and writes for the session check and `on_finalize`.
+			let mut weight = T::DbWeight::get().reads_writes(3, 1);
+
+			// Approval votes can only be rewarded within the session of the candidate, so the
+			// bookkeeping of the previous session can go.
+			let current_session = <shared::Pallet<T>>::session_index();
+			let rewarded_session = <RewardedSession<T>>::get();
+			if rewarded_session != current_session {
+				<RewardedApprovals<T>>::remove_prefix(rewarded_session, None);
+				<RewardedSession<T>>::put(current_session);
+				weight += T::DbWeight::get().writes(2);
+			}
+
+			weight
+		}
+
+		fn on_finalize(_: T::BlockNumber) {
+			<Included<T>>::kill();
+		}
+	}
+
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = MakeFatalError<()>;
+		const INHERENT_IDENTIFIER: InherentIdentifier = APPROVAL_VOTES_INHERENT_IDENTIFIER;
+
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			if <configuration::Pallet<T>>::config().approval_reward_points == 0 {
+				return None
+			}
+
+			let approvals = match data.get_data::<MultiApprovalVoteSet>(&Self::INHERENT_IDENTIFIER)
+			{
+				Ok(Some(approvals)) => approvals,
+				Ok(None) => return None,
+				Err(_) => {
+					log::warn!(target: LOG_TARGET, "approval votes inherent data failed to decode");
+					return None
+				},
+			};
+
+			let approvals = Self::filter_approval_votes(approvals);
+			if approvals.is_empty() {
+				None
+			} else {
+				Some(Call::note_approvals { approvals })
+			}
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::note_approvals { .. })
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Reward the given approval votes on candidates included in the current session.
+		#[pallet::weight((
+			approval_vote_sets_weight::<T>(approvals),
+			DispatchClass::Mandatory,
+		))]
+		pub fn note_approvals(
+			origin: OriginFor<T>,
+			approvals: MultiApprovalVoteSet,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			ensure!(!<Included<T>>::exists(), Error::<T>::TooManyApprovalInherents);
+			<Included<T>>::put(());
+
+			let points = <configuration::Pallet<T>>::config().approval_reward_points;
+			for set in approvals {
+				ensure!(
+					set.votes.windows(2).all(|w| w[0].0 < w[1].0),
+					Error::<T>::ApprovalVotesUnsortedOrDuplicates,
+				);
+
+				let (validators, mut rewarded, remaining) =
+					Self::rewardable_approvals(set.session, set.candidate_hash)?;
+				let payload = ApprovalVote(set.candidate_hash).signing_payload(set.session);
+				for (validator_index, signature) in &set.votes {
+					Self::check_approval_vote(
+						&validators,
+						&rewarded,
+						&payload,
+						*validator_index,
+						signature,
+					)?;
+					rewarded.set(validator_index.0 as usize, true);
+				}
+				// TODO: reward only the assigned checkers once assignments are brought on chain
+				// along with the approval votes, see the known limitations in the module docs.
+				ensure!(set.votes.len() <= remaining, Error::<T>::TooManyApprovals);
+
+				<RewardedApprovals<T>>::insert(set.session, set.candidate_hash, rewarded);
+				T::RewardApprovals::reward_approvals(set.votes.iter().map(|(i, _)| *i), points);
+				Self::deposit_event(Event::ApprovalsRewarded(
+					set.session,
+					set.candidate_hash,
+					set.votes.len() as u32,
+				));
+			}
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the validators of the session, the ones already rewarded for approving the
+	/// candidate and how many more may be rewarded, if approvals on the candidate can be rewarded
+	/// at all.
+	fn rewardable_approvals(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+	) -> Result<(Vec<ValidatorId>, BitVec<BitOrderLsb0, u8>, usize), Error<T>> {
+		ensure!(session == <shared::Pallet<T>>::session_index(), Error::<T>::NotCurrentSession);
+
+		type Disputes<T> = <T as inclusion::Config>::DisputesHandler;
+		ensure!(
+			Disputes::<T>::included_state(session, candidate_hash).is_some() &&
+				!Disputes::<T>::concluded_invalid(session, candidate_hash),
+			Error::<T>::CandidateNotIncluded,
+		);
+
+		let (validators, needed_approvals) = <session_info::Pallet<T>>::session_info(session)
+			.map(|info| (info.validators, info.needed_approvals))
+			.unwrap_or_default();
+		let rewarded = <RewardedApprovals<T>>::get(session, candidate_hash)
+			.unwrap_or_else(|| bitvec![BitOrderLsb0, u8; 0; validators.len()]);
+		let remaining = (needed_approvals as usize).saturating_sub(rewarded.count_ones());
+
+		Ok((validators, rewarded, remaining))
+	}
+
+	fn check_approval_vote(
+		validators: &[ValidatorId],
+		rewarded: &BitVec<BitOrderLsb0, u8>,
+		payload: &[u8],
+		validator_index: ValidatorIndex,
+		signature: &ValidatorSignature,
+	) -> Result<(), Error<T>> {
+		let validator = validators
+			.get(validator_index.0 as usize)
+			.ok_or(Error::<T>::ValidatorIndexOutOfBounds)?;
+		ensure!(
+			!rewarded.get(validator_index.0 as usize).map_or(false, |b| *b),
+			Error::<T>::ApprovalAlreadyRewarded,
+		);
+		ensure!(signature.verify(payload, validator), Error::<T>::InvalidApprovalSignature);
+
+		Ok(())
+	}
+
+	/// Reduce the approval votes provided by the block author to the ones that can be rewarded,
+	/// while keeping their weight within `MAX_APPROVALS_BLOCK_WEIGHT` of the block.
+	pub(crate) fn filter_approval_votes(
+		mut approvals: MultiApprovalVoteSet,
+	) -> MultiApprovalVoteSet {
+		let max_weight =
+			MAX_APPROVALS_BLOCK_WEIGHT * <T as frame_system::Config>::BlockWeights::get().max_block;
+
+		approvals.sort_by(|a, b| (a.session, a.candidate_hash).cmp(&(b.session, b.candidate_hash)));
+		approvals.dedup_by(|a, b| (a.session, a.candidate_hash) == (b.session, b.candidate_hash));
+
+		let mut weight: Weight = 0;
+		approvals
+			.into_iter()
+			.filter_map(|mut set| {
+				let (validators, rewarded, remaining) =
+					Self::rewardable_approvals(set.session, set.candidate_hash).ok()?;
+				let payload = ApprovalVote(set.candidate_hash).signing_payload(set.session);
+
+				set.votes.sort_by_key(|(i, _)| *i);
+				set.votes.dedup_by_key(|(i, _)| *i);
+				set.votes.retain(|(validator_index, signature)| {
+					Self::check_approval_vote(
+						&validators,
+						&rewarded,
+						&payload,
+						*validator_index,
+						signature,
+					)
+					.is_ok()
+				});
+				set.votes.truncate(remaining);
+
+				if set.votes.is_empty() {
+					None
+				} else {
+					Some(set)
+				}
+			})
+			.take_while(|set| {
+				weight = weight.saturating_add(<T as Config>::WeightInfo::note_approvals(
+					set.votes.len() as u32,
+				));
+				weight <= max_weight
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		configuration::HostConfiguration,
+		mock::{
+			approval_rewards, new_test_ext, ApprovalRewards, Disputes, MockGenesisConfig, Origin,
+			ParasShared, System, Test,
+		},
+	};
+	use frame_support::{assert_noop, assert_ok};
+	use keyring::Sr25519Keyring;
+	use primitives::v2::SessionInfo;
+
+	const POINTS: u32 = 5;
+
+	fn genesis_config(points: u32) -> MockGenesisConfig {
+		MockGenesisConfig {
+			configuration: configuration::GenesisConfig {
+				config: HostConfiguration { approval_reward_points: points, ..Default::default() },
+			},
+			..Default::default()
+		}
+	}
+
+	const VALIDATORS: [Sr25519Keyring; 3] =
+		[Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie];
+
+	// Start `session` with `VALIDATORS` as the active validators, all of which are needed to
+	// approve a candidate.
+	fn new_session(session: SessionIndex) {
+		new_session_with_needed_approvals(session, VALIDATORS.len() as u32);
+	}
+
+	fn new_session_with_needed_approvals(session: SessionIndex, needed_approvals: u32) {
+		ParasShared::set_session_index(session);
+		session_info::Sessions::<Test>::insert(
+			session,
+			SessionInfo {
+				validators: VALIDATORS.iter().map(|v| v.public().into()).collect(),
+				discovery_keys: Vec::new(),
+				assignment_keys: Vec::new(),
+				validator_groups: Vec::new(),
+				n_cores: 0,
+				zeroth_delay_tranche_width: 0,
+				relay_vrf_modulo_samples: 0,
+				n_delay_tranches: 0,
+				no_show_slots: 0,
+				needed_approvals,
+				active_validator_indices: (0..VALIDATORS.len() as u32)
+					.map(ValidatorIndex)
+					.collect(),
+				random_seed: [0; 32],
+				dispute_period: 6,
+			},
+		);
+
+		let b = System::block_number() + 1;
+		System::set_block_number(b);
+		ApprovalRewards::on_initialize(b);
+	}
+
+	fn include_candidate(session: SessionIndex, candidate_hash: CandidateHash) {
+		<Disputes as DisputesHandler<_>>::note_included(
+			session,
+			candidate_hash,
+			System::block_number(),
+		);
+	}
+
+	fn approval_vote_set(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		validators: &[u32],
+	) -> ApprovalVoteSet {
+		let payload = ApprovalVote(candidate_hash).signing_payload(session);
+		ApprovalVoteSet {
+			candidate_hash,
+			session,
+			votes: validators
+				.iter()
+				.map(|i| (ValidatorIndex(*i), VALIDATORS[*i as usize].sign(&payload).into()))
+				.collect(),
+		}
+	}
+
+	fn next_block() {
+		ApprovalRewards::on_finalize(System::block_number());
+		let b = System::block_number() + 1;
+		System::set_block_number(b);
+		ApprovalRewards::on_initialize(b);
+	}
+
+	#[test]
+	fn approvals_are_rewarded_once() {
+		new_test_ext(genesis_config(POINTS)).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1);
+			include_candidate(1, candidate_hash);
+
+			assert_ok!(ApprovalRewards::note_approvals(
+				Origin::none(),
+				vec![approval_vote_set(1, candidate_hash, &[0, 2])],
+			));
+			assert_eq!(
+				approval_rewards(),
+				vec![(vec![ValidatorIndex(0), ValidatorIndex(2)], POINTS)]
+			);
+			assert_eq!(
+				ApprovalRewards::rewarded_approvals(1, candidate_hash),
+				Some(bitvec![BitOrderLsb0, u8; 1, 0, 1]),
+			);
+
+			next_block();
+			assert_noop!(
+				ApprovalRewards::note_approvals(
+					Origin::none(),
+					vec![approval_vote_set(1, candidate_hash, &[1, 2])],
+				),
+				Error::<Test>::ApprovalAlreadyRewarded,
+			);
+
+			assert_ok!(ApprovalRewards::note_approvals(
+				Origin::none(),
+				vec![approval_vote_set(1, candidate_hash, &[1])],
+			));
+			assert_eq!(approval_rewards().len(), 2);
+			assert_eq!(
+				ApprovalRewards::rewarded_approvals(1, candidate_hash),
+				Some(bitvec![BitOrderLsb0, u8; 1, 1, 1]),
+			);
+		});
+	}
+
+	#[test]
+	fn rewards_are_capped_by_needed_approvals() {
+		new_test_ext(genesis_config(POINTS)).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session_with_needed_approvals(1, 2);
+			include_candidate(1, candidate_hash);
+
+			assert_noop!(
+				ApprovalRewards::note_approvals(
+					Origin::none(),
+					vec![approval_vote_set(1, candidate_hash, &[0, 1, 2])],
+				),
+				Error::<Test>::TooManyApprovals,
+			);
+
+			assert_ok!(ApprovalRewards::note_approvals(
+				Origin::none(),
+				vec![approval_vote_set(1, candidate_hash, &[2])],
+			));
+			next_block();
+
+			// the inherent only keeps as many votes as may still be rewarded.
+			let mut data = InherentData::new();
+			data.put_data(
+				APPROVAL_VOTES_INHERENT_IDENTIFIER,
+				&vec![approval_vote_set(1, candidate_hash, &[0, 1])],
+			)
+			.unwrap();
+			let approvals = match ApprovalRewards::create_inherent(&data) {
+				Some(Call::note_approvals { approvals }) => approvals,
+				_ => panic!("approval votes are expected to be provided"),
+			};
+			assert_eq!(approvals.len(), 1);
+			assert_eq!(approvals[0].votes.len(), 1);
+			assert_ok!(ApprovalRewards::note_approvals(Origin::none(), approvals));
+			assert_eq!(
+				ApprovalRewards::rewarded_approvals(1, candidate_hash),
+				Some(bitvec![BitOrderLsb0, u8; 1, 0, 1]),
+			);
+
+			next_block();
+			assert_eq!(ApprovalRewards::create_inherent(&data), None);
+			assert_noop!(
+				ApprovalRewards::note_approvals(
+					Origin::none(),
+					vec![approval_vote_set(1, candidate_hash, &[1])],
+				),
+				Error::<Test>::TooManyApprovals,
+			);
+		});
+	}
+
+	#[test]
+	fn only_one_inherent_per_block() {
+		new_test_ext(genesis_config(POINTS)).execute_with(|| {
+			new_session(1);
+
+			assert_ok!(ApprovalRewards::note_approvals(Origin::none(), Vec::new()));
+			assert_noop!(
+				ApprovalRewards::note_approvals(Origin::none(), Vec::new()),
+				Error::<Test>::TooManyApprovalInherents,
+			);
+
+			next_block();
+			assert_ok!(ApprovalRewards::note_approvals(Origin::none(), Vec::new()));
+		});
+	}
+
+	#[test]
+	fn invalid_approvals_are_rejected() {
+		new_test_ext(genesis_config(POINTS)).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			let unknown_hash = CandidateHash(sp_core::H256::repeat_byte(2));
+			new_session(1);
+			include_candidate(1, candidate_hash);
+
+			let reject = |approvals, error: Error<Test>| {
+				assert_noop!(ApprovalRewards::note_approvals(Origin::none(), approvals), error);
+			};
+
+			reject(
+				vec![approval_vote_set(1, unknown_hash, &[0])],
+				Error::<Test>::CandidateNotIncluded,
+			);
+			reject(
+				vec![approval_vote_set(0, candidate_hash, &[0])],
+				Error::<Test>::NotCurrentSession,
+			);
+			reject(
+				vec![approval_vote_set(1, candidate_hash, &[1, 0])],
+				Error::<Test>::ApprovalVotesUnsortedOrDuplicates,
+			);
+
+			let mut out_of_bounds = approval_vote_set(1, candidate_hash, &[0]);
+			out_of_bounds.votes[0].0 = ValidatorIndex(3);
+			reject(vec![out_of_bounds], Error::<Test>::ValidatorIndexOutOfBounds);
+
+			let mut bad_signature = approval_vote_set(1, candidate_hash, &[0, 1]);
+			bad_signature.votes[1].1 = bad_signature.votes[0].1.clone();
+			reject(vec![bad_signature], Error::<Test>::InvalidApprovalSignature);
+
+			assert!(approval_rewards().is_empty());
+		});
+	}
+
+	#[test]
+	fn inherent_filters_approvals() {
+		new_test_ext(genesis_config(POINTS)).execute_with(|| {
+			let candidate_a = CandidateHash(sp_core::H256::repeat_byte(1));
+			let candidate_b = CandidateHash(sp_core::H256::repeat_byte(2));
+			let unknown_hash = CandidateHash(sp_core::H256::repeat_byte(3));
+			new_session(1);
+			include_candidate(1, candidate_a);
+			include_candidate(1, candidate_b);
+
+			assert_ok!(ApprovalRewards::note_approvals(
+				Origin::none(),
+				vec![approval_vote_set(1, candidate_a, &[0])],
+			));
+			next_block();
+
+			let mut bad_signature = approval_vote_set(1, candidate_b, &[1, 2]);
+			bad_signature.votes[1].1 = bad_signature.votes[0].1.clone();
+			let mut unsorted = approval_vote_set(1, candidate_a, &[0, 2, 1]);
+			unsorted.votes.push(unsorted.votes[1].clone());
+
+			let mut data = InherentData::new();
+			data.put_data(
+				APPROVAL_VOTES_INHERENT_IDENTIFIER,
+				&vec![
+					bad_signature,
+					approval_vote_set(1, unknown_hash, &[0]),
+					unsorted,
+					approval_vote_set(0, candidate_b, &[0]),
+				],
+			)
+			.unwrap();
+
+			let approvals = match ApprovalRewards::create_inherent(&data) {
+				Some(Call::note_approvals { approvals }) => approvals,
+				_ => panic!("approval votes are expected to be provided"),
+			};
+			assert_eq!(
+				approvals
+					.iter()
+					.map(|set| (set.candidate_hash, set.votes.iter().map(|(i, _)| i.0).collect()))
+					.collect::<Vec<(_, Vec<_>)>>(),
+				vec![(candidate_a, vec![1, 2]), (candidate_b, vec![1])],
+			);
+			assert_ok!(ApprovalRewards::note_approvals(Origin::none(), approvals));
+		});
+	}
+
+	#[test]
+	fn no_inherent_without_reward_points() {
+		new_test_ext(genesis_config(0)).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1);
+			include_candidate(1, candidate_hash);
+
+			let mut data = InherentData::new();
+			data.put_data(
+				APPROVAL_VOTES_INHERENT_IDENTIFIER,
+				&vec![approval_vote_set(1, candidate_hash, &[0])],
+			)
+			.unwrap();
+
+			assert_eq!(ApprovalRewards::create_inherent(&data), None);
+		});
+	}
+
+	#[test]
+	fn rewarded_approvals_are_pruned_on_new_session() {
+		new_test_ext(genesis_config(POINTS)).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1);
+			include_candidate(1, candidate_hash);
+
+			assert_ok!(ApprovalRewards::note_approvals(
+				Origin::none(),
+				vec![approval_vote_set(1, candidate_hash, &[0])],
+			));
+			assert!(ApprovalRewards::rewarded_approvals(1, candidate_hash).is_some());
+
+			next_block();
+			assert!(ApprovalRewards::rewarded_approvals(1, candidate_hash).is_some());
+
+			new_session(2);
+			assert!(ApprovalRewards::rewarded_approvals(1, candidate_hash).is_none());
+		});
+	}
+}
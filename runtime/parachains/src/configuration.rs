@@ -238,6 +238,17 @@ pub struct HostConfiguration<BlockNumber> {
 	/// This value should be greater than [`chain_availability_period`] and
 	/// [`thread_availability_period`].
 	pub minimum_validation_upgrade_delay: BlockNumber,
+	/// The amount of era points credited to a validator for every approval vote on an included
+	/// candidate that is brought on chain.
+	///
+	/// 0 means approval checking is not rewarded.
+	pub approval_reward_points: u32,
+	/// This flag controls whether erasure chunks are assigned to validators by a shuffled mapping.
+	///
+	/// If the flag is false, validator `i` holds chunk `i` of every candidate. Otherwise the
+	/// assignment is rotated depending on the core and the relay parent of the candidate, so that
+	/// the validators holding the systematic chunks change between candidates.
+	pub availability_chunk_shuffling: bool,
 }
 
 impl<BlockNumber: Default + From<u32>> Default for HostConfiguration<BlockNumber> {
@@ -286,6 +297,8 @@ impl<BlockNumber: Default + From<u32>> Default for HostConfiguration<BlockNumber
 			pvf_checking_enabled: false,
 			pvf_voting_ttl: 2u32.into(),
 			minimum_validation_upgrade_delay: 2.into(),
+			approval_reward_points: 0,
+			availability_chunk_shuffling: false,
 		}
 	}
 }
@@ -1081,6 +1094,31 @@ pub mod pallet {
 			})
 		}
 
+		/// Set the amount of era points credited for an approval vote brought on chain.
+		#[pallet::weight((
+			T::WeightInfo::set_config_with_u32(),
+			DispatchClass::Operational,
+		))]
+		pub fn set_approval_reward_points(origin: OriginFor<T>, new: u32) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::schedule_config_update(|config| {
+				config.approval_reward_points = new;
+			})
+		}
+
+		/// Enable or disable the shuffled assignment of erasure chunks to validators.
+		#[pallet::weight((
+			// Using u32 here is a little bit of cheating, but that should be fine.
+			T::WeightInfo::set_config_with_u32(),
+			DispatchClass::Operational,
+		))]
+		pub fn set_availability_chunk_shuffling(origin: OriginFor<T>, new: bool) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::schedule_config_update(|config| {
+				config.availability_chunk_shuffling = new;
+			})
+		}
+
 		/// Setting this to true will disable consistency checks for the configuration setters.
 		/// Use with caution.
 		#[pallet::weight((
@@ -1618,6 +1656,8 @@ mod tests {
 				pvf_checking_enabled: true,
 				pvf_voting_ttl: 3,
 				minimum_validation_upgrade_delay: 20,
+				approval_reward_points: 5,
+				availability_chunk_shuffling: true,
 			};
 
 			assert!(<Configuration as Store>::PendingConfig::get(shared::SESSION_DELAY).is_none());
@@ -1797,6 +1837,16 @@ mod tests {
 			)
 			.unwrap();
 			Configuration::set_pvf_voting_ttl(Origin::root(), new_config.pvf_voting_ttl).unwrap();
+			Configuration::set_approval_reward_points(
+				Origin::root(),
+				new_config.approval_reward_points,
+			)
+			.unwrap();
+			Configuration::set_availability_chunk_shuffling(
+				Origin::root(),
+				new_config.availability_chunk_shuffling,
+			)
+			.unwrap();
 
 			assert_eq!(
 				<Configuration as Store>::PendingConfigs::get(),
@@ -17,15 +17,20 @@
 //! A module that is responsible for migration of storage.
 
 use crate::configuration::{self, Config, Pallet, Store};
-use frame_support::{pallet_prelude::*, traits::StorageVersion, weights::Weight};
+use frame_support::{
+	pallet_prelude::*, storage::unhashed, traits::StorageVersion, weights::Weight,
+};
 use frame_system::pallet_prelude::BlockNumberFor;
+use primitives::v1::SessionIndex;
 use sp_std::prelude::*;
 
 /// The current storage version.
 ///
 /// v0-v1: https://github.com/paritytech/polkadot/pull/3575
 /// v1-v2: https://github.com/paritytech/polkadot/pull/4420
-pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+/// v2-v3: adds `approval_reward_points`.
+/// v3-v4: adds `availability_chunk_shuffling`.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
 /// Migrates the pallet storage to the most recent version, checking and setting the `StorageVersion`.
 pub fn migrate_to_latest<T: Config>() -> Weight {
@@ -34,6 +39,14 @@ pub fn migrate_to_latest<T: Config>() -> Weight {
 		weight += migrate_to_v2::<T>();
 		StorageVersion::new(2).put::<Pallet<T>>();
 	}
+	if StorageVersion::get::<Pallet<T>>() == 2 {
+		weight += migrate_to_v3::<T>();
+		StorageVersion::new(3).put::<Pallet<T>>();
+	}
+	if StorageVersion::get::<Pallet<T>>() == 3 {
+		weight += migrate_to_v4::<T>();
+		StorageVersion::new(4).put::<Pallet<T>>();
+	}
 	weight
 }
 
@@ -138,6 +151,222 @@ pub mod v1 {
 	}
 }
 
+pub mod v2 {
+	use super::*;
+	use primitives::v1::{Balance, SessionIndex};
+
+	// Copied over from configuration.rs before `approval_reward_points` was added and removed
+	// all the comments.
+	#[derive(
+		parity_scale_codec::Encode, parity_scale_codec::Decode, scale_info::TypeInfo, Debug, Clone,
+	)]
+	pub struct HostConfiguration<BlockNumber> {
+		pub max_code_size: u32,
+		pub max_head_data_size: u32,
+		pub max_upward_queue_count: u32,
+		pub max_upward_queue_size: u32,
+		pub max_upward_message_size: u32,
+		pub max_upward_message_num_per_candidate: u32,
+		pub hrmp_max_message_num_per_candidate: u32,
+		pub validation_upgrade_cooldown: BlockNumber,
+		pub validation_upgrade_delay: BlockNumber,
+		pub max_pov_size: u32,
+		pub max_downward_message_size: u32,
+		pub ump_service_total_weight: Weight,
+		pub hrmp_max_parachain_outbound_channels: u32,
+		pub hrmp_max_parathread_outbound_channels: u32,
+		pub hrmp_sender_deposit: Balance,
+		pub hrmp_recipient_deposit: Balance,
+		pub hrmp_channel_max_capacity: u32,
+		pub hrmp_channel_max_total_size: u32,
+		pub hrmp_max_parachain_inbound_channels: u32,
+		pub hrmp_max_parathread_inbound_channels: u32,
+		pub hrmp_channel_max_message_size: u32,
+		pub code_retention_period: BlockNumber,
+		pub parathread_cores: u32,
+		pub parathread_retries: u32,
+		pub group_rotation_frequency: BlockNumber,
+		pub chain_availability_period: BlockNumber,
+		pub thread_availability_period: BlockNumber,
+		pub scheduling_lookahead: u32,
+		pub max_validators_per_core: Option<u32>,
+		pub max_validators: Option<u32>,
+		pub dispute_period: SessionIndex,
+		pub dispute_post_conclusion_acceptance_period: BlockNumber,
+		pub dispute_max_spam_slots: u32,
+		pub dispute_conclusion_by_time_out_period: BlockNumber,
+		pub no_show_slots: u32,
+		pub n_delay_tranches: u32,
+		pub zeroth_delay_tranche_width: u32,
+		pub needed_approvals: u32,
+		pub relay_vrf_modulo_samples: u32,
+		pub ump_max_individual_weight: Weight,
+		pub pvf_checking_enabled: bool,
+		pub pvf_voting_ttl: SessionIndex,
+		pub minimum_validation_upgrade_delay: BlockNumber,
+	}
+
+	impl<BlockNumber: Default + From<u32>> Default for HostConfiguration<BlockNumber> {
+		fn default() -> Self {
+			Self {
+				max_code_size: Default::default(),
+				max_head_data_size: Default::default(),
+				max_upward_queue_count: Default::default(),
+				max_upward_queue_size: Default::default(),
+				max_upward_message_size: Default::default(),
+				max_upward_message_num_per_candidate: Default::default(),
+				hrmp_max_message_num_per_candidate: Default::default(),
+				validation_upgrade_cooldown: Default::default(),
+				validation_upgrade_delay: 2u32.into(),
+				max_pov_size: Default::default(),
+				max_downward_message_size: Default::default(),
+				ump_service_total_weight: Default::default(),
+				hrmp_max_parachain_outbound_channels: Default::default(),
+				hrmp_max_parathread_outbound_channels: Default::default(),
+				hrmp_sender_deposit: Default::default(),
+				hrmp_recipient_deposit: Default::default(),
+				hrmp_channel_max_capacity: Default::default(),
+				hrmp_channel_max_total_size: Default::default(),
+				hrmp_max_parachain_inbound_channels: Default::default(),
+				hrmp_max_parathread_inbound_channels: Default::default(),
+				hrmp_channel_max_message_size: Default::default(),
+				code_retention_period: Default::default(),
+				parathread_cores: Default::default(),
+				parathread_retries: Default::default(),
+				group_rotation_frequency: 1u32.into(),
+				chain_availability_period: 1u32.into(),
+				thread_availability_period: 1u32.into(),
+				scheduling_lookahead: Default::default(),
+				max_validators_per_core: Default::default(),
+				max_validators: None,
+				dispute_period: 6,
+				dispute_post_conclusion_acceptance_period: 100.into(),
+				dispute_max_spam_slots: 2,
+				dispute_conclusion_by_time_out_period: 200.into(),
+				no_show_slots: 1u32.into(),
+				n_delay_tranches: Default::default(),
+				zeroth_delay_tranche_width: Default::default(),
+				needed_approvals: Default::default(),
+				relay_vrf_modulo_samples: Default::default(),
+				ump_max_individual_weight: 20 *
+					frame_support::weights::constants::WEIGHT_PER_MILLIS,
+				pvf_checking_enabled: false,
+				pvf_voting_ttl: 2u32.into(),
+				minimum_validation_upgrade_delay: 2.into(),
+			}
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+	use primitives::v1::{Balance, SessionIndex};
+
+	// Copied over from configuration.rs before `availability_chunk_shuffling` was added and
+	// removed all the comments.
+	#[derive(
+		parity_scale_codec::Encode, parity_scale_codec::Decode, scale_info::TypeInfo, Debug, Clone,
+	)]
+	pub struct HostConfiguration<BlockNumber> {
+		pub max_code_size: u32,
+		pub max_head_data_size: u32,
+		pub max_upward_queue_count: u32,
+		pub max_upward_queue_size: u32,
+		pub max_upward_message_size: u32,
+		pub max_upward_message_num_per_candidate: u32,
+		pub hrmp_max_message_num_per_candidate: u32,
+		pub validation_upgrade_cooldown: BlockNumber,
+		pub validation_upgrade_delay: BlockNumber,
+		pub max_pov_size: u32,
+		pub max_downward_message_size: u32,
+		pub ump_service_total_weight: Weight,
+		pub hrmp_max_parachain_outbound_channels: u32,
+		pub hrmp_max_parathread_outbound_channels: u32,
+		pub hrmp_sender_deposit: Balance,
+		pub hrmp_recipient_deposit: Balance,
+		pub hrmp_channel_max_capacity: u32,
+		pub hrmp_channel_max_total_size: u32,
+		pub hrmp_max_parachain_inbound_channels: u32,
+		pub hrmp_max_parathread_inbound_channels: u32,
+		pub hrmp_channel_max_message_size: u32,
+		pub code_retention_period: BlockNumber,
+		pub parathread_cores: u32,
+		pub parathread_retries: u32,
+		pub group_rotation_frequency: BlockNumber,
+		pub chain_availability_period: BlockNumber,
+		pub thread_availability_period: BlockNumber,
+		pub scheduling_lookahead: u32,
+		pub max_validators_per_core: Option<u32>,
+		pub max_validators: Option<u32>,
+		pub dispute_period: SessionIndex,
+		pub dispute_post_conclusion_acceptance_period: BlockNumber,
+		pub dispute_max_spam_slots: u32,
+		pub dispute_conclusion_by_time_out_period: BlockNumber,
+		pub no_show_slots: u32,
+		pub n_delay_tranches: u32,
+		pub zeroth_delay_tranche_width: u32,
+		pub needed_approvals: u32,
+		pub relay_vrf_modulo_samples: u32,
+		pub ump_max_individual_weight: Weight,
+		pub pvf_checking_enabled: bool,
+		pub pvf_voting_ttl: SessionIndex,
+		pub minimum_validation_upgrade_delay: BlockNumber,
+		pub approval_reward_points: u32,
+	}
+
+	impl<BlockNumber: Default + From<u32>> Default for HostConfiguration<BlockNumber> {
+		fn default() -> Self {
+			Self {
+				max_code_size: Default::default(),
+				max_head_data_size: Default::default(),
+				max_upward_queue_count: Default::default(),
+				max_upward_queue_size: Default::default(),
+				max_upward_message_size: Default::default(),
+				max_upward_message_num_per_candidate: Default::default(),
+				hrmp_max_message_num_per_candidate: Default::default(),
+				validation_upgrade_cooldown: Default::default(),
+				validation_upgrade_delay: 2u32.into(),
+				max_pov_size: Default::default(),
+				max_downward_message_size: Default::default(),
+				ump_service_total_weight: Default::default(),
+				hrmp_max_parachain_outbound_channels: Default::default(),
+				hrmp_max_parathread_outbound_channels: Default::default(),
+				hrmp_sender_deposit: Default::default(),
+				hrmp_recipient_deposit: Default::default(),
+				hrmp_channel_max_capacity: Default::default(),
+				hrmp_channel_max_total_size: Default::default(),
+				hrmp_max_parachain_inbound_channels: Default::default(),
+				hrmp_max_parathread_inbound_channels: Default::default(),
+				hrmp_channel_max_message_size: Default::default(),
+				code_retention_period: Default::default(),
+				parathread_cores: Default::default(),
+				parathread_retries: Default::default(),
+				group_rotation_frequency: 1u32.into(),
+				chain_availability_period: 1u32.into(),
+				thread_availability_period: 1u32.into(),
+				scheduling_lookahead: Default::default(),
+				max_validators_per_core: Default::default(),
+				max_validators: None,
+				dispute_period: 6,
+				dispute_post_conclusion_acceptance_period: 100.into(),
+				dispute_max_spam_slots: 2,
+				dispute_conclusion_by_time_out_period: 200.into(),
+				no_show_slots: 1u32.into(),
+				n_delay_tranches: Default::default(),
+				zeroth_delay_tranche_width: Default::default(),
+				needed_approvals: Default::default(),
+				relay_vrf_modulo_samples: Default::default(),
+				ump_max_individual_weight: 20 *
+					frame_support::weights::constants::WEIGHT_PER_MILLIS,
+				pvf_checking_enabled: false,
+				pvf_voting_ttl: 2u32.into(),
+				minimum_validation_upgrade_delay: 2.into(),
+				approval_reward_points: 0,
+			}
+		}
+	}
+}
+
 pub fn migrate_to_v2<T: Config>() -> Weight {
 	// Unusual formatting is justified:
 	// - make it easier to verify that fields assign what they supposed to assign.
@@ -145,9 +374,9 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 	// - this code is important enough to optimize for legibility sacrificing consistency.
 	#[rustfmt::skip]
 	let translate =
-		|pre: v1::HostConfiguration<BlockNumberFor<T>>| -> configuration::HostConfiguration<BlockNumberFor<T>>
+		|pre: v1::HostConfiguration<BlockNumberFor<T>>| -> v2::HostConfiguration<BlockNumberFor<T>>
 	{
-		super::HostConfiguration {
+		v2::HostConfiguration {
 
 max_code_size                            : pre.max_code_size,
 max_head_data_size                       : pre.max_head_data_size,
@@ -200,17 +429,22 @@ minimum_validation_upgrade_delay: pre.chain_availability_period + 10u32.into(),
 
 	// First, ActiveConfig
 
+	// The translated configuration is not of the current type anymore, so it is written raw.
 	weight += T::DbWeight::get().reads_writes(1, 1);
-	if let Err(_) = <Pallet<T> as Store>::ActiveConfig::translate(|pre| pre.map(translate)) {
-		// `Err` is returned when the pre-migration type cannot be deserialized. This
-		// cannot happen if the migration runs correctly, i.e. against the expected version.
-		//
-		// This happening almost surely will lead to a panic somewhere else. Corruption seems
-		// to be unlikely to be caused by this. So we just log. Maybe it'll work out still?
-		log::error!(
-			target: configuration::LOG_TARGET,
-			"unexpected error when performing translation of the configuration type during storage upgrade to v2."
-		);
+	let active_config_key = <Pallet<T> as Store>::ActiveConfig::hashed_key();
+	if let Some(raw) = unhashed::get_raw(&active_config_key) {
+		match v1::HostConfiguration::<BlockNumberFor<T>>::decode(&mut &raw[..]) {
+			Ok(pre) => unhashed::put(&active_config_key, &translate(pre)),
+			// An error is returned when the pre-migration type cannot be deserialized. This
+			// cannot happen if the migration runs correctly, i.e. against the expected version.
+			//
+			// This happening almost surely will lead to a panic somewhere else. Corruption seems
+			// to be unlikely to be caused by this. So we just log. Maybe it'll work out still?
+			Err(_) => log::error!(
+				target: configuration::LOG_TARGET,
+				"unexpected error when performing translation of the configuration type during storage upgrade to v2."
+			),
+		}
 	}
 
 	// Second, PendingConfig -> PendingConfigs
@@ -228,7 +462,189 @@ minimum_validation_upgrade_delay: pre.chain_availability_period + 10u32.into(),
 	}
 
 	weight += T::DbWeight::get().writes(1);
-	<Pallet<T> as Store>::PendingConfigs::put(&pending_configs);
+	unhashed::put(&<Pallet<T> as Store>::PendingConfigs::hashed_key(), &pending_configs);
+
+	weight
+}
+
+pub fn migrate_to_v3<T: Config>() -> Weight {
+	// The same motivation as for the formatting in `migrate_to_v2`.
+	#[rustfmt::skip]
+	let translate =
+		|pre: v2::HostConfiguration<BlockNumberFor<T>>| -> v3::HostConfiguration<BlockNumberFor<T>>
+	{
+		v3::HostConfiguration {
+
+max_code_size                            : pre.max_code_size,
+max_head_data_size                       : pre.max_head_data_size,
+max_upward_queue_count                   : pre.max_upward_queue_count,
+max_upward_queue_size                    : pre.max_upward_queue_size,
+max_upward_message_size                  : pre.max_upward_message_size,
+max_upward_message_num_per_candidate     : pre.max_upward_message_num_per_candidate,
+hrmp_max_message_num_per_candidate       : pre.hrmp_max_message_num_per_candidate,
+validation_upgrade_cooldown              : pre.validation_upgrade_cooldown,
+validation_upgrade_delay                 : pre.validation_upgrade_delay,
+max_pov_size                             : pre.max_pov_size,
+max_downward_message_size                : pre.max_downward_message_size,
+ump_service_total_weight                 : pre.ump_service_total_weight,
+hrmp_max_parachain_outbound_channels     : pre.hrmp_max_parachain_outbound_channels,
+hrmp_max_parathread_outbound_channels    : pre.hrmp_max_parathread_outbound_channels,
+hrmp_sender_deposit                      : pre.hrmp_sender_deposit,
+hrmp_recipient_deposit                   : pre.hrmp_recipient_deposit,
+hrmp_channel_max_capacity                : pre.hrmp_channel_max_capacity,
+hrmp_channel_max_total_size              : pre.hrmp_channel_max_total_size,
+hrmp_max_parachain_inbound_channels      : pre.hrmp_max_parachain_inbound_channels,
+hrmp_max_parathread_inbound_channels     : pre.hrmp_max_parathread_inbound_channels,
+hrmp_channel_max_message_size            : pre.hrmp_channel_max_message_size,
+code_retention_period                    : pre.code_retention_period,
+parathread_cores                         : pre.parathread_cores,
+parathread_retries                       : pre.parathread_retries,
+group_rotation_frequency                 : pre.group_rotation_frequency,
+chain_availability_period                : pre.chain_availability_period,
+thread_availability_period               : pre.thread_availability_period,
+scheduling_lookahead                     : pre.scheduling_lookahead,
+max_validators_per_core                  : pre.max_validators_per_core,
+max_validators                           : pre.max_validators,
+dispute_period                           : pre.dispute_period,
+dispute_post_conclusion_acceptance_period: pre.dispute_post_conclusion_acceptance_period,
+dispute_max_spam_slots                   : pre.dispute_max_spam_slots,
+dispute_conclusion_by_time_out_period    : pre.dispute_conclusion_by_time_out_period,
+no_show_slots                            : pre.no_show_slots,
+n_delay_tranches                         : pre.n_delay_tranches,
+zeroth_delay_tranche_width               : pre.zeroth_delay_tranche_width,
+needed_approvals                         : pre.needed_approvals,
+relay_vrf_modulo_samples                 : pre.relay_vrf_modulo_samples,
+ump_max_individual_weight                : pre.ump_max_individual_weight,
+pvf_checking_enabled                     : pre.pvf_checking_enabled,
+pvf_voting_ttl                           : pre.pvf_voting_ttl,
+minimum_validation_upgrade_delay         : pre.minimum_validation_upgrade_delay,
+
+approval_reward_points: 0,
+		}
+	};
+
+	let mut weight = 0;
+
+	// The translated configurations are not of the current type anymore, so they are written raw.
+	weight += T::DbWeight::get().reads_writes(1, 1);
+	let active_config_key = <Pallet<T> as Store>::ActiveConfig::hashed_key();
+	if let Some(raw) = unhashed::get_raw(&active_config_key) {
+		match v2::HostConfiguration::<BlockNumberFor<T>>::decode(&mut &raw[..]) {
+			Ok(pre) => unhashed::put(&active_config_key, &translate(pre)),
+			// See the comment in `migrate_to_v2` for why we only log here.
+			Err(_) => log::error!(
+				target: configuration::LOG_TARGET,
+				"unexpected error when performing translation of the configuration type during storage upgrade to v3."
+			),
+		}
+	}
+
+	weight += T::DbWeight::get().reads_writes(1, 1);
+	let pending_configs_key = <Pallet<T> as Store>::PendingConfigs::hashed_key();
+	if let Some(raw) = unhashed::get_raw(&pending_configs_key) {
+		match Vec::<(SessionIndex, v2::HostConfiguration<BlockNumberFor<T>>)>::decode(&mut &raw[..])
+		{
+			Ok(pending) => {
+				let pending: Vec<_> = pending
+					.into_iter()
+					.map(|(session, config)| (session, translate(config)))
+					.collect();
+				unhashed::put(&pending_configs_key, &pending);
+			},
+			Err(_) => log::error!(
+				target: configuration::LOG_TARGET,
+				"unexpected error when performing translation of the pending configurations during storage upgrade to v3."
+			),
+		}
+	}
+
+	weight
+}
+
+pub fn migrate_to_v4<T: Config>() -> Weight {
+	// The same motivation as for the formatting in `migrate_to_v2`.
+	#[rustfmt::skip]
+	let translate =
+		|pre: v3::HostConfiguration<BlockNumberFor<T>>| -> configuration::HostConfiguration<BlockNumberFor<T>>
+	{
+		super::HostConfiguration {
+
+max_code_size                            : pre.max_code_size,
+max_head_data_size                       : pre.max_head_data_size,
+max_upward_queue_count                   : pre.max_upward_queue_count,
+max_upward_queue_size                    : pre.max_upward_queue_size,
+max_upward_message_size                  : pre.max_upward_message_size,
+max_upward_message_num_per_candidate     : pre.max_upward_message_num_per_candidate,
+hrmp_max_message_num_per_candidate       : pre.hrmp_max_message_num_per_candidate,
+validation_upgrade_cooldown              : pre.validation_upgrade_cooldown,
+validation_upgrade_delay                 : pre.validation_upgrade_delay,
+max_pov_size                             : pre.max_pov_size,
+max_downward_message_size                : pre.max_downward_message_size,
+ump_service_total_weight                 : pre.ump_service_total_weight,
+hrmp_max_parachain_outbound_channels     : pre.hrmp_max_parachain_outbound_channels,
+hrmp_max_parathread_outbound_channels    : pre.hrmp_max_parathread_outbound_channels,
+hrmp_sender_deposit                      : pre.hrmp_sender_deposit,
+hrmp_recipient_deposit                   : pre.hrmp_recipient_deposit,
+hrmp_channel_max_capacity                : pre.hrmp_channel_max_capacity,
+hrmp_channel_max_total_size              : pre.hrmp_channel_max_total_size,
+hrmp_max_parachain_inbound_channels      : pre.hrmp_max_parachain_inbound_channels,
+hrmp_max_parathread_inbound_channels     : pre.hrmp_max_parathread_inbound_channels,
+hrmp_channel_max_message_size            : pre.hrmp_channel_max_message_size,
+code_retention_period                    : pre.code_retention_period,
+parathread_cores                         : pre.parathread_cores,
+parathread_retries                       : pre.parathread_retries,
+group_rotation_frequency                 : pre.group_rotation_frequency,
+chain_availability_period                : pre.chain_availability_period,
+thread_availability_period               : pre.thread_availability_period,
+scheduling_lookahead                     : pre.scheduling_lookahead,
+max_validators_per_core                  : pre.max_validators_per_core,
+max_validators                           : pre.max_validators,
+dispute_period                           : pre.dispute_period,
+dispute_post_conclusion_acceptance_period: pre.dispute_post_conclusion_acceptance_period,
+dispute_max_spam_slots                   : pre.dispute_max_spam_slots,
+dispute_conclusion_by_time_out_period    : pre.dispute_conclusion_by_time_out_period,
+no_show_slots                            : pre.no_show_slots,
+n_delay_tranches                         : pre.n_delay_tranches,
+zeroth_delay_tranche_width               : pre.zeroth_delay_tranche_width,
+needed_approvals                         : pre.needed_approvals,
+relay_vrf_modulo_samples                 : pre.relay_vrf_modulo_samples,
+ump_max_individual_weight                : pre.ump_max_individual_weight,
+pvf_checking_enabled                     : pre.pvf_checking_enabled,
+pvf_voting_ttl                           : pre.pvf_voting_ttl,
+minimum_validation_upgrade_delay         : pre.minimum_validation_upgrade_delay,
+approval_reward_points                   : pre.approval_reward_points,
+
+availability_chunk_shuffling: false,
+		}
+	};
+
+	let mut weight = 0;
+
+	weight += T::DbWeight::get().reads_writes(1, 1);
+	if let Err(_) = <Pallet<T> as Store>::ActiveConfig::translate(|pre| pre.map(translate)) {
+		// See the comment in `migrate_to_v2` for why we only log here.
+		log::error!(
+			target: configuration::LOG_TARGET,
+			"unexpected error when performing translation of the configuration type during storage upgrade to v4."
+		);
+	}
+
+	weight += T::DbWeight::get().reads_writes(1, 1);
+	if let Err(_) = <Pallet<T> as Store>::PendingConfigs::translate(
+		|pre: Option<Vec<(SessionIndex, v3::HostConfiguration<BlockNumberFor<T>>)>>| {
+			pre.map(|pending| {
+				pending
+					.into_iter()
+					.map(|(session, config)| (session, translate(config)))
+					.collect()
+			})
+		},
+	) {
+		log::error!(
+			target: configuration::LOG_TARGET,
+			"unexpected error when performing translation of the pending configurations during storage upgrade to v4."
+		);
+	}
 
 	weight
 }
@@ -321,10 +737,16 @@ mod tests {
 
 			migrate_to_v2::<Test>();
 
-			let v2 = configuration::ActiveConfig::<Test>::get();
+			let v2 = unhashed::get::<v2::HostConfiguration<primitives::v1::BlockNumber>>(
+				&configuration::ActiveConfig::<Test>::hashed_key(),
+			)
+			.unwrap();
 
 			assert_correct_translation(v1, v2);
-			let pending_configs_v2 = configuration::PendingConfigs::<Test>::get();
+			let pending_configs_v2 = unhashed::get::<
+				Vec<(SessionIndex, v2::HostConfiguration<primitives::v1::BlockNumber>)>,
+			>(&configuration::PendingConfigs::<Test>::hashed_key())
+			.unwrap();
 			assert_eq!(pending_configs_v1.len(), pending_configs_v2.len());
 			for ((session_index_v1, pending_config_v1), (session_index_v2, pending_configs_v2)) in
 				pending_configs_v1.into_iter().zip(pending_configs_v2.into_iter())
@@ -338,7 +760,7 @@ mod tests {
 		#[rustfmt::skip]
 		fn assert_correct_translation(
 			v1: v1::HostConfiguration<primitives::v1::BlockNumber>, 
-			v2: configuration::HostConfiguration<primitives::v1::BlockNumber>
+			v2: v2::HostConfiguration<primitives::v1::BlockNumber>
 		) {
 			assert_eq!(v1.max_code_size                            , v2.max_code_size);
 			assert_eq!(v1.max_head_data_size                       , v2.max_head_data_size);
@@ -386,4 +808,222 @@ mod tests {
 			assert_eq!(v2.minimum_validation_upgrade_delay, 43);
 		}
 	}
+
+	#[test]
+	fn test_migrate_to_v3() {
+		// This migration only adds `approval_reward_points`. Same as for `test_migrate_to_v2`, we
+		// pick a couple of fields to check and leave the rest to the `Default` implementation.
+		let v2 = v2::HostConfiguration::<primitives::v1::BlockNumber> {
+			ump_max_individual_weight: 0x71616e6f6e0au64,
+			needed_approvals: 69,
+			hrmp_recipient_deposit: 1337,
+			pvf_checking_enabled: true,
+			minimum_validation_upgrade_delay: 20,
+			..Default::default()
+		};
+		let pending_configs_v2 = vec![
+			(
+				1,
+				v2::HostConfiguration::<primitives::v1::BlockNumber> {
+					n_delay_tranches: 150,
+					..v2.clone()
+				},
+			),
+			(
+				2,
+				v2::HostConfiguration::<primitives::v1::BlockNumber> {
+					max_validators_per_core: Some(33),
+					..v2.clone()
+				},
+			),
+		];
+
+		new_test_ext(Default::default()).execute_with(|| {
+			// Implant the v2 data in the state.
+			unhashed::put(&configuration::ActiveConfig::<Test>::hashed_key(), &v2);
+			unhashed::put(
+				&configuration::PendingConfigs::<Test>::hashed_key(),
+				&pending_configs_v2,
+			);
+
+			migrate_to_v3::<Test>();
+
+			let v3 = unhashed::get::<v3::HostConfiguration<primitives::v1::BlockNumber>>(
+				&configuration::ActiveConfig::<Test>::hashed_key(),
+			)
+			.unwrap();
+
+			assert_correct_translation(v2, v3);
+			let pending_configs_v3 = unhashed::get::<
+				Vec<(SessionIndex, v3::HostConfiguration<primitives::v1::BlockNumber>)>,
+			>(&configuration::PendingConfigs::<Test>::hashed_key())
+			.unwrap();
+			assert_eq!(pending_configs_v2.len(), pending_configs_v3.len());
+			for ((session_index_v2, pending_config_v2), (session_index_v3, pending_config_v3)) in
+				pending_configs_v2.into_iter().zip(pending_configs_v3.into_iter())
+			{
+				assert_eq!(session_index_v2, session_index_v3);
+				assert_correct_translation(pending_config_v2, pending_config_v3);
+			}
+		});
+
+		// The same motivation as for the migration code. See `migrate_to_v2`.
+		#[rustfmt::skip]
+		fn assert_correct_translation(
+			v2: v2::HostConfiguration<primitives::v1::BlockNumber>,
+			v3: v3::HostConfiguration<primitives::v1::BlockNumber>
+		) {
+			assert_eq!(v2.max_code_size                            , v3.max_code_size);
+			assert_eq!(v2.max_head_data_size                       , v3.max_head_data_size);
+			assert_eq!(v2.max_upward_queue_count                   , v3.max_upward_queue_count);
+			assert_eq!(v2.max_upward_queue_size                    , v3.max_upward_queue_size);
+			assert_eq!(v2.max_upward_message_size                  , v3.max_upward_message_size);
+			assert_eq!(v2.max_upward_message_num_per_candidate     , v3.max_upward_message_num_per_candidate);
+			assert_eq!(v2.hrmp_max_message_num_per_candidate       , v3.hrmp_max_message_num_per_candidate);
+			assert_eq!(v2.validation_upgrade_cooldown              , v3.validation_upgrade_cooldown);
+			assert_eq!(v2.validation_upgrade_delay                 , v3.validation_upgrade_delay);
+			assert_eq!(v2.max_pov_size                             , v3.max_pov_size);
+			assert_eq!(v2.max_downward_message_size                , v3.max_downward_message_size);
+			assert_eq!(v2.ump_service_total_weight                 , v3.ump_service_total_weight);
+			assert_eq!(v2.hrmp_max_parachain_outbound_channels     , v3.hrmp_max_parachain_outbound_channels);
+			assert_eq!(v2.hrmp_max_parathread_outbound_channels    , v3.hrmp_max_parathread_outbound_channels);
+			assert_eq!(v2.hrmp_sender_deposit                      , v3.hrmp_sender_deposit);
+			assert_eq!(v2.hrmp_recipient_deposit                   , v3.hrmp_recipient_deposit);
+			assert_eq!(v2.hrmp_channel_max_capacity                , v3.hrmp_channel_max_capacity);
+			assert_eq!(v2.hrmp_channel_max_total_size              , v3.hrmp_channel_max_total_size);
+			assert_eq!(v2.hrmp_max_parachain_inbound_channels      , v3.hrmp_max_parachain_inbound_channels);
+			assert_eq!(v2.hrmp_max_parathread_inbound_channels     , v3.hrmp_max_parathread_inbound_channels);
+			assert_eq!(v2.hrmp_channel_max_message_size            , v3.hrmp_channel_max_message_size);
+			assert_eq!(v2.code_retention_period                    , v3.code_retention_period);
+			assert_eq!(v2.parathread_cores                         , v3.parathread_cores);
+			assert_eq!(v2.parathread_retries                       , v3.parathread_retries);
+			assert_eq!(v2.group_rotation_frequency                 , v3.group_rotation_frequency);
+			assert_eq!(v2.chain_availability_period                , v3.chain_availability_period);
+			assert_eq!(v2.thread_availability_period               , v3.thread_availability_period);
+			assert_eq!(v2.scheduling_lookahead                     , v3.scheduling_lookahead);
+			assert_eq!(v2.max_validators_per_core                  , v3.max_validators_per_core);
+			assert_eq!(v2.max_validators                           , v3.max_validators);
+			assert_eq!(v2.dispute_period                           , v3.dispute_period);
+			assert_eq!(v2.dispute_post_conclusion_acceptance_period, v3.dispute_post_conclusion_acceptance_period);
+			assert_eq!(v2.dispute_max_spam_slots                   , v3.dispute_max_spam_slots);
+			assert_eq!(v2.dispute_conclusion_by_time_out_period    , v3.dispute_conclusion_by_time_out_period);
+			assert_eq!(v2.no_show_slots                            , v3.no_show_slots);
+			assert_eq!(v2.n_delay_tranches                         , v3.n_delay_tranches);
+			assert_eq!(v2.zeroth_delay_tranche_width               , v3.zeroth_delay_tranche_width);
+			assert_eq!(v2.needed_approvals                         , v3.needed_approvals);
+			assert_eq!(v2.relay_vrf_modulo_samples                 , v3.relay_vrf_modulo_samples);
+			assert_eq!(v2.ump_max_individual_weight                , v3.ump_max_individual_weight);
+			assert_eq!(v2.pvf_checking_enabled                     , v3.pvf_checking_enabled);
+			assert_eq!(v2.pvf_voting_ttl                           , v3.pvf_voting_ttl);
+			assert_eq!(v2.minimum_validation_upgrade_delay         , v3.minimum_validation_upgrade_delay);
+
+			assert_eq!(v3.approval_reward_points, 0);
+		}
+	}
+
+	#[test]
+	fn test_migrate_to_v4() {
+		// This migration only adds `availability_chunk_shuffling`. Same as for `test_migrate_to_v2`,
+		// we pick a couple of fields to check and leave the rest to the `Default` implementation.
+		let v3 = v3::HostConfiguration::<primitives::v1::BlockNumber> {
+			ump_max_individual_weight: 0x71616e6f6e0au64,
+			needed_approvals: 69,
+			hrmp_recipient_deposit: 1337,
+			pvf_checking_enabled: true,
+			minimum_validation_upgrade_delay: 20,
+			approval_reward_points: 5,
+			..Default::default()
+		};
+		let pending_configs_v3 = vec![
+			(
+				1,
+				v3::HostConfiguration::<primitives::v1::BlockNumber> {
+					n_delay_tranches: 150,
+					..v3.clone()
+				},
+			),
+			(
+				2,
+				v3::HostConfiguration::<primitives::v1::BlockNumber> {
+					max_validators_per_core: Some(33),
+					..v3.clone()
+				},
+			),
+		];
+
+		new_test_ext(Default::default()).execute_with(|| {
+			// Implant the v3 data in the state.
+			unhashed::put(&configuration::ActiveConfig::<Test>::hashed_key(), &v3);
+			unhashed::put(
+				&configuration::PendingConfigs::<Test>::hashed_key(),
+				&pending_configs_v3,
+			);
+
+			migrate_to_v4::<Test>();
+
+			assert_correct_translation(v3, configuration::ActiveConfig::<Test>::get());
+			let pending_configs_v4 = configuration::PendingConfigs::<Test>::get();
+			assert_eq!(pending_configs_v3.len(), pending_configs_v4.len());
+			for ((session_index_v3, pending_config_v3), (session_index_v4, pending_config_v4)) in
+				pending_configs_v3.into_iter().zip(pending_configs_v4.into_iter())
+			{
+				assert_eq!(session_index_v3, session_index_v4);
+				assert_correct_translation(pending_config_v3, pending_config_v4);
+			}
+		});
+
+		// The same motivation as for the migration code. See `migrate_to_v2`.
+		#[rustfmt::skip]
+		fn assert_correct_translation(
+			v3: v3::HostConfiguration<primitives::v1::BlockNumber>,
+			v4: configuration::HostConfiguration<primitives::v1::BlockNumber>
+		) {
+			assert_eq!(v3.max_code_size                            , v4.max_code_size);
+			assert_eq!(v3.max_head_data_size                       , v4.max_head_data_size);
+			assert_eq!(v3.max_upward_queue_count                   , v4.max_upward_queue_count);
+			assert_eq!(v3.max_upward_queue_size                    , v4.max_upward_queue_size);
+			assert_eq!(v3.max_upward_message_size                  , v4.max_upward_message_size);
+			assert_eq!(v3.max_upward_message_num_per_candidate     , v4.max_upward_message_num_per_candidate);
+			assert_eq!(v3.hrmp_max_message_num_per_candidate       , v4.hrmp_max_message_num_per_candidate);
+			assert_eq!(v3.validation_upgrade_cooldown              , v4.validation_upgrade_cooldown);
+			assert_eq!(v3.validation_upgrade_delay                 , v4.validation_upgrade_delay);
+			assert_eq!(v3.max_pov_size                             , v4.max_pov_size);
+			assert_eq!(v3.max_downward_message_size                , v4.max_downward_message_size);
+			assert_eq!(v3.ump_service_total_weight                 , v4.ump_service_total_weight);
+			assert_eq!(v3.hrmp_max_parachain_outbound_channels     , v4.hrmp_max_parachain_outbound_channels);
+			assert_eq!(v3.hrmp_max_parathread_outbound_channels    , v4.hrmp_max_parathread_outbound_channels);
+			assert_eq!(v3.hrmp_sender_deposit                      , v4.hrmp_sender_deposit);
+			assert_eq!(v3.hrmp_recipient_deposit                   , v4.hrmp_recipient_deposit);
+			assert_eq!(v3.hrmp_channel_max_capacity                , v4.hrmp_channel_max_capacity);
+			assert_eq!(v3.hrmp_channel_max_total_size              , v4.hrmp_channel_max_total_size);
+			assert_eq!(v3.hrmp_max_parachain_inbound_channels      , v4.hrmp_max_parachain_inbound_channels);
+			assert_eq!(v3.hrmp_max_parathread_inbound_channels     , v4.hrmp_max_parathread_inbound_channels);
+			assert_eq!(v3.hrmp_channel_max_message_size            , v4.hrmp_channel_max_message_size);
+			assert_eq!(v3.code_retention_period                    , v4.code_retention_period);
+			assert_eq!(v3.parathread_cores                         , v4.parathread_cores);
+			assert_eq!(v3.parathread_retries                       , v4.parathread_retries);
+			assert_eq!(v3.group_rotation_frequency                 , v4.group_rotation_frequency);
+			assert_eq!(v3.chain_availability_period                , v4.chain_availability_period);
+			assert_eq!(v3.thread_availability_period               , v4.thread_availability_period);
+			assert_eq!(v3.scheduling_lookahead                     , v4.scheduling_lookahead);
+			assert_eq!(v3.max_validators_per_core                  , v4.max_validators_per_core);
+			assert_eq!(v3.max_validators                           , v4.max_validators);
+			assert_eq!(v3.dispute_period                           , v4.dispute_period);
+			assert_eq!(v3.dispute_post_conclusion_acceptance_period, v4.dispute_post_conclusion_acceptance_period);
+			assert_eq!(v3.dispute_max_spam_slots                   , v4.dispute_max_spam_slots);
+			assert_eq!(v3.dispute_conclusion_by_time_out_period    , v4.dispute_conclusion_by_time_out_period);
+			assert_eq!(v3.no_show_slots                            , v4.no_show_slots);
+			assert_eq!(v3.n_delay_tranches                         , v4.n_delay_tranches);
+			assert_eq!(v3.zeroth_delay_tranche_width               , v4.zeroth_delay_tranche_width);
+			assert_eq!(v3.needed_approvals                         , v4.needed_approvals);
+			assert_eq!(v3.relay_vrf_modulo_samples                 , v4.relay_vrf_modulo_samples);
+			assert_eq!(v3.ump_max_individual_weight                , v4.ump_max_individual_weight);
+			assert_eq!(v3.pvf_checking_enabled                     , v4.pvf_checking_enabled);
+			assert_eq!(v3.pvf_voting_ttl                           , v4.pvf_voting_ttl);
+			assert_eq!(v3.minimum_validation_upgrade_delay         , v4.minimum_validation_upgrade_delay);
+			assert_eq!(v3.approval_reward_points                   , v4.approval_reward_points);
+
+			assert_eq!(v4.availability_chunk_shuffling, false);
+		}
+	}
 }
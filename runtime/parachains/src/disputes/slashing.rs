@@ -0,0 +1,885 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dispute slashing: an implementation of [`PunishValidators`] reporting the losing side of
+//! disputes as offences, e.g. to `pallet-offences` and from there to `pallet-staking`.
+//!
+//! Dispute statements only identify validators by their [`ValidatorIndex`] within the session
+//! of the dispute, which may well be over by the time the dispute concludes. This pallet therefore
+//! records the validator set of every session for as long as `session_info` keeps the session
+//! around, and resolves offenders against it. Their identification, e.g. their exposure, is
+//! resolved as of the session of the dispute by [`Config::HistoricalIdentification`], so that
+//! validators that have since left the active set can still be slashed.
+//!
+//! Slashes are not reported right away, but kept as unapplied slashes for
+//! [`Config::SlashDeferDuration`] blocks, during which [`Config::CancelOrigin`] may cancel them.
+//! No offence is reported for a side of disputes whose slash fraction is zero.
+//!
+//! Due slashes are applied at the start of a block, for at most [`Config::MaxOffendersPerBlock`]
+//! offenders. The slashes of a dispute are always applied at once, while those of further due
+//! disputes are postponed to the next block.
+
+use crate::{disputes::PunishValidators, session_info};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{EnsureOrigin, ValidatorSet},
+};
+use frame_system::pallet_prelude::*;
+use primitives::v1::{CandidateHash, SessionIndex, ValidatorIndex};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Convert, One, Saturating, Zero},
+	Perbill,
+};
+use sp_staking::offence::{Kind, Offence, ReportOffence};
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub use pallet::*;
+
+const LOG_TARGET: &str = "runtime::disputes::slashing";
+
+/// The validator ID type of the validator set of a runtime.
+pub type ValidatorIdOf<T> = <<T as Config>::ValidatorSet as ValidatorSet<
+	<T as frame_system::Config>::AccountId,
+>>::ValidatorId;
+
+/// The identification type of validators of a runtime.
+pub type IdentificationOf<T> = <<T as Config>::HistoricalIdentification as HistoricalIdentification<
+	ValidatorIdOf<T>,
+>>::Identification;
+
+/// A validator ID along with its full identification, as reported to the offences handler.
+pub type IdentificationTuple<T> = (ValidatorIdOf<T>, IdentificationOf<T>);
+
+/// The offence reported for validators on a given side of a dispute.
+pub type DisputeOffenceOf<T, SlashFraction> = DisputeOffence<IdentificationTuple<T>, SlashFraction>;
+
+/// Weight functions needed for the dispute slashing pallet.
+pub trait WeightInfo {
+	/// The weight of identifying and reporting `o` offenders of a dispute.
+	fn apply_slashes(o: u32) -> Weight;
+}
+
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn apply_slashes(_o: u32) -> Weight {
+		0
+	}
+}
+
+/// Placeholder weights for runtimes which identify offenders without accessing storage, e.g. by
+/// [`CurrentIdentification`] of `()`, and report them to `pallet-offences` only.
+///
+/// These weights were not produced by benchmarks, but are hand-written from the storage accesses
+/// of `pallet_offences::report_offence`.
+pub struct OffenceWeights<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for OffenceWeights<T> {
+	fn apply_slashes(o: u32) -> Weight {
+		// the concurrent and by-kind report indices of each side of the dispute, and the report of
+		// each offender.
+		let accesses = 6u64.saturating_add(o as u64);
+		T::DbWeight::get().reads_writes(accesses, accesses)
+	}
+}
+
+/// Placeholder weights for runtimes which identify offenders by [`EraExposure`] and report them
+/// to `pallet-offences` and on to `pallet-staking`.
+///
+/// These weights were not produced by benchmarks, but are hand-written from the storage accesses
+/// of identifying an offender and of `pallet_staking`'s `on_offence` slashing it along with as
+/// many nominators as it may be exposed to.
+pub struct StakingOffenceWeights<T>(PhantomData<T>);
+impl<T: frame_system::Config + pallet_staking::Config> WeightInfo for StakingOffenceWeights<T> {
+	fn apply_slashes(o: u32) -> Weight {
+		let history_depth = <pallet_staking::Pallet<T>>::history_depth() as u64;
+		let nominators = T::MaxNominatorRewardedPerValidator::get() as u64;
+
+		// the active era, the history depth, the start of every era in it and the exposure.
+		let identify = T::DbWeight::get().reads(history_depth.saturating_add(4));
+		// the slash of the validator in the era, its slashing spans and span slash, the
+		// invulnerables and the deferred slash, and the same for each of its nominators.
+		let slash = T::DbWeight::get().reads_writes(
+			nominators.saturating_mul(3).saturating_add(5),
+			nominators.saturating_mul(3).saturating_add(4),
+		);
+
+		<OffenceWeights<T> as WeightInfo>::apply_slashes(o)
+			.saturating_add(identify.saturating_add(slash).saturating_mul(o as Weight))
+	}
+}
+
+/// Resolves the identification of a validator as of a past session.
+pub trait HistoricalIdentification<ValidatorId> {
+	/// The full identification of a validator, e.g. its exposure.
+	type Identification;
+
+	/// Identify `validator` as of `session`, if possible.
+	fn identify(session: SessionIndex, validator: &ValidatorId) -> Option<Self::Identification>;
+}
+
+/// Identifies validators by their exposure in the staking era `session` belonged to.
+pub struct EraExposure<T>(PhantomData<T>);
+
+impl<T: pallet_staking::Config> HistoricalIdentification<T::AccountId> for EraExposure<T> {
+	type Identification = pallet_staking::Exposure<T::AccountId, pallet_staking::BalanceOf<T>>;
+
+	fn identify(session: SessionIndex, validator: &T::AccountId) -> Option<Self::Identification> {
+		let active_era = <pallet_staking::Pallet<T>>::active_era()?.index;
+		let history_depth = <pallet_staking::Pallet<T>>::history_depth();
+
+		// the latest era which started at or before `session`.
+		let era = (active_era.saturating_sub(history_depth)..=active_era).rev().find(|era| {
+			<pallet_staking::Pallet<T>>::eras_start_session_index(era)
+				.map_or(false, |start| start <= session)
+		})?;
+
+		Some(<pallet_staking::Pallet<T>>::eras_stakers(era, validator))
+	}
+}
+
+/// Identifies validators by their current identification, regardless of the session.
+///
+/// Only suitable for runtimes where identifications don't change between sessions, e.g. because
+/// they are `()`.
+pub struct CurrentIdentification<T>(PhantomData<T>);
+
+impl<T: pallet_session::historical::Config> HistoricalIdentification<T::ValidatorId>
+	for CurrentIdentification<T>
+{
+	type Identification = T::FullIdentification;
+
+	fn identify(
+		_session: SessionIndex,
+		validator: &T::ValidatorId,
+	) -> Option<Self::Identification> {
+		T::FullIdentificationOf::convert(validator.clone())
+	}
+}
+
+/// The side of a dispute a validator lost on.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo)]
+pub enum SlashingOffenceKind {
+	/// The validator voted for an invalid candidate.
+	ForInvalid,
+	/// The validator voted against a valid candidate.
+	AgainstValid,
+	/// The validator participated in a dispute which never concluded.
+	Inconclusive,
+}
+
+/// The time slot of a dispute offence: a side of the dispute of a candidate.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo)]
+pub struct DisputesTimeSlot {
+	/// The session the candidate was disputed in.
+	pub session_index: SessionIndex,
+	/// The disputed candidate.
+	pub candidate_hash: CandidateHash,
+	/// The side of the dispute the offenders lost on.
+	pub kind: SlashingOffenceKind,
+}
+
+/// An offence committed by validators on the losing side of a dispute, slashing
+/// `SlashFraction` of their stake.
+#[derive(RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct DisputeOffence<Offender, SlashFraction> {
+	/// The side of the dispute of a candidate the offenders lost on.
+	pub time_slot: DisputesTimeSlot,
+	/// The size of the validator set in the session of the dispute.
+	pub validator_set_count: u32,
+	/// The validators on the losing side.
+	pub offenders: Vec<Offender>,
+	_slash_fraction: PhantomData<SlashFraction>,
+}
+
+impl<Offender, SlashFraction> DisputeOffence<Offender, SlashFraction> {
+	/// Create a new dispute offence.
+	pub fn new(
+		time_slot: DisputesTimeSlot,
+		validator_set_count: u32,
+		offenders: Vec<Offender>,
+	) -> Self {
+		DisputeOffence { time_slot, validator_set_count, offenders, _slash_fraction: PhantomData }
+	}
+}
+
+impl<Offender: Clone, SlashFraction: Get<Perbill>> Offence<Offender>
+	for DisputeOffence<Offender, SlashFraction>
+{
+	const ID: Kind = *b"disputes:offence";
+
+	type TimeSlot = DisputesTimeSlot;
+
+	fn offenders(&self) -> Vec<Offender> {
+		self.offenders.clone()
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.time_slot.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		self.time_slot.clone()
+	}
+
+	fn slash_fraction(_offenders_count: u32, _validator_set_count: u32) -> Perbill {
+		SlashFraction::get()
+	}
+}
+
+/// The slashes resulting from the dispute of a candidate, waiting for their deferral period to
+/// end.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PendingSlashes<ValidatorId> {
+	/// The validators to slash, along with the side of the dispute they lost on.
+	pub offenders: Vec<(ValidatorId, SlashingOffenceKind)>,
+	/// The size of the validator set in the session of the dispute.
+	pub validator_set_count: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + session_info::Config {
+		/// The outer event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The validator set of each session, in the order the session's `ValidatorIndex`es are
+		/// based on, e.g. `pallet_session`.
+		type ValidatorSet: ValidatorSet<Self::AccountId>;
+
+		/// Resolves the identification of offenders as of the session of the dispute.
+		type HistoricalIdentification: HistoricalIdentification<ValidatorIdOf<Self>>;
+
+		/// The handler offences are reported to, e.g. `pallet_offences`.
+		type HandleReports: ReportOffence<
+				Self::AccountId,
+				IdentificationTuple<Self>,
+				DisputeOffenceOf<Self, Self::SlashForInvalid>,
+			> + ReportOffence<
+				Self::AccountId,
+				IdentificationTuple<Self>,
+				DisputeOffenceOf<Self, Self::SlashAgainstValid>,
+			> + ReportOffence<
+				Self::AccountId,
+				IdentificationTuple<Self>,
+				DisputeOffenceOf<Self, Self::SlashInconclusive>,
+			>;
+
+		/// The fraction of their stake slashed from validators who voted for an invalid candidate.
+		#[pallet::constant]
+		type SlashForInvalid: Get<Perbill>;
+
+		/// The fraction of their stake slashed from validators who voted against a valid candidate.
+		#[pallet::constant]
+		type SlashAgainstValid: Get<Perbill>;
+
+		/// The fraction of their stake slashed from validators who participated in a dispute which
+		/// never concluded.
+		#[pallet::constant]
+		type SlashInconclusive: Get<Perbill>;
+
+		/// The number of blocks slashes are deferred for before being reported.
+		///
+		/// Zero means slashes are reported right away and cannot be cancelled.
+		#[pallet::constant]
+		type SlashDeferDuration: Get<Self::BlockNumber>;
+
+		/// The origin which may cancel unapplied slashes.
+		type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximal number of offenders reported at the start of a block.
+		///
+		/// Slashes of due disputes beyond it are postponed to the next block. The slashes of a
+		/// single dispute are never split, so a dispute with more offenders exceeds it.
+		#[pallet::constant]
+		type MaxOffendersPerBlock: Get<u32>;
+
+		/// Weight information for the reporting of slashes.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Slashes for the dispute of a candidate have been deferred until the given block.
+		/// `[session_index, candidate_hash, apply_at]`
+		SlashesDeferred(SessionIndex, CandidateHash, T::BlockNumber),
+		/// Slashes for the dispute of a candidate have been reported.
+		/// `[session_index, candidate_hash]`
+		SlashesApplied(SessionIndex, CandidateHash),
+		/// Unapplied slashes for the dispute of a candidate have been cancelled.
+		/// `[session_index, candidate_hash]`
+		SlashesCancelled(SessionIndex, CandidateHash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There are no unapplied slashes for the dispute.
+		NoUnappliedSlashes,
+	}
+
+	/// The validator set of each session, as long as the session is stored by `session_info`.
+	#[pallet::storage]
+	pub(super) type SessionValidators<T: Config> =
+		StorageMap<_, Twox64Concat, SessionIndex, Vec<ValidatorIdOf<T>>>;
+
+	/// The earliest session for which the validator set is stored.
+	///
+	/// `None` until the first validator set has been recorded.
+	#[pallet::storage]
+	pub(super) type EarliestStoredSession<T: Config> = StorageValue<_, SessionIndex>;
+
+	/// Slashes waiting for their deferral period to end, by the dispute they result from.
+	#[pallet::storage]
+	#[pallet::getter(fn unapplied_slashes)]
+	pub(crate) type UnappliedSlashes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Blake2_128Concat,
+		CandidateHash,
+		PendingSlashes<ValidatorIdOf<T>>,
+	>;
+
+	/// The disputes whose slashes are to be applied at the start of the given block.
+	#[pallet::storage]
+	pub(super) type SlashQueue<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<(SessionIndex, CandidateHash)>, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::note_validator_set().saturating_add(Self::apply_due_slashes(now))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Cancel the unapplied slashes resulting from the dispute of `candidate_hash` in
+		/// `session`.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn cancel_slashes(
+			origin: OriginFor<T>,
+			session: SessionIndex,
+			candidate_hash: CandidateHash,
+		) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+
+			// the entry in `SlashQueue` is left in place and skipped once due.
+			UnappliedSlashes::<T>::take(session, candidate_hash)
+				.ok_or(Error::<T>::NoUnappliedSlashes)?;
+
+			Self::deposit_event(Event::SlashesCancelled(session, candidate_hash));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Record the validator set of the current session, if not done yet, and prune validator sets
+	/// of sessions `session_info` no longer stores.
+	fn note_validator_set() -> Weight {
+		let session = T::ValidatorSet::session_index();
+		if SessionValidators::<T>::contains_key(session) {
+			return T::DbWeight::get().reads(1)
+		}
+
+		SessionValidators::<T>::insert(session, T::ValidatorSet::validators());
+
+		let new_earliest_stored_session = <session_info::Pallet<T>>::earliest_stored_session();
+		let mut writes = 1;
+		match EarliestStoredSession::<T>::get() {
+			// This is the first validator set recorded, e.g. right after the pallet has been
+			// added to a running chain, so there is nothing older to prune.
+			None => {
+				EarliestStoredSession::<T>::set(Some(session));
+				writes += 1;
+			},
+			Some(old_earliest_stored_session)
+				if new_earliest_stored_session > old_earliest_stored_session =>
+			{
+				for idx in old_earliest_stored_session..new_earliest_stored_session {
+					SessionValidators::<T>::remove(idx);
+				}
+				EarliestStoredSession::<T>::set(Some(new_earliest_stored_session));
+				writes += (new_earliest_stored_session - old_earliest_stored_session) as u64 + 1;
+			},
+			Some(_) => {},
+		}
+
+		T::DbWeight::get().reads_writes(3, writes)
+	}
+
+	/// Report the slashes which are due at the start of block `now`, for at most
+	/// [`Config::MaxOffendersPerBlock`] offenders, and postpone the rest to the next block.
+	fn apply_due_slashes(now: T::BlockNumber) -> Weight {
+		let mut due = SlashQueue::<T>::take(now).into_iter();
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		let max_offenders = T::MaxOffendersPerBlock::get();
+		let mut offenders = 0u32;
+
+		for (session, candidate_hash) in due.by_ref() {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			if let Some(pending) = UnappliedSlashes::<T>::take(session, candidate_hash) {
+				let reported = Self::apply_slashes(session, candidate_hash, pending);
+				weight = weight.saturating_add(T::WeightInfo::apply_slashes(reported));
+				offenders = offenders.saturating_add(reported);
+			}
+
+			if offenders >= max_offenders {
+				break
+			}
+		}
+
+		let mut postponed: Vec<_> = due.collect();
+		if !postponed.is_empty() {
+			// the postponed slashes go ahead of those due in the next block.
+			SlashQueue::<T>::mutate(now.saturating_add(One::one()), |queue| {
+				postponed.append(queue);
+				*queue = postponed;
+			});
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		}
+
+		weight
+	}
+
+	/// Report the offences of the validators in `pending`, grouped by the side of the dispute they
+	/// lost on, and return the number of offenders reported.
+	fn apply_slashes(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		pending: PendingSlashes<ValidatorIdOf<T>>,
+	) -> u32 {
+		let reported = Self::report::<T::SlashForInvalid>(
+			session,
+			candidate_hash,
+			SlashingOffenceKind::ForInvalid,
+			&pending,
+		)
+		.saturating_add(Self::report::<T::SlashAgainstValid>(
+			session,
+			candidate_hash,
+			SlashingOffenceKind::AgainstValid,
+			&pending,
+		))
+		.saturating_add(Self::report::<T::SlashInconclusive>(
+			session,
+			candidate_hash,
+			SlashingOffenceKind::Inconclusive,
+			&pending,
+		));
+
+		Self::deposit_event(Event::SlashesApplied(session, candidate_hash));
+		reported
+	}
+
+	/// Identify the validators in `pending` who lost on the `kind` side of the dispute and report
+	/// their offence, returning the number of offenders reported.
+	fn report<SlashFraction: Get<Perbill>>(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		kind: SlashingOffenceKind,
+		pending: &PendingSlashes<ValidatorIdOf<T>>,
+	) -> u32
+	where
+		T::HandleReports:
+			ReportOffence<T::AccountId, IdentificationTuple<T>, DisputeOffenceOf<T, SlashFraction>>,
+	{
+		// a zero fraction disables slashing for this side of disputes altogether.
+		if SlashFraction::get().is_zero() {
+			return 0
+		}
+
+		let validators: Vec<_> =
+			pending.offenders.iter().filter(|(_, k)| *k == kind).map(|(v, _)| v).collect();
+		if validators.is_empty() {
+			return 0
+		}
+
+		let offenders: Vec<_> = validators
+			.iter()
+			.filter_map(|validator| {
+				let identification = T::HistoricalIdentification::identify(session, validator);
+				if identification.is_none() {
+					log::warn!(
+						target: LOG_TARGET,
+						"Could not identify offender {:?} of session {}",
+						validator,
+						session,
+					);
+				}
+				identification.map(|identification| ((*validator).clone(), identification))
+			})
+			.collect();
+
+		if !offenders.is_empty() {
+			let time_slot = DisputesTimeSlot { session_index: session, candidate_hash, kind };
+			let offence = DisputeOffenceOf::<T, SlashFraction>::new(
+				time_slot,
+				pending.validator_set_count,
+				offenders,
+			);
+
+			// there is no reporter, as the offence is established on-chain.
+			if let Err(e) = T::HandleReports::report_offence(Vec::new(), offence) {
+				log::warn!(target: LOG_TARGET, "Failed to report dispute offence: {:?}", e);
+			}
+		}
+
+		validators.len() as u32
+	}
+
+	/// Resolve the validators at the given indices in `session` and defer slashing them.
+	fn defer_slashes(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		kind: SlashingOffenceKind,
+		validators: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		let mut validators = validators.into_iter().peekable();
+		if validators.peek().is_none() {
+			return
+		}
+
+		let (session_info, session_validators) = match (
+			<session_info::Pallet<T>>::session_info(session),
+			SessionValidators::<T>::get(session),
+		) {
+			(Some(session_info), Some(session_validators)) => (session_info, session_validators),
+			_ => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Validator set of session {} unknown, not slashing for candidate {:?}",
+					session,
+					candidate_hash,
+				);
+				return
+			},
+		};
+
+		// dispute statements index into the active validators of the session.
+		let offenders: Vec<_> = validators
+			.filter_map(|i| session_info.active_validator_indices.get(i.0 as usize))
+			.filter_map(|i| session_validators.get(i.0 as usize))
+			.map(|validator| (validator.clone(), kind))
+			.collect();
+
+		let validator_set_count = session_info.validators.len() as u32;
+		let pending = PendingSlashes { offenders, validator_set_count };
+
+		let defer_duration = T::SlashDeferDuration::get();
+		if defer_duration.is_zero() {
+			Self::apply_slashes(session, candidate_hash, pending);
+			return
+		}
+
+		UnappliedSlashes::<T>::mutate(session, candidate_hash, |maybe_pending| {
+			match maybe_pending {
+				// slashes for another side of the same dispute are applied along with the
+				// existing ones.
+				Some(existing) => existing.offenders.extend(pending.offenders),
+				None => {
+					let apply_at =
+						<frame_system::Pallet<T>>::block_number().saturating_add(defer_duration);
+					SlashQueue::<T>::append(apply_at, (session, candidate_hash));
+					*maybe_pending = Some(pending);
+
+					Self::deposit_event(Event::SlashesDeferred(session, candidate_hash, apply_at));
+				},
+			}
+		});
+	}
+}
+
+impl<T: Config> PunishValidators for Pallet<T> {
+	fn punish_for_invalid(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		validators: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		Self::defer_slashes(session, candidate_hash, SlashingOffenceKind::ForInvalid, validators)
+	}
+
+	fn punish_against_valid(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		validators: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		Self::defer_slashes(session, candidate_hash, SlashingOffenceKind::AgainstValid, validators)
+	}
+
+	fn punish_inconclusive(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		validators: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		Self::defer_slashes(session, candidate_hash, SlashingOffenceKind::Inconclusive, validators)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{
+		new_test_ext, set_session_validators, MaxOffendersPerBlock, MockGenesisConfig, Origin,
+		SlashDeferDuration, Slashing, System, Test, REPORTED_OFFENCES,
+	};
+	use frame_support::{assert_noop, assert_ok};
+	use keyring::Sr25519Keyring;
+	use primitives::{
+		v1::{BlockNumber, ValidatorId},
+		v2::SessionInfo,
+	};
+	use sp_runtime::traits::BadOrigin;
+
+	fn run_to_block(to: BlockNumber) {
+		while System::block_number() < to {
+			let b = System::block_number() + 1;
+			System::set_block_number(b);
+			Slashing::on_initialize(b);
+		}
+	}
+
+	// Start `session` with the given validators, of which those at `active` are active.
+	fn new_session(session: SessionIndex, validators: Vec<u64>, active: Vec<u32>) {
+		let keys: Vec<ValidatorId> =
+			active.iter().map(|_| Sr25519Keyring::Alice.public().into()).collect();
+
+		set_session_validators(session, validators);
+		run_to_block(System::block_number() + 1);
+
+		session_info::Sessions::<Test>::insert(
+			session,
+			SessionInfo {
+				validators: keys,
+				discovery_keys: Vec::new(),
+				assignment_keys: Vec::new(),
+				validator_groups: Vec::new(),
+				n_cores: 0,
+				zeroth_delay_tranche_width: 0,
+				relay_vrf_modulo_samples: 0,
+				n_delay_tranches: 0,
+				no_show_slots: 0,
+				needed_approvals: 0,
+				active_validator_indices: active.into_iter().map(ValidatorIndex).collect(),
+				random_seed: [0; 32],
+				dispute_period: 6,
+			},
+		);
+	}
+
+	fn reported_offences() -> Vec<(SessionIndex, Vec<(u64, SessionIndex)>, Perbill)> {
+		REPORTED_OFFENCES.with(|r| r.borrow().clone())
+	}
+
+	#[test]
+	fn slashes_are_deferred() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1, vec![10, 11, 12, 13], vec![3, 1, 2]);
+
+			Slashing::punish_for_invalid(
+				1,
+				candidate_hash,
+				vec![ValidatorIndex(0), ValidatorIndex(2)],
+			);
+
+			let apply_at = System::block_number() + SlashDeferDuration::get();
+			assert_eq!(
+				Slashing::unapplied_slashes(1, candidate_hash),
+				Some(PendingSlashes {
+					offenders: vec![
+						(13, SlashingOffenceKind::ForInvalid),
+						(12, SlashingOffenceKind::ForInvalid),
+					],
+					validator_set_count: 3,
+				}),
+			);
+
+			run_to_block(apply_at - 1);
+			assert!(reported_offences().is_empty());
+
+			run_to_block(apply_at);
+			assert_eq!(
+				reported_offences(),
+				vec![(1, vec![(13, 1), (12, 1)], Perbill::from_percent(100))],
+			);
+			assert!(Slashing::unapplied_slashes(1, candidate_hash).is_none());
+		});
+	}
+
+	#[test]
+	fn sides_of_a_dispute_are_slashed_by_their_fraction() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1, vec![10, 11, 12, 13], vec![0, 1, 2, 3]);
+
+			Slashing::punish_inconclusive(1, candidate_hash, vec![ValidatorIndex(0)]);
+			Slashing::punish_against_valid(1, candidate_hash, vec![ValidatorIndex(1)]);
+			// empty sides are ignored.
+			Slashing::punish_for_invalid(1, candidate_hash, vec![]);
+
+			run_to_block(System::block_number() + SlashDeferDuration::get());
+			assert_eq!(
+				reported_offences(),
+				vec![
+					(1, vec![(11, 1)], Perbill::from_percent(1)),
+					(1, vec![(10, 1)], Perbill::from_parts(1_000)),
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn slashes_can_be_cancelled() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1, vec![10, 11, 12, 13], vec![0, 1, 2, 3]);
+
+			Slashing::punish_against_valid(1, candidate_hash, vec![ValidatorIndex(1)]);
+
+			assert_noop!(Slashing::cancel_slashes(Origin::signed(1), 1, candidate_hash), BadOrigin);
+			assert_ok!(Slashing::cancel_slashes(Origin::root(), 1, candidate_hash));
+			assert_noop!(
+				Slashing::cancel_slashes(Origin::root(), 1, candidate_hash),
+				Error::<Test>::NoUnappliedSlashes,
+			);
+
+			run_to_block(System::block_number() + SlashDeferDuration::get());
+			assert!(reported_offences().is_empty());
+		});
+	}
+
+	#[test]
+	fn slashes_without_deferral_are_applied_immediately() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			SlashDeferDuration::set(0);
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1, vec![10, 11, 12, 13], vec![0, 1, 2, 3]);
+
+			Slashing::punish_for_invalid(1, candidate_hash, vec![ValidatorIndex(3)]);
+
+			assert_eq!(reported_offences(), vec![(1, vec![(13, 1)], Perbill::from_percent(100))]);
+			assert!(Slashing::unapplied_slashes(1, candidate_hash).is_none());
+		});
+	}
+
+	#[test]
+	fn slashes_beyond_the_block_limit_are_postponed() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			MaxOffendersPerBlock::set(2);
+			let candidate_a = CandidateHash(sp_core::H256::repeat_byte(1));
+			let candidate_b = CandidateHash(sp_core::H256::repeat_byte(2));
+			let candidate_c = CandidateHash(sp_core::H256::repeat_byte(3));
+			new_session(1, vec![10, 11, 12, 13], vec![0, 1, 2, 3]);
+
+			// the slashes of a single dispute are applied at once, even beyond the limit.
+			Slashing::punish_for_invalid(
+				1,
+				candidate_a,
+				vec![ValidatorIndex(0), ValidatorIndex(1), ValidatorIndex(2)],
+			);
+			Slashing::punish_for_invalid(1, candidate_b, vec![ValidatorIndex(3)]);
+			run_to_block(System::block_number() + 1);
+			Slashing::punish_for_invalid(1, candidate_c, vec![ValidatorIndex(0)]);
+
+			let apply_at = System::block_number() - 1 + SlashDeferDuration::get();
+			run_to_block(apply_at);
+			assert_eq!(
+				reported_offences(),
+				vec![(1, vec![(10, 1), (11, 1), (12, 1)], Perbill::from_percent(100))],
+			);
+			assert!(Slashing::unapplied_slashes(1, candidate_b).is_some());
+
+			// postponed slashes go ahead of those due in the next block.
+			run_to_block(apply_at + 1);
+			assert_eq!(reported_offences().len(), 3);
+			assert_eq!(reported_offences()[1], (1, vec![(13, 1)], Perbill::from_percent(100)));
+			assert_eq!(reported_offences()[2], (1, vec![(10, 1)], Perbill::from_percent(100)));
+		});
+	}
+
+	#[test]
+	fn validators_of_past_sessions_are_slashed() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			new_session(1, vec![10, 11, 12, 13], vec![0, 1, 2, 3]);
+			// the validators of session 1 have all left the active set.
+			new_session(2, vec![20, 21, 22, 23], vec![0, 1, 2, 3]);
+
+			Slashing::punish_for_invalid(1, candidate_hash, vec![ValidatorIndex(1)]);
+
+			run_to_block(System::block_number() + SlashDeferDuration::get());
+			assert_eq!(reported_offences(), vec![(1, vec![(11, 1)], Perbill::from_percent(100))]);
+		});
+	}
+
+	#[test]
+	fn validator_sets_are_pruned_with_session_info() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			new_session(1, vec![10], vec![0]);
+			new_session(2, vec![20], vec![0]);
+
+			session_info::EarliestStoredSession::<Test>::set(2);
+			new_session(3, vec![30], vec![0]);
+
+			assert!(SessionValidators::<Test>::get(1).is_none());
+			assert_eq!(SessionValidators::<Test>::get(2), Some(vec![20]));
+			assert_eq!(SessionValidators::<Test>::get(3), Some(vec![30]));
+
+			// unknown sessions are not slashed.
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			Slashing::punish_for_invalid(1, candidate_hash, vec![ValidatorIndex(0)]);
+			assert!(Slashing::unapplied_slashes(1, candidate_hash).is_none());
+		});
+	}
+
+	#[test]
+	fn first_validator_set_starts_the_stored_range() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			// the pallet is added to a chain which is well past genesis.
+			session_info::EarliestStoredSession::<Test>::set(994);
+			new_session(1000, vec![10], vec![0]);
+
+			assert_eq!(EarliestStoredSession::<Test>::get(), Some(1000));
+			assert_eq!(SessionValidators::<Test>::get(1000), Some(vec![10]));
+
+			// pruning only starts once `session_info` moves past the first recorded session.
+			session_info::EarliestStoredSession::<Test>::set(1000);
+			new_session(1001, vec![20], vec![0]);
+			assert_eq!(EarliestStoredSession::<Test>::get(), Some(1000));
+			assert_eq!(SessionValidators::<Test>::get(1000), Some(vec![10]));
+
+			session_info::EarliestStoredSession::<Test>::set(1001);
+			new_session(1002, vec![30], vec![0]);
+			assert_eq!(EarliestStoredSession::<Test>::get(), Some(1001));
+			assert!(SessionValidators::<Test>::get(1000).is_none());
+			assert_eq!(SessionValidators::<Test>::get(1001), Some(vec![20]));
+		});
+	}
+}
@@ -17,30 +17,31 @@
 //! Mocks for all the traits.
 
 use crate::{
-	configuration, disputes, dmp, hrmp, inclusion, initializer, origin, paras, paras_inherent,
-	scheduler, session_info, shared,
+	approval_rewards, configuration, disputes, dmp, hrmp, inclusion, initializer, on_demand,
+	origin, paras, paras_inherent, scheduler, session_info, shared,
 	ump::{self, MessageId, UmpSink},
 	ParaId,
 };
 
 use frame_support::{
 	parameter_types,
-	traits::{GenesisBuild, KeyOwnerProofSystem},
+	traits::{GenesisBuild, KeyOwnerProofSystem, ValidatorSet},
 	weights::Weight,
 };
 use frame_support_test::TestRandomness;
 use parity_scale_codec::Decode;
 use primitives::v1::{
-	AuthorityDiscoveryId, Balance, BlockNumber, Header, Moment, SessionIndex, UpwardMessage,
-	ValidatorIndex,
+	AuthorityDiscoveryId, Balance, BlockNumber, CandidateHash, Header, Moment, SessionIndex,
+	UpwardMessage, ValidatorIndex,
 };
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
 	transaction_validity::TransactionPriority,
-	KeyTypeId, Permill,
+	KeyTypeId, Perbill, Permill,
 };
+use sp_staking::offence::{Offence, OffenceError, ReportOffence};
 use std::{cell::RefCell, collections::HashMap};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -68,6 +69,9 @@ frame_support::construct_runtime!(
 		SessionInfo: session_info,
 		Disputes: disputes,
 		Babe: pallet_babe,
+		OnDemand: on_demand,
+		Slashing: disputes::slashing,
+		ApprovalRewards: approval_rewards,
 	}
 );
 
@@ -264,6 +268,7 @@ impl crate::disputes::RewardValidators for Test {
 impl crate::disputes::PunishValidators for Test {
 	fn punish_for_invalid(
 		session: SessionIndex,
+		_: CandidateHash,
 		validators: impl IntoIterator<Item = ValidatorIndex>,
 	) {
 		PUNISH_VALIDATORS_FOR
@@ -272,6 +277,7 @@ impl crate::disputes::PunishValidators for Test {
 
 	fn punish_against_valid(
 		session: SessionIndex,
+		_: CandidateHash,
 		validators: impl IntoIterator<Item = ValidatorIndex>,
 	) {
 		PUNISH_VALIDATORS_AGAINST
@@ -280,6 +286,7 @@ impl crate::disputes::PunishValidators for Test {
 
 	fn punish_inconclusive(
 		session: SessionIndex,
+		_: CandidateHash,
 		validators: impl IntoIterator<Item = ValidatorIndex>,
 	) {
 		PUNISH_VALIDATORS_INCONCLUSIVE
@@ -287,7 +294,101 @@ impl crate::disputes::PunishValidators for Test {
 	}
 }
 
-impl crate::scheduler::Config for Test {}
+parameter_types! {
+	pub const SlashForInvalid: Perbill = Perbill::from_percent(100);
+	pub const SlashAgainstValid: Perbill = Perbill::from_percent(1);
+	pub const SlashInconclusive: Perbill = Perbill::from_parts(1_000);
+	pub static SlashDeferDuration: BlockNumber = 10;
+	pub static MaxOffendersPerBlock: u32 = 100;
+}
+
+impl crate::disputes::slashing::Config for Test {
+	type Event = Event;
+	type ValidatorSet = TestValidatorSet;
+	type HistoricalIdentification = Self;
+	type HandleReports = Self;
+	type SlashForInvalid = SlashForInvalid;
+	type SlashAgainstValid = SlashAgainstValid;
+	type SlashInconclusive = SlashInconclusive;
+	type SlashDeferDuration = SlashDeferDuration;
+	type CancelOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxOffendersPerBlock = MaxOffendersPerBlock;
+	type WeightInfo = crate::disputes::slashing::TestWeightInfo;
+}
+
+thread_local! {
+	pub static SESSION_VALIDATORS: RefCell<(SessionIndex, Vec<AccountId>)> = RefCell::new((0, Vec::new()));
+	pub static REPORTED_OFFENCES: RefCell<Vec<(SessionIndex, Vec<(AccountId, SessionIndex)>, Perbill)>> = RefCell::new(Vec::new());
+}
+
+/// Set the session index and validators `TestValidatorSet` provides.
+pub fn set_session_validators(session: SessionIndex, validators: Vec<AccountId>) {
+	SESSION_VALIDATORS.with(|v| *v.borrow_mut() = (session, validators));
+}
+
+pub struct TestValidatorSet;
+
+impl ValidatorSet<AccountId> for TestValidatorSet {
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+
+	fn session_index() -> SessionIndex {
+		SESSION_VALIDATORS.with(|v| v.borrow().0)
+	}
+
+	fn validators() -> Vec<AccountId> {
+		SESSION_VALIDATORS.with(|v| v.borrow().1.clone())
+	}
+}
+
+/// Identifies validators by the session they are identified as of.
+impl crate::disputes::slashing::HistoricalIdentification<AccountId> for Test {
+	type Identification = SessionIndex;
+
+	fn identify(session: SessionIndex, _validator: &AccountId) -> Option<SessionIndex> {
+		Some(session)
+	}
+}
+
+impl<O: Offence<(AccountId, SessionIndex)>> ReportOffence<AccountId, (AccountId, SessionIndex), O>
+	for Test
+{
+	fn report_offence(_reporters: Vec<AccountId>, offence: O) -> Result<(), OffenceError> {
+		let slash_fraction =
+			O::slash_fraction(offence.offenders().len() as u32, offence.validator_set_count());
+		REPORTED_OFFENCES.with(|r| {
+			r.borrow_mut()
+				.push((offence.session_index(), offence.offenders(), slash_fraction))
+		});
+		Ok(())
+	}
+
+	fn is_known_offence(
+		_offenders: &[(AccountId, SessionIndex)],
+		_time_slot: &O::TimeSlot,
+	) -> bool {
+		false
+	}
+}
+
+impl crate::scheduler::Config for Test {
+	type OnClaimEnded = OnDemand;
+}
+
+parameter_types! {
+	pub const OnDemandBasePrice: Balance = 10;
+	pub const OnDemandMaxPriceMultiplier: u32 = 5;
+	pub const OnDemandMaxOrders: u32 = 4;
+}
+
+impl crate::on_demand::Config for Test {
+	type Event = Event;
+	type Currency = pallet_balances::Pallet<Test>;
+	type BasePrice = OnDemandBasePrice;
+	type MaxPriceMultiplier = OnDemandMaxPriceMultiplier;
+	type MaxOrders = OnDemandMaxOrders;
+	type WeightInfo = crate::on_demand::TestWeightInfo;
+}
 
 impl crate::inclusion::Config for Test {
 	type Event = Event;
@@ -301,6 +402,12 @@ impl crate::paras_inherent::Config for Test {
 
 impl crate::session_info::Config for Test {}
 
+impl crate::approval_rewards::Config for Test {
+	type Event = Event;
+	type RewardApprovals = TestRewardApprovals;
+	type WeightInfo = crate::approval_rewards::TestWeightInfo;
+}
+
 thread_local! {
 	pub static DISCOVERY_AUTHORITIES: RefCell<Vec<AuthorityDiscoveryId>> = RefCell::new(Vec::new());
 }
@@ -325,6 +432,9 @@ thread_local! {
 
 	pub static AVAILABILITY_REWARDS: RefCell<HashMap<ValidatorIndex, usize>>
 		= RefCell::new(HashMap::new());
+
+	pub static APPROVAL_REWARDS: RefCell<Vec<(Vec<ValidatorIndex>, u32)>>
+		= RefCell::new(Vec::new());
 }
 
 pub fn backing_rewards() -> HashMap<ValidatorIndex, usize> {
@@ -335,6 +445,10 @@ pub fn availability_rewards() -> HashMap<ValidatorIndex, usize> {
 	AVAILABILITY_REWARDS.with(|r| r.borrow().clone())
 }
 
+pub fn approval_rewards() -> Vec<(Vec<ValidatorIndex>, u32)> {
+	APPROVAL_REWARDS.with(|r| r.borrow().clone())
+}
+
 std::thread_local! {
 	static PROCESSED: RefCell<Vec<(ParaId, UpwardMessage)>> = RefCell::new(vec![]);
 }
@@ -391,12 +505,23 @@ impl inclusion::RewardValidators for TestRewardValidators {
 	}
 }
 
+pub struct TestRewardApprovals;
+
+impl approval_rewards::RewardApprovals for TestRewardApprovals {
+	fn reward_approvals(v: impl IntoIterator<Item = ValidatorIndex>, points: u32) {
+		APPROVAL_REWARDS.with(|r| r.borrow_mut().push((v.into_iter().collect(), points)))
+	}
+}
+
 /// Create a new set of test externalities.
 pub fn new_test_ext(state: MockGenesisConfig) -> TestExternalities {
 	use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStorePtr};
 	use sp_std::sync::Arc;
 	BACKING_REWARDS.with(|r| r.borrow_mut().clear());
 	AVAILABILITY_REWARDS.with(|r| r.borrow_mut().clear());
+	APPROVAL_REWARDS.with(|r| r.borrow_mut().clear());
+	REPORTED_OFFENCES.with(|r| r.borrow_mut().clear());
+	set_session_validators(0, Vec::new());
 
 	let mut t = state.system.build_storage::<Test>().unwrap();
 	state.configuration.assimilate_storage(&mut t).unwrap();
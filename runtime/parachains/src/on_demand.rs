@@ -0,0 +1,488 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The on-demand order pallet for parathreads.
+//!
+//! Accounts buy parathread blocks by placing orders. Orders wait in a bounded queue until the
+//! scheduler has room for another parathread claim, at which point they are handed to it via
+//! [`scheduler::Pallet::add_parathread_claim`].
+//!
+//! The price of an order is reserved when it is placed. It is burned once a candidate of the
+//! parathread has been made available and refunded if the claim expires, e.g. because it was
+//! retried more than `parathread_retries` times.
+//!
+//! A candidate which is disputed and concluded invalid while pending availability frees its core
+//! just like an available one does, so the order is considered fulfilled and its payment is
+//! burned as well. This is deliberate: the core was spent on the candidate the order asked for,
+//! and refunding would let collators of the parathread spam the validators with invalid
+//! candidates for free.
+//!
+//! The spot price depends on the occupancy of the order queue: it rises linearly from
+//! [`Config::BasePrice`] for an empty queue to `BasePrice * MaxPriceMultiplier` for a full one.
+
+use crate::{
+	configuration, paras,
+	scheduler::{self, OnParathreadClaimEnded},
+};
+use frame_support::{pallet_prelude::*, traits::ReservableCurrency};
+use frame_system::pallet_prelude::*;
+use primitives::{
+	v1::{Balance, CollatorId, Id as ParaId, ParathreadClaim},
+	v2::ParathreadOrder,
+};
+use sp_runtime::{
+	traits::{Saturating, UniqueSaturatedInto},
+	Perbill,
+};
+use sp_std::prelude::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub use pallet::*;
+
+pub trait WeightInfo {
+	fn place_order() -> Weight;
+}
+
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn place_order() -> Weight {
+		0
+	}
+}
+
+/// An order placed by an account of the runtime.
+pub type OrderOf<T> = ParathreadOrder<<T as frame_system::Config>::AccountId>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + configuration::Config + paras::Config + scheduler::Config
+	{
+		/// The outer event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency orders are paid with.
+		///
+		/// NOTE that the `Balance` of the `Currency` implementation should be the same as the
+		/// `Balance` used for prices.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The price of an order placed while the order queue is empty.
+		#[pallet::constant]
+		type BasePrice: Get<Balance>;
+
+		/// How many times the base price an order placed while the order queue is full costs.
+		#[pallet::constant]
+		type MaxPriceMultiplier: Get<u32>;
+
+		/// The maximum number of orders waiting for room in the parathread claim queue.
+		#[pallet::constant]
+		type MaxOrders: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An order for a parathread block has been placed. `[para_id, who, price]`
+		OrderPlaced(ParaId, T::AccountId, Balance),
+		/// An order has been handed to the scheduler as a parathread claim. `[para_id]`
+		ClaimQueued(ParaId),
+		/// A candidate of the parathread has been made available and the order has been paid.
+		/// `[para_id, who, price]`
+		OrderFulfilled(ParaId, T::AccountId, Balance),
+		/// An order has been refunded as its claim expired. `[para_id, who, price]`
+		OrderRefunded(ParaId, T::AccountId, Balance),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The para is not a parathread.
+		NotParathread,
+		/// There already is an order for the parathread that hasn't been fulfilled or refunded.
+		OrderAlreadyPending,
+		/// The order queue is full.
+		QueueFull,
+		/// The spot price is higher than the maximum amount the caller is willing to pay.
+		SpotPriceHigherThanMaxAmount,
+	}
+
+	/// The orders waiting for room in the parathread claim queue, oldest first.
+	///
+	/// Bounded by `MaxOrders`.
+	#[pallet::storage]
+	pub(crate) type OrderQueue<T: Config> = StorageValue<_, Vec<OrderOf<T>>, ValueQuery>;
+
+	/// The orders which have been handed to the scheduler, by the parathread they are for.
+	///
+	/// There is at most one claim per parathread in the scheduler, so this is bounded by the
+	/// number of parathreads.
+	#[pallet::storage]
+	pub(crate) type ActiveOrders<T: Config> = StorageMap<_, Twox64Concat, ParaId, OrderOf<T>>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			Self::queue_claims()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Order a block of the parathread `para_id`, to be provided by `collator`.
+		///
+		/// The order costs the spot price at the time it is placed, which is reserved until the
+		/// order is either fulfilled or refunded. Fails if the spot price exceeds `max_amount`.
+		///
+		/// Only one order per parathread may be pending at a time.
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			para_id: ParaId,
+			collator: CollatorId,
+			max_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<paras::Pallet<T>>::is_parathread(para_id), Error::<T>::NotParathread);
+			ensure!(!Self::has_pending_order(para_id), Error::<T>::OrderAlreadyPending);
+
+			let price = Self::spot_price();
+			ensure!(price <= max_amount, Error::<T>::SpotPriceHigherThanMaxAmount);
+
+			OrderQueue::<T>::try_mutate(|queue| -> DispatchResult {
+				ensure!(queue.len() < T::MaxOrders::get() as usize, Error::<T>::QueueFull);
+
+				T::Currency::reserve(&who, price.unique_saturated_into())?;
+				queue.push(ParathreadOrder { para_id, collator, who: who.clone(), price });
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OrderPlaced(para_id, who, price));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The price an order placed now would pay.
+	pub fn spot_price() -> Balance {
+		let queued = OrderQueue::<T>::decode_len().unwrap_or(0) as u32;
+		let occupancy = Perbill::from_rational(queued, T::MaxOrders::get().max(1));
+
+		let base_price = T::BasePrice::get();
+		let surcharge =
+			base_price.saturating_mul(T::MaxPriceMultiplier::get().saturating_sub(1).into());
+
+		base_price.saturating_add(occupancy * surcharge)
+	}
+
+	/// The orders waiting for room in the parathread claim queue, oldest first.
+	pub fn order_queue() -> Vec<OrderOf<T>> {
+		OrderQueue::<T>::get()
+	}
+
+	/// The orders which have been handed to the scheduler.
+	pub fn active_orders() -> Vec<OrderOf<T>> {
+		ActiveOrders::<T>::iter_values().collect()
+	}
+
+	fn has_pending_order(para_id: ParaId) -> bool {
+		ActiveOrders::<T>::contains_key(para_id) ||
+			OrderQueue::<T>::get().iter().any(|order| order.para_id == para_id)
+	}
+
+	/// Hands the oldest orders to the scheduler, for as long as it has room for them.
+	fn queue_claims() -> Weight {
+		let mut reads = 1;
+		let mut writes = 0;
+
+		let mut queue = OrderQueue::<T>::get();
+		let mut handled = 0;
+		for order in &queue {
+			reads += 3;
+			if <paras::Pallet<T>>::is_parathread(order.para_id) &&
+				!<scheduler::Pallet<T>>::can_queue_parathread_claim()
+			{
+				break
+			}
+
+			handled += 1;
+			writes += 3;
+			let claim = ParathreadClaim(order.para_id, order.collator.clone());
+			if <scheduler::Pallet<T>>::add_parathread_claim(claim) {
+				ActiveOrders::<T>::insert(order.para_id, order.clone());
+				Self::deposit_event(Event::ClaimQueued(order.para_id));
+			} else {
+				// Defensive: the para stopped being a parathread since the order was placed.
+				Self::refund(order.clone());
+			}
+		}
+
+		if handled > 0 {
+			queue.drain(..handled);
+			OrderQueue::<T>::put(queue);
+			writes += 1;
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	fn refund(order: OrderOf<T>) {
+		T::Currency::unreserve(&order.who, order.price.unique_saturated_into());
+		Self::deposit_event(Event::OrderRefunded(order.para_id, order.who, order.price));
+	}
+}
+
+impl<T: Config> OnParathreadClaimEnded for Pallet<T> {
+	fn claim_fulfilled(para_id: ParaId) {
+		if let Some(order) = ActiveOrders::<T>::take(para_id) {
+			// The imbalance is dropped, burning the payment. This includes candidates which were
+			// disputed and concluded invalid, see the module docs.
+			let _ = T::Currency::slash_reserved(&order.who, order.price.unique_saturated_into());
+			Self::deposit_event(Event::OrderFulfilled(order.para_id, order.who, order.price));
+		}
+	}
+
+	fn claim_expired(para_id: ParaId) {
+		if let Some(order) = ActiveOrders::<T>::take(para_id) {
+			Self::refund(order);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		initializer::SessionChangeNotification,
+		mock::{
+			new_test_ext, Balances, Configuration, Event as MockEvent, MockGenesisConfig, OnDemand,
+			Origin, Paras, ParasShared, Scheduler, System, Test,
+		},
+		paras::ParaGenesisArgs,
+		scheduler::FreedReason,
+	};
+	use frame_support::{assert_noop, assert_ok, traits::Currency as _};
+	use keyring::Sr25519Keyring;
+	use primitives::v1::{BlockNumber, CoreIndex, ValidatorId};
+
+	const ALICE: u64 = 1;
+	const BOB: u64 = 2;
+
+	fn default_config() -> configuration::HostConfiguration<BlockNumber> {
+		configuration::HostConfiguration {
+			parathread_cores: 1,
+			group_rotation_frequency: 10,
+			chain_availability_period: 3,
+			thread_availability_period: 5,
+			scheduling_lookahead: 1,
+			parathread_retries: 1,
+			pvf_checking_enabled: false,
+			minimum_validation_upgrade_delay: 6,
+			..Default::default()
+		}
+	}
+
+	fn run_to_block(to: BlockNumber) {
+		while System::block_number() < to {
+			let b = System::block_number();
+
+			Scheduler::initializer_finalize();
+			Paras::initializer_finalize(b);
+
+			if b == 0 {
+				let notification = SessionChangeNotification {
+					new_config: Configuration::config(),
+					validators: vec![ValidatorId::from(Sr25519Keyring::Alice.public())],
+					session_index: ParasShared::scheduled_session(),
+					..Default::default()
+				};
+				Paras::initializer_on_new_session(&notification);
+				Scheduler::initializer_on_new_session(&notification);
+			}
+
+			System::on_finalize(b);
+
+			System::on_initialize(b + 1);
+			System::set_block_number(b + 1);
+
+			Paras::initializer_initialize(b + 1);
+			Scheduler::initializer_initialize(b + 1);
+			OnDemand::on_initialize(b + 1);
+
+			// In the real runtime this is expected to be called by the `InclusionInherent` pallet.
+			Scheduler::clear();
+			Scheduler::schedule(Vec::new(), b + 1);
+		}
+	}
+
+	fn new_test_ext_with_parathreads(threads: &[ParaId]) -> sp_io::TestExternalities {
+		let genesis_config = MockGenesisConfig {
+			configuration: crate::configuration::GenesisConfig {
+				config: default_config(),
+				..Default::default()
+			},
+			..Default::default()
+		};
+
+		let mut ext = new_test_ext(genesis_config);
+		ext.execute_with(|| {
+			for thread in threads {
+				assert_ok!(Paras::schedule_para_initialize(
+					*thread,
+					ParaGenesisArgs {
+						genesis_head: Vec::new().into(),
+						validation_code: vec![1, 2, 3].into(),
+						parachain: false,
+					}
+				));
+			}
+
+			Balances::make_free_balance_be(&ALICE, 1_000);
+			Balances::make_free_balance_be(&BOB, 1_000);
+
+			run_to_block(1);
+		});
+		ext
+	}
+
+	fn collator() -> CollatorId {
+		CollatorId::from(Sr25519Keyring::Alice.public())
+	}
+
+	#[test]
+	fn spot_price_rises_with_queue_occupancy() {
+		new_test_ext_with_parathreads(&[]).execute_with(|| {
+			let base_price = <Test as Config>::BasePrice::get();
+			let max_orders = <Test as Config>::MaxOrders::get();
+			let multiplier = <Test as Config>::MaxPriceMultiplier::get() as u128;
+
+			assert_eq!(OnDemand::spot_price(), base_price);
+
+			let order = ParathreadOrder {
+				para_id: ParaId::from(1),
+				collator: collator(),
+				who: ALICE,
+				price: base_price,
+			};
+			OrderQueue::<Test>::put(vec![order; max_orders as usize]);
+
+			assert_eq!(OnDemand::spot_price(), base_price * multiplier);
+		});
+	}
+
+	#[test]
+	fn place_order_checks_para_price_and_duplicates() {
+		let thread = ParaId::from(1);
+		new_test_ext_with_parathreads(&[thread]).execute_with(|| {
+			let price = OnDemand::spot_price();
+
+			assert_noop!(
+				OnDemand::place_order(Origin::signed(ALICE), ParaId::from(2), collator(), price),
+				Error::<Test>::NotParathread,
+			);
+			assert_noop!(
+				OnDemand::place_order(Origin::signed(ALICE), thread, collator(), price - 1),
+				Error::<Test>::SpotPriceHigherThanMaxAmount,
+			);
+
+			assert_ok!(OnDemand::place_order(Origin::signed(ALICE), thread, collator(), price));
+			assert_eq!(Balances::reserved_balance(&ALICE), price);
+
+			assert_noop!(
+				OnDemand::place_order(Origin::signed(BOB), thread, collator(), u128::MAX),
+				Error::<Test>::OrderAlreadyPending,
+			);
+		});
+	}
+
+	#[test]
+	fn orders_are_handed_to_the_scheduler_when_there_is_room() {
+		let (thread_a, thread_b) = (ParaId::from(1), ParaId::from(2));
+		new_test_ext_with_parathreads(&[thread_a, thread_b]).execute_with(|| {
+			assert_ok!(OnDemand::place_order(Origin::signed(ALICE), thread_a, collator(), 1_000));
+			assert_ok!(OnDemand::place_order(Origin::signed(BOB), thread_b, collator(), 1_000));
+			assert_eq!(OnDemand::order_queue().len(), 2);
+
+			// The claim queue only has room for a single claim.
+			run_to_block(2);
+			assert_eq!(OnDemand::order_queue().len(), 1);
+			assert!(ActiveOrders::<Test>::contains_key(thread_a));
+			assert_eq!(Scheduler::scheduled()[0].para_id, thread_a);
+		});
+	}
+
+	#[test]
+	fn fulfilled_orders_are_paid() {
+		let thread = ParaId::from(1);
+		new_test_ext_with_parathreads(&[thread]).execute_with(|| {
+			let price = OnDemand::spot_price();
+			assert_ok!(OnDemand::place_order(Origin::signed(ALICE), thread, collator(), price));
+			run_to_block(2);
+
+			Scheduler::occupied(&[CoreIndex(0)]);
+			Scheduler::free_cores(vec![(CoreIndex(0), FreedReason::Concluded)]);
+
+			assert!(!ActiveOrders::<Test>::contains_key(thread));
+			assert_eq!(Balances::reserved_balance(&ALICE), 0);
+			assert_eq!(Balances::free_balance(&ALICE), 1_000 - price);
+			assert_eq!(
+				System::events().last().unwrap().event,
+				MockEvent::OnDemand(Event::OrderFulfilled(thread, ALICE, price)),
+			);
+		});
+	}
+
+	#[test]
+	fn expired_orders_are_refunded() {
+		let thread = ParaId::from(1);
+		new_test_ext_with_parathreads(&[thread]).execute_with(|| {
+			let price = OnDemand::spot_price();
+			assert_ok!(OnDemand::place_order(Origin::signed(ALICE), thread, collator(), price));
+
+			// Nobody provides a candidate, so the claim is retried `parathread_retries` times.
+			let retries = default_config().parathread_retries;
+			run_to_block(2 + retries);
+			assert!(ActiveOrders::<Test>::contains_key(thread));
+			assert_eq!(Balances::reserved_balance(&ALICE), price);
+
+			run_to_block(2 + retries + 1);
+			assert!(!ActiveOrders::<Test>::contains_key(thread));
+			assert_eq!(Balances::reserved_balance(&ALICE), 0);
+			assert_eq!(Balances::free_balance(&ALICE), 1_000);
+			assert_eq!(
+				System::events().last().unwrap().event,
+				MockEvent::OnDemand(Event::OrderRefunded(thread, ALICE, price)),
+			);
+
+			// The parathread can be ordered again.
+			assert_ok!(OnDemand::place_order(Origin::signed(ALICE), thread, collator(), 1_000));
+		});
+	}
+}
@@ -0,0 +1,90 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::{paras::ParaGenesisArgs, shared};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{assert_ok, traits::Currency};
+use frame_system::RawOrigin;
+use primitives::v1::ValidationCode;
+use sp_core::sr25519;
+use sp_runtime::traits::Bounded;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	// compare to the last event record
+	let frame_system::EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+fn collator() -> CollatorId {
+	CollatorId::from(sr25519::Public::from_raw([42u8; 32]))
+}
+
+/// Onboards `para_id` as a parathread, fast-tracking its code through PVF pre-checking.
+fn register_parathread<T: Config>(para_id: ParaId) {
+	let validation_code = ValidationCode(vec![1, 2, 3]);
+	assert_ok!(paras::Pallet::<T>::add_trusted_validation_code(
+		RawOrigin::Root.into(),
+		validation_code.clone(),
+	));
+	assert_ok!(paras::Pallet::<T>::schedule_para_initialize(
+		para_id,
+		ParaGenesisArgs { genesis_head: vec![0].into(), validation_code, parachain: false },
+	));
+
+	shared::Pallet::<T>::set_session_index(shared::Pallet::<T>::scheduled_session());
+	paras::Pallet::<T>::test_on_new_session();
+	assert!(paras::Pallet::<T>::is_parathread(para_id));
+}
+
+/// Fills the order queue up to one less than its capacity with orders for other paras, so that
+/// looking for a pending order has to walk through all of them.
+fn fill_order_queue<T: Config>() {
+	let who: T::AccountId = account("orderer", 0, 0);
+	let queue = (0..T::MaxOrders::get().saturating_sub(1))
+		.map(|i| ParathreadOrder {
+			para_id: ParaId::from(2000 + i),
+			collator: collator(),
+			who: who.clone(),
+			price: T::BasePrice::get(),
+		})
+		.collect::<Vec<_>>();
+	OrderQueue::<T>::put(queue);
+}
+
+benchmarks! {
+	place_order {
+		let para_id = ParaId::from(1000);
+		register_parathread::<T>(para_id);
+		fill_order_queue::<T>();
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, Bounded::max_value());
+		let price = Pallet::<T>::spot_price();
+	}: _(RawOrigin::Signed(caller.clone()), para_id, collator(), price)
+	verify {
+		assert_last_event::<T>(Event::OrderPlaced(para_id, caller, price).into());
+		assert_eq!(OrderQueue::<T>::decode_len(), Some(T::MaxOrders::get() as usize));
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(Default::default()),
+		crate::mock::Test
+	);
+}
@@ -14,8 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-//! An implementation of the `RewardValidators` trait used by `inclusion` that employs
-//! `pallet-staking` to compute the rewards.
+//! An implementation of the `RewardValidators` trait used by `inclusion` and the
+//! `RewardApprovals` trait used by `approval_rewards` that employs `pallet-staking` to compute
+//! the rewards.
 //!
 //! Based on https://research.web3.foundation/en/latest/polkadot/overview/2-token-economics.html
 //! which doesn't currently mention availability bitfields. As such, we don't reward them
@@ -66,6 +67,22 @@ where
 	fn reward_bitfields(_validators: impl IntoIterator<Item = ValidatorIndex>) {}
 }
 
+impl<C> crate::approval_rewards::RewardApprovals for RewardValidatorsWithEraPoints<C>
+where
+	C: pallet_staking::Config + shared::Config,
+{
+	fn reward_approvals(indirect_indices: impl IntoIterator<Item = ValidatorIndex>, points: u32) {
+		// Approvals are only rewarded within the current session, see `reward_backing`.
+		let validators = C::SessionInterface::validators();
+
+		let rewards = validators_to_reward::<C, _, _>(&validators, indirect_indices)
+			.into_iter()
+			.map(|v| (v.clone(), points));
+
+		<pallet_staking::Pallet<C>>::reward_by_ids(rewards);
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
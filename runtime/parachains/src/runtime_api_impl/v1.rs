@@ -401,3 +401,8 @@ where
 		<paras::Pallet<T>>::current_code_hash(&para_id)
 	})
 }
+
+/// Returns whether erasure chunks are assigned to validators by a shuffled mapping.
+pub fn availability_chunk_shuffling<T: configuration::Config>() -> bool {
+	<configuration::Pallet<T>>::config().availability_chunk_shuffling
+}
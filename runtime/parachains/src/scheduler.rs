@@ -104,6 +104,25 @@ pub enum FreedReason {
 	TimedOut,
 }
 
+/// A handler for parathread claims leaving the scheduler.
+pub trait OnParathreadClaimEnded {
+	/// A candidate of the given parathread has been made available, fulfilling its claim.
+	///
+	/// This is also called when the candidate occupying the core was disputed and concluded
+	/// invalid, as that frees the core with [`FreedReason::Concluded`] as well.
+	fn claim_fulfilled(para_id: ParaId);
+
+	/// The claim of the given parathread has been dropped without a candidate becoming available,
+	/// either because it exceeded `parathread_retries` or because the para is no longer a
+	/// parathread.
+	fn claim_expired(para_id: ParaId);
+}
+
+impl OnParathreadClaimEnded for () {
+	fn claim_fulfilled(_: ParaId) {}
+	fn claim_expired(_: ParaId) {}
+}
+
 /// The assignment type.
 #[derive(Clone, Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(PartialEq, Debug))]
@@ -160,7 +179,10 @@ pub mod pallet {
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + configuration::Config + paras::Config {}
+	pub trait Config: frame_system::Config + configuration::Config + paras::Config {
+		/// Informed about parathread claims being fulfilled or expiring.
+		type OnClaimEnded: OnParathreadClaimEnded;
+	}
 
 	/// All the validator groups. One for each core. Indices are into `ActiveValidators` - not the
 	/// broader set of Polkadot validators, but instead just the subset used for parachains during
@@ -301,6 +323,9 @@ impl<T: Config> Pallet<T> {
 		ParathreadClaimIndex::<T>::mutate(|claim_index| {
 			// wipe all parathread metadata if no parathread cores are configured.
 			if config.parathread_cores == 0 {
+				for queued in thread_queue.queue.drain(..) {
+					T::OnClaimEnded::claim_expired(queued.claim.claim.0);
+				}
 				thread_queue = ParathreadClaimQueue { queue: Vec::new(), next_core_offset: 0 };
 				claim_index.clear();
 				return
@@ -318,6 +343,8 @@ impl<T: Config> Pallet<T> {
 					if let Ok(i) = claim_index.binary_search(&claim_para) {
 						claim_index.remove(i);
 					}
+
+					T::OnClaimEnded::claim_expired(claim_para);
 				}
 
 				will_keep
@@ -343,10 +370,11 @@ impl<T: Config> Pallet<T> {
 	/// assigned to a core, this call will fail. This call will also fail if the queue is full.
 	///
 	/// Fails if the claim does not correspond to any live parathread.
-	#[allow(unused)]
-	pub fn add_parathread_claim(claim: ParathreadClaim) {
+	///
+	/// Returns whether the claim has been added to the queue.
+	pub fn add_parathread_claim(claim: ParathreadClaim) -> bool {
 		if !<paras::Pallet<T>>::is_parathread(claim.0) {
-			return
+			return false
 		}
 
 		let config = <configuration::Pallet<T>>::config();
@@ -354,7 +382,7 @@ impl<T: Config> Pallet<T> {
 
 		ParathreadQueue::<T>::mutate(|queue| {
 			if queue.queue.len() >= queue_max_size as usize {
-				return
+				return false
 			}
 
 			let para_id = claim.0;
@@ -369,14 +397,23 @@ impl<T: Config> Pallet<T> {
 				});
 
 			if competes_with_another {
-				return
+				return false
 			}
 
 			let entry = ParathreadEntry { claim, retries: 0 };
 			queue.enqueue_entry(entry, config.parathread_cores);
+			true
 		})
 	}
 
+	/// Returns whether the parathread claim queue can take another claim.
+	pub fn can_queue_parathread_claim() -> bool {
+		let config = <configuration::Pallet<T>>::config();
+		let queue_max_size = config.parathread_cores * config.scheduling_lookahead;
+
+		(ParathreadQueue::<T>::decode_len().unwrap_or(0) as u32) < queue_max_size
+	}
+
 	/// Free unassigned cores. Provide a list of cores that should be considered newly-freed along with the reason
 	/// for them being freed. The list is assumed to be sorted in ascending order by core index.
 	pub(crate) fn free_cores(just_freed_cores: impl IntoIterator<Item = (CoreIndex, FreedReason)>) {
@@ -397,7 +434,8 @@ impl<T: Config> Pallet<T> {
 										if let Ok(i) = index.binary_search(&entry.claim.0) {
 											index.remove(i);
 										}
-									})
+									});
+									T::OnClaimEnded::claim_fulfilled(entry.claim.0);
 								},
 								FreedReason::TimedOut => {
 									// If a parathread candidate times out, it's not the collator's fault,
@@ -743,17 +781,23 @@ impl<T: Config> Pallet<T> {
 		ParathreadQueue::<T>::mutate(|queue| {
 			for core_assignment in Scheduled::<T>::take() {
 				if let AssignmentKind::Parathread(collator, retries) = core_assignment.kind {
-					if !<paras::Pallet<T>>::is_parathread(core_assignment.para_id) {
-						continue
-					}
-
 					let entry = ParathreadEntry {
 						claim: ParathreadClaim(core_assignment.para_id, collator),
 						retries: retries + 1,
 					};
 
-					if entry.retries <= config.parathread_retries {
+					if entry.retries <= config.parathread_retries &&
+						<paras::Pallet<T>>::is_parathread(core_assignment.para_id)
+					{
 						queue.enqueue_entry(entry, config.parathread_cores);
+					} else {
+						// The claim is dropped, so open the parathread up for further claims.
+						ParathreadClaimIndex::<T>::mutate(|index| {
+							if let Ok(i) = index.binary_search(&core_assignment.para_id) {
+								index.remove(i);
+							}
+						});
+						T::OnClaimEnded::claim_expired(core_assignment.para_id);
 					}
 				}
 			}
@@ -128,8 +128,8 @@ pub type Barrier = (
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type Call = Call;
-	type XcmSender = XcmRouter;
-	type AssetTransactor = LocalAssetTransactor;
+	type XcmSender = pallet_xcm::dry_run::RecordingRouter<XcmRouter>;
+	type AssetTransactor = pallet_xcm::dry_run::RecordingAssetTransactor<LocalAssetTransactor>;
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
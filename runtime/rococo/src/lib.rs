@@ -57,7 +57,7 @@ use sp_runtime::{
 		OpaqueKeys, SaturatedConversion, Verify,
 	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, KeyTypeId,
+	ApplyExtrinsicResult, KeyTypeId, Perbill,
 };
 use sp_staking::SessionIndex;
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
@@ -67,8 +67,9 @@ use sp_version::RuntimeVersion;
 
 use runtime_parachains::{
 	configuration as parachains_configuration, disputes as parachains_disputes,
-	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	disputes::slashing as parachains_slashing, dmp as parachains_dmp, hrmp as parachains_hrmp,
+	inclusion as parachains_inclusion, initializer as parachains_initializer,
+	on_demand as parachains_on_demand, origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, scheduler as parachains_scheduler,
 	session_info as parachains_session_info, shared as parachains_shared, ump as parachains_ump,
 };
@@ -233,6 +234,7 @@ construct_runtime! {
 		Hrmp: parachains_hrmp,
 		ParaSessionInfo: parachains_session_info,
 		ParasDisputes: parachains_disputes,
+		ParasSlashing: parachains_slashing,
 
 		// Parachain Onboarding Pallets
 		Registrar: paras_registrar::{Pallet, Call, Storage, Event<T>, Config},
@@ -277,6 +279,9 @@ construct_runtime! {
 		// Pallet for sending XCM.
 		XcmPallet: pallet_xcm = 99,
 
+		// On-demand parathread orders.
+		OnDemand: parachains_on_demand = 100,
+
 	}
 }
 
@@ -394,10 +399,32 @@ impl pallet_session::historical::Config for Runtime {
 impl parachains_disputes::Config for Runtime {
 	type Event = Event;
 	type RewardValidators = ();
-	type PunishValidators = ();
+	type PunishValidators = ParasSlashing;
 	type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const SlashForInvalid: Perbill = Perbill::from_percent(100);
+	pub const SlashAgainstValid: Perbill = Perbill::from_percent(1);
+	pub const SlashInconclusive: Perbill = Perbill::from_perthousand(1);
+	pub const DisputeSlashDeferDuration: BlockNumber = 1 * HOURS;
+	pub const MaxDisputeOffendersPerBlock: u32 = 100;
+}
+
+impl parachains_slashing::Config for Runtime {
+	type Event = Event;
+	type ValidatorSet = Historical;
+	type HistoricalIdentification = parachains_slashing::CurrentIdentification<Runtime>;
+	type HandleReports = Offences;
+	type SlashForInvalid = SlashForInvalid;
+	type SlashAgainstValid = SlashAgainstValid;
+	type SlashInconclusive = SlashInconclusive;
+	type SlashDeferDuration = DisputeSlashDeferDuration;
+	type CancelOrigin = EnsureRoot<AccountId>;
+	type MaxOffendersPerBlock = MaxDisputeOffendersPerBlock;
+	type WeightInfo = parachains_slashing::OffenceWeights<Runtime>;
+}
+
 parameter_types! {
 	pub SessionDuration: BlockNumber = EpochDurationInBlocks::get() as _;
 }
@@ -651,7 +678,24 @@ impl parachains_paras_inherent::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
 
-impl parachains_scheduler::Config for Runtime {}
+impl parachains_scheduler::Config for Runtime {
+	type OnClaimEnded = OnDemand;
+}
+
+parameter_types! {
+	pub const OnDemandBasePrice: Balance = 1 * DOLLARS;
+	pub const OnDemandMaxPriceMultiplier: u32 = 10;
+	pub const OnDemandMaxOrders: u32 = 100;
+}
+
+impl parachains_on_demand::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasePrice = OnDemandBasePrice;
+	type MaxPriceMultiplier = OnDemandMaxPriceMultiplier;
+	type MaxOrders = OnDemandMaxOrders;
+	type WeightInfo = weights::runtime_parachains_on_demand::WeightInfo<Runtime>;
+}
 
 impl parachains_initializer::Config for Runtime {
 	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
@@ -1247,6 +1291,10 @@ sp_api::impl_runtime_apis! {
 		{
 			runtime_api_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn availability_chunk_shuffling() -> bool {
+			runtime_api_impl::availability_chunk_shuffling::<Runtime>()
+		}
 	}
 
 	impl fg_primitives::GrandpaApi<Block> for Runtime {
@@ -1356,6 +1404,20 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl primitives::v2::OnDemandApi<Block, AccountId> for Runtime {
+		fn order_queue() -> Vec<primitives::v2::ParathreadOrder<AccountId>> {
+			OnDemand::order_queue()
+		}
+
+		fn active_orders() -> Vec<primitives::v2::ParathreadOrder<AccountId>> {
+			OnDemand::active_orders()
+		}
+
+		fn spot_price() -> Balance {
+			OnDemand::spot_price()
+		}
+	}
+
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
 		fn validator_set() -> Option<beefy_primitives::ValidatorSet<BeefyId>> {
 			Beefy::validator_set()
@@ -1545,6 +1607,16 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: Vec<u8>,
+			max_weight: frame_support::weights::Weight,
+		) -> Result<pallet_xcm::dry_run::XcmDryRunEffects, pallet_xcm::dry_run::XcmDryRunError> {
+			XcmPallet::dry_run_xcm(origin, message, max_weight)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -1558,6 +1630,7 @@ sp_api::impl_runtime_apis! {
 
 			list_benchmark!(list, extra, runtime_parachains::configuration, Configuration);
 			list_benchmark!(list, extra, runtime_parachains::disputes, ParasDisputes);
+			list_benchmark!(list, extra, runtime_parachains::on_demand, OnDemand);
 			list_benchmark!(list, extra, runtime_parachains::paras_inherent, ParaInherent);
 			list_benchmark!(list, extra, runtime_parachains::paras, Paras);
 
@@ -1591,6 +1664,7 @@ sp_api::impl_runtime_apis! {
 
 			add_benchmark!(params, batches, runtime_parachains::configuration, Configuration);
 			add_benchmark!(params, batches, runtime_parachains::disputes, ParasDisputes);
+			add_benchmark!(params, batches, runtime_parachains::on_demand, OnDemand);
 			add_benchmark!(params, batches, runtime_parachains::paras_inherent, ParaInherent);
 			add_benchmark!(params, batches, runtime_parachains::paras, Paras);
 
@@ -25,9 +25,11 @@ use parity_scale_codec::Encode;
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use polkadot_runtime_parachains::{
-	configuration as parachains_configuration, disputes as parachains_disputes,
+	approval_rewards as parachains_approval_rewards, configuration as parachains_configuration,
+	disputes as parachains_disputes, disputes::slashing as parachains_slashing,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, on_demand as parachains_on_demand,
+	origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, runtime_api_impl::v1 as runtime_impl,
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
 	shared as parachains_shared, ump as parachains_ump,
@@ -478,10 +480,38 @@ impl parachains_inclusion::Config for Runtime {
 impl parachains_disputes::Config for Runtime {
 	type Event = Event;
 	type RewardValidators = ();
-	type PunishValidators = ();
+	type PunishValidators = ParasSlashing;
 	type WeightInfo = parachains_disputes::TestWeightInfo;
 }
 
+parameter_types! {
+	pub storage SlashForInvalid: Perbill = Perbill::from_percent(100);
+	pub storage SlashAgainstValid: Perbill = Perbill::from_percent(1);
+	pub storage SlashInconclusive: Perbill = Perbill::from_perthousand(1);
+	pub storage DisputeSlashDeferDuration: BlockNumber = 10;
+	pub storage MaxDisputeOffendersPerBlock: u32 = 100;
+}
+
+impl parachains_slashing::Config for Runtime {
+	type Event = Event;
+	type ValidatorSet = Historical;
+	type HistoricalIdentification = parachains_slashing::EraExposure<Runtime>;
+	type HandleReports = Offences;
+	type SlashForInvalid = SlashForInvalid;
+	type SlashAgainstValid = SlashAgainstValid;
+	type SlashInconclusive = SlashInconclusive;
+	type SlashDeferDuration = DisputeSlashDeferDuration;
+	type CancelOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxOffendersPerBlock = MaxDisputeOffendersPerBlock;
+	type WeightInfo = parachains_slashing::TestWeightInfo;
+}
+
+impl parachains_approval_rewards::Config for Runtime {
+	type Event = Event;
+	type RewardApprovals = RewardValidatorsWithEraPoints<Runtime>;
+	type WeightInfo = parachains_approval_rewards::TestWeightInfo;
+}
+
 impl parachains_paras_inherent::Config for Runtime {
 	type WeightInfo = parachains_paras_inherent::TestWeightInfo;
 }
@@ -551,7 +581,24 @@ impl parachains_hrmp::Config for Runtime {
 	type Currency = Balances;
 }
 
-impl parachains_scheduler::Config for Runtime {}
+impl parachains_scheduler::Config for Runtime {
+	type OnClaimEnded = OnDemand;
+}
+
+parameter_types! {
+	pub const OnDemandBasePrice: Balance = 1_000;
+	pub const OnDemandMaxPriceMultiplier: u32 = 10;
+	pub const OnDemandMaxOrders: u32 = 100;
+}
+
+impl parachains_on_demand::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasePrice = OnDemandBasePrice;
+	type MaxPriceMultiplier = OnDemandMaxPriceMultiplier;
+	type MaxOrders = OnDemandMaxOrders;
+	type WeightInfo = parachains_on_demand::TestWeightInfo;
+}
 
 impl paras_sudo_wrapper::Config for Runtime {}
 
@@ -691,6 +738,9 @@ construct_runtime! {
 		Dmp: parachains_dmp::{Pallet, Call, Storage},
 		Xcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
 		ParasDisputes: parachains_disputes::{Pallet, Storage, Event<T>},
+		ParasSlashing: parachains_slashing::{Pallet, Call, Storage, Event<T>},
+		ParasApprovalRewards: parachains_approval_rewards::{Pallet, Call, Storage, Event<T>, Inherent},
+		OnDemand: parachains_on_demand::{Pallet, Call, Storage, Event<T>},
 
 		Sudo: pallet_sudo::{Pallet, Call, Storage, Config<T>, Event<T>},
 
@@ -894,6 +944,24 @@ sp_api::impl_runtime_apis! {
 		{
 			runtime_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn availability_chunk_shuffling() -> bool {
+			runtime_impl::availability_chunk_shuffling::<Runtime>()
+		}
+	}
+
+	impl primitives::v2::OnDemandApi<Block, AccountId> for Runtime {
+		fn order_queue() -> Vec<primitives::v2::ParathreadOrder<AccountId>> {
+			OnDemand::order_queue()
+		}
+
+		fn active_orders() -> Vec<primitives::v2::ParathreadOrder<AccountId>> {
+			OnDemand::active_orders()
+		}
+
+		fn spot_price() -> Balance {
+			OnDemand::spot_price()
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -1029,6 +1097,16 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: Vec<u8>,
+			max_weight: frame_support::weights::Weight,
+		) -> Result<pallet_xcm::dry_run::XcmDryRunEffects, pallet_xcm::dry_run::XcmDryRunError> {
+			Xcm::dry_run_xcm(origin, message, max_weight)
+		}
+	}
+
 	impl crate::GetLastTimestamp<Block> for Runtime {
 		fn get_last_timestamp() -> u64 {
 			Timestamp::now()
@@ -79,8 +79,8 @@ impl InvertLocation for InvertNothing {
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type Call = super::Call;
-	type XcmSender = DoNothingRouter;
-	type AssetTransactor = DummyAssetTransactor;
+	type XcmSender = pallet_xcm::dry_run::RecordingRouter<DoNothingRouter>;
+	type AssetTransactor = pallet_xcm::dry_run::RecordingAssetTransactor<DummyAssetTransactor>;
 	type OriginConverter = pallet_xcm::XcmPassthrough<super::Origin>;
 	type IsReserve = ();
 	type IsTeleporter = ();
@@ -32,8 +32,10 @@ mod dry_run;
 mod emergency_solution;
 mod monitor;
 mod prelude;
+mod prometheus;
 mod rpc_helpers;
 mod signer;
+mod snapshot;
 
 pub(crate) use prelude::*;
 pub(crate) use signer::get_account_info;
@@ -43,7 +45,8 @@ use frame_support::traits::Get;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use remote_externalities::{Builder, Mode, OnlineConfig};
 use sp_npos_elections::ExtendedBalance;
-use sp_runtime::{traits::Block as BlockT, DeserializeOwned};
+use sp_runtime::{traits::Block as BlockT, DeserializeOwned, Perbill};
+use std::{path::PathBuf, str::FromStr};
 use structopt::StructOpt;
 
 pub(crate) enum AnyRuntime {
@@ -240,6 +243,8 @@ enum Error<T: EPM::Config> {
 	IncorrectPhase,
 	AlreadySubmitted,
 	VersionMismatch,
+	StrategyNotSatisfied,
+	Prometheus(#[from] prometheus_endpoint::PrometheusError),
 }
 
 impl<T: EPM::Config> From<sp_core::crypto::SecretStringError> for Error<T> {
@@ -280,6 +285,16 @@ enum Command {
 	DryRun(DryRunConfig),
 	/// Provide a solution that can be submitted to the chain as an emergency response.
 	EmergencySolution(EmergencySolutionConfig),
+	/// Manage election snapshot files.
+	Snapshot(SnapshotCommand),
+	/// Mine solutions offline from a snapshot file with all solvers, and compare them.
+	Mine(MineConfig),
+}
+
+#[derive(Debug, Clone, StructOpt)]
+enum SnapshotCommand {
+	/// Export the election snapshot to a file, to be mined offline with `mine`.
+	Export(SnapshotExportConfig),
 }
 
 #[derive(Debug, Clone, StructOpt)]
@@ -294,6 +309,41 @@ enum Solvers {
 	},
 }
 
+/// When to submit a mined solution, given the solutions already queued on chain.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq))]
+enum SubmissionStrategy {
+	/// Always submit.
+	Always,
+	/// Only submit if our solution is better than all queued solutions.
+	IfLeading,
+	/// Only submit if our solution is better than all queued solutions by at least the given
+	/// fraction.
+	IfBetterThanBy(Perbill),
+}
+
+impl FromStr for SubmissionStrategy {
+	type Err = String;
+
+	/// Parse `always`, `if-leading` or `if-better-than-by-percent=<percent>`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if s == "always" {
+			Ok(Self::Always)
+		} else if s == "if-leading" {
+			Ok(Self::IfLeading)
+		} else if let Some(percent) = s.strip_prefix("if-better-than-by-percent=") {
+			let percent: u32 = percent.parse().map_err(|e| format!("{:?}", e))?;
+			if percent > 100 {
+				return Err(format!("percentage must be at most 100, got {}", percent))
+			}
+			Ok(Self::IfBetterThanBy(Perbill::from_percent(percent)))
+		} else {
+			Err(format!("unknown submission strategy: {:?}", s))
+		}
+	}
+}
+
 frame_support::parameter_types! {
 	/// Number of balancing iterations for a solution algorithm. Set based on the [`Solvers`] CLI
 	/// config.
@@ -311,6 +361,22 @@ struct MonitorConfig {
 	#[structopt(long, default_value = "head", possible_values = &["head", "finalized"])]
 	listen: String,
 
+	/// When to submit a mined solution, given the solutions already queued on chain.
+	///
+	/// `always`: always submit. `if-leading`: only submit if our solution is better than all
+	/// queued solutions. `if-better-than-by-percent=<percent>`: only submit if our solution is
+	/// better than all queued solutions by at least the given percentage.
+	#[structopt(long, default_value = "always")]
+	submission_strategy: SubmissionStrategy,
+
+	/// Expose Prometheus metrics on the given port.
+	#[structopt(long)]
+	prometheus_port: Option<u16>,
+
+	/// Expose Prometheus metrics on all interfaces, not only on localhost.
+	#[structopt(long)]
+	prometheus_external: bool,
+
 	/// The solver algorithm to use.
 	#[structopt(subcommand)]
 	solver: Solvers,
@@ -343,18 +409,43 @@ struct DryRunConfig {
 	solver: Solvers,
 }
 
+#[derive(Debug, Clone, StructOpt)]
+struct SnapshotExportConfig {
+	/// The block hash at which scraping happens. If none is provided, the latest head is used.
+	///
+	/// If there is no snapshot at this block, one is created.
+	#[structopt(long)]
+	at: Option<Hash>,
+
+	/// The file to write the snapshot to.
+	#[structopt(long, parse(from_os_str), default_value = "snapshot.bin")]
+	output: PathBuf,
+}
+
+#[derive(Debug, Clone, StructOpt)]
+struct MineConfig {
+	/// The snapshot file to mine from, as written by `snapshot export`.
+	#[structopt(long, parse(from_os_str))]
+	from_snapshot: PathBuf,
+
+	/// The numbers of balancing iterations to mine with, separated by commas. Each solver is run
+	/// once with each of them.
+	#[structopt(long, default_value = "0,10", use_delimiter = true)]
+	iterations: Vec<usize>,
+}
+
 #[derive(Debug, Clone, StructOpt)]
 struct SharedConfig {
 	/// The `ws` node to connect to.
 	#[structopt(long, short, default_value = DEFAULT_URI, env = "URI")]
 	uri: String,
 
-	/// The seed of a funded account in hex.
+	/// The seed of a funded account in hex. Only needed by the `monitor` and `dry-run` commands.
 	///
 	/// WARNING: Don't use an account with a large stash for this. Based on how the bot is
 	/// configured, it might re-try and lose funds through transaction fees/deposits.
 	#[structopt(long, short, env = "SEED")]
-	seed: String,
+	seed: Option<String>,
 }
 
 #[derive(Debug, Clone, StructOpt)]
@@ -517,37 +608,10 @@ pub(crate) async fn check_versions<T: frame_system::Config + EPM::Config>(
 	}
 }
 
-#[tokio::main]
-async fn main() {
-	env_logger::Builder::from_default_env()
-		.format_module_path(true)
-		.format_level(true)
-		.init();
-	let Opt { shared, command } = Opt::from_args();
-	log::debug!(target: LOG_TARGET, "attempting to connect to {:?}", shared.uri);
-
-	let client = loop {
-		let maybe_client = WsClientBuilder::default()
-			.connection_timeout(std::time::Duration::new(20, 0))
-			.max_request_body_size(u32::MAX)
-			.build(&shared.uri)
-			.await;
-		match maybe_client {
-			Ok(client) => break client,
-			Err(why) => {
-				log::warn!(
-					target: LOG_TARGET,
-					"failed to connect to client due to {:?}, retrying soon..",
-					why
-				);
-				std::thread::sleep(std::time::Duration::from_millis(2500));
-			},
-		}
-	};
-
-	let chain = rpc_helpers::rpc::<String>(&client, "system_chain", None)
-		.await
-		.expect("system_chain infallible; qed.");
+/// Set up the runtime, the address format and the token for `chain`.
+///
+/// Returns `false` if the chain is not supported.
+fn set_runtime(chain: &str) -> bool {
 	match chain.to_lowercase().as_str() {
 		"polkadot" | "development" => {
 			sp_core::crypto::set_default_ss58_version(
@@ -585,10 +649,73 @@ async fn main() {
 				RUNTIME = AnyRuntime::Westend;
 			}
 		},
-		_ => {
-			eprintln!("unexpected chain: {:?}", chain);
+		_ => return false,
+	}
+
+	true
+}
+
+/// The signer is checked to be present for the commands that need it.
+const SIGNER_CHECKED: &str = "signer is checked to exist for commands that sign; qed.";
+
+#[tokio::main]
+async fn main() {
+	env_logger::Builder::from_default_env()
+		.format_module_path(true)
+		.format_level(true)
+		.init();
+	let Opt { shared, command } = Opt::from_args();
+
+	// mining from a snapshot file is done offline.
+	if let Command::Mine(ref config) = command {
+		let file = match snapshot::SnapshotFile::load(&config.from_snapshot) {
+			Ok(file) => file,
+			Err(why) => {
+				eprintln!("{}", why);
+				return
+			},
+		};
+		if !set_runtime(&file.chain) {
+			eprintln!("unexpected chain in snapshot file: {:?}", file.chain);
 			return
-		},
+		}
+
+		let outcome = any_runtime! {
+			snapshot::mine_from_snapshot_cmd::<Runtime>(file, config.clone()).map_err(|e| {
+				log::error!(target: LOG_TARGET, "Mine error: {:?}", e);
+			})
+		};
+		log::info!(target: LOG_TARGET, "round of execution finished. outcome = {:?}", outcome);
+		return
+	}
+
+	log::debug!(target: LOG_TARGET, "attempting to connect to {:?}", shared.uri);
+
+	let client = loop {
+		let maybe_client = WsClientBuilder::default()
+			.connection_timeout(std::time::Duration::new(20, 0))
+			.max_request_body_size(u32::MAX)
+			.build(&shared.uri)
+			.await;
+		match maybe_client {
+			Ok(client) => break client,
+			Err(why) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"failed to connect to client due to {:?}, retrying soon..",
+					why
+				);
+				std::thread::sleep(std::time::Duration::from_millis(2500));
+			},
+		}
+	};
+
+	let chain = rpc_helpers::rpc::<String>(&client, "system_chain", None)
+		.await
+		.expect("system_chain infallible; qed.");
+	if !set_runtime(&chain) {
+		eprintln!("unexpected chain: {:?}", chain);
+		return
 	}
 	log::info!(target: LOG_TARGET, "connected to chain {:?}", chain);
 
@@ -596,19 +723,26 @@ async fn main() {
 		check_versions::<Runtime>(&client).await
 	};
 
-	let signer_account = any_runtime! {
-		signer::signer_uri_from_string::<Runtime>(&shared.seed, &client)
-			.await
-			.expect("Provided account is invalid, terminating.")
+	let signer_account = match shared.seed {
+		Some(ref seed) => Some(any_runtime! {
+			signer::signer_uri_from_string::<Runtime>(seed, &client)
+				.await
+				.expect("Provided account is invalid, terminating.")
+		}),
+		None => None,
 	};
+	if signer_account.is_none() && matches!(command, Command::Monitor(_) | Command::DryRun(_)) {
+		eprintln!("a seed is required to sign transactions, provide one with --seed");
+		return
+	}
 
 	let outcome = any_runtime! {
 		match command.clone() {
-			Command::Monitor(c) => monitor_cmd(&client, shared, c, signer_account).await
+			Command::Monitor(c) => monitor_cmd(&client, shared, c, signer_account.expect(SIGNER_CHECKED)).await
 				.map_err(|e| {
 					log::error!(target: LOG_TARGET, "Monitor error: {:?}", e);
 				}),
-			Command::DryRun(c) => dry_run_cmd(&client, shared, c, signer_account).await
+			Command::DryRun(c) => dry_run_cmd(&client, shared, c, signer_account.expect(SIGNER_CHECKED)).await
 				.map_err(|e| {
 					log::error!(target: LOG_TARGET, "DryRun error: {:?}", e);
 				}),
@@ -616,6 +750,12 @@ async fn main() {
 				.map_err(|e| {
 					log::error!(target: LOG_TARGET, "EmergencySolution error: {:?}", e);
 				}),
+			Command::Snapshot(SnapshotCommand::Export(c)) =>
+				snapshot::snapshot_export_cmd::<Runtime, Block>(shared.clone(), c, chain.clone()).await
+				.map_err(|e| {
+					log::error!(target: LOG_TARGET, "SnapshotExport error: {:?}", e);
+				}),
+			Command::Mine(_) => unreachable!("mine command is handled offline above; qed."),
 		}
 	};
 	log::info!(target: LOG_TARGET, "round of execution finished. outcome = {:?}", outcome);
@@ -644,4 +784,17 @@ mod tests {
 		assert_eq!(polkadot_version.spec_name, "polkadot".into());
 		assert_eq!(kusama_version.spec_name, "kusama".into());
 	}
+
+	#[test]
+	fn submission_strategy_from_str_works() {
+		assert_eq!(SubmissionStrategy::from_str("always"), Ok(SubmissionStrategy::Always));
+		assert_eq!(SubmissionStrategy::from_str("if-leading"), Ok(SubmissionStrategy::IfLeading));
+		assert_eq!(
+			SubmissionStrategy::from_str("if-better-than-by-percent=5"),
+			Ok(SubmissionStrategy::IfBetterThanBy(Perbill::from_percent(5)))
+		);
+		assert!(SubmissionStrategy::from_str("if-better-than-by-percent=101").is_err());
+		assert!(SubmissionStrategy::from_str("if-better-than-by-percent=x").is_err());
+		assert!(SubmissionStrategy::from_str("sometimes").is_err());
+	}
 }
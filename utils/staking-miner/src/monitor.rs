@@ -16,7 +16,13 @@
 
 //! The monitor command.
 
-use crate::{prelude::*, rpc_helpers::*, signer::Signer, Error, MonitorConfig, SharedConfig};
+use crate::{
+	prelude::*,
+	prometheus::{self, SubmissionOutcome},
+	rpc_helpers::*,
+	signer::Signer,
+	Error, MonitorConfig, SharedConfig, SubmissionStrategy,
+};
 use codec::Encode;
 use jsonrpsee::{
 	rpc_params,
@@ -26,6 +32,8 @@ use jsonrpsee::{
 
 use sc_transaction_pool_api::TransactionStatus;
 use sp_core::storage::StorageKey;
+use sp_npos_elections::ElectionScore;
+use sp_runtime::Perbill;
 
 /// Ensure that now is the signed phase.
 async fn ensure_signed_phase<T: EPM::Config, B: BlockT>(
@@ -63,6 +71,40 @@ async fn ensure_no_previous_solution<
 	})
 }
 
+/// Ensure that our solution with `score` should be submitted according to `strategy`, given the
+/// solutions already queued.
+fn ensure_strategy_met<T: EPM::Config, B: BlockT>(
+	ext: &mut Ext,
+	score: ElectionScore,
+	strategy: SubmissionStrategy,
+) -> Result<(), Error<T>> {
+	use EPM::signed::SignedSubmissions;
+	ext.execute_with(|| {
+		let queued = <SignedSubmissions<T>>::get();
+		if strategy_met(score, queued.iter().map(|ss| ss.raw_solution.score), strategy) {
+			Ok(())
+		} else {
+			Err(Error::StrategyNotSatisfied)
+		}
+	})
+}
+
+/// Whether a solution with `score` should be submitted according to `strategy`, given the scores
+/// of the solutions already queued.
+fn strategy_met(
+	score: ElectionScore,
+	mut queued: impl Iterator<Item = ElectionScore>,
+	strategy: SubmissionStrategy,
+) -> bool {
+	let epsilon = match strategy {
+		SubmissionStrategy::Always => return true,
+		SubmissionStrategy::IfLeading => Perbill::zero(),
+		SubmissionStrategy::IfBetterThanBy(epsilon) => epsilon,
+	};
+
+	queued.all(|queued| sp_npos_elections::is_score_better(score, queued, epsilon))
+}
+
 macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 	/// The monitor command.
 	pub(crate) async fn [<monitor_cmd_ $runtime>](
@@ -73,6 +115,50 @@ macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 	) -> Result<(), Error<$crate::[<$runtime _runtime_exports>]::Runtime>> {
 		use $crate::[<$runtime _runtime_exports>]::*;
 
+		/// The fee paid by the extrinsic `bytes` in block `at`, from the balance events of `who`
+		/// emitted by it, if the extrinsic is in the block.
+		async fn fee_paid(
+			client: &WsClient,
+			at: <Block as BlockT>::Hash,
+			bytes: &[u8],
+			who: &AccountId,
+		) -> Result<Option<Balance>, RpcHelperError> {
+			let block = rpc::<sp_runtime::generic::SignedBlock<Block>>(
+				client,
+				"chain_getBlock",
+				rpc_params! { at },
+			).await?;
+			let index = match block.block.extrinsics.iter().position(|xt| xt.encode() == bytes) {
+				Some(index) => index as u32,
+				None => return Ok(None),
+			};
+
+			let key = StorageKey(frame_support::storage::storage_prefix(b"System", b"Events").to_vec());
+			let events = get_storage::<Vec<frame_system::EventRecord<Event, <Block as BlockT>::Hash>>>(
+				client,
+				rpc_params! { key, at },
+			).await?.unwrap_or_default();
+
+			// the estimated fee is withdrawn before dispatch, and any excess refunded afterwards.
+			let (withdrawn, refunded) = events
+				.into_iter()
+				.filter(|record| record.phase == frame_system::Phase::ApplyExtrinsic(index))
+				.fold((0 as Balance, 0 as Balance), |(withdrawn, refunded), record| match record.event {
+					Event::Balances(pallet_balances::Event::Withdraw { who: account, amount }) if &account == who =>
+						(withdrawn.saturating_add(amount), refunded),
+					Event::Balances(pallet_balances::Event::Deposit { who: account, amount }) if &account == who =>
+						(withdrawn, refunded.saturating_add(amount)),
+					_ => (withdrawn, refunded),
+				});
+
+			Ok(Some(withdrawn.saturating_sub(refunded)))
+		}
+
+		let metrics = match config.prometheus_port {
+			Some(port) => prometheus::start(port, config.prometheus_external)?,
+			None => Default::default(),
+		};
+
 		let (sub, unsub) = if config.listen == "head" {
 			("chain_subscribeNewHeads", "chain_unsubscribeNewHeads")
 		} else {
@@ -112,9 +198,21 @@ macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 				}
 
 				// mine a solution, and run feasibility check on it as well.
+				let mining_start = std::time::Instant::now();
 				let (raw_solution, witness) = crate::mine_with::<Runtime>(&config.solver, &mut ext, true)?;
+				metrics.on_solution_mined(raw_solution.score, mining_start.elapsed());
 				log::info!(target: LOG_TARGET, "mined solution with {:?}", &raw_solution.score);
 
+				if ensure_strategy_met::<Runtime, Block>(&mut ext, raw_solution.score, config.submission_strategy).is_err() {
+					log::info!(
+						target: LOG_TARGET,
+						"submission strategy {:?} not met by our solution, skipping.",
+						config.submission_strategy,
+					);
+					metrics.on_submission(SubmissionOutcome::Skipped);
+					continue;
+				}
+
 				let nonce = crate::get_account_info::<Runtime>(client, &signer.account, Some(hash))
 					.await?
 					.map(|i| i.nonce)
@@ -134,7 +232,7 @@ macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 				let mut tx_subscription: Subscription<
 					TransactionStatus<<Block as BlockT>::Hash, <Block as BlockT>::Hash>
 				> = match client
-					.subscribe(&"author_submitAndWatchExtrinsic", rpc_params! { bytes }, "author_unwatchExtrinsic")
+					.subscribe(&"author_submitAndWatchExtrinsic", rpc_params! { bytes.clone() }, "author_unwatchExtrinsic")
 					.await
 				{
 					Ok(sub) => sub,
@@ -147,10 +245,13 @@ macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 					// other fancy features, we should make this multi-threaded and do the
 					// computation outside of this callback.
 						log::warn!(target: LOG_TARGET, "failing to submit a transaction {:?}. continuing...", why);
+						metrics.on_submission(SubmissionOutcome::Failed);
 						continue
 					}
 				};
 
+				// only the final outcome of the submission is recorded, once.
+				let mut outcome = SubmissionOutcome::Dropped;
 				while let Some(status_update) = tx_subscription.next().await? {
 					log::trace!(target: LOG_TARGET, "status update {:?}", status_update);
 					match status_update {
@@ -167,6 +268,13 @@ macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 						}
 						TransactionStatus::Finalized(hash) => {
 							log::info!(target: LOG_TARGET, "Finalized at {:?}", hash);
+							outcome = SubmissionOutcome::Finalized;
+							// the fee actually paid is only known from the events of the finalized block.
+							match fee_paid(client, hash, &bytes, &signer.account).await {
+								Ok(Some(fee)) => metrics.on_fee_paid(fee),
+								Ok(None) => log::warn!(target: LOG_TARGET, "solution not found in finalized block {:?}", hash),
+								Err(why) => log::warn!(target: LOG_TARGET, "failed to get the fee paid: {:?}", why),
+							}
 							break
 						}
 						_ => {
@@ -175,6 +283,7 @@ macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 						}
 					}
 				};
+				metrics.on_submission(outcome);
 			}
 
 			log::warn!(target: LOG_TARGET, "subscription to {} terminated. Retrying..", sub)
@@ -185,3 +294,56 @@ macro_rules! monitor_cmd_for { ($runtime:tt) => { paste::paste! {
 monitor_cmd_for!(polkadot);
 monitor_cmd_for!(kusama);
 monitor_cmd_for!(westend);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn strategy_met_works() {
+		let queued = [[10, 100, 100], [20, 100, 100]];
+
+		// better than all queued solutions.
+		let score = [30, 100, 100];
+		assert!(strategy_met(score, queued.iter().copied(), SubmissionStrategy::Always));
+		assert!(strategy_met(score, queued.iter().copied(), SubmissionStrategy::IfLeading));
+		assert!(strategy_met(
+			score,
+			queued.iter().copied(),
+			SubmissionStrategy::IfBetterThanBy(Perbill::from_percent(10))
+		));
+		assert!(!strategy_met(
+			score,
+			queued.iter().copied(),
+			SubmissionStrategy::IfBetterThanBy(Perbill::from_percent(60))
+		));
+
+		// only better than some queued solutions.
+		let score = [15, 100, 100];
+		assert!(strategy_met(score, queued.iter().copied(), SubmissionStrategy::Always));
+		assert!(!strategy_met(score, queued.iter().copied(), SubmissionStrategy::IfLeading));
+
+		// nothing queued.
+		assert!(strategy_met(score, std::iter::empty(), SubmissionStrategy::IfLeading));
+	}
+
+	#[test]
+	fn ensure_strategy_met_without_queued_solutions() {
+		use crate::polkadot_runtime_exports::{Block, Runtime};
+
+		let mut ext = Ext::default();
+		let score = [15, 100, 100];
+		assert!(ensure_strategy_met::<Runtime, Block>(
+			&mut ext,
+			score,
+			SubmissionStrategy::IfLeading
+		)
+		.is_ok());
+		assert!(ensure_strategy_met::<Runtime, Block>(
+			&mut ext,
+			score,
+			SubmissionStrategy::IfBetterThanBy(Perbill::from_percent(50))
+		)
+		.is_ok());
+	}
+}
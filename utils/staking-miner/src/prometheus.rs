@@ -0,0 +1,158 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The Prometheus metrics of the monitor command.
+
+use crate::prelude::*;
+use prometheus_endpoint::{
+	register, Counter, CounterVec, Gauge, GaugeVec, Histogram, HistogramOpts, Opts,
+	PrometheusError, Registry, F64, U64,
+};
+use sp_npos_elections::ElectionScore;
+use std::{net::SocketAddr, time::Duration};
+
+/// The outcome of an attempt to submit a solution.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SubmissionOutcome {
+	/// The solution was not submitted, as the submission strategy was not met.
+	Skipped,
+	/// The transaction could not be submitted.
+	Failed,
+	/// The transaction was finalized.
+	Finalized,
+	/// The transaction was dropped, invalidated or usurped, or we stopped watching it before it
+	/// was finalized.
+	Dropped,
+}
+
+impl SubmissionOutcome {
+	fn as_label(&self) -> &'static str {
+		match self {
+			Self::Skipped => "skipped",
+			Self::Failed => "failed",
+			Self::Finalized => "finalized",
+			Self::Dropped => "dropped",
+		}
+	}
+}
+
+#[derive(Clone)]
+struct MetricsInner {
+	mined_score: GaugeVec<F64>,
+	mining_duration: Histogram,
+	submissions: CounterVec<U64>,
+	fees_paid: Counter<F64>,
+	last_fee: Gauge<F64>,
+}
+
+/// The metrics of the monitor command. All methods are no-ops if Prometheus is not enabled.
+#[derive(Default, Clone)]
+pub(crate) struct Metrics(Option<MetricsInner>);
+
+impl Metrics {
+	/// Register the metrics in `registry`.
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let metrics = MetricsInner {
+			mined_score: register(
+				GaugeVec::new(
+					Opts::new(
+						"staking_miner_mined_score",
+						"The score of the last mined solution, by component.",
+					),
+					&["component"],
+				)?,
+				registry,
+			)?,
+			mining_duration: register(
+				Histogram::with_opts(HistogramOpts::new(
+					"staking_miner_mining_duration_seconds",
+					"Time spent mining a solution, including the feasibility check.",
+				))?,
+				registry,
+			)?,
+			submissions: register(
+				CounterVec::new(
+					Opts::new(
+						"staking_miner_submissions_total",
+						"Number of solutions mined, by the outcome of their submission.",
+					),
+					&["outcome"],
+				)?,
+				registry,
+			)?,
+			fees_paid: register(
+				Counter::new(
+					"staking_miner_fees_paid_total",
+					"Transaction fees paid for finalized solutions, in the smallest unit.",
+				)?,
+				registry,
+			)?,
+			last_fee: register(
+				Gauge::new(
+					"staking_miner_last_fee",
+					"Transaction fee paid for the last finalized solution, in the smallest unit.",
+				)?,
+				registry,
+			)?,
+		};
+		Ok(Metrics(Some(metrics)))
+	}
+
+	/// Record a freshly mined solution.
+	pub(crate) fn on_solution_mined(&self, score: ElectionScore, duration: Duration) {
+		if let Some(metrics) = &self.0 {
+			let components = ["minimal_stake", "sum_stake", "sum_stake_squared"];
+			for (component, value) in components.iter().zip(score.iter()) {
+				metrics.mined_score.with_label_values(&[component]).set(*value as f64);
+			}
+			metrics.mining_duration.observe(duration.as_secs_f64());
+		}
+	}
+
+	/// Record the final outcome of submitting a solution.
+	pub(crate) fn on_submission(&self, outcome: SubmissionOutcome) {
+		if let Some(metrics) = &self.0 {
+			metrics.submissions.with_label_values(&[outcome.as_label()]).inc();
+		}
+	}
+
+	/// Record the fee paid for a finalized solution.
+	pub(crate) fn on_fee_paid(&self, fee: Balance) {
+		if let Some(metrics) = &self.0 {
+			metrics.fees_paid.inc_by(fee as f64);
+			metrics.last_fee.set(fee as f64);
+		}
+	}
+}
+
+/// Register the metrics and serve them on the given port in the background.
+///
+/// Only local connections are accepted, unless `external` is set.
+pub(crate) fn start(port: u16, external: bool) -> Result<Metrics, PrometheusError> {
+	let registry = Registry::new();
+	let metrics = Metrics::register(&registry)?;
+
+	let interface = if external { [0, 0, 0, 0] } else { [127, 0, 0, 1] };
+	let addr = SocketAddr::from((interface, port));
+	tokio::spawn(async move {
+		if let Err(why) = prometheus_endpoint::init_prometheus(addr, registry).await {
+			log::warn!(target: LOG_TARGET, "prometheus endpoint failed: {:?}", why);
+		}
+	});
+	log::info!(target: LOG_TARGET, "serving prometheus metrics on {}", addr);
+
+	Ok(metrics)
+}
@@ -0,0 +1,259 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The snapshot export and the offline mine commands.
+
+use crate::{
+	dry_run::force_create_snapshot, prelude::*, Error, MineConfig, SharedConfig,
+	SnapshotExportConfig, Solvers,
+};
+use codec::{Decode, Encode};
+use frame_election_provider_support::NposSolver;
+use frame_support::{traits::Get, weights::Weight};
+use sp_runtime::DeserializeOwned;
+use std::{path::Path, time::Instant};
+
+/// Everything needed to mine a solution offline, as stored in a snapshot file.
+#[derive(Debug, Encode, Decode)]
+pub(crate) struct SnapshotFile {
+	/// The chain the snapshot was taken from.
+	pub(crate) chain: String,
+	/// The spec version of the runtime at the time the snapshot was taken.
+	pub(crate) spec_version: u32,
+	/// The round of the election.
+	pub(crate) round: u32,
+	/// The number of winners to elect.
+	pub(crate) desired_targets: u32,
+	/// The size of the snapshot.
+	pub(crate) snapshot_metadata: EPM::SolutionOrSnapshotSize,
+	/// The encoded `RoundSnapshot`.
+	pub(crate) snapshot: Vec<u8>,
+	/// The maximum weight of a solution mined by the runtime.
+	pub(crate) miner_max_weight: Weight,
+	/// The maximum length of a solution mined by the runtime.
+	pub(crate) miner_max_length: u32,
+}
+
+impl SnapshotFile {
+	/// Read a snapshot file.
+	pub(crate) fn load(path: &Path) -> Result<Self, String> {
+		let bytes =
+			std::fs::read(path).map_err(|e| format!("failed to read {:?}: {:?}", path, e))?;
+		Self::decode(&mut &bytes[..]).map_err(|e| format!("failed to decode {:?}: {:?}", path, e))
+	}
+
+	/// Build externalities holding the snapshot, ready to be mined.
+	fn into_ext<T: EPM::Config>(self) -> Ext {
+		let mut ext = Ext::new_empty();
+		ext.execute_with(|| {
+			<EPM::Round<T>>::put(self.round);
+			<EPM::DesiredTargets<T>>::put(self.desired_targets);
+			<EPM::SnapshotMetadata<T>>::put(self.snapshot_metadata);
+			frame_support::storage::unhashed::put_raw(
+				&<EPM::Snapshot<T>>::hashed_key(),
+				&self.snapshot,
+			);
+		});
+		ext
+	}
+}
+
+/// Execute the snapshot export command.
+pub(crate) async fn snapshot_export_cmd<T, B>(
+	shared: SharedConfig,
+	config: SnapshotExportConfig,
+	chain: String,
+) -> Result<(), Error<T>>
+where
+	T: EPM::Config,
+	B: BlockT + DeserializeOwned,
+{
+	let mut ext = crate::create_election_ext::<T, B>(
+		shared.uri.clone(),
+		config.at,
+		vec!["Staking".to_string(), "System".to_string()],
+	)
+	.await?;
+	force_create_snapshot::<T>(&mut ext)?;
+
+	let file = ext.execute_with(|| SnapshotFile {
+		chain,
+		spec_version: T::Version::get().spec_version,
+		round: <EPM::Pallet<T>>::round(),
+		desired_targets: <EPM::Pallet<T>>::desired_targets()
+			.expect("snapshot must exist by now; qed."),
+		snapshot_metadata: <EPM::Pallet<T>>::snapshot_metadata()
+			.expect("snapshot must exist by now; qed."),
+		snapshot: <EPM::Pallet<T>>::snapshot().expect("snapshot must exist by now; qed.").encode(),
+		miner_max_weight: T::MinerMaxWeight::get(),
+		miner_max_length: T::MinerMaxLength::get(),
+	});
+
+	std::fs::write(&config.output, file.encode())?;
+	log::info!(
+		target: LOG_TARGET,
+		"exported snapshot of round {} with {:?} and {} desired targets to {:?}",
+		file.round,
+		file.snapshot_metadata,
+		file.desired_targets,
+		config.output,
+	);
+	Ok(())
+}
+
+/// Execute the mine command, mining the given snapshot with all solvers.
+pub(crate) fn mine_from_snapshot_cmd<T>(
+	file: SnapshotFile,
+	config: MineConfig,
+) -> Result<(), Error<T>>
+where
+	T: EPM::Config,
+	T::Solver: NposSolver<Error = sp_npos_elections::Error>,
+{
+	if file.spec_version != T::Version::get().spec_version {
+		log::warn!(
+			target: LOG_TARGET,
+			"snapshot taken with spec version {}, mining with spec version {}",
+			file.spec_version,
+			T::Version::get().spec_version,
+		);
+	}
+
+	let (size, max_weight, max_length) =
+		(file.snapshot_metadata, file.miner_max_weight, file.miner_max_length);
+	let mut ext = file.into_ext::<T>();
+
+	println!(
+		"{:<12} {:>10} {:>24} {:>24} {:>24} {:>14} {:>8} {:>10} {:>8}",
+		"solver",
+		"iterations",
+		"minimal stake",
+		"sum stake",
+		"sum stake squared",
+		"weight",
+		"length",
+		"time (ms)",
+		"feasible",
+	);
+
+	for iterations in config.iterations {
+		let solvers = [
+			("seq-phragmen", Solvers::SeqPhragmen { iterations }),
+			("phrag-mms", Solvers::PhragMMS { iterations }),
+		];
+		for (name, solver) in solvers.iter() {
+			let start = Instant::now();
+			let (raw_solution, _witness) = crate::mine_with::<T>(solver, &mut ext, false)?;
+			let elapsed = start.elapsed();
+
+			let (weight, length, feasible) = ext.execute_with(|| {
+				let weight = <EPM::Pallet<T>>::solution_weight_of(&raw_solution, size);
+				let length = raw_solution.encode().len();
+				let feasible = <EPM::Pallet<T>>::feasibility_check(
+					raw_solution.clone(),
+					EPM::ElectionCompute::Signed,
+				)
+				.is_ok();
+				(weight, length, feasible)
+			});
+
+			let [minimal_stake, sum_stake, sum_stake_squared] = raw_solution.score;
+			println!(
+				"{:<12} {:>10} {:>24} {:>24} {:>24} {:>14} {:>8} {:>10} {:>8}",
+				name,
+				iterations,
+				Token::from(minimal_stake).to_string(),
+				Token::from(sum_stake).to_string(),
+				sum_stake_squared,
+				weight,
+				length,
+				elapsed.as_millis(),
+				feasible,
+			);
+
+			if weight > max_weight || length > max_length as usize {
+				log::warn!(
+					target: LOG_TARGET,
+					"{} solution exceeds the limits of the snapshot (weight {}, length {})",
+					name,
+					max_weight,
+					max_length,
+				);
+			}
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polkadot_runtime_exports::Runtime;
+	use sp_core::crypto::AccountId32;
+
+	fn account(i: u8) -> AccountId32 {
+		AccountId32::new([i; 32])
+	}
+
+	#[test]
+	fn snapshot_file_round_trip_works() {
+		let snapshot = EPM::RoundSnapshot {
+			voters: vec![
+				(account(1), 100, vec![account(10), account(20)]),
+				(account(2), 50, vec![account(20), account(30)]),
+				(account(3), 80, vec![account(10), account(30)]),
+			],
+			targets: vec![account(10), account(20), account(30)],
+		};
+		let file = SnapshotFile {
+			chain: "polkadot".to_string(),
+			spec_version: 9160,
+			round: 7,
+			desired_targets: 2,
+			snapshot_metadata: EPM::SolutionOrSnapshotSize { voters: 3, targets: 3 },
+			snapshot: snapshot.encode(),
+			miner_max_weight: Weight::max_value(),
+			miner_max_length: u32::max_value(),
+		};
+
+		let path = std::env::temp_dir()
+			.join(format!("staking-miner-snapshot-test-{}.bin", std::process::id()));
+		std::fs::write(&path, file.encode()).unwrap();
+		let loaded = SnapshotFile::load(&path);
+		std::fs::remove_file(&path).unwrap();
+		let loaded = loaded.unwrap();
+
+		assert_eq!(loaded.encode(), file.encode());
+
+		let mut ext = loaded.into_ext::<Runtime>();
+		ext.execute_with(|| {
+			assert_eq!(<EPM::Pallet<Runtime>>::round(), 7);
+			assert_eq!(<EPM::Pallet<Runtime>>::desired_targets(), Some(2));
+			assert_eq!(
+				<EPM::Pallet<Runtime>>::snapshot_metadata(),
+				Some(EPM::SolutionOrSnapshotSize { voters: 3, targets: 3 })
+			);
+			assert_eq!(<EPM::Pallet<Runtime>>::snapshot(), Some(snapshot));
+		});
+
+		// the externalities can be mined.
+		let (raw_solution, _) =
+			crate::mine_with::<Runtime>(&Solvers::SeqPhragmen { iterations: 0 }, &mut ext, false)
+				.unwrap();
+		assert_eq!(raw_solution.round, 7);
+	}
+}
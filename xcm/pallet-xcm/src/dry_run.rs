@@ -0,0 +1,233 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dry-running XCM messages.
+//!
+//! A dry run executes a message in a storage transaction which is rolled back afterwards, so it
+//! can be used to preview the effects of a message, e.g. to estimate its fees or to find out why
+//! it fails, without changing the state of the chain.
+//!
+//! Which messages would have been sent and which assets would have moved is observed through
+//! [`RecordingRouter`] and [`RecordingAssetTransactor`], which a runtime wraps around the router
+//! and the asset transactor of its XCM executor. They only record anything while a dry run is in
+//! progress and otherwise just forward to the wrapped type.
+
+use crate::{Config, Pallet};
+use codec::{Decode, DecodeLimit, Encode};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	weights::Weight,
+};
+use frame_system::Config as SysConfig;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+use xcm::prelude::*;
+use xcm_executor::{traits::TransactAsset, Assets};
+
+/// The assets deposited into and withdrawn from a location during a dry run.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AssetChanges {
+	/// The location whose assets changed.
+	pub location: VersionedMultiLocation,
+	/// The assets deposited into the location.
+	pub deposited: VersionedMultiAssets,
+	/// The assets withdrawn from the location.
+	pub withdrawn: VersionedMultiAssets,
+}
+
+/// The effects of dry-running an XCM message.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct XcmDryRunEffects {
+	/// The outcome of executing the message.
+	pub outcome: Outcome,
+	/// The weight used by the execution.
+	pub weight_used: Weight,
+	/// The SCALE encoded runtime events emitted during the execution.
+	pub emitted_events: Vec<Vec<u8>>,
+	/// The assets that changed hands, per location, in order of the first change.
+	pub asset_changes: Vec<AssetChanges>,
+	/// The messages that would have been sent, with their destinations.
+	pub forwarded_messages: Vec<(VersionedMultiLocation, VersionedXcm<()>)>,
+}
+
+/// Errors preventing a dry run from being performed at all.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum XcmDryRunError {
+	/// The origin is in a version of XCM that is not supported.
+	UnsupportedOriginVersion,
+	/// The message could not be decoded.
+	UndecodableMessage,
+	/// The message is in a version of XCM that is not supported.
+	UnsupportedMessageVersion,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to dry-run XCM messages.
+	pub trait XcmDryRunApi {
+		/// Execute `message` with the given `origin`, in the same way `pallet_xcm::execute` does
+		/// it, and roll back all changes afterwards.
+		///
+		/// `message` is a SCALE encoded `VersionedXcm` of the runtime `Call`. `max_weight` is
+		/// both the weight limit of the execution and the weight credit it starts with.
+		fn dry_run_xcm(
+			origin: VersionedMultiLocation,
+			message: Vec<u8>,
+			max_weight: Weight,
+		) -> Result<XcmDryRunEffects, XcmDryRunError>;
+	}
+}
+
+/// The assets deposited into and withdrawn from a location, in that order.
+type RecordedAssetChanges = (MultiLocation, Assets, Assets);
+
+/// What was observed during a dry run.
+#[derive(Default)]
+struct Recording {
+	forwarded_messages: Vec<(MultiLocation, Xcm<()>)>,
+	asset_changes: Vec<RecordedAssetChanges>,
+}
+
+impl Recording {
+	fn asset_changes_of(&mut self, location: &MultiLocation) -> &mut RecordedAssetChanges {
+		let index = match self.asset_changes.iter().position(|(l, _, _)| l == location) {
+			Some(index) => index,
+			None => {
+				self.asset_changes.push((location.clone(), Assets::new(), Assets::new()));
+				self.asset_changes.len() - 1
+			},
+		};
+		&mut self.asset_changes[index]
+	}
+
+	fn deposited(&mut self, location: &MultiLocation, assets: &Assets) {
+		self.asset_changes_of(location).1.subsume_assets(assets.clone());
+	}
+
+	fn withdrawn(&mut self, location: &MultiLocation, assets: &Assets) {
+		self.asset_changes_of(location).2.subsume_assets(assets.clone());
+	}
+}
+
+environmental::environmental!(recording: Recording);
+
+impl<T: Config> Pallet<T> {
+	/// Dry-run `message`, see [`XcmDryRunApi::dry_run_xcm`].
+	pub fn dry_run_xcm(
+		origin: VersionedMultiLocation,
+		message: Vec<u8>,
+		max_weight: Weight,
+	) -> Result<XcmDryRunEffects, XcmDryRunError> {
+		let origin: MultiLocation =
+			origin.try_into().map_err(|()| XcmDryRunError::UnsupportedOriginVersion)?;
+		let message = VersionedXcm::<<T as SysConfig>::Call>::decode_all_with_depth_limit(
+			xcm::MAX_XCM_DECODE_DEPTH,
+			&mut &message[..],
+		)
+		.map_err(|_| XcmDryRunError::UndecodableMessage)?;
+		let message: Xcm<<T as SysConfig>::Call> =
+			message.try_into().map_err(|()| XcmDryRunError::UnsupportedMessageVersion)?;
+
+		Ok(with_transaction(|| {
+			// Only the events of the dry run are of interest.
+			frame_system::Pallet::<T>::reset_events();
+
+			let mut observed = Recording::default();
+			let outcome = recording::using(&mut observed, || {
+				T::XcmExecutor::execute_xcm_in_credit(origin, message, max_weight, max_weight)
+			});
+			let emitted_events = frame_system::Pallet::<T>::read_events_no_consensus()
+				.into_iter()
+				.map(|record| record.event.encode())
+				.collect();
+
+			TransactionOutcome::Rollback(XcmDryRunEffects {
+				weight_used: outcome.weight_used(),
+				outcome,
+				emitted_events,
+				asset_changes: observed
+					.asset_changes
+					.into_iter()
+					.map(|(location, deposited, withdrawn)| AssetChanges {
+						location: location.into(),
+						deposited: MultiAssets::from(deposited).into(),
+						withdrawn: MultiAssets::from(withdrawn).into(),
+					})
+					.collect(),
+				forwarded_messages: observed
+					.forwarded_messages
+					.into_iter()
+					.map(|(dest, message)| (dest.into(), message.into()))
+					.collect(),
+			})
+		}))
+	}
+}
+
+/// A router recording the messages sent through `Inner` during a dry run.
+pub struct RecordingRouter<Inner>(PhantomData<Inner>);
+impl<Inner: SendXcm> SendXcm for RecordingRouter<Inner> {
+	fn send_xcm(dest: impl Into<MultiLocation>, message: Xcm<()>) -> SendResult {
+		let dest = dest.into();
+		let recorded = recording::with(|_| (dest.clone(), message.clone()));
+		Inner::send_xcm(dest, message)?;
+		if let Some(sent) = recorded {
+			recording::with(|r| r.forwarded_messages.push(sent));
+		}
+		Ok(())
+	}
+}
+
+/// An asset transactor recording the assets moved by `Inner` during a dry run.
+pub struct RecordingAssetTransactor<Inner>(PhantomData<Inner>);
+impl<Inner: TransactAsset> TransactAsset for RecordingAssetTransactor<Inner> {
+	fn can_check_in(origin: &MultiLocation, what: &MultiAsset) -> XcmResult {
+		Inner::can_check_in(origin, what)
+	}
+
+	fn check_in(origin: &MultiLocation, what: &MultiAsset) {
+		Inner::check_in(origin, what)
+	}
+
+	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
+		Inner::check_out(dest, what)
+	}
+
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		Inner::deposit_asset(what, who)?;
+		recording::with(|r| r.deposited(who, &what.clone().into()));
+		Ok(())
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		let withdrawn = Inner::withdraw_asset(what, who)?;
+		recording::with(|r| r.withdrawn(who, &withdrawn));
+		Ok(withdrawn)
+	}
+
+	fn transfer_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+	) -> Result<Assets, XcmError> {
+		let transferred = Inner::transfer_asset(asset, from, to)?;
+		recording::with(|r| {
+			r.withdrawn(from, &transferred);
+			r.deposited(to, &transferred);
+		});
+		Ok(transferred)
+	}
+}
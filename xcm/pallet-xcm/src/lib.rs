@@ -18,6 +18,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod dry_run;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -252,8 +252,8 @@ pub type Barrier = (
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type Call = Call;
-	type XcmSender = TestSendXcm;
-	type AssetTransactor = LocalAssetTransactor;
+	type XcmSender = crate::dry_run::RecordingRouter<TestSendXcm>;
+	type AssetTransactor = crate::dry_run::RecordingAssetTransactor<LocalAssetTransactor>;
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = Case<TrustedAssets>;
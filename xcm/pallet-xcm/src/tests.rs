@@ -15,8 +15,9 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	mock::*, AssetTraps, CurrentMigration, Error, LatestVersionedMultiLocation, Queries,
-	QueryStatus, VersionDiscoveryQueue, VersionNotifiers, VersionNotifyTargets,
+	dry_run::AssetChanges, mock::*, AssetTraps, CurrentMigration, Error,
+	LatestVersionedMultiLocation, Queries, QueryStatus, VersionDiscoveryQueue, VersionNotifiers,
+	VersionNotifyTargets,
 };
 use frame_support::{
 	assert_noop, assert_ok,
@@ -499,6 +500,76 @@ fn execute_withdraw_to_deposit_works() {
 	});
 }
 
+/// Test that a dry run reports the effects of a message but doesn't apply them.
+#[test]
+fn dry_run_reports_effects_without_applying_them() {
+	use codec::{Decode, Encode};
+	let balances =
+		vec![(ALICE, INITIAL_BALANCE), (ParaId::from(PARA_ID).into_account(), INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let weight = 3 * BaseXcmWeight::get();
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+		let dest: MultiLocation = Parachain(PARA_ID).into();
+		let message = VersionedXcm::<Call>::from(Xcm(vec![TransferReserveAsset {
+			assets: (Here, SEND_AMOUNT).into(),
+			dest: dest.clone(),
+			xcm: Xcm(vec![]),
+		}]));
+
+		let effects =
+			XcmPallet::dry_run_xcm(origin.clone().into(), message.encode(), weight).unwrap();
+
+		assert_eq!(effects.outcome, Outcome::Complete(BaseXcmWeight::get()));
+		assert_eq!(effects.weight_used, BaseXcmWeight::get());
+		let emitted_events = effects
+			.emitted_events
+			.iter()
+			.map(|event| Event::decode(&mut &event[..]).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(
+			emitted_events,
+			vec![
+				Event::Balances(pallet_balances::Event::Withdraw {
+					who: ALICE,
+					amount: SEND_AMOUNT
+				}),
+				Event::Balances(pallet_balances::Event::Deposit {
+					who: ParaId::from(PARA_ID).into_account(),
+					amount: SEND_AMOUNT
+				}),
+			]
+		);
+		let sent: MultiAssets = (Here, SEND_AMOUNT).into();
+		assert_eq!(
+			effects.asset_changes,
+			vec![
+				AssetChanges {
+					location: origin.into(),
+					deposited: MultiAssets::new().into(),
+					withdrawn: sent.clone().into(),
+				},
+				AssetChanges {
+					location: dest.clone().into(),
+					deposited: sent.into(),
+					withdrawn: MultiAssets::new().into(),
+				},
+			]
+		);
+		assert_eq!(
+			effects.forwarded_messages,
+			vec![(
+				dest.into(),
+				Xcm(vec![ReserveAssetDeposited((Parent, SEND_AMOUNT).into()), ClearOrigin]).into()
+			)]
+		);
+
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::total_balance(&ParaId::from(PARA_ID).into_account()), INITIAL_BALANCE);
+		assert!(System::events().is_empty());
+	});
+}
+
 /// Test drop/claim assets.
 #[test]
 fn trapped_assets_can_be_claimed() {
@@ -57,6 +57,11 @@ pub use fungibles_adapter::{
 	FungiblesMutateAdapter, FungiblesTransferAdapter,
 };
 
+mod nonfungibles_adapter;
+pub use nonfungibles_adapter::{
+	AsIndexedInstance, NonFungiblesAdapter, NonFungiblesMutateAdapter, NonFungiblesTransferAdapter,
+};
+
 mod weight;
 #[allow(deprecated)]
 pub use weight::FixedRateOfConcreteFungible;
@@ -17,7 +17,9 @@
 use sp_std::result;
 use xcm::latest::{Error as XcmError, MultiAsset};
 
-/// Errors associated with [`MatchesFungibles`] operation.
+/// Errors associated with [`MatchesFungibles`] and [`MatchesNonFungibles`] operations.
+///
+/// [`MatchesNonFungibles`]: super::MatchesNonFungibles
 pub enum Error {
 	/// Asset not found.
 	AssetNotFound,
@@ -27,6 +29,8 @@ pub enum Error {
 	AmountToBalanceConversionFailed,
 	/// `MultiLocation` to `AssetId` conversion failed.
 	AssetIdConversionFailed,
+	/// `AssetInstance` to non-fungibles instance ID conversion failed.
+	InstanceConversionFailed,
 }
 
 impl From<Error> for XcmError {
@@ -38,6 +42,7 @@ impl From<Error> for XcmError {
 			Error::AmountToBalanceConversionFailed =>
 				FailedToTransactAsset("AmountToBalanceConversionFailed"),
 			Error::AssetIdConversionFailed => FailedToTransactAsset("AssetIdConversionFailed"),
+			Error::InstanceConversionFailed => FailedToTransactAsset("InstanceConversionFailed"),
 		}
 	}
 }
@@ -0,0 +1,38 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::traits::Error;
+use sp_std::result;
+use xcm::latest::MultiAsset;
+
+pub trait MatchesNonFungibles<ClassId, InstanceId> {
+	fn matches_nonfungibles(a: &MultiAsset) -> result::Result<(ClassId, InstanceId), Error>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<ClassId: Clone, InstanceId: Clone> MatchesNonFungibles<ClassId, InstanceId> for Tuple {
+	fn matches_nonfungibles(a: &MultiAsset) -> result::Result<(ClassId, InstanceId), Error> {
+		for_tuples!( #(
+			match Tuple::matches_nonfungibles(a) { o @ Ok(_) => return o, _ => () }
+		)* );
+		log::trace!(
+			target: "xcm::matches_nonfungibles",
+			"did not match nonfungibles asset: {:?}",
+			&a,
+		);
+		Err(Error::AssetNotFound)
+	}
+}
@@ -26,6 +26,8 @@ mod matches_fungible;
 pub use matches_fungible::MatchesFungible;
 mod matches_fungibles;
 pub use matches_fungibles::{Error, MatchesFungibles};
+mod matches_nonfungibles;
+pub use matches_nonfungibles::MatchesNonFungibles;
 mod on_response;
 pub use on_response::{OnResponse, VersionChangeNotifier};
 mod should_execute;
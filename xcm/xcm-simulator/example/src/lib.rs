@@ -17,7 +17,7 @@
 mod parachain;
 mod relay_chain;
 
-use polkadot_parachain::primitives::Id as ParaId;
+use polkadot_parachain::primitives::{Id as ParaId, Sibling};
 use sp_runtime::traits::AccountIdConversion;
 use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
 
@@ -64,6 +64,10 @@ pub fn para_account_id(id: u32) -> relay_chain::AccountId {
 	ParaId::from(id).into_account()
 }
 
+pub fn sibling_account_id(id: u32) -> parachain::AccountId {
+	Sibling::from(id).into_account()
+}
+
 pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
 	use parachain::{MsgQueue, Runtime, System};
 
@@ -114,6 +118,16 @@ mod tests {
 		BuyExecution { fees: fees.into(), weight_limit: Unlimited }
 	}
 
+	// Helper function for creating an NFT class on a parachain, minting the given instances to
+	// `ALICE`.
+	fn create_nft_class(class: u32, instances: &[u32]) {
+		use parachain::{Origin, Uniques};
+		assert_ok!(Uniques::force_create(Origin::root(), class, ALICE, true));
+		for instance in instances {
+			assert_ok!(Uniques::mint(Origin::signed(ALICE), class, *instance, ALICE));
+		}
+	}
+
 	#[test]
 	fn dmp() {
 		MockNet::reset();
@@ -315,4 +329,93 @@ mod tests {
 			);
 		});
 	}
+
+	/// Scenario:
+	/// ALICE reserve-transfers an NFT from its home chain, ParaA, to her account on ParaB.
+	///
+	/// Asserts that ParaB's sovereign account holds the NFT on ParaA, and that ALICE owns the
+	/// mirrored NFT on ParaB.
+	#[test]
+	fn reserve_transfer_nft() {
+		MockNet::reset();
+
+		ParaA::execute_with(|| create_nft_class(1, &[42]));
+		ParaB::execute_with(|| create_nft_class(1, &[]));
+
+		ParaA::execute_with(|| {
+			let message = Xcm(vec![TransferReserveAsset {
+				assets: ((PalletInstance(4), GeneralIndex(1)), Index(42)).into(),
+				dest: (Parent, Parachain(2)).into(),
+				xcm: Xcm(vec![DepositAsset {
+					assets: All.into(),
+					max_assets: 1,
+					beneficiary: AccountId32 { network: Any, id: ALICE.into() }.into(),
+				}]),
+			}]);
+			assert_ok!(ParachainPalletXcm::execute(
+				parachain::Origin::signed(ALICE),
+				Box::new(xcm::VersionedXcm::from(message)),
+				1_000_000_000,
+			));
+			assert_eq!(parachain::Uniques::owner(1, 42), Some(sibling_account_id(2)));
+		});
+
+		ParaB::execute_with(|| {
+			assert_eq!(parachain::Uniques::owner(1, 42), Some(ALICE));
+		});
+	}
+
+	/// Scenario:
+	/// ALICE teleports an NFT from its home chain, ParaA, to ParaB and back again.
+	///
+	/// Asserts that ParaA's checking account holds the NFT while it is away, and that it is
+	/// returned to ALICE afterwards.
+	#[test]
+	fn teleport_nft() {
+		MockNet::reset();
+
+		ParaA::execute_with(|| create_nft_class(1, &[42]));
+		ParaB::execute_with(|| create_nft_class(1, &[]));
+
+		let teleport = |nft: MultiAsset, dest: u32| {
+			let message = Xcm(vec![
+				WithdrawAsset(nft.into()),
+				InitiateTeleport {
+					assets: All.into(),
+					dest: (Parent, Parachain(dest)).into(),
+					xcm: Xcm(vec![DepositAsset {
+						assets: All.into(),
+						max_assets: 1,
+						beneficiary: AccountId32 { network: Any, id: ALICE.into() }.into(),
+					}]),
+				},
+			]);
+			assert_ok!(ParachainPalletXcm::execute(
+				parachain::Origin::signed(ALICE),
+				Box::new(xcm::VersionedXcm::from(message)),
+				1_000_000_000,
+			));
+		};
+
+		ParaA::execute_with(|| {
+			teleport(((PalletInstance(4), GeneralIndex(1)), Index(42)).into(), 2);
+			assert_eq!(
+				parachain::Uniques::owner(1, 42),
+				Some(parachain::NftCheckingAccount::get())
+			);
+		});
+
+		ParaB::execute_with(|| {
+			assert_eq!(parachain::Uniques::owner(1, 42), Some(ALICE));
+			teleport(
+				((Parent, Parachain(1), PalletInstance(4), GeneralIndex(1)), Index(42)).into(),
+				1,
+			);
+			assert_eq!(parachain::Uniques::owner(1, 42), None);
+		});
+
+		ParaA::execute_with(|| {
+			assert_eq!(parachain::Uniques::owner(1, 42), Some(ALICE));
+		});
+	}
 }
@@ -19,9 +19,10 @@
 use codec::{Decode, Encode};
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{Everything, Nothing},
+	traits::{Contains, Everything, Nothing},
 	weights::{constants::WEIGHT_PER_SECOND, Weight},
 };
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -37,12 +38,16 @@ use polkadot_parachain::primitives::{
 };
 use xcm::{latest::prelude::*, VersionedXcm};
 use xcm_builder::{
-	AccountId32Aliases, AllowUnpaidExecutionFrom, CurrencyAdapter as XcmCurrencyAdapter,
-	EnsureXcmOrigin, FixedRateOfFungible, FixedWeightBounds, IsConcrete, LocationInverter,
-	NativeAsset, ParentIsDefault, SiblingParachainConvertsVia, SignedAccountId32AsNative,
+	AccountId32Aliases, AllowUnpaidExecutionFrom, AsIndexedInstance, AsPrefixedGeneralIndex,
+	ConvertedConcreteAssetId, CurrencyAdapter as XcmCurrencyAdapter, EnsureXcmOrigin,
+	FixedRateOfFungible, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset,
+	NonFungiblesAdapter, ParentIsDefault, SiblingParachainConvertsVia, SignedAccountId32AsNative,
 	SignedToAccountId32, SovereignSignedViaLocation,
 };
-use xcm_executor::{Config, XcmExecutor};
+use xcm_executor::{
+	traits::{FilterAssetLocation, JustTry},
+	Config, XcmExecutor,
+};
 
 pub type AccountId = AccountId32;
 pub type Balance = u128;
@@ -96,6 +101,34 @@ impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+	pub const ClassDeposit: Balance = 10;
+	pub const InstanceDeposit: Balance = 1;
+	pub const MetadataDepositBase: Balance = 1;
+	pub const AttributeDepositBase: Balance = 1;
+	pub const DepositPerByte: Balance = 1;
+	pub const StringLimit: u32 = 50;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 64;
+}
+
+impl pallet_uniques::Config for Runtime {
+	type Event = Event;
+	type ClassId = u32;
+	type InstanceId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type StringLimit = StringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = WEIGHT_PER_SECOND / 4;
 	pub const ReservedDmpWeight: Weight = WEIGHT_PER_SECOND / 4;
@@ -128,6 +161,53 @@ parameter_types! {
 pub type LocalAssetTransactor =
 	XcmCurrencyAdapter<Balances, IsConcrete<KsmLocation>, LocationToAccountId, AccountId, ()>;
 
+/// The home chain of the non-fungibles in this network. Its siblings mirror them in their own
+/// `Uniques` pallet under the same class and instance IDs.
+pub const NFT_HOME_PARA_ID: u32 = 1;
+
+parameter_types! {
+	pub UniquesPalletLocation: MultiLocation = PalletInstance(4).into();
+	pub HomeUniquesPalletLocation: MultiLocation =
+		(Parent, Parachain(NFT_HOME_PARA_ID), PalletInstance(4)).into();
+	pub const NftCheckingAccount: AccountId = AccountId32::new([42u8; 32]);
+}
+
+pub type UniquesConvertedConcreteId<Prefix> = ConvertedConcreteAssetId<
+	u32,
+	u32,
+	AsPrefixedGeneralIndex<Prefix, u32, JustTry>,
+	AsIndexedInstance<u32>,
+>;
+
+/// Teleports of non-fungibles are only tracked on their home chain.
+pub struct NftsHomedHere;
+impl Contains<u32> for NftsHomedHere {
+	fn contains(_class: &u32) -> bool {
+		MsgQueue::parachain_id() == NFT_HOME_PARA_ID.into()
+	}
+}
+
+pub type NftTransactor = NonFungiblesAdapter<
+	Uniques,
+	(
+		UniquesConvertedConcreteId<UniquesPalletLocation>,
+		UniquesConvertedConcreteId<HomeUniquesPalletLocation>,
+	),
+	LocationToAccountId,
+	AccountId,
+	NftsHomedHere,
+	NftCheckingAccount,
+>;
+
+/// Trusts sibling parachains as reserves and teleporters of non-fungibles.
+pub struct SiblingNfts;
+impl FilterAssetLocation for SiblingNfts {
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		matches!(asset.fun, NonFungible(_)) &&
+			matches!(origin, MultiLocation { parents: 1, interior: X1(Parachain(_)) })
+	}
+}
+
 pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
 pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
 
@@ -135,10 +215,10 @@ pub struct XcmConfig;
 impl Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = (LocalAssetTransactor, NftTransactor);
 	type OriginConverter = XcmOriginToCallOrigin;
-	type IsReserve = NativeAsset;
-	type IsTeleporter = ();
+	type IsReserve = (NativeAsset, SiblingNfts);
+	type IsTeleporter = SiblingNfts;
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
@@ -325,5 +405,6 @@ construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		MsgQueue: mock_msg_queue::{Pallet, Storage, Event<T>},
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
 	}
 );